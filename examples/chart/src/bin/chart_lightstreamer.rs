@@ -47,7 +47,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let client = Client::default();
     let ws_info = client.get_ws_info().await;
-    let password = ws_info.get_ws_password();
+    let password = ws_info.get_ws_password()?;
 
     debug!("{ws_info:?}");
     // Create a new Lightstreamer client instance and wrap it in an Arc<Mutex<>> so it can be shared across threads.
@@ -11,7 +11,7 @@
 //! By default, it fetches instruments for the VANILLA_OPTIONS category.
 //!
 //! Note: This endpoint may not be available for all account types or in demo mode.
-//! If you receive a 500 error, try using a production account.
+//! In that case the call fails with `AppError::NotSupported`; try using a production account.
 
 use ig_client::prelude::*;
 use ig_client::utils::setup_logger;
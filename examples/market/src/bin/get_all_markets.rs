@@ -1,3 +1,4 @@
+use ig_client::constants::DEFAULT_MARKET_TRAVERSAL_DEPTH;
 use ig_client::prelude::*;
 use ig_client::utils::setup_logger;
 use tracing::info;
@@ -10,13 +11,24 @@ async fn main() -> IgResult<()> {
 
     info!("\n=== Getting All Markets from Hierarchy ===");
 
-    // Get all markets using the traversal method
-    let all_markets = client.get_all_markets().await?;
+    // Get all markets using the traversal method, keeping the traversal stats
+    let (all_markets, stats) = client
+        .get_all_markets_verbose(DEFAULT_MARKET_TRAVERSAL_DEPTH)
+        .await?;
 
     info!(
-        "✅ Found {} total markets across all levels",
-        all_markets.len()
+        "✅ Found {} total markets across {} levels ({} nodes visited, {} node errors)",
+        all_markets.len(),
+        stats.levels_processed,
+        stats.nodes_visited,
+        stats.node_errors
     );
+    if stats.hit_depth_limit() {
+        info!(
+            "⚠️  Depth limit reached with {} nodes left unvisited",
+            stats.unvisited_nodes
+        );
+    }
 
     // Show some sample markets
     info!("\n📊 Sample of markets found:");
@@ -43,8 +55,11 @@ async fn main() -> IgResult<()> {
     }
 
     info!("\n=== Example completed successfully! ===");
-    info!("💡 The traversal goes up to 6 levels deep in the market hierarchy");
-    info!("   This ensures maximum coverage of all available markets");
+    info!(
+        "💡 The traversal goes up to {} levels deep in the market hierarchy",
+        DEFAULT_MARKET_TRAVERSAL_DEPTH
+    );
+    info!("   Use get_all_markets_with_depth to walk further when the limit is hit");
 
     Ok(())
 }
@@ -80,7 +80,12 @@ async fn main() -> Result<(), Box<dyn Error>> {
             }
 
             // If it's a rate limit error, provide specific guidance
-            if matches!(e, AppError::RateLimitExceeded | AppError::Unexpected(_)) {
+            if matches!(
+                e,
+                AppError::RateLimitExceeded
+                    | AppError::RateLimited { .. }
+                    | AppError::Unexpected(_)
+            ) {
                 error!("API rate limit exceeded or access denied.");
                 info!("Consider implementing exponential backoff or reducing request frequency.");
                 info!(
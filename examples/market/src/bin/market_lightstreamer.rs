@@ -23,7 +23,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     setup_logger();
     let http_client = Client::default();
     let ws_info = http_client.get_ws_info().await;
-    let password = ws_info.get_ws_password();
+    let password = ws_info.get_ws_password()?;
 
     // Create a subscription for a market
     let epic = "MARKET:OP.D.OTCSPXWK.6720C.IP".to_string();
@@ -12,15 +12,10 @@ async fn main() -> IgResult<()> {
 
     // Example 1: Get recent prices
     info!("📊 Example 1: Recent Prices (last 5 minutes)");
-    let params = RecentPricesRequest {
-        epic: "CS.D.EURUSD.CFD.IP",
-        resolution: Some("MINUTE"),
-        max_points: Some(5),
-        page_size: None,
-        page_number: None,
-        from: None,
-        to: None,
-    };
+    let params = RecentPricesRequest::builder("CS.D.EURUSD.CFD.IP")
+        .resolution("MINUTE")
+        .max_points(5)
+        .build()?;
 
     let recent_prices = client.get_recent_prices(&params).await?;
     info!("\n{}", recent_prices);
@@ -17,15 +17,10 @@ async fn main() -> IgResult<()> {
     info!("EPIC: {}", epic);
 
     // Create request parameters
-    let params = RecentPricesRequest {
-        epic: &epic,
-        resolution: Some("MINUTE"),
-        max_points: Some(20),
-        page_size: None,
-        page_number: None,
-        from: None,
-        to: None,
-    };
+    let params = RecentPricesRequest::builder(&epic)
+        .resolution("MINUTE")
+        .max_points(20)
+        .build()?;
 
     // Get recent prices
     let prices = client.get_recent_prices(&params).await?;
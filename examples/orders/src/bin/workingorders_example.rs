@@ -73,8 +73,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             info!("\n--- Step 3: Deleting working order ---");
             info!("Deleting working order with deal ID: {}", deal_id);
 
-            client.delete_working_order(deal_id).await?;
-            info!("Working order deleted successfully");
+            let deal_reference = client.delete_working_order(deal_id).await?;
+            info!(
+                "Working order deleted successfully (deal reference: {})",
+                deal_reference
+            );
 
             // Verify deletion
             tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
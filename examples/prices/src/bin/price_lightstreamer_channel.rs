@@ -81,7 +81,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize the IG client and get WebSocket credentials
     let http_client = Client::default();
     let ws_info = http_client.get_ws_info().await;
-    let password = ws_info.get_ws_password();
+    let password = ws_info.get_ws_password()?;
     debug!("{ws_info:?}");
 
     // Create a channel for receiving updates
@@ -54,7 +54,7 @@ async fn main() -> Result<(), AppError> {
     // Connect and maintain the connection
     // This will block until SIGINT/SIGTERM or connection failure
     info!("Connecting to Lightstreamer server...");
-    client.connect(None).await?;
+    client.connect(None).await?.into_result()?;
 
     // Cleanup (only reached after graceful shutdown)
     info!("Account streaming example completed");
@@ -81,7 +81,7 @@ async fn main() -> Result<(), AppError> {
 
     // Maintain the connection
     info!("Connecting to Lightstreamer server...");
-    client.connect(None).await?;
+    client.connect(None).await?.into_result()?;
 
     // Cleanup after graceful shutdown
     info!("Chart streaming example completed");
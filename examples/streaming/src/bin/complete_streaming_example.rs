@@ -55,7 +55,7 @@ async fn main() -> Result<(), AppError> {
 
     tokio::spawn(async move {
         while let Some(price_data) = market_receiver.recv().await {
-            info!("Market update: {}", price_data);
+            info!("Market update: {}", price_data.summary());
         }
     });
 
@@ -75,7 +75,7 @@ async fn main() -> Result<(), AppError> {
 
     tokio::spawn(async move {
         while let Some(price_data) = price_receiver.recv().await {
-            info!("Price update: {}", price_data);
+            info!("Price update: {}", price_data.summary());
         }
     });
 
@@ -113,7 +113,7 @@ async fn main() -> Result<(), AppError> {
     // Both run in parallel
     info!("Connecting to Lightstreamer server...");
     info!("All subscriptions are now active. Press Ctrl+C to exit.");
-    client.connect(None).await?;
+    client.connect(None).await?.into_result()?;
 
     // Cleanup (only reached after graceful shutdown)
     info!("Complete streaming example completed");
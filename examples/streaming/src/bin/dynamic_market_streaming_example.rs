@@ -52,7 +52,7 @@ async fn main() -> Result<(), AppError> {
     // Spawn a task to handle incoming market data updates
     tokio::spawn(async move {
         while let Some(price_data) = receiver.recv().await {
-            info!("Market update - {}", price_data);
+            info!("Market update - {}", price_data.summary());
         }
     });
 
@@ -62,10 +62,8 @@ async fn main() -> Result<(), AppError> {
         "OP.D.OTCSPX3.6875C.IP".to_string(),
     ];
 
-    for epic in &initial_epics {
-        streamer.add(epic.clone()).await?;
-        info!("Added initial market: {}", epic);
-    }
+    streamer.add_many(initial_epics.clone()).await?;
+    info!("Added initial markets: {:?}", initial_epics);
 
     // Clone the streamer for use in other threads
     let streamer_clone1 = streamer.clone();
@@ -82,11 +80,10 @@ async fn main() -> Result<(), AppError> {
             "OP.D.OTCSPX3.6880C.IP".to_string(),
         ];
 
-        for epic in new_epics {
-            match streamer_clone1.add(epic.clone()).await {
-                Ok(_) => info!("Thread 1 successfully added: {}", epic),
-                Err(e) => tracing::error!("Failed to add {}: {:?}", epic, e),
-            }
+        // One batch, so the pair triggers at most one resubscription
+        match streamer_clone1.add_many(new_epics.clone()).await {
+            Ok(_) => info!("Thread 1 successfully added: {:?}", new_epics),
+            Err(e) => tracing::error!("Failed to add {:?}: {:?}", new_epics, e),
         }
 
         let current_epics = streamer_clone1.get_epics().await;
@@ -106,11 +103,9 @@ async fn main() -> Result<(), AppError> {
             "OP.D.OTCSPX3.6900C.IP".to_string(),
         ];
 
-        for epic in more_epics {
-            match streamer_clone2.add(epic.clone()).await {
-                Ok(_) => info!("Thread 2 successfully added: {}", epic),
-                Err(e) => tracing::error!("Failed to add {}: {:?}", epic, e),
-            }
+        match streamer_clone2.add_many(more_epics.clone()).await {
+            Ok(_) => info!("Thread 2 successfully added: {:?}", more_epics),
+            Err(e) => tracing::error!("Failed to add {:?}: {:?}", more_epics, e),
         }
 
         let current_epics = streamer_clone2.get_epics().await;
@@ -130,11 +125,9 @@ async fn main() -> Result<(), AppError> {
             "OP.D.OTCSPX3.6910C.IP".to_string(),
         ];
 
-        for epic in final_epics {
-            match streamer_clone3.add(epic.clone()).await {
-                Ok(_) => info!("Thread 3 successfully added: {}", epic),
-                Err(e) => tracing::error!("Failed to add {}: {:?}", epic, e),
-            }
+        match streamer_clone3.add_many(final_epics.clone()).await {
+            Ok(_) => info!("Thread 3 successfully added: {:?}", final_epics),
+            Err(e) => tracing::error!("Failed to add {:?}: {:?}", final_epics, e),
         }
 
         // Wait a bit and then remove one market
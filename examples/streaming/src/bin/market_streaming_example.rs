@@ -57,7 +57,7 @@ async fn main() -> Result<(), AppError> {
     // Spawn a task to handle incoming market data updates
     tokio::spawn(async move {
         while let Some(price_data) = receiver.recv().await {
-            info!("Market update - {}", price_data);
+            info!("Market update - {}", price_data.summary());
         }
     });
 
@@ -68,7 +68,7 @@ async fn main() -> Result<(), AppError> {
         "Monitoring {} instruments. Press Ctrl+C to exit.",
         epics.len()
     );
-    client.connect(None).await?;
+    client.connect(None).await?.into_result()?;
 
     // Cleanup (only reached after graceful shutdown)
     info!("Market streaming example completed");
@@ -62,7 +62,7 @@ async fn main() -> Result<(), AppError> {
     // Spawn a task to handle incoming price data updates
     tokio::spawn(async move {
         while let Some(price_data) = receiver.recv().await {
-            info!("Price update - {}", price_data);
+            info!("Price update - {}", price_data.summary());
         }
     });
 
@@ -73,7 +73,7 @@ async fn main() -> Result<(), AppError> {
     // Connect and maintain the connection
     // This will block until SIGINT/SIGTERM or connection failure
     info!("Connecting to Lightstreamer server...");
-    client.connect(None).await?;
+    client.connect(None).await?.into_result()?;
 
     // Cleanup (only reached after graceful shutdown)
     info!("Price streaming example completed");
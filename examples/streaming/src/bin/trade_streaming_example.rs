@@ -15,7 +15,7 @@
 //! allowing you to monitor position changes, order executions, and trade confirmations
 //! as they happen.
 
-use ig_client::application::client::StreamerClient;
+use ig_client::application::client::{StreamClose, StreamerClient};
 use ig_client::error::AppError;
 use ig_client::prelude::setup_logger;
 use tracing::{error, info, warn};
@@ -51,10 +51,20 @@ async fn main() -> Result<(), AppError> {
             info!("📊 Trade Update Received");
 
             // Process CONFIRMS field - Trade confirmations and deal references
-            if let Some(confirms) = &trade_fields.confirms {
+            if let Some(confirmation) = trade_fields.confirmation() {
                 info!("✅ TRADE CONFIRMATION:");
-                info!("  Deal Reference: {}", confirms);
-                info!("  This indicates a trade has been executed or confirmed");
+                if let Some(deal_ref) = &confirmation.deal_reference {
+                    info!("  Deal Reference: {}", deal_ref);
+                }
+                if let Some(deal_status) = &confirmation.deal_status {
+                    info!("  Deal Status: {:?}", deal_status);
+                }
+                if let Some(reason) = &confirmation.reason {
+                    info!("  Reason: {}", reason);
+                }
+            } else if let Some(confirms) = &trade_fields.confirms {
+                info!("✅ TRADE CONFIRMATION:");
+                info!("  Raw payload: {}", confirms);
             }
 
             // Process OPU (Open Position Update) - New positions or position changes
@@ -213,9 +223,13 @@ async fn main() -> Result<(), AppError> {
     // Connect and maintain the connection
     // This will block until SIGINT/SIGTERM or connection failure
     match client.connect(None).await {
-        Ok(_) => {
+        Ok(StreamClose::Graceful) => {
             info!("✅ Connected successfully to trade streaming");
         }
+        Ok(StreamClose::Unexpected(reason)) => {
+            error!("❌ Trade streaming closed unexpectedly: {}", reason);
+            return Err(AppError::WebSocketError(reason));
+        }
         Err(e) => {
             error!("❌ Failed to connect to streaming server: {}", e);
             return Err(e);
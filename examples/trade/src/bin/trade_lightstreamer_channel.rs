@@ -70,7 +70,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let client = Client::default();
     let ws_info = client.get_ws_info().await;
-    let password = ws_info.get_ws_password();
+    let password = ws_info.get_ws_password()?;
 
     debug!("{ws_info:?}");
     info!("Using Lightstreamer server: {}", ws_info.server);
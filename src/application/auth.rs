@@ -15,7 +15,7 @@
 use crate::application::config::Config;
 use crate::application::rate_limiter::RateLimiter;
 use crate::error::AppError;
-pub(crate) use crate::model::auth::{OAuthToken, SecurityHeaders, SessionResponse};
+pub(crate) use crate::model::auth::{OAuthSession, OAuthToken, SecurityHeaders, SessionResponse};
 use crate::model::http::make_http_request;
 use crate::model::retry::RetryConfig;
 use crate::prelude::Deserialize;
@@ -24,7 +24,7 @@ use pretty_simple_display::{DebugPretty, DisplaySimple};
 use reqwest::{Client, Method};
 use serde::Serialize;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, watch};
 use tracing::{debug, error, info, warn};
 
 const USER_AGENT: &str = "ig-client/0.6.0";
@@ -48,17 +48,120 @@ pub struct WebsocketInfo {
 impl WebsocketInfo {
     /// Generates the WebSocket password for Lightstreamer authentication
     ///
+    /// IG's Lightstreamer server expects the v2 session tokens joined as
+    /// `CST-{cst}|XST-{x_security_token}`, e.g. `CST-abc123|XST-def456` for a CST of
+    /// `abc123` and an X-SECURITY-TOKEN of `def456`. The tokens are used verbatim;
+    /// OAuth (v3) sessions carry no such tokens, which is why `get_ws_info` performs a
+    /// v2 login for them.
+    ///
     /// # Returns
-    /// * Password in format "CST-{cst}|XST-{token}" if both tokens are available
-    /// * Empty string if tokens are not available
-    pub fn get_ws_password(&self) -> String {
-        match (&self.cst, &self.x_security_token) {
-            (Some(cst), Some(x_security_token)) => {
-                format!("CST-{}|XST-{}", cst, x_security_token)
-            }
-            _ => String::new(),
+    /// * `Ok(String)` - The password, if both tokens are available
+    /// * `Err(AppError::InvalidInput)` - Naming the missing, empty or malformed token
+    pub fn get_ws_password(&self) -> Result<String, AppError> {
+        let cst = ws_token("CST", self.cst.as_deref())?;
+        let x_security_token = ws_token("X-SECURITY-TOKEN", self.x_security_token.as_deref())?;
+        Ok(format!("CST-{cst}|XST-{x_security_token}"))
+    }
+
+    /// Replaces the Lightstreamer server, keeping the credentials
+    ///
+    /// # Arguments
+    /// * `endpoint` - Lightstreamer endpoint, normalised as by `lightstreamer_server_url`
+    #[must_use]
+    pub fn with_server(mut self, endpoint: &str) -> Self {
+        self.server = lightstreamer_server_url(endpoint);
+        self
+    }
+}
+
+/// Checks a session token before it is embedded in the Lightstreamer password
+fn ws_token<'a>(name: &str, token: Option<&'a str>) -> Result<&'a str, AppError> {
+    match token {
+        None => Err(AppError::InvalidInput(format!(
+            "streaming password needs a {name} token, but the session has none; \
+             streaming requires a v2 (CST/X-SECURITY-TOKEN) session"
+        ))),
+        Some(token) if token.trim().is_empty() => Err(AppError::InvalidInput(format!(
+            "streaming password needs a {name} token, but it is empty"
+        ))),
+        Some(token) if token.contains('|') => Err(AppError::InvalidInput(format!(
+            "{name} token contains '|', which would corrupt the streaming password"
+        ))),
+        Some(token) => Ok(token),
+    }
+}
+
+/// Builds the Lightstreamer server URL from the endpoint returned by IG
+///
+/// IG returns the endpoint as a bare host or as a URL; the `https://` scheme is added
+/// when missing and the `/lightstreamer` path appended unless already present.
+///
+/// # Arguments
+/// * `endpoint` - Lightstreamer endpoint, e.g. `https://demo-apd.marketdatasystems.com`
+///
+/// # Returns
+/// * The server URL, e.g. `https://demo-apd.marketdatasystems.com/lightstreamer`
+#[must_use]
+pub fn lightstreamer_server_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim().trim_end_matches('/');
+    let url = if endpoint.starts_with("http://") || endpoint.starts_with("https://") {
+        endpoint.to_string()
+    } else {
+        format!("https://{endpoint}")
+    };
+    if url.ends_with("/lightstreamer") {
+        url
+    } else {
+        format!("{url}/lightstreamer")
+    }
+}
+
+/// Shared handle linking a REST session with the streaming clients built from it
+///
+/// Every time the session is refreshed through `Client::refresh_session`, the fresh
+/// `WebsocketInfo` is published on this handle. Any `StreamerClient` created with
+/// `StreamerClient::from_client` watches the handle and rebuilds its Lightstreamer
+/// password from the published credentials.
+#[derive(Clone)]
+pub struct SessionHandle {
+    sender: Arc<watch::Sender<Option<WebsocketInfo>>>,
+}
+
+impl SessionHandle {
+    /// Creates a new handle with no published credentials
+    #[must_use]
+    pub fn new() -> Self {
+        let (sender, _) = watch::channel(None);
+        Self {
+            sender: Arc::new(sender),
         }
     }
+
+    /// Publishes fresh WebSocket credentials to every subscriber
+    ///
+    /// # Arguments
+    /// * `ws_info` - The credentials obtained after a session refresh
+    pub fn publish(&self, ws_info: WebsocketInfo) {
+        self.sender.send_replace(Some(ws_info));
+    }
+
+    /// Gets the most recently published credentials, if any
+    #[must_use]
+    pub fn current(&self) -> Option<WebsocketInfo> {
+        self.sender.borrow().clone()
+    }
+
+    /// Creates a receiver notified on every credential publication
+    #[must_use]
+    pub fn subscribe(&self) -> watch::Receiver<Option<WebsocketInfo>> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for SessionHandle {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Session information for authenticated requests
@@ -91,6 +194,23 @@ impl Session {
         self.oauth_token.is_some()
     }
 
+    /// Gets the OAuth tokens of this session
+    ///
+    /// # Returns
+    /// * `Some(OAuthSession)` for API v3 sessions, `None` for CST/X-SECURITY-TOKEN sessions
+    #[must_use]
+    pub fn oauth_session(&self) -> Option<OAuthSession> {
+        let token = self.oauth_token.as_ref()?;
+        let expires_in = token.expires_in.parse::<i64>().unwrap_or(0);
+        Some(OAuthSession {
+            account_id: self.account_id.clone(),
+            access_token: token.access_token.clone(),
+            refresh_token: token.refresh_token.clone(),
+            token_type: token.token_type.clone(),
+            expires_at: token.created_at + chrono::Duration::seconds(expires_in),
+        })
+    }
+
     /// Checks if session is expired or will expire soon
     ///
     /// # Arguments
@@ -131,17 +251,8 @@ impl Session {
     /// * `WebsocketInfo` containing endpoint and authentication tokens
     #[must_use]
     pub fn get_websocket_info(&self) -> WebsocketInfo {
-        // Ensure the server URL has the https:// prefix
-        let server = if self.lightstreamer_endpoint.starts_with("http://")
-            || self.lightstreamer_endpoint.starts_with("https://")
-        {
-            format!("{}/lightstreamer", self.lightstreamer_endpoint)
-        } else {
-            format!("https://{}/lightstreamer", self.lightstreamer_endpoint)
-        };
-
         WebsocketInfo {
-            server,
+            server: lightstreamer_server_url(&self.lightstreamer_endpoint),
             cst: self.cst.clone(),
             x_security_token: self.x_security_token.clone(),
             account_id: self.account_id.clone(),
@@ -181,6 +292,15 @@ impl Auth {
             .build()
             .expect("Failed to create HTTP client");
 
+        Self::with_client(config, client)
+    }
+
+    /// Creates a new Auth instance that sends its requests through `client`
+    ///
+    /// # Arguments
+    /// * `config` - Configuration containing credentials and API settings
+    /// * `client` - Preconfigured reqwest client, e.g. with custom timeouts
+    pub fn with_client(config: Arc<Config>, client: Client) -> Self {
         let rate_limiter = Arc::new(RwLock::new(RateLimiter::new(&config.rate_limiter)));
 
         Self {
@@ -193,16 +313,37 @@ impl Auth {
 
     /// Gets the WebSocket password for Lightstreamer authentication
     ///
+    /// The stored CST/X-SECURITY-TOKEN session is reused when available, so the
+    /// streaming credentials follow the account selected with `switch_account`.
+    /// Otherwise a v2 login is performed to obtain the tokens.
+    ///
     /// # Returns
     /// * WebSocket password in format "CST-{cst}|XST-{token}" or empty string if session is not available
     pub async fn get_ws_info(&self) -> WebsocketInfo {
-        match self.login_v2().await {
-            Ok(sess) => sess.get_websocket_info(),
-            Err(e) => {
-                error!("Failed to get WebSocket info, login failed: {}", e);
-                WebsocketInfo::default()
-            }
+        self.try_get_ws_info().await.unwrap_or_else(|e| {
+            error!("Failed to get WebSocket info, login failed: {}", e);
+            WebsocketInfo::default()
+        })
+    }
+
+    /// Gets the WebSocket connection information, reporting login failures
+    ///
+    /// Behaves like `get_ws_info`, but returns the login error instead of empty
+    /// credentials.
+    ///
+    /// # Returns
+    /// * `Ok(WebsocketInfo)` - Server endpoint, streaming credentials and account ID
+    /// * `Err(AppError)` - If the v2 login needed to obtain the tokens fails
+    pub async fn try_get_ws_info(&self) -> Result<WebsocketInfo, AppError> {
+        if let Some(sess) = self.current_session().await
+            && sess.cst.is_some()
+            && sess.x_security_token.is_some()
+            && !sess.is_expired(None)
+        {
+            return Ok(sess.get_websocket_info());
         }
+
+        Ok(self.login_v2().await?.get_websocket_info())
     }
 
     /// Gets the current session, ensuring tokens are valid
@@ -232,6 +373,11 @@ impl Auth {
         self.login().await
     }
 
+    /// Gets the stored session without refreshing or logging in
+    pub async fn current_session(&self) -> Option<Session> {
+        self.session.read().await.clone()
+    }
+
     /// Performs initial login to IG Markets API
     ///
     /// Automatically detects API version from config and uses appropriate authentication method.
@@ -245,7 +391,7 @@ impl Auth {
         debug!("Logging in with API v{}", api_version);
 
         let session = if api_version == 3 {
-            self.login_oauth().await?
+            self.request_oauth_session().await?
         } else {
             self.login_v2().await?
         };
@@ -330,7 +476,7 @@ impl Auth {
         // Get response body as text first for debugging
         let body_text = response.text().await.map_err(|e| {
             error!("Failed to read response body: {}", e);
-            AppError::Network(e)
+            AppError::from(e)
         })?;
         debug!("Login response body length: {} bytes", body_text.len());
 
@@ -345,8 +491,90 @@ impl Auth {
         Ok(session)
     }
 
-    /// Performs login using API v3 (OAuth) with automatic retry on rate limit
-    async fn login_oauth(&self) -> Result<Session, AppError> {
+    /// Performs login using API v3 (OAuth) regardless of the configured API version
+    ///
+    /// The new session is stored, so subsequent requests send the
+    /// `Authorization: Bearer` and `IG-ACCOUNT-ID` headers.
+    ///
+    /// # Returns
+    /// * `Ok(Session)` - Authenticated OAuth session
+    /// * `Err(AppError)` - If login fails
+    pub async fn login_oauth(&self) -> Result<Session, AppError> {
+        let session = self.request_oauth_session().await?;
+
+        let mut sess = self.session.write().await;
+        *sess = Some(session.clone());
+
+        info!("✓ OAuth login successful, account: {}", session.account_id);
+        Ok(session)
+    }
+
+    /// Refreshes the OAuth access token using the stored refresh token
+    ///
+    /// Falls back to a full login when there is no OAuth session or when IG
+    /// rejects the refresh token.
+    ///
+    /// # Returns
+    /// * `Ok(Session)` - Session with fresh OAuth tokens
+    /// * `Err(AppError)` - If both refresh and re-authentication fail
+    pub async fn refresh_oauth(&self) -> Result<Session, AppError> {
+        let current_session = {
+            let session = self.session.read().await;
+            session.clone()
+        };
+
+        let Some((mut session, refresh_token)) = current_session.and_then(|sess| {
+            let refresh_token = sess.oauth_token.as_ref()?.refresh_token.clone();
+            Some((sess, refresh_token))
+        }) else {
+            warn!("No OAuth session to refresh, performing login");
+            return self.login().await;
+        };
+
+        let url = format!("{}/session/refresh-token", self.config.rest_api.base_url);
+        let body = serde_json::json!({ "refresh_token": refresh_token });
+        let headers = vec![
+            ("X-IG-API-KEY", self.config.credentials.api_key.as_str()),
+            ("Content-Type", "application/json"),
+            ("Version", "1"),
+        ];
+
+        debug!("Sending OAuth refresh request to: {}", url);
+        let token = match make_http_request(
+            &self.client,
+            self.rate_limiter.clone(),
+            Method::POST,
+            &url,
+            headers,
+            &Some(body),
+            RetryConfig::infinite(),
+        )
+        .await
+        {
+            Ok(response) => response.json::<OAuthToken>().await.map_err(AppError::from),
+            Err(e) => Err(e),
+        };
+
+        match token {
+            Ok(token) => {
+                session.expires_at = token.expire_at(1);
+                session.oauth_token = Some(token);
+
+                let mut sess = self.session.write().await;
+                *sess = Some(session.clone());
+
+                debug!("OAuth token refreshed for account: {}", session.account_id);
+                Ok(session)
+            }
+            Err(e) => {
+                warn!("OAuth token refresh failed ({}), performing login", e);
+                self.login().await
+            }
+        }
+    }
+
+    /// Requests a new API v3 (OAuth) session with automatic retry on rate limit
+    async fn request_oauth_session(&self) -> Result<Session, AppError> {
         let url = format!("{}/session", self.config.rest_api.base_url);
 
         let body = serde_json::json!({
@@ -397,7 +625,10 @@ impl Auth {
         };
 
         if let Some(sess) = current_session {
-            if sess.is_expired(Some(1)) {
+            if sess.is_expired(Some(1)) && sess.is_oauth() {
+                debug!("OAuth token expired, refreshing");
+                self.refresh_oauth().await
+            } else if sess.is_expired(Some(1)) {
                 debug!("Session expired, performing login");
                 self.login().await
             } else {
@@ -3,22 +3,49 @@
    Email: jb@taunais.com
    Date: 19/10/25
 ******************************************************************************/
-use crate::application::auth::WebsocketInfo;
+use crate::application::auth::{OAuthSession, Session, SessionHandle, WebsocketInfo};
+use crate::application::config::{Config, IgEnvironment};
 use crate::application::interfaces::account::AccountService;
 use crate::application::interfaces::market::MarketService;
 use crate::application::interfaces::order::OrderService;
-use crate::error::AppError;
-use crate::model::http::HttpClient;
-use crate::model::requests::RecentPricesRequest;
+use crate::application::interfaces::watchlist::WatchlistService;
+use crate::application::market_cache::MarketDetailsCache;
+use crate::application::price_allowance::PriceAllowanceMonitor;
+use crate::application::rate_limiter::RateLimiters;
+use crate::application::stream_stats::{StreamStats, StreamStatsRecorder};
+use crate::application::streamer_config::{StreamReconnectPolicy, StreamerConfig};
+use crate::application::subscription_events::{
+    SubscriptionEvent, SubscriptionEventHub, SubscriptionStatusListener,
+};
+use crate::constants::{
+    ACTIVITY_QUERY_DATE_FORMAT, BACKFILL_ALLOWANCE_PAUSE_SECS, BACKFILL_ALLOWANCE_RESERVE,
+    DEFAULT_BULK_CLOSE_CONCURRENCY, DEFAULT_DEAL_POLL_INTERVAL_MS,
+    DEFAULT_MARKET_DETAILS_BULK_CONCURRENCY, DEFAULT_MARKET_TRAVERSAL_CONCURRENCY,
+    DEFAULT_MARKET_TRAVERSAL_DEPTH, DRY_RUN_DEAL_REFERENCE_PREFIX, IDLE_WATCHDOG_POLLS,
+    MAX_CLOCK_DRIFT_SECS, MAX_MARKET_DETAILS_EPICS, MAX_OPTION_CHAIN_DEPTH,
+    PRICES_PATH_DATE_FORMAT, STREAM_HEALTH_POLL_INTERVAL_MS, TRADE_BACKFILL_SETTLE_SECS,
+    TRANSACTIONS_PAGE_SIZE, WS_INFO_RETRIES, WS_INFO_RETRY_DELAY_MS,
+};
+use crate::error::{AppError, RateLimitScope};
+use crate::model::http::{HttpClient, HttpClientConfig, parse_http_date};
+use crate::model::http_observer::HttpObserver;
+use crate::model::requests::{
+    ActivityFilter, MarketDetailFilter, MarketSearchFilter, RecentPricesRequest,
+};
 use crate::model::requests::{
-    ClosePositionRequest, CreateOrderRequest, CreateWorkingOrderRequest, UpdatePositionRequest,
+    AddToWatchlistRequest, ClosePositionRequest, CreateOrderRequest, CreateWatchlistRequest,
+    CreateWorkingOrderRequest, UpdatePositionRequest, UpdateWorkingOrderRequest,
 };
 use crate::model::responses::{
-    CategoriesResponse, CategoryInstrumentsResponse, DBEntryResponse, HistoricalPricesResponse,
-    MarketNavigationResponse, MarketSearchResponse, MultipleMarketDetailsResponse,
+    CategoriesResponse, CategoryInstrumentsResponse, ClientSentimentListResponse,
+    ClientSentimentResponse, DBEntryResponse, HistoricalPricesResponse, MarketDetailsBatchOutcome,
+    MarketHierarchyStats, MarketNavigationResponse, MarketSearchResponse,
+    MultipleMarketDetailsResponse,
 };
 use crate::model::responses::{
-    ClosePositionResponse, CreateOrderResponse, CreateWorkingOrderResponse, UpdatePositionResponse,
+    ClosePositionOutcome, ClosePositionResponse, CreateOrderResponse, CreateWatchlistResponse,
+    CreateWorkingOrderResponse, UpdatePositionResponse, WatchlistResponse, WatchlistStatusResponse,
+    WatchlistsResponse,
 };
 use crate::model::streaming::{
     StreamingAccountDataField, StreamingChartField, StreamingMarketField, StreamingPriceField,
@@ -26,34 +53,347 @@ use crate::model::streaming::{
     get_streaming_price_fields,
 };
 use crate::prelude::{
-    AccountActivityResponse, AccountFields, AccountsResponse, ChartData, ChartScale,
-    OrderConfirmationResponse, PositionsResponse, TradeFields, TransactionHistoryResponse,
-    WorkingOrdersResponse,
+    AccountActivityResponse, AccountFields, AccountTransaction, AccountsResponse, ChartData,
+    ChartScale, OrderConfirmationResponse, OrderOutcome, Position, PositionsResponse, TradeFields,
+    TransactionHistoryResponse, WorkingOrdersResponse,
 };
-use crate::presentation::market::{MarketData, MarketDetails};
-use crate::presentation::price::PriceData;
+use crate::presentation::account::AccountData;
+use crate::presentation::market::{
+    FilteredMarketDetails, HistoricalPrice, MarketData, MarketDetails, MarketNavigationNode,
+    PriceAllowance, Resolution,
+};
+use crate::presentation::option_chain::OptionChain;
+use crate::presentation::order::Status;
+use crate::presentation::price::{PriceData, Quote, QuoteAggregator};
+use crate::presentation::trade::{TradeConfirmation, TradeData};
+use crate::utils::retry::{RetryPolicy, is_transient, retry_with_backoff};
 use async_trait::async_trait;
-use lightstreamer_rs::client::{LightstreamerClient, Transport};
+use chrono::{DateTime, Utc};
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use lightstreamer_rs::client::{LightstreamerClient, SubscriptionRequest};
 use lightstreamer_rs::subscription::{
-    ChannelSubscriptionListener, Snapshot, Subscription, SubscriptionMode,
+    ChannelSubscriptionListener, ItemUpdate, Snapshot, Subscription, SubscriptionMode,
 };
 use lightstreamer_rs::utils::setup_signal_hook;
-use serde_json::Value;
-use std::collections::HashSet;
+use reqwest::StatusCode;
+use reqwest::header::DATE;
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::Duration;
-use tokio::sync::{Mutex, Notify, RwLock, mpsc};
-use tokio::time::sleep;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{Mutex, Notify, mpsc, watch};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, sleep, sleep_until, timeout_at};
 use tracing::{debug, error, info, warn};
 
-const MAX_CONNECTION_ATTEMPTS: u64 = 3;
-
 /// Main client for interacting with IG Markets API
 ///
 /// This client provides a unified interface for all IG Markets API operations,
 /// including market data, account management, and order execution.
 pub struct Client {
     http_client: Arc<HttpClient>,
+    session_handle: SessionHandle,
+    price_allowance: Arc<PriceAllowanceMonitor>,
+    dry_run: bool,
+    market_cache: Option<MarketDetailsCache>,
+}
+
+/// Checks whether a deal reference was generated by a client in dry-run mode
+///
+/// # Arguments
+/// * `deal_reference` - Reference returned when the deal was requested
+#[must_use]
+pub fn is_dry_run_reference(deal_reference: &str) -> bool {
+    deal_reference.starts_with(DRY_RUN_DEAL_REFERENCE_PREFIX)
+}
+
+/// Sequence number keeping dry-run deal references unique within the process
+static DRY_RUN_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Generates a unique synthetic deal reference for a skipped order
+///
+/// The reference combines the current time with a process-wide sequence number.
+fn dry_run_reference() -> String {
+    format!(
+        "{}{}-{}",
+        DRY_RUN_DEAL_REFERENCE_PREFIX,
+        Utc::now().timestamp_millis(),
+        DRY_RUN_SEQUENCE.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// Streams historical prices chunk by chunk, as described on `Client::backfill_prices`
+///
+/// `fetch` requests one window given its start and end dates, formatted with
+/// `PRICES_PATH_DATE_FORMAT`; the allowance it reports is expected to reach `allowance`.
+fn backfill_chunks<'a, F, Fut>(
+    allowance: &'a PriceAllowanceMonitor,
+    epic: &'a str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    chunk: chrono::Duration,
+    fetch: F,
+) -> impl Stream<Item = Result<HistoricalPrice, AppError>> + 'a
+where
+    F: Fn(String, String) -> Fut + 'a,
+    Fut: Future<Output = Result<HistoricalPricesResponse, AppError>> + 'a,
+{
+    stream::try_unfold(
+        (from, None::<DateTime<Utc>>),
+        move |(cursor, last_emitted)| {
+            let end = cursor
+                .checked_add_signed(chunk)
+                .map_or(to, |end| end.min(to));
+            let start_date = cursor.format(PRICES_PATH_DATE_FORMAT).to_string();
+            let end_date = end.format(PRICES_PATH_DATE_FORMAT).to_string();
+            // Futures are lazy: nothing is requested before the pause below
+            let request = (cursor < to && chunk > chrono::Duration::zero())
+                .then(|| fetch(start_date.clone(), end_date));
+            async move {
+                if cursor >= to {
+                    return Ok(None);
+                }
+                let Some(request) = request else {
+                    return Err(AppError::InvalidInput(
+                        "backfill chunk must be positive".to_string(),
+                    ));
+                };
+                if let Some(last) = allowance.last_allowance()
+                    && last.remaining() < BACKFILL_ALLOWANCE_RESERVE
+                {
+                    let resets_in = allowance.resets_in().unwrap_or_default();
+                    warn!(
+                        "Backfill of {} paused: {} historical points left, resets in {}s",
+                        epic,
+                        last.remaining(),
+                        resets_in.as_secs()
+                    );
+                    sleep(resets_in).await;
+                }
+
+                let response = match request.await {
+                    Ok(response) => response,
+                    Err(AppError::AllowanceExceeded { resets_in }) => {
+                        let pause =
+                            resets_in.unwrap_or(Duration::from_secs(BACKFILL_ALLOWANCE_PAUSE_SECS));
+                        warn!(
+                            "Backfill of {} hit the price allowance, retrying {} in {}s",
+                            epic,
+                            start_date,
+                            pause.as_secs()
+                        );
+                        sleep(pause).await;
+                        return Ok(Some((Vec::new(), (cursor, last_emitted))));
+                    }
+                    Err(e) => return Err(e),
+                };
+
+                let mut latest = last_emitted;
+                let prices: Vec<_> = response
+                    .prices
+                    .into_iter()
+                    .filter(|price| match price.snapshot_time_utc() {
+                        Some(time) if last_emitted.is_some_and(|last| time <= last) => false,
+                        Some(time) => {
+                            latest = latest.max(Some(time));
+                            true
+                        }
+                        None => true,
+                    })
+                    .collect();
+                Ok::<_, AppError>(Some((prices, (end, latest))))
+            }
+        },
+    )
+    .map_ok(|prices| stream::iter(prices.into_iter().map(Ok)))
+    .try_flatten()
+}
+
+/// Gets the deal reference of the confirmation carried by a trade update, if any
+fn confirmed_deal_reference(update: &ItemUpdate) -> Option<String> {
+    let confirms = update.fields.get("CONFIRMS")?.as_deref()?;
+    serde_json::from_str::<TradeConfirmation>(confirms)
+        .ok()?
+        .deal_reference
+}
+
+/// Relays trade updates, replaying the activity missed around each reconnection
+///
+/// A change of `reconnects` only opens a gap starting at the last relayed update. The
+/// gap is fetched `TRADE_BACKFILL_SETTLE_SECS` after the last reconnection, so its end
+/// is taken once the connection is back up. Live confirmations relayed meanwhile are
+/// not replayed again.
+async fn relay_trades<F, Fut>(
+    mut reconnects: watch::Receiver<u64>,
+    mut item_receiver: mpsc::UnboundedReceiver<ItemUpdate>,
+    tx: mpsc::UnboundedSender<ItemUpdate>,
+    fetch: F,
+) where
+    F: Fn(DateTime<Utc>, DateTime<Utc>) -> Fut,
+    Fut: Future<Output = Vec<ItemUpdate>>,
+{
+    let settle = Duration::from_secs(TRADE_BACKFILL_SETTLE_SECS);
+    let mut last_seen = Utc::now();
+    let mut reconnects_open = true;
+    // Start of the gap waiting to be fetched, and when to fetch it
+    let mut pending: Option<(DateTime<Utc>, Instant)> = None;
+    let mut relayed_while_pending: HashSet<String> = HashSet::new();
+    loop {
+        let deadline = pending.map(|(_, deadline)| deadline);
+        tokio::select! {
+            update = item_receiver.recv() => {
+                let Some(update) = update else { break };
+                last_seen = Utc::now();
+                if pending.is_some()
+                    && let Some(reference) = confirmed_deal_reference(&update)
+                {
+                    relayed_while_pending.insert(reference);
+                }
+                if tx.send(update).is_err() {
+                    break;
+                }
+            }
+            changed = reconnects.changed(), if reconnects_open => {
+                if changed.is_err() {
+                    reconnects_open = false;
+                    continue;
+                }
+                let from = pending.map_or(last_seen, |(from, _)| from);
+                pending = Some((from, Instant::now() + settle));
+            }
+            _ = sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                let Some((from, _)) = pending.take() else { continue };
+                let to = Utc::now();
+                for update in fetch(from, to).await {
+                    if confirmed_deal_reference(&update)
+                        .is_some_and(|reference| relayed_while_pending.contains(&reference))
+                    {
+                        continue;
+                    }
+                    if tx.send(update).is_err() {
+                        return;
+                    }
+                }
+                relayed_while_pending.clear();
+                last_seen = last_seen.max(to);
+            }
+        }
+    }
+}
+
+/// Fetches market details in batches of `MAX_MARKET_DETAILS_EPICS`, as described on
+/// `MarketService::get_market_details_batches`
+///
+/// `fetch` requests the details of one batch; outcomes are returned in batch order.
+/// Batches are owned and the fetch futures boxed so that no borrow of `epics` ends
+/// up in a higher-ranked lifetime of the `async_trait` futures calling this.
+async fn market_details_batches<'a, F>(epics: &[String], fetch: F) -> Vec<MarketDetailsBatchOutcome>
+where
+    F: Fn(Vec<String>) -> BoxFuture<'a, Result<MultipleMarketDetailsResponse, AppError>>,
+{
+    stream::iter(
+        epics
+            .chunks(MAX_MARKET_DETAILS_EPICS)
+            .map(<[String]>::to_vec),
+    )
+    .map(|batch| {
+        let response = fetch(batch.clone());
+        async move {
+            let result = response.await.map(|response| {
+                let mut by_epic: HashMap<String, MarketDetails> = response
+                    .market_details
+                    .into_iter()
+                    .map(|details| (details.instrument.epic.clone(), details))
+                    .collect();
+                batch
+                    .iter()
+                    .filter_map(|epic| by_epic.remove(epic))
+                    .collect()
+            });
+            if let Err(e) = &result {
+                error!(
+                    "Failed to get market details for a batch of {} EPICs: {:?}",
+                    batch.len(),
+                    e
+                );
+            }
+            MarketDetailsBatchOutcome {
+                epics: batch,
+                result,
+            }
+        }
+    })
+    .buffered(DEFAULT_MARKET_DETAILS_BULK_CONCURRENCY)
+    .collect()
+    .await
+}
+
+/// Concatenates the details of every batch, failing with the first batch error
+fn collect_market_details(
+    outcomes: Vec<MarketDetailsBatchOutcome>,
+) -> Result<Vec<MarketDetails>, AppError> {
+    let mut market_details = Vec::new();
+    for outcome in outcomes {
+        market_details.extend(outcome.result?);
+    }
+    Ok(market_details)
+}
+
+/// Polls a deal confirmation until it is accepted or rejected, or `timeout` elapses
+///
+/// An unknown deal, a pending confirmation and transient errors are polled again
+/// every `poll_interval`; any other error is returned at once.
+async fn poll_deal_confirmation<F, Fut>(
+    deal_reference: &str,
+    timeout: Duration,
+    poll_interval: Duration,
+    mut fetch: F,
+) -> Result<OrderConfirmationResponse, AppError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<OrderConfirmationResponse, AppError>>,
+{
+    let deadline = Instant::now() + timeout;
+    loop {
+        match timeout_at(deadline, fetch()).await {
+            Ok(Ok(confirmation)) if confirmation.is_terminal() => {
+                debug!(
+                    "Deal {} confirmed with status {:?}",
+                    deal_reference, confirmation.deal_status
+                );
+                return Ok(confirmation);
+            }
+            Ok(Ok(confirmation)) => {
+                debug!(
+                    "Deal {} still pending: {:?}",
+                    deal_reference, confirmation.deal_status
+                );
+            }
+            Ok(Err(AppError::NotFound | AppError::Unexpected(StatusCode::NOT_FOUND))) => {
+                debug!("Deal {} not known yet", deal_reference);
+            }
+            Ok(Err(e)) if is_transient(&e) => {
+                warn!("Failed to get confirmation for {}: {}", deal_reference, e);
+            }
+            Ok(Err(e)) => return Err(e),
+            Err(_) => break,
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+        sleep(poll_interval.min(deadline - now)).await;
+    }
+
+    Err(AppError::Timeout(format!(
+        "deal {} not confirmed within {} ms",
+        deal_reference,
+        timeout.as_millis()
+    )))
 }
 
 impl Client {
@@ -62,56 +402,127 @@ impl Client {
     /// # Returns
     /// A new Client with default configuration
     pub fn new() -> Self {
-        let http_client = Arc::new(HttpClient::default());
-        Self { http_client }
+        Self::with_config(HttpClientConfig::default()).expect("Failed to create HTTP client")
     }
 
-    /// Gets WebSocket connection information for Lightstreamer
+    /// Creates a new client with custom connection and timeout settings
+    ///
+    /// # Arguments
+    /// * `http_config` - Timeouts and user agent for the underlying HTTP client
     ///
     /// # Returns
-    /// * `WebsocketInfo` containing server endpoint, authentication tokens, and account ID
-    pub async fn get_ws_info(&self) -> WebsocketInfo {
-        self.http_client.get_ws_info().await
+    /// * `Ok(Client)` - Client whose requests fail with `AppError::Timeout` when too slow
+    /// * `Err(AppError)` - If the HTTP client cannot be built
+    pub fn with_config(http_config: HttpClientConfig) -> Result<Self, AppError> {
+        Self::from_config(Config::default(), http_config)
     }
-}
 
-impl Default for Client {
-    fn default() -> Self {
-        Self::new()
+    /// Creates a new client from an explicit configuration
+    ///
+    /// Every other constructor ends up here. Non-trading requests are throttled as
+    /// set in `config.rate_limiter` (the `IG_RATE_LIMIT_*` variables by default).
+    ///
+    /// # Arguments
+    /// * `config` - Credentials, REST host and rate limits
+    /// * `http_config` - Timeouts and user agent for the underlying HTTP client
+    ///
+    /// # Returns
+    /// * `Ok(Client)` - Client authenticating lazily on the first request
+    /// * `Err(AppError)` - If the HTTP client cannot be built
+    pub fn from_config(config: Config, http_config: HttpClientConfig) -> Result<Self, AppError> {
+        let http_client = HttpClient::lazy_with_http_config(config, http_config)?;
+        Ok(Self {
+            http_client: Arc::new(http_client),
+            session_handle: SessionHandle::new(),
+            price_allowance: Arc::new(PriceAllowanceMonitor::new()),
+            dry_run: false,
+            market_cache: None,
+        })
     }
-}
 
-#[async_trait]
-impl MarketService for Client {
-    async fn search_markets(&self, search_term: &str) -> Result<MarketSearchResponse, AppError> {
-        let path = format!("markets?searchTerm={}", search_term);
-        info!("Searching markets with term: {}", search_term);
-        let result: MarketSearchResponse = self.http_client.get(&path, Some(1)).await?;
-        debug!("{} markets found", result.markets.len());
-        Ok(result)
+    /// Creates a new client targeting the given IG environment
+    ///
+    /// Credentials and every other setting are read as in [`Client::new`]; only the
+    /// REST host is overridden. The Lightstreamer endpoint follows, as `get_ws_info`
+    /// returns the one of the session opened against that host.
+    ///
+    /// # Arguments
+    /// * `env` - Demo or live environment
+    ///
+    /// # Returns
+    /// A new Client for the environment
+    pub fn new_for(env: IgEnvironment) -> Self {
+        let mut config = Config::new();
+        config.rest_api.base_url = env.rest_base_url().to_string();
+        Self::from_config(config, HttpClientConfig::default())
+            .expect("Failed to create HTTP client")
     }
 
-    async fn get_market_details(&self, epic: &str) -> Result<MarketDetails, AppError> {
-        let path = format!("markets/{epic}");
-        info!("Getting market details: {}", epic);
-        let market_value: Value = self.http_client.get(&path, Some(3)).await?;
-        let market_details: MarketDetails = serde_json::from_value(market_value)?;
-        debug!("Market details obtained for: {}", epic);
-        Ok(market_details)
+    /// Enables or disables dry-run mode
+    ///
+    /// In dry-run mode no deal is sent to IG: order creation, position updates and
+    /// closes, and working order changes return a synthetic response whose deal
+    /// reference starts with `DRYRUN-`, and `get_order_confirmation` answers those
+    /// references with an ACCEPTED confirmation. Read-only requests still hit the API.
+    ///
+    /// # Arguments
+    /// * `dry_run` - Whether to skip dealing requests
+    ///
+    /// # Returns
+    /// The client with the mode applied
+    #[must_use]
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
     }
 
-    async fn get_multiple_market_details(
+    /// Enables an in-memory cache of market details
+    ///
+    /// `get_market_details` and `get_multiple_market_details` serve epics fetched
+    /// within `ttl` from memory and only request the others. Only successful
+    /// responses are cached; filtered detail requests bypass the cache.
+    ///
+    /// # Arguments
+    /// * `ttl` - How long fetched details are reused
+    ///
+    /// # Returns
+    /// The client with the cache enabled
+    #[must_use]
+    pub fn with_market_cache(mut self, ttl: Duration) -> Self {
+        self.market_cache = Some(MarketDetailsCache::new(ttl));
+        self
+    }
+
+    /// Empties the market details cache, if enabled
+    pub fn clear_market_cache(&self) {
+        if let Some(cache) = &self.market_cache {
+            cache.clear();
+        }
+    }
+
+    /// Checks whether the client is in dry-run mode
+    #[must_use]
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Gets the rate limiters applied to the client's REST requests
+    #[must_use]
+    pub fn rate_limiters(&self) -> &RateLimiters {
+        self.http_client.rate_limiters()
+    }
+
+    /// Gets the environment the client targets, inferred from its REST host
+    #[must_use]
+    pub fn environment(&self) -> IgEnvironment {
+        self.http_client.config().environment()
+    }
+
+    /// Requests the details of up to 50 epics in a single call
+    async fn fetch_multiple_market_details(
         &self,
         epics: &[String],
     ) -> Result<MultipleMarketDetailsResponse, AppError> {
-        if epics.is_empty() {
-            return Ok(MultipleMarketDetailsResponse::default());
-        } else if epics.len() > 50 {
-            return Err(AppError::InvalidInput(
-                "The maximum number of EPICs is 50".to_string(),
-            ));
-        }
-
         let epics_str = epics.join(",");
         let path = format!("markets?epics={}", epics_str);
         debug!(
@@ -124,152 +535,484 @@ impl MarketService for Client {
         Ok(response)
     }
 
-    async fn get_historical_prices(
-        &self,
-        epic: &str,
-        resolution: &str,
-        from: &str,
-        to: &str,
-    ) -> Result<HistoricalPricesResponse, AppError> {
-        let path = format!(
-            "prices/{}?resolution={}&from={}&to={}",
-            epic, resolution, from, to
-        );
-        info!("Getting historical prices for: {}", epic);
-        let result: HistoricalPricesResponse = self.http_client.get(&path, Some(3)).await?;
-        debug!("Historical prices obtained for: {}", epic);
-        Ok(result)
+    /// Logs a prominent warning before an order is sent to the live environment
+    fn warn_if_live(&self, action: &str, epic: &str) {
+        if self.environment() == IgEnvironment::Live {
+            warn!(
+                "LIVE ENVIRONMENT: {} on {} will trade real money",
+                action, epic
+            );
+        }
     }
 
-    async fn get_historical_prices_by_date_range(
-        &self,
-        epic: &str,
-        resolution: &str,
-        start_date: &str,
-        end_date: &str,
-    ) -> Result<HistoricalPricesResponse, AppError> {
-        let path = format!("prices/{}/{}/{}/{}", epic, resolution, start_date, end_date);
-        info!(
-            "Getting historical prices for epic: {}, resolution: {}, from: {} to: {}",
-            epic, resolution, start_date, end_date
-        );
-        let result: HistoricalPricesResponse = self.http_client.get(&path, Some(2)).await?;
-        debug!(
-            "Historical prices obtained for epic: {}, {} data points",
-            epic,
-            result.prices.len()
-        );
-        Ok(result)
+    /// Gets the synthetic deal reference to return instead of a skipped dealing request
+    ///
+    /// # Arguments
+    /// * `action` - Skipped request, for the log
+    ///
+    /// # Returns
+    /// The reference in dry-run mode, or `None` when the request must be sent
+    fn skip_in_dry_run(&self, action: &str) -> Option<String> {
+        if !self.dry_run {
+            return None;
+        }
+        let deal_reference = dry_run_reference();
+        info!("Dry run: {} not sent, reference {}", action, deal_reference);
+        Some(deal_reference)
     }
 
-    async fn get_recent_prices(
+    /// Creates a new client from validated environment variables
+    ///
+    /// See [`Config::from_env`] for the variables read.
+    ///
+    /// # Returns
+    /// * `Ok(Client)` - Client for the environment selected by `IG_ENV`
+    /// * `Err(AppError::Config)` - If required variables are missing or invalid
+    pub fn from_env() -> Result<Self, AppError> {
+        Self::from_config(Config::from_env()?, HttpClientConfig::default())
+    }
+
+    /// Gets WebSocket connection information for Lightstreamer
+    ///
+    /// # Returns
+    /// * `WebsocketInfo` containing server endpoint, authentication tokens, and account ID
+    pub async fn get_ws_info(&self) -> WebsocketInfo {
+        self.http_client.get_ws_info().await
+    }
+
+    /// Gets WebSocket connection information, retrying transient failures
+    ///
+    /// Network errors, timeouts, rate limiting and 5xx responses are retried after a
+    /// fixed delay. Authentication failures, such as rejected credentials, are
+    /// returned at once since retrying cannot fix them.
+    ///
+    /// # Arguments
+    /// * `retries` - Maximum number of retries after the first attempt
+    /// * `delay` - Delay between two attempts
+    ///
+    /// # Returns
+    /// * `Ok(WebsocketInfo)` - Server endpoint, authentication tokens and account ID
+    /// * `Err(AppError)` - The authentication error, or the last transient error
+    pub async fn get_ws_info_with_retry(
         &self,
-        params: &RecentPricesRequest<'_>,
-    ) -> Result<HistoricalPricesResponse, AppError> {
-        let mut query_params = Vec::new();
+        retries: u32,
+        delay: Duration,
+    ) -> Result<WebsocketInfo, AppError> {
+        retry_with_backoff(
+            || self.http_client.try_get_ws_info(),
+            RetryPolicy::new(retries, delay).with_multiplier(1.0),
+        )
+        .await
+    }
 
-        if let Some(res) = params.resolution {
-            query_params.push(format!("resolution={}", res));
-        }
-        if let Some(f) = params.from {
-            query_params.push(format!("from={}", f));
-        }
-        if let Some(t) = params.to {
-            query_params.push(format!("to={}", t));
-        }
-        if let Some(max) = params.max_points {
-            query_params.push(format!("max={}", max));
-        }
-        if let Some(size) = params.page_size {
-            query_params.push(format!("pageSize={}", size));
-        }
-        if let Some(num) = params.page_number {
-            query_params.push(format!("pageNumber={}", num));
+    /// Gets the shared session handle used to keep streaming clients in sync
+    ///
+    /// # Returns
+    /// A clone of the `SessionHandle` on which refreshed credentials are published
+    #[must_use]
+    pub fn session_handle(&self) -> SessionHandle {
+        self.session_handle.clone()
+    }
+
+    /// Forces a fresh login and propagates the new credentials to streaming clients
+    ///
+    /// The ordering is guaranteed as follows:
+    /// 1. The REST session is re-authenticated; on failure nothing is published.
+    /// 2. Fresh `WebsocketInfo` is fetched and published on the `SessionHandle`.
+    /// 3. Every `StreamerClient` created with `StreamerClient::from_client` closes its
+    ///    current connection, applies the new password, re-issues its subscriptions in
+    ///    their original order and reconnects. Updates published by the server while
+    ///    the connection is being rebuilt are not delivered; a fresh snapshot is sent
+    ///    for every re-issued subscription instead.
+    ///
+    /// # Returns
+    /// * `Ok(Session)` - The refreshed session
+    /// * `Err(AppError)` - If re-authentication fails
+    pub async fn refresh_session(&self) -> Result<Session, AppError> {
+        info!("Refreshing session");
+        let session = self.http_client.auth().login().await?;
+
+        let ws_info = self.http_client.get_ws_info().await;
+        match ws_info.get_ws_password() {
+            Ok(_) => {
+                self.session_handle.publish(ws_info);
+                debug!("Fresh streaming credentials published");
+            }
+            Err(e) => warn!(
+                "Session refreshed but no streaming credentials were obtained: {}",
+                e
+            ),
         }
 
-        let query_string = if query_params.is_empty() {
-            String::new()
-        } else {
-            format!("?{}", query_params.join("&"))
-        };
+        Ok(session)
+    }
 
-        let path = format!("prices/{}{}", params.epic, query_string);
-        info!("Getting recent prices for epic: {}", params.epic);
-        let result: HistoricalPricesResponse = self.http_client.get(&path, Some(3)).await?;
-        debug!(
-            "Recent prices obtained for epic: {}, {} data points",
-            params.epic,
-            result.prices.len()
-        );
-        Ok(result)
+    /// Gets IG's server time from the `Date` header of a lightweight request
+    ///
+    /// A warning is logged when the local clock differs from the server time by
+    /// more than `MAX_CLOCK_DRIFT_SECS`. The header has a one-second resolution.
+    ///
+    /// # Returns
+    /// * `Ok(DateTime<Utc>)` - Server time reported by IG
+    /// * `Err(AppError)` - If the request fails or the response has no valid `Date` header
+    pub async fn get_server_time(&self) -> Result<DateTime<Utc>, AppError> {
+        let sent_at = Utc::now();
+        let response = self.http_client.get_response("session", Some(1)).await?;
+        let received_at = Utc::now();
+        let server_time = response
+            .headers()
+            .get(DATE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_http_date)
+            .ok_or_else(|| {
+                AppError::Deserialization("missing or invalid Date header".to_string())
+            })?;
+        check_clock_drift(server_time, sent_at + (received_at - sent_at) / 2);
+        Ok(server_time)
     }
 
-    async fn get_historical_prices_by_count_v1(
-        &self,
-        epic: &str,
-        resolution: &str,
-        num_points: i32,
-    ) -> Result<HistoricalPricesResponse, AppError> {
-        let path = format!("prices/{}/{}/{}", epic, resolution, num_points);
-        info!(
-            "Getting historical prices (v1) for epic: {}, resolution: {}, points: {}",
-            epic, resolution, num_points
-        );
-        let result: HistoricalPricesResponse = self.http_client.get(&path, Some(1)).await?;
-        debug!(
-            "Historical prices (v1) obtained for epic: {}, {} data points",
-            epic,
-            result.prices.len()
-        );
-        Ok(result)
+    /// Issues a cheap authenticated GET and measures the round-trip latency
+    ///
+    /// # Returns
+    /// * `Ok(Duration)` - Time from sending the request until the response headers arrived
+    /// * `Err(AppError)` - If the request fails
+    pub async fn ping(&self) -> Result<Duration, AppError> {
+        let started = Instant::now();
+        let sent_at = Utc::now();
+        let response = self.http_client.get_response("session", Some(1)).await?;
+        let latency = started.elapsed();
+        if let Some(server_time) = response
+            .headers()
+            .get(DATE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_http_date)
+        {
+            let half_trip = chrono::Duration::from_std(latency / 2).unwrap_or_default();
+            check_clock_drift(server_time, sent_at + half_trip);
+        }
+        debug!("Ping round trip: {} ms", latency.as_millis());
+        Ok(latency)
     }
 
-    async fn get_historical_prices_by_count_v2(
-        &self,
-        epic: &str,
-        resolution: &str,
-        num_points: i32,
+    /// Logs in with the API v3 (OAuth) flow, whatever the configured API version
+    ///
+    /// Subsequent requests are sent with the `Authorization: Bearer` and
+    /// `IG-ACCOUNT-ID` headers, and the access token is refreshed automatically
+    /// when IG reports it as invalid.
+    ///
+    /// # Returns
+    /// The OAuth tokens of the new session
+    pub async fn login_oauth(&self) -> Result<OAuthSession, AppError> {
+        let session = self.http_client.auth().login_oauth().await?;
+        session
+            .oauth_session()
+            .ok_or_else(|| AppError::Generic("login did not return an OAuth session".to_string()))
+    }
+
+    /// Refreshes the OAuth access token using the current refresh token
+    ///
+    /// Falls back to a full login when the refresh token is rejected.
+    ///
+    /// # Returns
+    /// The refreshed OAuth tokens
+    pub async fn refresh_oauth(&self) -> Result<OAuthSession, AppError> {
+        let session = self.http_client.auth().refresh_oauth().await?;
+        session
+            .oauth_session()
+            .ok_or_else(|| AppError::Generic("session is not an OAuth session".to_string()))
+    }
+
+    /// Registers a callback fired when the historical price allowance runs low
+    ///
+    /// Every historical prices response carries the remaining allowance; the callback
+    /// is invoked each time that value is below `threshold`.
+    ///
+    /// # Arguments
+    /// * `threshold` - Remaining allowance below which the callback fires
+    /// * `callback` - Function invoked with the reported `PriceAllowance`
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// client.on_price_allowance_low(500, |allowance| {
+    ///     warn!("Only {} historical points left", allowance.remaining_allowance);
+    /// });
+    /// ```
+    pub fn on_price_allowance_low<F>(&self, threshold: i64, callback: F)
+    where
+        F: Fn(&PriceAllowance) + Send + Sync + 'static,
+    {
+        self.price_allowance.set_threshold(threshold, callback);
+    }
+
+    /// Sets the observer notified of every REST call sent by this client
+    ///
+    /// Credentials are redacted from the headers the observer receives.
+    ///
+    /// # Arguments
+    /// * `observer` - Receives the method, path, status and latency of each call
+    pub fn set_http_observer(&self, observer: Arc<dyn HttpObserver>) {
+        self.http_client.set_observer(observer);
+    }
+
+    /// Logs a warning whenever the remaining historical price allowance is below `threshold`
+    ///
+    /// # Arguments
+    /// * `threshold` - Remaining allowance below which to warn, or `None` to disable
+    pub fn set_price_allowance_warning(&self, threshold: Option<i64>) {
+        self.price_allowance.set_warning_threshold(threshold);
+    }
+
+    /// Sets a delay applied before historical requests while the allowance is low
+    ///
+    /// # Arguments
+    /// * `delay` - Delay to apply, or `None` to disable automatic throttling
+    pub fn set_price_allowance_delay(&self, delay: Option<Duration>) {
+        self.price_allowance.set_auto_delay(delay);
+    }
+
+    /// Gets the historical price allowance monitor
+    #[must_use]
+    pub fn price_allowance(&self) -> &PriceAllowanceMonitor {
+        &self.price_allowance
+    }
+
+    /// Performs a historical prices request honouring the allowance monitor
+    async fn get_historical(
+        &self,
+        path: &str,
+        version: u8,
     ) -> Result<HistoricalPricesResponse, AppError> {
-        let path = format!("prices/{}/{}/{}", epic, resolution, num_points);
-        info!(
-            "Getting historical prices (v2) for epic: {}, resolution: {}, points: {}",
-            epic, resolution, num_points
+        if let Some(delay) = self.price_allowance.pending_delay() {
+            debug!(
+                "Price allowance low, delaying historical request by {:?}",
+                delay
+            );
+            sleep(delay).await;
+        }
+
+        let result: HistoricalPricesResponse = match self.http_client.get(path, Some(version)).await
+        {
+            Ok(result) => result,
+            Err(AppError::RateLimited {
+                scope: RateLimitScope::HistoricalData,
+                retry_after,
+            }) => {
+                let resets_in = retry_after.or_else(|| {
+                    self.price_allowance
+                        .last_allowance()
+                        .map(|allowance| allowance.resets_in())
+                });
+                return Err(AppError::AllowanceExceeded { resets_in });
+            }
+            Err(e) => return Err(e),
+        };
+        if let Some(allowance) = &result.allowance {
+            self.price_allowance.observe(allowance);
+        }
+        Ok(result)
+    }
+
+    /// Closes the given positions at market with bounded concurrency
+    async fn close_positions(&self, positions: Vec<Position>) -> Vec<ClosePositionOutcome> {
+        stream::iter(positions)
+            .map(|position| async move {
+                let request = ClosePositionRequest::from(&position);
+                let result = self.close_position(&request).await;
+                if let Err(e) = &result {
+                    error!(
+                        "Failed to close position {} on {}: {:?}",
+                        position.position.deal_id, position.market.epic, e
+                    );
+                }
+                ClosePositionOutcome {
+                    deal_id: position.position.deal_id,
+                    epic: position.market.epic,
+                    result,
+                }
+            })
+            .buffered(DEFAULT_BULK_CLOSE_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Gets one page of the transaction history
+    async fn get_transactions_page(
+        &self,
+        from: &str,
+        to: &str,
+        page: u32,
+    ) -> Result<TransactionHistoryResponse, AppError> {
+        let path = format!(
+            "history/transactions?from={}&to={}&pageSize={}&pageNumber={}",
+            from, to, TRANSACTIONS_PAGE_SIZE, page
         );
-        let result: HistoricalPricesResponse = self.http_client.get(&path, Some(2)).await?;
-        debug!(
-            "Historical prices (v2) obtained for epic: {}, {} data points",
+        info!("Getting transaction history page {}", page);
+        self.http_client.get(&path, Some(2)).await
+    }
+
+    /// Streams the transaction history of a period, fetching one page at a time
+    ///
+    /// Unlike `get_transactions`, which collects every page first, the next page is
+    /// only requested once the transactions of the current one have been consumed.
+    /// The stream ends after the first error.
+    ///
+    /// # Arguments
+    /// * `from` - Start date in ISO format (e.g. "2023-01-01T00:00:00")
+    /// * `to` - End date in ISO format (e.g. "2023-02-01T00:00:00")
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut transactions = std::pin::pin!(client.transactions_stream(from, to));
+    /// while let Some(transaction) = transactions.next().await {
+    ///     writer.write_record(&transaction?)?;
+    /// }
+    /// ```
+    pub fn transactions_stream<'a>(
+        &'a self,
+        from: &'a str,
+        to: &'a str,
+    ) -> impl Stream<Item = Result<AccountTransaction, AppError>> + 'a {
+        stream::try_unfold(Some(1u32), move |page| async move {
+            let Some(page) = page else {
+                return Ok(None);
+            };
+            let result = self.get_transactions_page(from, to, page).await?;
+            let total_pages = result.metadata.page_data.total_pages as u32;
+            let next = (page < total_pages).then_some(page + 1);
+            Ok::<_, AppError>(Some((
+                stream::iter(result.transactions.into_iter().map(Ok)),
+                next,
+            )))
+        })
+        .try_flatten()
+    }
+
+    /// Streams historical prices over a long date range, one chunk at a time
+    ///
+    /// The range is split into windows of `chunk`, each fetched with
+    /// `get_historical_prices_by_date_range`. Points repeated on chunk boundaries are
+    /// emitted once. When the remaining allowance drops below
+    /// `BACKFILL_ALLOWANCE_RESERVE`, or IG reports it exceeded, the stream pauses until
+    /// the allowance resets and then carries on with the same chunk.
+    ///
+    /// Nothing is fetched until the stream is polled and dropping it cancels any
+    /// pending request, so a backfill can be stopped at any point and resumed by
+    /// calling this again with `from` set to the last snapshot time received.
+    ///
+    /// # Arguments
+    /// * `epic` - Instrument epic
+    /// * `resolution` - Price resolution, e.g. `MINUTE_5`
+    /// * `from` - Start of the range
+    /// * `to` - End of the range
+    /// * `chunk` - Length of the window requested at once
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut prices = std::pin::pin!(client.backfill_prices(
+    ///     "CS.D.EURUSD.CFD.IP",
+    ///     "MINUTE",
+    ///     from,
+    ///     to,
+    ///     chrono::Duration::days(1),
+    /// ));
+    /// while let Some(price) = prices.next().await {
+    ///     db.insert_price(&price?)?;
+    /// }
+    /// ```
+    pub fn backfill_prices<'a>(
+        &'a self,
+        epic: &'a str,
+        resolution: &'a str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        chunk: chrono::Duration,
+    ) -> impl Stream<Item = Result<HistoricalPrice, AppError>> + 'a {
+        backfill_chunks(
+            &self.price_allowance,
             epic,
-            result.prices.len()
-        );
-        Ok(result)
+            from,
+            to,
+            chunk,
+            move |start_date, end_date| async move {
+                self.get_historical_prices_by_date_range(epic, resolution, &start_date, &end_date)
+                    .await
+            },
+        )
     }
 
-    async fn get_market_navigation(&self) -> Result<MarketNavigationResponse, AppError> {
-        let path = "marketnavigation";
-        info!("Getting top-level market navigation nodes");
-        let result: MarketNavigationResponse = self.http_client.get(path, Some(1)).await?;
-        debug!("{} navigation nodes found", result.nodes.len());
-        debug!("{} markets found at root level", result.markets.len());
-        Ok(result)
+    /// Walks the market hierarchy, calling `visitor` for each node as it is fetched
+    ///
+    /// Nothing is accumulated, so this suits cataloguing jobs that stream markets
+    /// into storage. Top-level markets are reported first under a node with an
+    /// empty id named `"root"`. Returning [`ControlFlow::Break`] from the visitor
+    /// stops the traversal and drops any in-flight requests.
+    ///
+    /// # Arguments
+    /// * `visitor` - Callback receiving each navigation node and its markets
+    ///
+    /// # Returns
+    /// Statistics of the traversal, or an error if the root could not be loaded
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use std::ops::ControlFlow;
+    ///
+    /// let stats = client
+    ///     .walk_markets(|node, markets| {
+    ///         db.insert_markets(&node.id, markets);
+    ///         ControlFlow::Continue(())
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn walk_markets<F>(&self, visitor: F) -> Result<MarketHierarchyStats, AppError>
+    where
+        F: FnMut(&MarketNavigationNode, &[MarketData]) -> ControlFlow<()>,
+    {
+        self.walk_market_hierarchy(
+            DEFAULT_MARKET_TRAVERSAL_DEPTH,
+            DEFAULT_MARKET_TRAVERSAL_CONCURRENCY,
+            visitor,
+        )
+        .await
     }
 
-    async fn get_market_navigation_node(
+    /// Collects every market below the root, up to `max_depth` levels
+    async fn collect_market_hierarchy(
         &self,
-        node_id: &str,
-    ) -> Result<MarketNavigationResponse, AppError> {
-        let path = format!("marketnavigation/{}", node_id);
-        info!("Getting market navigation node: {}", node_id);
-        let result: MarketNavigationResponse = self.http_client.get(&path, Some(1)).await?;
-        debug!("{} child nodes found", result.nodes.len());
-        debug!("{} markets found in node {}", result.markets.len(), node_id);
-        Ok(result)
+        max_depth: usize,
+        max_concurrency: usize,
+    ) -> Result<(Vec<MarketData>, MarketHierarchyStats), AppError> {
+        let mut all_markets = Vec::new();
+        let stats = self
+            .walk_market_hierarchy(max_depth, max_concurrency, |_, markets| {
+                all_markets.extend_from_slice(markets);
+                ControlFlow::Continue(())
+            })
+            .await?;
+        Ok((all_markets, stats))
     }
 
-    async fn get_all_markets(&self) -> Result<Vec<MarketData>, AppError> {
-        let max_depth = 6;
+    /// Walks the market navigation tree breadth-first, handing each node to `visitor`
+    ///
+    /// Node failures are logged and counted in the returned stats rather than
+    /// aborting the traversal; only a failure to load the root is an error.
+    async fn walk_market_hierarchy<F>(
+        &self,
+        max_depth: usize,
+        max_concurrency: usize,
+        mut visitor: F,
+    ) -> Result<MarketHierarchyStats, AppError>
+    where
+        F: FnMut(&MarketNavigationNode, &[MarketData]) -> ControlFlow<()>,
+    {
+        let max_concurrency = max_concurrency.max(1);
         info!(
-            "Starting comprehensive market hierarchy traversal (max {} levels)",
-            max_depth
+            "Starting comprehensive market hierarchy traversal (max {} levels, {} concurrent requests)",
+            max_depth, max_concurrency
         );
 
         let root_response = self.get_market_navigation().await?;
@@ -279,8 +1022,20 @@ impl MarketService for Client {
             root_response.markets.len()
         );
 
-        let mut all_markets = root_response.markets.clone();
-        let mut nodes_to_process = root_response.nodes.clone();
+        let mut stats = MarketHierarchyStats {
+            markets_found: root_response.markets.len(),
+            ..MarketHierarchyStats::default()
+        };
+        let root = MarketNavigationNode {
+            id: String::new(),
+            name: "root".to_string(),
+        };
+        if visitor(&root, &root_response.markets).is_break() {
+            info!("Market hierarchy traversal stopped by visitor at the root");
+            return Ok(stats);
+        }
+
+        let mut nodes_to_process = root_response.nodes;
         let mut processed_levels = 0;
 
         while !nodes_to_process.is_empty() && processed_levels < max_depth {
@@ -293,9 +1048,17 @@ impl MarketService for Client {
                 nodes_to_process.len()
             );
 
-            for node in &nodes_to_process {
-                match self.get_market_navigation_node(&node.id).await {
+            let mut responses = stream::iter(std::mem::take(&mut nodes_to_process))
+                .map(|node| async move {
+                    let result = self.get_market_navigation_node(&node.id).await;
+                    (node, result)
+                })
+                .buffer_unordered(max_concurrency);
+
+            while let Some((node, result)) = responses.next().await {
+                match result {
                     Ok(node_response) => {
+                        stats.nodes_visited += 1;
                         let node_markets = node_response.markets.len();
                         let node_children = node_response.nodes.len();
 
@@ -306,11 +1069,20 @@ impl MarketService for Client {
                             );
                         }
 
-                        all_markets.extend(node_response.markets);
+                        stats.markets_found += node_markets;
                         level_market_count += node_markets;
+                        if visitor(&node, &node_response.markets).is_break() {
+                            stats.levels_processed = processed_levels;
+                            info!(
+                                "Market hierarchy traversal stopped by visitor at node '{}' (level {})",
+                                node.name, processed_levels
+                            );
+                            return Ok(stats);
+                        }
                         next_level_nodes.extend(node_response.nodes);
                     }
                     Err(e) => {
+                        stats.node_errors += 1;
                         tracing::error!(
                             "Failed to get markets for node '{}' at level {}: {:?}",
                             node.name,
@@ -334,136 +1106,595 @@ impl MarketService for Client {
 
         info!(
             "Market hierarchy traversal completed: {} total markets found across {} levels",
-            all_markets.len(),
-            processed_levels
+            stats.markets_found, processed_levels
         );
 
-        Ok(all_markets)
-    }
+        stats.levels_processed = processed_levels;
+        stats.unvisited_nodes = nodes_to_process.len();
+        if stats.hit_depth_limit() {
+            warn!(
+                "Depth limit of {} levels reached with {} nodes left unvisited",
+                max_depth, stats.unvisited_nodes
+            );
+        }
 
-    async fn get_vec_db_entries(&self) -> Result<Vec<DBEntryResponse>, AppError> {
-        info!("Getting all markets from hierarchy for DB entries");
+        Ok(stats)
+    }
+}
 
-        let all_markets = self.get_all_markets().await?;
-        info!("Collected {} markets from hierarchy", all_markets.len());
+/// Picks one entry per symbol, in first-seen order, skipping entries without an epic
+fn symbol_representatives(entries: &[DBEntryResponse]) -> Vec<&DBEntryResponse> {
+    let mut seen_symbols: HashSet<&str> = HashSet::new();
+    entries
+        .iter()
+        .filter(|entry| {
+            !entry.symbol.is_empty() && !entry.epic.is_empty() && seen_symbols.insert(&entry.symbol)
+        })
+        .collect()
+}
 
-        let mut vec_db_entries: Vec<DBEntryResponse> = all_markets
-            .iter()
-            .map(DBEntryResponse::from)
-            .filter(|entry| !entry.epic.is_empty())
-            .collect();
+/// Logs a warning when the local clock drifts from the server time
+fn check_clock_drift(server_time: DateTime<Utc>, local_time: DateTime<Utc>) {
+    let drift = (local_time - server_time).num_seconds();
+    if drift.abs() > MAX_CLOCK_DRIFT_SECS {
+        warn!(
+            "Local clock differs from IG server time by {}s (server {}, local {})",
+            drift, server_time, local_time
+        );
+    }
+}
 
-        info!("Created {} DB entries from markets", vec_db_entries.len());
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        // Collect unique symbols
-        let unique_symbols: std::collections::HashSet<String> = vec_db_entries
-            .iter()
-            .map(|entry| entry.symbol.clone())
-            .filter(|symbol| !symbol.is_empty())
-            .collect();
+#[async_trait]
+impl MarketService for Client {
+    async fn search_markets(&self, search_term: &str) -> Result<MarketSearchResponse, AppError> {
+        let path = format!("markets?searchTerm={}", search_term);
+        info!("Searching markets with term: {}", search_term);
+        let result: MarketSearchResponse = self.http_client.get(&path, Some(1)).await?;
+        debug!("{} markets found", result.markets.len());
+        Ok(result)
+    }
 
-        info!(
-            "Found {} unique symbols to fetch expiry dates for",
-            unique_symbols.len()
+    async fn search_markets_filtered(
+        &self,
+        search_term: &str,
+        filter: MarketSearchFilter,
+    ) -> Result<MarketSearchResponse, AppError> {
+        let mut result = self.search_markets(search_term).await?;
+        let found = result.markets.len();
+        result.markets.retain(|market| filter.matches(market));
+        debug!(
+            "{} of {} markets kept after filtering",
+            result.markets.len(),
+            found
         );
+        Ok(result)
+    }
 
-        let mut symbol_expiry_map: std::collections::HashMap<String, String> =
-            std::collections::HashMap::new();
-
-        for symbol in unique_symbols {
-            if let Some(entry) = vec_db_entries
-                .iter()
-                .find(|e| e.symbol == symbol && !e.epic.is_empty())
-            {
-                match self.get_market_details(&entry.epic).await {
-                    Ok(market_details) => {
-                        let expiry_date = market_details
-                            .instrument
-                            .expiry_details
-                            .as_ref()
-                            .map(|details| details.last_dealing_date.clone())
-                            .unwrap_or_else(|| market_details.instrument.expiry.clone());
-
-                        symbol_expiry_map.insert(symbol.clone(), expiry_date);
-                        info!(
-                            "Fetched expiry date for symbol {}: {}",
-                            symbol,
-                            symbol_expiry_map.get(&symbol).unwrap()
-                        );
-                    }
-                    Err(e) => {
-                        tracing::error!(
-                            "Failed to get market details for epic {} (symbol {}): {:?}",
-                            entry.epic,
-                            symbol,
-                            e
-                        );
-                        symbol_expiry_map.insert(symbol.clone(), entry.expiry.clone());
-                    }
-                }
-            }
+    async fn get_market_details(&self, epic: &str) -> Result<MarketDetails, AppError> {
+        if let Some(details) = self.market_cache.as_ref().and_then(|cache| cache.get(epic)) {
+            debug!("Market details for {} served from cache", epic);
+            return Ok(details);
         }
-
-        for entry in &mut vec_db_entries {
-            if let Some(expiry_date) = symbol_expiry_map.get(&entry.symbol) {
-                entry.expiry = expiry_date.clone();
-            }
+        let path = MarketDetailFilter::All.market_details_path(epic);
+        info!("Getting market details: {}", epic);
+        let details: MarketDetails = self.http_client.get(&path, Some(3)).await?;
+        debug!("Market details obtained for: {}", epic);
+        if let Some(cache) = &self.market_cache {
+            cache.insert(&details);
         }
-
-        info!("Updated expiry dates for {} entries", vec_db_entries.len());
-        Ok(vec_db_entries)
+        Ok(details)
     }
 
-    async fn get_categories(&self) -> Result<CategoriesResponse, AppError> {
-        info!("Getting all categories of instruments");
-        let result: CategoriesResponse = self.http_client.get("categories", Some(1)).await?;
-        debug!("{} categories found", result.categories.len());
-        Ok(result)
+    async fn get_market_details_filtered(
+        &self,
+        epic: &str,
+        filter: MarketDetailFilter,
+    ) -> Result<FilteredMarketDetails, AppError> {
+        let path = filter.market_details_path(epic);
+        info!("Getting market details: {} ({})", epic, filter.as_str());
+        let market_details: FilteredMarketDetails = self.http_client.get(&path, Some(3)).await?;
+        debug!("Market details obtained for: {}", epic);
+        Ok(market_details)
     }
 
-    async fn get_category_instruments(
+    async fn get_multiple_market_details(
         &self,
-        category_id: &str,
-        page_number: Option<i32>,
-        page_size: Option<i32>,
-    ) -> Result<CategoryInstrumentsResponse, AppError> {
-        let mut path = format!("categories/{}/instruments", category_id);
+        epics: &[String],
+    ) -> Result<MultipleMarketDetailsResponse, AppError> {
+        if epics.is_empty() {
+            return Ok(MultipleMarketDetailsResponse::default());
+        } else if epics.len() > MAX_MARKET_DETAILS_EPICS {
+            return Err(AppError::InvalidInput(
+                "The maximum number of EPICs is 50".to_string(),
+            ));
+        }
 
-        let mut query_params = Vec::new();
-        if let Some(page) = page_number {
-            query_params.push(format!("pageNumber={}", page));
+        let Some(cache) = &self.market_cache else {
+            return self.fetch_multiple_market_details(epics).await;
+        };
+
+        let mut found: HashMap<String, MarketDetails> = HashMap::new();
+        let mut missing: Vec<String> = Vec::new();
+        for epic in epics {
+            match cache.get(epic) {
+                Some(details) => {
+                    found.insert(epic.clone(), details);
+                }
+                None => missing.push(epic.clone()),
+            }
         }
-        if let Some(size) = page_size {
-            if size > 1000 {
-                return Err(AppError::InvalidInput(
-                    "pageSize cannot exceed 1000".to_string(),
-                ));
+        if !missing.is_empty() {
+            for details in self
+                .fetch_multiple_market_details(&missing)
+                .await?
+                .market_details
+            {
+                cache.insert(&details);
+                found.insert(details.instrument.epic.clone(), details);
             }
-            query_params.push(format!("pageSize={}", size));
         }
+        debug!(
+            "{} of {} market details served from cache",
+            epics.len() - missing.len(),
+            epics.len()
+        );
 
-        if !query_params.is_empty() {
-            path = format!("{}?{}", path, query_params.join("&"));
-        }
+        // Keep the requested order; epics IG did not return are left out as before
+        let market_details = epics.iter().filter_map(|epic| found.remove(epic)).collect();
+        Ok(MultipleMarketDetailsResponse { market_details })
+    }
 
+    async fn get_market_details_batches(&self, epics: &[String]) -> Vec<MarketDetailsBatchOutcome> {
         info!(
-            "Getting instruments for category: {} (page: {:?}, size: {:?})",
-            category_id, page_number, page_size
-        );
-        let result: CategoryInstrumentsResponse = self.http_client.get(&path, Some(1)).await?;
-        debug!(
-            "{} instruments found in category {}",
-            result.instruments.len(),
-            category_id
+            "Getting market details for {} EPICs in batches of {}",
+            epics.len(),
+            MAX_MARKET_DETAILS_EPICS
         );
-        Ok(result)
+        market_details_batches(epics, move |batch| {
+            async move { self.get_multiple_market_details(&batch).await }.boxed()
+        })
+        .await
     }
-}
 
-#[async_trait]
-impl AccountService for Client {
-    async fn get_accounts(&self) -> Result<AccountsResponse, AppError> {
-        info!("Getting account information");
+    async fn get_market_details_bulk(
+        &self,
+        epics: &[String],
+    ) -> Result<Vec<MarketDetails>, AppError> {
+        collect_market_details(self.get_market_details_batches(epics).await)
+    }
+
+    async fn get_historical_prices(
+        &self,
+        epic: &str,
+        resolution: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<HistoricalPricesResponse, AppError> {
+        let path = format!(
+            "prices/{}?resolution={}&from={}&to={}",
+            epic, resolution, from, to
+        );
+        info!("Getting historical prices for: {}", epic);
+        let result = self.get_historical(&path, 3).await?;
+        debug!("Historical prices obtained for: {}", epic);
+        Ok(result)
+    }
+
+    async fn get_historical_prices_by_date_range(
+        &self,
+        epic: &str,
+        resolution: &str,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<HistoricalPricesResponse, AppError> {
+        let path = format!("prices/{}/{}/{}/{}", epic, resolution, start_date, end_date);
+        info!(
+            "Getting historical prices for epic: {}, resolution: {}, from: {} to: {}",
+            epic, resolution, start_date, end_date
+        );
+        let result = self.get_historical(&path, 2).await?;
+        debug!(
+            "Historical prices obtained for epic: {}, {} data points",
+            epic,
+            result.prices.len()
+        );
+        Ok(result)
+    }
+
+    async fn get_historical_prices_typed(
+        &self,
+        epic: &str,
+        resolution: Resolution,
+        from: &str,
+        to: &str,
+    ) -> Result<HistoricalPricesResponse, AppError> {
+        self.get_historical_prices(epic, resolution.as_str(), from, to)
+            .await
+    }
+
+    async fn get_historical_prices_by_date_range_typed(
+        &self,
+        epic: &str,
+        resolution: Resolution,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<HistoricalPricesResponse, AppError> {
+        self.get_historical_prices_by_date_range(epic, resolution.as_str(), start_date, end_date)
+            .await
+    }
+
+    async fn get_recent_prices(
+        &self,
+        params: &RecentPricesRequest<'_>,
+    ) -> Result<HistoricalPricesResponse, AppError> {
+        let mut query_params = Vec::new();
+
+        if let Some(res) = params.resolution {
+            query_params.push(format!("resolution={}", res));
+        }
+        if let Some(f) = params.from {
+            query_params.push(format!("from={}", f));
+        }
+        if let Some(t) = params.to {
+            query_params.push(format!("to={}", t));
+        }
+        if let Some(max) = params.max_points {
+            query_params.push(format!("max={}", max));
+        }
+        if let Some(size) = params.page_size {
+            query_params.push(format!("pageSize={}", size));
+        }
+        if let Some(num) = params.page_number {
+            query_params.push(format!("pageNumber={}", num));
+        }
+
+        let query_string = if query_params.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", query_params.join("&"))
+        };
+
+        let path = format!("prices/{}{}", params.epic, query_string);
+        info!("Getting recent prices for epic: {}", params.epic);
+        let result = self.get_historical(&path, 3).await?;
+        debug!(
+            "Recent prices obtained for epic: {}, {} data points",
+            params.epic,
+            result.prices.len()
+        );
+        Ok(result)
+    }
+
+    async fn get_historical_prices_by_count_v1(
+        &self,
+        epic: &str,
+        resolution: &str,
+        num_points: i32,
+    ) -> Result<HistoricalPricesResponse, AppError> {
+        let path = format!("prices/{}/{}/{}", epic, resolution, num_points);
+        info!(
+            "Getting historical prices (v1) for epic: {}, resolution: {}, points: {}",
+            epic, resolution, num_points
+        );
+        let result = self.get_historical(&path, 1).await?;
+        debug!(
+            "Historical prices (v1) obtained for epic: {}, {} data points",
+            epic,
+            result.prices.len()
+        );
+        Ok(result)
+    }
+
+    async fn get_historical_prices_by_count_v2(
+        &self,
+        epic: &str,
+        resolution: &str,
+        num_points: i32,
+    ) -> Result<HistoricalPricesResponse, AppError> {
+        let path = format!("prices/{}/{}/{}", epic, resolution, num_points);
+        info!(
+            "Getting historical prices (v2) for epic: {}, resolution: {}, points: {}",
+            epic, resolution, num_points
+        );
+        let result = self.get_historical(&path, 2).await?;
+        debug!(
+            "Historical prices (v2) obtained for epic: {}, {} data points",
+            epic,
+            result.prices.len()
+        );
+        Ok(result)
+    }
+
+    async fn get_historical_prices_by_count_typed(
+        &self,
+        epic: &str,
+        resolution: Resolution,
+        num_points: i32,
+    ) -> Result<HistoricalPricesResponse, AppError> {
+        self.get_historical_prices_by_count_v2(epic, resolution.as_str(), num_points)
+            .await
+    }
+
+    async fn get_market_navigation(&self) -> Result<MarketNavigationResponse, AppError> {
+        let path = "marketnavigation";
+        info!("Getting top-level market navigation nodes");
+        let result: MarketNavigationResponse = self.http_client.get(path, Some(1)).await?;
+        debug!("{} navigation nodes found", result.nodes.len());
+        debug!("{} markets found at root level", result.markets.len());
+        Ok(result)
+    }
+
+    async fn get_market_navigation_node(
+        &self,
+        node_id: &str,
+    ) -> Result<MarketNavigationResponse, AppError> {
+        let path = format!("marketnavigation/{}", node_id);
+        info!("Getting market navigation node: {}", node_id);
+        let result: MarketNavigationResponse = self.http_client.get(&path, Some(1)).await?;
+        debug!("{} child nodes found", result.nodes.len());
+        debug!("{} markets found in node {}", result.markets.len(), node_id);
+        Ok(result)
+    }
+
+    async fn get_all_markets(&self) -> Result<Vec<MarketData>, AppError> {
+        self.get_all_markets_with_depth(DEFAULT_MARKET_TRAVERSAL_DEPTH)
+            .await
+    }
+
+    async fn get_all_markets_with_depth(
+        &self,
+        max_depth: usize,
+    ) -> Result<Vec<MarketData>, AppError> {
+        let (markets, _) = self.get_all_markets_verbose(max_depth).await?;
+        Ok(markets)
+    }
+
+    async fn get_all_markets_verbose(
+        &self,
+        max_depth: usize,
+    ) -> Result<(Vec<MarketData>, MarketHierarchyStats), AppError> {
+        self.collect_market_hierarchy(max_depth, DEFAULT_MARKET_TRAVERSAL_CONCURRENCY)
+            .await
+    }
+
+    async fn get_all_markets_with_concurrency(
+        &self,
+        max_concurrency: usize,
+    ) -> Result<Vec<MarketData>, AppError> {
+        let (markets, _) = self
+            .collect_market_hierarchy(DEFAULT_MARKET_TRAVERSAL_DEPTH, max_concurrency)
+            .await?;
+        Ok(markets)
+    }
+
+    async fn get_option_chain(&self, underlying_node_id: &str) -> Result<OptionChain, AppError> {
+        info!("Building option chain for node: {}", underlying_node_id);
+        let root = self.get_market_navigation_node(underlying_node_id).await?;
+        let mut markets = root.markets;
+        let mut nodes_to_process = root.nodes;
+        let mut depth = 0;
+
+        while !nodes_to_process.is_empty() && depth < MAX_OPTION_CHAIN_DEPTH {
+            let responses: Vec<_> = stream::iter(std::mem::take(&mut nodes_to_process))
+                .map(|node| async move { self.get_market_navigation_node(&node.id).await })
+                .buffer_unordered(DEFAULT_MARKET_TRAVERSAL_CONCURRENCY)
+                .collect()
+                .await;
+            for response in responses {
+                let response = response?;
+                markets.extend(response.markets);
+                nodes_to_process.extend(response.nodes);
+            }
+            depth += 1;
+        }
+
+        let chain = OptionChain::from_markets(underlying_node_id, &markets);
+        debug!(
+            "Option chain for {}: {} expiries from {} markets",
+            chain.underlying,
+            chain.expiries.len(),
+            markets.len()
+        );
+        Ok(chain)
+    }
+
+    async fn get_vec_db_entries(&self) -> Result<Vec<DBEntryResponse>, AppError> {
+        info!("Getting all markets from hierarchy for DB entries");
+
+        let all_markets = self.get_all_markets().await?;
+        info!("Collected {} markets from hierarchy", all_markets.len());
+
+        let mut vec_db_entries: Vec<DBEntryResponse> = all_markets
+            .iter()
+            .map(DBEntryResponse::from)
+            .filter(|entry| !entry.epic.is_empty())
+            .collect();
+
+        info!("Created {} DB entries from markets", vec_db_entries.len());
+
+        let representatives = symbol_representatives(&vec_db_entries);
+
+        info!(
+            "Found {} unique symbols to fetch expiry dates for",
+            representatives.len()
+        );
+
+        // Fetch the details of every representative concurrently, batch by batch, so
+        // that a failed batch only affects its own symbols
+        let epics: Vec<String> = representatives
+            .iter()
+            .map(|entry| entry.epic.clone())
+            .collect();
+        let mut details_by_epic: HashMap<String, MarketDetails> = HashMap::new();
+        for outcome in self.get_market_details_batches(&epics).await {
+            match outcome.result {
+                Ok(details) => details_by_epic.extend(
+                    details
+                        .into_iter()
+                        .map(|details| (details.instrument.epic.clone(), details)),
+                ),
+                Err(e) => tracing::error!(
+                    "Failed to get market details for {} epics: {:?}",
+                    outcome.epics.len(),
+                    e
+                ),
+            }
+        }
+
+        let mut symbol_expiry_map: HashMap<String, String> = HashMap::new();
+        for entry in representatives {
+            let expiry_date = match details_by_epic.get(&entry.epic) {
+                Some(market_details) => market_details
+                    .instrument
+                    .expiry_details
+                    .as_ref()
+                    .map(|details| details.last_dealing_date.clone())
+                    .unwrap_or_else(|| market_details.instrument.expiry.clone()),
+                None => {
+                    tracing::error!(
+                        "No market details for epic {} (symbol {}), keeping its expiry",
+                        entry.epic,
+                        entry.symbol
+                    );
+                    entry.expiry.clone()
+                }
+            };
+            info!(
+                "Fetched expiry date for symbol {}: {}",
+                entry.symbol, expiry_date
+            );
+            symbol_expiry_map.insert(entry.symbol.clone(), expiry_date);
+        }
+
+        for entry in &mut vec_db_entries {
+            if let Some(expiry_date) = symbol_expiry_map.get(&entry.symbol) {
+                entry.expiry = expiry_date.clone();
+            }
+        }
+
+        info!("Updated expiry dates for {} entries", vec_db_entries.len());
+        Ok(vec_db_entries)
+    }
+
+    async fn get_categories(&self) -> Result<CategoriesResponse, AppError> {
+        info!("Getting all categories of instruments");
+        let result: CategoriesResponse = self.http_client.get("categories", Some(1)).await?;
+        debug!("{} categories found", result.categories.len());
+        Ok(result)
+    }
+
+    async fn get_category_instruments(
+        &self,
+        category_id: &str,
+        page_number: Option<i32>,
+        page_size: Option<i32>,
+    ) -> Result<CategoryInstrumentsResponse, AppError> {
+        let mut path = format!("categories/{}/instruments", category_id);
+
+        let mut query_params = Vec::new();
+        if let Some(page) = page_number {
+            query_params.push(format!("pageNumber={}", page));
+        }
+        if let Some(size) = page_size {
+            if size > 1000 {
+                return Err(AppError::InvalidInput(
+                    "pageSize cannot exceed 1000".to_string(),
+                ));
+            }
+            query_params.push(format!("pageSize={}", size));
+        }
+
+        if !query_params.is_empty() {
+            path = format!("{}?{}", path, query_params.join("&"));
+        }
+
+        info!(
+            "Getting instruments for category: {} (page: {:?}, size: {:?})",
+            category_id, page_number, page_size
+        );
+        let result: CategoryInstrumentsResponse = match self.http_client.get(&path, Some(1)).await {
+            Ok(result) => result,
+            // IG answers 500 instead of an error code when the account cannot list
+            // category instruments, which is the case for demo accounts
+            Err(AppError::Unexpected(StatusCode::INTERNAL_SERVER_ERROR)) => {
+                return Err(AppError::NotSupported(format!(
+                    "instruments of category {category_id} cannot be listed for this account; \
+                     demo accounts do not support this endpoint, try a live account"
+                )));
+            }
+            Err(e) => return Err(e),
+        };
+        debug!(
+            "{} instruments found in category {}",
+            result.instruments.len(),
+            category_id
+        );
+        Ok(result)
+    }
+
+    async fn get_client_sentiment(
+        &self,
+        market_id: &str,
+    ) -> Result<ClientSentimentResponse, AppError> {
+        let path = format!("clientsentiment/{}", market_id);
+        info!("Getting client sentiment for: {}", market_id);
+        let result: ClientSentimentResponse = self.http_client.get(&path, Some(1)).await?;
+        debug!(
+            "Client sentiment for {}: {}% long / {}% short",
+            market_id, result.long_position_percentage, result.short_position_percentage
+        );
+        Ok(result)
+    }
+
+    async fn get_related_sentiment(
+        &self,
+        market_id: &str,
+    ) -> Result<ClientSentimentListResponse, AppError> {
+        let path = format!("clientsentiment/related/{}", market_id);
+        info!("Getting related client sentiment for: {}", market_id);
+        let result: ClientSentimentListResponse = self.http_client.get(&path, Some(1)).await?;
+        debug!(
+            "{} related sentiments found for {}",
+            result.client_sentiments.len(),
+            market_id
+        );
+        Ok(result)
+    }
+
+    async fn get_client_sentiment_batch(
+        &self,
+        market_ids: &[String],
+    ) -> Result<ClientSentimentListResponse, AppError> {
+        if market_ids.is_empty() {
+            return Err(AppError::InvalidInput(
+                "At least one market id is required".to_string(),
+            ));
+        } else if market_ids.len() > MAX_MARKET_DETAILS_EPICS {
+            return Err(AppError::InvalidInput(format!(
+                "The maximum number of market ids is {MAX_MARKET_DETAILS_EPICS}"
+            )));
+        }
+
+        let path = format!("clientsentiment?marketIds={}", market_ids.join(","));
+        debug!(
+            "Getting client sentiment for {} markets in a batch",
+            market_ids.len()
+        );
+        let result: ClientSentimentListResponse = self.http_client.get(&path, Some(1)).await?;
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl AccountService for Client {
+    async fn get_accounts(&self) -> Result<AccountsResponse, AppError> {
+        info!("Getting account information");
         let result: AccountsResponse = self.http_client.get("accounts", Some(1)).await?;
         debug!(
             "Account information obtained: {} accounts",
@@ -472,6 +1703,36 @@ impl AccountService for Client {
         Ok(result)
     }
 
+    async fn set_active_account(&self, account_id: &str) -> Result<(), AppError> {
+        let accounts = self.get_accounts().await?;
+        if !accounts
+            .accounts
+            .iter()
+            .any(|account| account.account_id == account_id)
+        {
+            return Err(AppError::InvalidInput(format!(
+                "account {} not found among the user accounts",
+                account_id
+            )));
+        }
+
+        self.http_client
+            .switch_account(account_id, Some(false))
+            .await?;
+
+        let ws_info = self.http_client.get_ws_info().await;
+        if ws_info.get_ws_password().is_ok() {
+            self.session_handle.publish(ws_info);
+        }
+
+        info!("Active account set to {}", account_id);
+        Ok(())
+    }
+
+    async fn active_account_id(&self) -> String {
+        self.http_client.active_account_id().await
+    }
+
     async fn get_positions(&self) -> Result<PositionsResponse, AppError> {
         debug!("Getting open positions");
         let result: PositionsResponse = self.http_client.get("positions", Some(2)).await?;
@@ -479,6 +1740,16 @@ impl AccountService for Client {
         Ok(result)
     }
 
+    async fn get_position_by_deal_id(&self, deal_id: &str) -> Result<Position, AppError> {
+        debug!("Getting position: {}", deal_id);
+        let path = format!("positions/{}", deal_id);
+        match self.http_client.get(&path, Some(2)).await {
+            Ok(position) => Ok(position),
+            Err(AppError::Unexpected(StatusCode::NOT_FOUND)) => Err(AppError::NotFound),
+            Err(e) => Err(e),
+        }
+    }
+
     async fn get_positions_w_filter(&self, filter: &str) -> Result<PositionsResponse, AppError> {
         debug!("Getting open positions with filter: {}", filter);
         let mut positions = self.get_positions().await?;
@@ -504,21 +1775,84 @@ impl AccountService for Client {
         Ok(result)
     }
 
-    async fn get_activity(
+    async fn get_activity(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<AccountActivityResponse, AppError> {
+        let path = format!("history/activity?from={}&to={}&pageSize=500", from, to);
+        info!("Getting account activity");
+        let result: AccountActivityResponse = self.http_client.get(&path, Some(3)).await?;
+        debug!(
+            "Account activity obtained: {} activities",
+            result.activities.len()
+        );
+        Ok(result)
+    }
+
+    async fn get_activity_filtered(
         &self,
         from: &str,
         to: &str,
+        filter: &ActivityFilter,
     ) -> Result<AccountActivityResponse, AppError> {
-        let path = format!("history/activity?from={}&to={}&pageSize=500", from, to);
-        info!("Getting account activity");
+        let mut path = format!("history/activity?from={}&to={}&pageSize=500", from, to);
+        if !filter.is_empty() {
+            path.push_str("&filter=");
+            path.push_str(&filter.to_query_value());
+        }
+        info!("Getting account activity with filter {}", filter.to_fiql());
         let result: AccountActivityResponse = self.http_client.get(&path, Some(3)).await?;
         debug!(
-            "Account activity obtained: {} activities",
+            "Filtered account activity obtained: {} activities",
             result.activities.len()
         );
         Ok(result)
     }
 
+    async fn get_all_activity(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<AccountActivityResponse, AppError> {
+        let mut path = format!("history/activity?from={}&to={}&pageSize=500", from, to);
+        let mut visited = HashSet::new();
+        let mut all_activities = Vec::new();
+        let mut page = 1;
+
+        let metadata = loop {
+            info!("Getting account activity page {}", page);
+            visited.insert(path.clone());
+            let result: AccountActivityResponse = self.http_client.get(&path, Some(3)).await?;
+            let next = result.next_page_path();
+            all_activities.extend(result.activities);
+
+            match next {
+                None => break result.metadata,
+                Some(next) if visited.contains(&next) => {
+                    warn!(
+                        "Activity paging points back to an already fetched page ({}), stopping",
+                        next
+                    );
+                    break result.metadata;
+                }
+                Some(next) => {
+                    path = next;
+                    page += 1;
+                }
+            }
+        };
+
+        debug!(
+            "Total account activity obtained: {} activities",
+            all_activities.len()
+        );
+        Ok(AccountActivityResponse {
+            activities: all_activities,
+            metadata,
+        })
+    }
+
     async fn get_activity_with_details(
         &self,
         from: &str,
@@ -542,20 +1876,13 @@ impl AccountService for Client {
         from: &str,
         to: &str,
     ) -> Result<TransactionHistoryResponse, AppError> {
-        const PAGE_SIZE: u32 = 200;
         let mut all_transactions = Vec::new();
         let mut current_page = 1;
         #[allow(unused_assignments)]
         let mut last_metadata = None;
 
         loop {
-            let path = format!(
-                "history/transactions?from={}&to={}&pageSize={}&pageNumber={}",
-                from, to, PAGE_SIZE, current_page
-            );
-            info!("Getting transaction history page {}", current_page);
-
-            let result: TransactionHistoryResponse = self.http_client.get(&path, Some(2)).await?;
+            let result = self.get_transactions_page(from, to, current_page).await?;
 
             let total_pages = result.metadata.page_data.total_pages as u32;
             last_metadata = Some(result.metadata);
@@ -580,139 +1907,601 @@ impl AccountService for Client {
     }
 }
 
-#[async_trait]
-impl OrderService for Client {
-    async fn create_order(
-        &self,
-        order: &CreateOrderRequest,
-    ) -> Result<CreateOrderResponse, AppError> {
-        info!("Creating order for: {}", order.epic);
-        let result: CreateOrderResponse = self
-            .http_client
-            .post("positions/otc", order, Some(2))
-            .await?;
-        debug!("Order created with reference: {}", result.deal_reference);
-        Ok(result)
-    }
+#[async_trait]
+impl OrderService for Client {
+    async fn create_order(
+        &self,
+        order: &CreateOrderRequest,
+    ) -> Result<CreateOrderResponse, AppError> {
+        info!("Creating order for: {}", order.epic);
+        if let Some(deal_reference) = self.skip_in_dry_run("order") {
+            return Ok(CreateOrderResponse { deal_reference });
+        }
+        self.warn_if_live("market order", &order.epic);
+        let result: CreateOrderResponse = self
+            .http_client
+            .post("positions/otc", order, Some(2))
+            .await?;
+        debug!("Order created with reference: {}", result.deal_reference);
+        Ok(result)
+    }
+
+    async fn get_order_confirmation(
+        &self,
+        deal_reference: &str,
+    ) -> Result<OrderConfirmationResponse, AppError> {
+        if is_dry_run_reference(deal_reference) {
+            debug!("Dry run: synthetic confirmation for {}", deal_reference);
+            return Ok(OrderConfirmationResponse {
+                date: Utc::now().format("%Y-%m-%dT%H:%M:%S%.3f").to_string(),
+                status: Status::Accepted,
+                reason: Some("SUCCESS".to_string()),
+                deal_id: Some(deal_reference.to_string()),
+                deal_reference: deal_reference.to_string(),
+                deal_status: Some("ACCEPTED".to_string()),
+                epic: None,
+                expiry: None,
+                guaranteed_stop: None,
+                level: None,
+                limit_distance: None,
+                limit_level: None,
+                size: None,
+                stop_distance: None,
+                stop_level: None,
+                trailing_stop: None,
+                direction: None,
+            });
+        }
+        let path = format!("confirms/{}", deal_reference);
+        info!("Getting confirmation for order: {}", deal_reference);
+        let result: OrderConfirmationResponse = self.http_client.get(&path, Some(1)).await?;
+        debug!("Confirmation obtained for order: {}", deal_reference);
+        Ok(result)
+    }
+
+    async fn get_order_confirmation_w_retry(
+        &self,
+        deal_reference: &str,
+        retries: u64,
+        delay_ms: u64,
+    ) -> Result<OrderConfirmationResponse, AppError> {
+        let policy = RetryPolicy::new(
+            u32::try_from(retries).unwrap_or(u32::MAX),
+            Duration::from_millis(delay_ms),
+        )
+        .with_multiplier(1.0)
+        .with_retryable(|_| true);
+        retry_with_backoff(|| self.get_order_confirmation(deal_reference), policy).await
+    }
+
+    async fn wait_for_deal(
+        &self,
+        deal_reference: &str,
+        timeout: Duration,
+    ) -> Result<OrderConfirmationResponse, AppError> {
+        info!("Waiting for deal confirmation: {}", deal_reference);
+        poll_deal_confirmation(
+            deal_reference,
+            timeout,
+            Duration::from_millis(DEFAULT_DEAL_POLL_INTERVAL_MS),
+            || self.get_order_confirmation(deal_reference),
+        )
+        .await
+    }
+
+    async fn place_order_and_confirm(
+        &self,
+        order: &CreateOrderRequest,
+        timeout: Duration,
+    ) -> Result<OrderOutcome, AppError> {
+        let deal_reference = self.create_order(order).await?.deal_reference;
+        match self.wait_for_deal(&deal_reference, timeout).await {
+            Ok(confirmation) => Ok(OrderOutcome::from(confirmation)),
+            Err(AppError::Timeout(_)) => Err(AppError::DealConfirmationTimeout {
+                deal_reference,
+                timeout,
+            }),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn update_position(
+        &self,
+        deal_id: &str,
+        update: &UpdatePositionRequest,
+    ) -> Result<UpdatePositionResponse, AppError> {
+        let path = format!("positions/otc/{}", deal_id);
+        info!("Updating position: {}", deal_id);
+        if let Some(deal_reference) = self.skip_in_dry_run("position update") {
+            return Ok(UpdatePositionResponse { deal_reference });
+        }
+        let result: UpdatePositionResponse = self.http_client.put(&path, update, Some(2)).await?;
+        debug!(
+            "Position updated: {} with deal reference: {}",
+            deal_id, result.deal_reference
+        );
+        Ok(result)
+    }
+
+    async fn update_level_in_position(
+        &self,
+        deal_id: &str,
+        limit_level: Option<f64>,
+    ) -> Result<UpdatePositionResponse, AppError> {
+        let path = format!("positions/otc/{}", deal_id);
+        info!("Updating position: {}", deal_id);
+        if let Some(deal_reference) = self.skip_in_dry_run("position update") {
+            return Ok(UpdatePositionResponse { deal_reference });
+        }
+        // A missing limit level clears the existing limit on the position
+        let update = UpdatePositionRequest::limit_only(limit_level);
+        let result: UpdatePositionResponse = self.http_client.put(&path, update, Some(2)).await?;
+        debug!(
+            "Position updated: {} with deal reference: {}",
+            deal_id, result.deal_reference
+        );
+        Ok(result)
+    }
+
+    async fn close_position(
+        &self,
+        close_request: &ClosePositionRequest,
+    ) -> Result<ClosePositionResponse, AppError> {
+        info!("Closing position");
+        if let Some(deal_reference) = self.skip_in_dry_run("close") {
+            return Ok(ClosePositionResponse { deal_reference });
+        }
+
+        // IG API requires POST with _method: DELETE header for closing positions
+        // This is a workaround for HTTP client limitations with DELETE + body
+        let result: ClosePositionResponse = self
+            .http_client
+            .post_with_delete_method("positions/otc", close_request, Some(1))
+            .await?;
+
+        debug!("Position closed with reference: {}", result.deal_reference);
+        Ok(result)
+    }
+
+    async fn close_position_by_deal_id(
+        &self,
+        deal_id: &str,
+        size: Option<f64>,
+    ) -> Result<ClosePositionResponse, AppError> {
+        let position = self.get_position_by_deal_id(deal_id).await?;
+        let mut request = ClosePositionRequest::from(&position);
+        if let Some(size) = size {
+            if size <= 0.0 || size > position.position.size {
+                return Err(AppError::InvalidInput(format!(
+                    "close size {} must be positive and at most the position size {}",
+                    size, position.position.size
+                )));
+            }
+            request.size = size;
+        }
+        info!(
+            "Closing {} of position {} on {}",
+            request.size, deal_id, position.market.epic
+        );
+        self.close_position(&request).await
+    }
+
+    async fn close_all_positions(&self) -> Result<Vec<ClosePositionOutcome>, AppError> {
+        let positions = self.get_positions().await?.positions;
+        info!("Closing all {} open positions", positions.len());
+        let outcomes = self.close_positions(positions).await;
+        debug!(
+            "{} of {} positions closed",
+            outcomes.iter().filter(|o| o.is_success()).count(),
+            outcomes.len()
+        );
+        Ok(outcomes)
+    }
+
+    async fn close_positions_for_epic(
+        &self,
+        epic: &str,
+    ) -> Result<Vec<ClosePositionOutcome>, AppError> {
+        let positions: Vec<Position> = self
+            .get_positions()
+            .await?
+            .positions
+            .into_iter()
+            .filter(|position| position.market.epic == epic)
+            .collect();
+        info!("Closing {} open positions on {}", positions.len(), epic);
+        let outcomes = self.close_positions(positions).await;
+        debug!(
+            "{} of {} positions closed on {}",
+            outcomes.iter().filter(|o| o.is_success()).count(),
+            outcomes.len(),
+            epic
+        );
+        Ok(outcomes)
+    }
+
+    async fn create_working_order(
+        &self,
+        order: &CreateWorkingOrderRequest,
+    ) -> Result<CreateWorkingOrderResponse, AppError> {
+        info!("Creating working order for: {}", order.epic);
+        if let Some(deal_reference) = self.skip_in_dry_run("working order") {
+            return Ok(CreateWorkingOrderResponse { deal_reference });
+        }
+        self.warn_if_live("working order", &order.epic);
+        let result: CreateWorkingOrderResponse = self
+            .http_client
+            .post("workingorders/otc", order, Some(2))
+            .await?;
+        debug!(
+            "Working order created with reference: {}",
+            result.deal_reference
+        );
+        Ok(result)
+    }
+
+    async fn update_working_order(
+        &self,
+        deal_id: &str,
+        update: &UpdateWorkingOrderRequest,
+    ) -> Result<CreateWorkingOrderResponse, AppError> {
+        update.validate()?;
+        let path = format!("workingorders/otc/{}", deal_id);
+        info!("Updating working order: {}", deal_id);
+        if let Some(deal_reference) = self.skip_in_dry_run("working order update") {
+            return Ok(CreateWorkingOrderResponse { deal_reference });
+        }
+        let result: CreateWorkingOrderResponse =
+            self.http_client.put(&path, update, Some(2)).await?;
+        debug!(
+            "Working order updated: {} with deal reference: {}",
+            deal_id, result.deal_reference
+        );
+        Ok(result)
+    }
+
+    async fn delete_working_order(&self, deal_id: &str) -> Result<String, AppError> {
+        let path = format!("workingorders/otc/{}", deal_id);
+        info!("Deleting working order: {}", deal_id);
+        if let Some(deal_reference) = self.skip_in_dry_run("working order deletion") {
+            return Ok(deal_reference);
+        }
+        let result: CreateWorkingOrderResponse =
+            self.http_client.delete(path.as_str(), Some(2)).await?;
+        debug!(
+            "Working order deleted: {} with deal reference: {}",
+            deal_id, result.deal_reference
+        );
+        Ok(result.deal_reference)
+    }
+}
+
+#[async_trait]
+impl WatchlistService for Client {
+    async fn get_watchlists(&self) -> Result<WatchlistsResponse, AppError> {
+        info!("Getting watchlists");
+        let result: WatchlistsResponse = self.http_client.get("watchlists", Some(1)).await?;
+        debug!("{} watchlists found", result.watchlists.len());
+        Ok(result)
+    }
+
+    async fn create_watchlist(&self, name: &str, epics: &[String]) -> Result<String, AppError> {
+        info!("Creating watchlist: {}", name);
+        let request = CreateWatchlistRequest {
+            name: name.to_string(),
+            epics: epics.to_vec(),
+        };
+        let result: CreateWatchlistResponse = self
+            .http_client
+            .post("watchlists", request, Some(1))
+            .await?;
+
+        match (result.status.as_str(), result.watchlist_id) {
+            ("SUCCESS_NOT_CREATED", _) | (_, None) => {
+                Err(AppError::WatchlistNotCreated(name.to_string()))
+            }
+            (status, Some(watchlist_id)) => {
+                if status != "SUCCESS" {
+                    warn!("Watchlist {} created with status: {}", watchlist_id, status);
+                }
+                debug!("Watchlist created with id: {}", watchlist_id);
+                Ok(watchlist_id)
+            }
+        }
+    }
+
+    async fn get_watchlist(&self, watchlist_id: &str) -> Result<WatchlistResponse, AppError> {
+        let path = format!("watchlists/{}", watchlist_id);
+        info!("Getting watchlist: {}", watchlist_id);
+        let result: WatchlistResponse = self.http_client.get(&path, Some(1)).await?;
+        debug!(
+            "{} markets found in watchlist {}",
+            result.markets.len(),
+            watchlist_id
+        );
+        Ok(result)
+    }
+
+    async fn add_market_to_watchlist(
+        &self,
+        watchlist_id: &str,
+        epic: &str,
+    ) -> Result<(), AppError> {
+        let path = format!("watchlists/{}", watchlist_id);
+        info!("Adding {} to watchlist {}", epic, watchlist_id);
+        let request = AddToWatchlistRequest {
+            epic: epic.to_string(),
+        };
+        let result: WatchlistStatusResponse = self.http_client.put(&path, request, Some(1)).await?;
+        debug!("Market added to watchlist with status: {}", result.status);
+        Ok(())
+    }
+
+    async fn remove_market_from_watchlist(
+        &self,
+        watchlist_id: &str,
+        epic: &str,
+    ) -> Result<(), AppError> {
+        let path = format!("watchlists/{}/{}", watchlist_id, epic);
+        info!("Removing {} from watchlist {}", epic, watchlist_id);
+        let result: WatchlistStatusResponse = self.http_client.delete(&path, Some(1)).await?;
+        debug!(
+            "Market removed from watchlist with status: {}",
+            result.status
+        );
+        Ok(())
+    }
+}
+
+/// Description of a subscription issued by `StreamerClient`.
+///
+/// Specs are retained for every subscription so they can be re-issued on a fresh
+/// Lightstreamer connection, after a dropped connection is retried or when the
+/// session credentials change.
+#[derive(Clone)]
+pub(crate) struct SubscriptionSpec {
+    /// Subscription mode (MERGE, DISTINCT, ...)
+    mode: SubscriptionMode,
+    /// Full Lightstreamer item names (e.g. `MARKET:IX.D.DAX.DAILY.IP`)
+    items: Vec<String>,
+    /// Lightstreamer field names
+    fields: Vec<String>,
+    /// Data adapter, `None` for the default one
+    adapter: Option<String>,
+    /// Channel feeding the conversion task of the original subscription
+    sink: mpsc::UnboundedSender<ItemUpdate>,
+    /// Lightstreamer id of the subscription currently issued for this spec
+    id: Option<SubscriptionId>,
+    /// Hub receiving the subscription status events
+    events: SubscriptionEventHub,
+}
+
+impl SubscriptionSpec {
+    /// Builds a Lightstreamer subscription whose updates are forwarded to the spec sink.
+    ///
+    /// An empty field list is rejected up front: IG accepts it but never sends updates.
+    fn build(&self) -> Result<Subscription, AppError> {
+        if self.fields.is_empty() {
+            return Err(AppError::InvalidInput(
+                "at least one field required".to_string(),
+            ));
+        }
+        let mut subscription = Subscription::new(
+            self.mode,
+            Some(self.items.clone()),
+            Some(self.fields.clone()),
+        )?;
+
+        subscription.set_data_adapter(self.adapter.clone())?;
+        subscription.set_requested_snapshot(Some(Snapshot::Yes))?;
+
+        let (listener, item_receiver) = ChannelSubscriptionListener::create_channel();
+        subscription.add_listener(Box::new(listener));
+        subscription.add_listener(Box::new(SubscriptionStatusListener::new(
+            self.items.clone(),
+            self.events.clone(),
+        )));
+
+        let sink = self.sink.clone();
+        tokio::spawn(async move {
+            let mut receiver = item_receiver;
+            while let Some(item_update) = receiver.recv().await {
+                if sink.send(item_update).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(subscription)
+    }
+
+    /// Builds the subscription and records where its id will be published.
+    ///
+    /// # Returns
+    ///
+    /// The commands issuing it: an unsubscribe of the subscription previously issued
+    /// for this spec, if any, so the client never keeps a duplicate, then the new
+    /// subscription.
+    fn issue_commands(&mut self) -> Result<Vec<SubscriptionCommand>, AppError> {
+        let subscription = self.build()?;
+        let (id_sender, id) = watch::channel(None);
+        let mut commands = Vec::with_capacity(2);
+        if let Some(previous) = self.id.replace(id) {
+            commands.push(SubscriptionCommand::Unsubscribe(previous));
+        }
+        commands.push(SubscriptionCommand::Subscribe(
+            Box::new(subscription),
+            id_sender,
+        ));
+        Ok(commands)
+    }
+
+    /// Issues the subscription on the client channel, replacing the previous one.
+    fn issue(&mut self, sender: &SubscriptionSender) -> Result<(), AppError> {
+        for command in self.issue_commands()? {
+            command.dispatch(sender);
+        }
+        Ok(())
+    }
+
+    /// Issues the subscription through the relay, replacing the previous one, and records its id.
+    fn issue_via(&mut self, relay: &SubscriptionRelay) -> Result<(), AppError> {
+        for command in self.issue_commands()? {
+            relay.send(command)?;
+        }
+        Ok(())
+    }
+
+    /// Checks whether one of the spec items refers to one of the given epics.
+    fn covers_any_epic(&self, epics: &[&str], config: &StreamerConfig) -> bool {
+        self.items.iter().any(|item| {
+            config
+                .item_epic(item)
+                .is_some_and(|epic| epics.contains(&epic))
+        })
+    }
+}
+
+/// Channel of a Lightstreamer client accepting subscribe and unsubscribe requests.
+type SubscriptionSender = mpsc::Sender<SubscriptionRequest>;
 
-    async fn get_order_confirmation(
-        &self,
-        deal_reference: &str,
-    ) -> Result<OrderConfirmationResponse, AppError> {
-        let path = format!("confirms/{}", deal_reference);
-        info!("Getting confirmation for order: {}", deal_reference);
-        let result: OrderConfirmationResponse = self.http_client.get(&path, Some(1)).await?;
-        debug!("Confirmation obtained for order: {}", deal_reference);
-        Ok(result)
-    }
+/// Lightstreamer id of an issued subscription, `None` until the client assigned it.
+///
+/// The client only assigns ids once connected, so a subscription issued beforehand
+/// gets its id when the session starts.
+type SubscriptionId = watch::Receiver<Option<usize>>;
+
+/// Subscription request relayed to a Lightstreamer client.
+enum SubscriptionCommand {
+    /// Issue a new subscription, publishing its id on the sender
+    Subscribe(Box<Subscription>, watch::Sender<Option<usize>>),
+    /// Drop the subscription once its id is known
+    Unsubscribe(SubscriptionId),
+}
 
-    async fn get_order_confirmation_w_retry(
-        &self,
-        deal_reference: &str,
-        retries: u64,
-        delay_ms: u64,
-    ) -> Result<OrderConfirmationResponse, AppError> {
-        let mut attempts = 0;
-        loop {
-            match self.get_order_confirmation(deal_reference).await {
-                Ok(response) => return Ok(response),
-                Err(e) => {
-                    attempts += 1;
-                    if attempts > retries {
-                        return Err(e);
+impl SubscriptionCommand {
+    /// Forwards the command to the client channel on its own task.
+    ///
+    /// A subscription waits for its id, which is only assigned once the client is
+    /// connected, and an unsubscribe waits for the id of the subscription it drops.
+    /// An unsubscribe whose subscription never got an id has nothing to drop.
+    fn dispatch(self, sender: &SubscriptionSender) {
+        let sender = sender.clone();
+        match self {
+            SubscriptionCommand::Subscribe(subscription, id_sender) => {
+                tokio::spawn(async move {
+                    match LightstreamerClient::subscribe_get_id(sender, *subscription).await {
+                        Ok(id) => {
+                            id_sender.send_replace(Some(id));
+                        }
+                        Err(e) => warn!("No id assigned to the subscription: {}", e),
                     }
-                    warn!(
-                        "Failed to get order confirmation (attempt {}/{}): {}. Retrying in {} ms...",
-                        attempts, retries, e, delay_ms
-                    );
-                    sleep(Duration::from_millis(delay_ms)).await;
-                }
+                });
+            }
+            SubscriptionCommand::Unsubscribe(mut id) => {
+                tokio::spawn(async move {
+                    let assigned = id.wait_for(Option::is_some).await.ok().and_then(|id| *id);
+                    if let Some(assigned) = assigned {
+                        LightstreamerClient::unsubscribe(sender, assigned).await;
+                    }
+                });
             }
         }
     }
+}
 
-    async fn update_position(
-        &self,
-        deal_id: &str,
-        update: &UpdatePositionRequest,
-    ) -> Result<UpdatePositionResponse, AppError> {
-        let path = format!("positions/otc/{}", deal_id);
-        info!("Updating position: {}", deal_id);
-        let result: UpdatePositionResponse = self.http_client.put(&path, update, Some(2)).await?;
-        debug!(
-            "Position updated: {} with deal reference: {}",
-            deal_id, result.deal_reference
-        );
-        Ok(result)
+/// Relays subscription requests to a Lightstreamer client without locking it.
+///
+/// `connect` holds the client lock for the whole session, so subscriptions added or
+/// removed while connected are pushed straight onto the client's subscription channel.
+#[derive(Clone)]
+pub(crate) struct SubscriptionRelay {
+    commands: mpsc::UnboundedSender<SubscriptionCommand>,
+}
+
+impl SubscriptionRelay {
+    /// Spawns the task forwarding commands to the given client.
+    fn spawn(client: &LightstreamerClient) -> Self {
+        let sender = client.subscription_sender.clone();
+        let (commands, mut receiver) = mpsc::unbounded_channel::<SubscriptionCommand>();
+        tokio::spawn(async move {
+            while let Some(command) = receiver.recv().await {
+                command.dispatch(&sender);
+            }
+        });
+        Self { commands }
     }
 
-    async fn update_level_in_position(
-        &self,
-        deal_id: &str,
-        limit_level: Option<f64>,
-    ) -> Result<UpdatePositionResponse, AppError> {
-        let path = format!("positions/otc/{}", deal_id);
-        info!("Updating position: {}", deal_id);
-        let limit_level = limit_level.unwrap_or(0.0);
-
-        let update: UpdatePositionRequest = UpdatePositionRequest {
-            guaranteed_stop: None,
-            limit_level: Some(limit_level),
-            stop_level: None,
-            trailing_stop: None,
-            trailing_stop_distance: None,
-            trailing_stop_increment: None,
-        };
-        let result: UpdatePositionResponse = self.http_client.put(&path, update, Some(2)).await?;
-        debug!(
-            "Position updated: {} with deal reference: {}",
-            deal_id, result.deal_reference
-        );
-        Ok(result)
+    /// Queues a command for the client.
+    fn send(&self, command: SubscriptionCommand) -> Result<(), AppError> {
+        self.commands
+            .send(command)
+            .map_err(|_| AppError::WebSocketError("subscription relay closed".to_string()))
     }
+}
 
-    async fn close_position(
-        &self,
-        close_request: &ClosePositionRequest,
-    ) -> Result<ClosePositionResponse, AppError> {
-        info!("Closing position");
+/// Server close reasons that end a session on purpose rather than on failure.
+///
+/// - `No more requests to fulfill`: the server closed the session because no
+///   subscription is left on it.
+pub const GRACEFUL_CLOSE_REASONS: &[&str] = &["No more requests to fulfill"];
 
-        // IG API requires POST with _method: DELETE header for closing positions
-        // This is a workaround for HTTP client limitations with DELETE + body
-        let result: ClosePositionResponse = self
-            .http_client
-            .post_with_delete_method("positions/otc", close_request, Some(1))
-            .await?;
+/// Checks whether a Lightstreamer close reason denotes an intentional close.
+///
+/// lightstreamer-rs only reports the close as an error message, so the reason is
+/// matched, case-insensitively, against [`GRACEFUL_CLOSE_REASONS`].
+///
+/// # Arguments
+///
+/// * `reason` - Error message or close reason reported by the client
+#[must_use]
+pub fn is_graceful_close(reason: &str) -> bool {
+    let reason = reason.to_lowercase();
+    GRACEFUL_CLOSE_REASONS
+        .iter()
+        .any(|known| reason.contains(&known.to_lowercase()))
+}
 
-        debug!("Position closed with reference: {}", result.deal_reference);
-        Ok(result)
+/// Why a streaming connection stopped.
+///
+/// `Unexpected` means the client gave up: the stream is dead and will not come back
+/// on its own. Use [`StreamClose::into_result`] to turn it into an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use = "an `Unexpected` close means the stream gave up reconnecting"]
+pub enum StreamClose {
+    /// Closed on shutdown, or by the server because no subscription was left
+    Graceful,
+    /// Closed after the reconnect attempts were exhausted, with the last reason
+    Unexpected(String),
+}
+
+impl StreamClose {
+    /// Classifies a close reason reported by the Lightstreamer client.
+    pub fn from_reason(reason: &str) -> Self {
+        if is_graceful_close(reason) {
+            StreamClose::Graceful
+        } else {
+            StreamClose::Unexpected(reason.to_string())
+        }
     }
 
-    async fn create_working_order(
-        &self,
-        order: &CreateWorkingOrderRequest,
-    ) -> Result<CreateWorkingOrderResponse, AppError> {
-        info!("Creating working order for: {}", order.epic);
-        let result: CreateWorkingOrderResponse = self
-            .http_client
-            .post("workingorders/otc", order, Some(2))
-            .await?;
-        debug!(
-            "Working order created with reference: {}",
-            result.deal_reference
-        );
-        Ok(result)
+    /// Checks whether the connection was closed on purpose.
+    #[must_use]
+    pub fn is_graceful(&self) -> bool {
+        matches!(self, StreamClose::Graceful)
     }
 
-    async fn delete_working_order(&self, deal_id: &str) -> Result<(), AppError> {
-        let path = format!("workingorders/otc/{}", deal_id);
-        let result: CreateWorkingOrderResponse =
-            self.http_client.delete(path.as_str(), Some(2)).await?;
-        debug!(
-            "Working order created with reference: {}",
-            result.deal_reference
-        );
-        Ok(())
+    /// Converts an unexpected close into an error.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` for a graceful close, `AppError::WebSocketError` with the close
+    /// reason otherwise.
+    pub fn into_result(self) -> Result<(), AppError> {
+        match self {
+            StreamClose::Graceful => Ok(()),
+            StreamClose::Unexpected(reason) => Err(AppError::WebSocketError(reason)),
+        }
     }
 }
 
@@ -732,6 +2521,161 @@ pub struct StreamerClient {
     // Flags indicating whether there is at least one active subscription for each client
     has_market_stream_subs: bool,
     has_price_stream_subs: bool,
+    // Subscriptions issued on each client, re-issued whenever the connection is rebuilt
+    market_subscriptions: Arc<Mutex<Vec<SubscriptionSpec>>>,
+    price_subscriptions: Arc<Mutex<Vec<SubscriptionSpec>>>,
+    // Credentials published by the linked REST client on session refresh
+    credentials: Option<watch::Receiver<Option<WebsocketInfo>>>,
+    // Received/dropped counters per item, updated by the forwarding tasks
+    stats: StreamStatsRecorder,
+    // Lock-free subscription channels to each Lightstreamer client
+    market_relay: Option<SubscriptionRelay>,
+    price_relay: Option<SubscriptionRelay>,
+    // Adapter, transport and item prefixes applied to the connections
+    config: Arc<StreamerConfig>,
+    // Subscription status events, published once `subscription_events` is called
+    events: SubscriptionEventHub,
+    // REST client used to replay trade activity missed while reconnecting
+    backfill_client: Option<Arc<Client>>,
+    // Number of times each connection was re-established, bumped by `connect_client`
+    market_reconnects: Arc<watch::Sender<u64>>,
+    price_reconnects: Arc<watch::Sender<u64>>,
+}
+
+/// Cloneable handle adding and removing market subscriptions on a `StreamerClient`.
+///
+/// `StreamerClient::connect` borrows the client for the lifetime of the session. The
+/// handle lets other tasks change market subscriptions meanwhile, without tearing
+/// the connection down. Subscriptions only flow once the market connection is up,
+/// so at least one market subscription must exist before `connect` is called.
+#[derive(Clone)]
+pub struct StreamerHandle {
+    market_relay: Option<SubscriptionRelay>,
+    price_relay: Option<SubscriptionRelay>,
+    market_subscriptions: Arc<Mutex<Vec<SubscriptionSpec>>>,
+    price_subscriptions: Arc<Mutex<Vec<SubscriptionSpec>>>,
+    stats: StreamStatsRecorder,
+    config: Arc<StreamerConfig>,
+    events: SubscriptionEventHub,
+}
+
+impl StreamerHandle {
+    /// Subscribes to market data updates on the live market connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `epics` - List of instrument EPICs to subscribe to
+    /// * `fields` - Set of market data fields to receive
+    ///
+    /// # Returns
+    ///
+    /// Returns a receiver channel for `PriceData` updates, or an error if
+    /// the subscription could not be issued.
+    pub async fn market_subscribe(
+        &self,
+        epics: Vec<String>,
+        fields: HashSet<StreamingMarketField>,
+    ) -> Result<mpsc::UnboundedReceiver<PriceData>, AppError> {
+        let item_receiver = StreamerClient::issue_market_items(
+            self.market_relay.as_ref(),
+            &self.market_subscriptions,
+            &self.config,
+            &self.events,
+            epics,
+            fields,
+        )
+        .await?;
+        Ok(StreamerClient::forward_unbounded(
+            &self.stats,
+            item_receiver,
+            |item_update| PriceData::from(item_update),
+        ))
+    }
+
+    /// Unsubscribes an epic from every market, price and chart subscription.
+    ///
+    /// See [`StreamerClient::unsubscribe`].
+    ///
+    /// # Arguments
+    ///
+    /// * `epic` - Instrument EPIC to stop streaming
+    ///
+    /// # Returns
+    ///
+    /// `true` if at least one subscription referenced the epic.
+    pub async fn unsubscribe(&self, epic: &str) -> Result<bool, AppError> {
+        self.unsubscribe_many(&[epic]).await
+    }
+
+    /// Unsubscribes several epics from every market, price and chart subscription.
+    ///
+    /// Chart subscriptions share the market connection, so they are changed through
+    /// the market relay. Each affected subscription is changed once for all the
+    /// epics, so removing a batch costs a single re-subscription per subscription.
+    ///
+    /// # Arguments
+    ///
+    /// * `epics` - Instrument EPICs to stop streaming
+    ///
+    /// # Returns
+    ///
+    /// `true` if at least one subscription referenced one of the epics.
+    pub async fn unsubscribe_many(&self, epics: &[&str]) -> Result<bool, AppError> {
+        let market_removed = StreamerClient::remove_epics(
+            self.market_relay.as_ref(),
+            &self.market_subscriptions,
+            &self.config,
+            epics,
+        )
+        .await?;
+        let price_removed = StreamerClient::remove_epics(
+            self.price_relay.as_ref(),
+            &self.price_subscriptions,
+            &self.config,
+            epics,
+        )
+        .await?;
+
+        let removed = market_removed + price_removed;
+        if removed > 0 {
+            info!("Unsubscribed {:?} from {} subscription(s)", epics, removed);
+        } else {
+            debug!("No subscription found for {:?}", epics);
+        }
+        Ok(removed > 0)
+    }
+
+    /// Checks whether the stream is alive.
+    ///
+    /// See [`StreamerClient::is_healthy`].
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.stats.snapshot().is_fresh(self.config.staleness_window)
+    }
+
+    /// Waits until the stream is alive.
+    ///
+    /// See [`StreamerClient::wait_until_healthy`].
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait
+    pub async fn wait_until_healthy(&self, timeout: Duration) -> Result<(), AppError> {
+        let deadline = Instant::now() + timeout;
+        let poll_interval = Duration::from_millis(STREAM_HEALTH_POLL_INTERVAL_MS);
+        loop {
+            if self.is_healthy() {
+                return Ok(());
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(AppError::Timeout(format!(
+                    "no streaming update received within {timeout:?}"
+                )));
+            }
+            sleep(poll_interval.min(deadline - now)).await;
+        }
+    }
 }
 
 impl StreamerClient {
@@ -744,38 +2688,95 @@ impl StreamerClient {
     ///
     /// Returns a new `StreamerClient` instance or an error if initialization fails.
     pub async fn new() -> Result<Self, AppError> {
-        let http_client_raw = Arc::new(RwLock::new(Client::new()));
-        let http_client = http_client_raw.read().await;
-        let ws_info = http_client.get_ws_info().await;
-        let password = ws_info.get_ws_password();
+        Self::from_client(&Client::new()).await
+    }
+
+    /// Creates a new streaming client linked to an existing REST client.
+    ///
+    /// The streamer watches the client's `SessionHandle`: whenever
+    /// `Client::refresh_session` publishes fresh credentials, each active connection
+    /// is closed, its password rebuilt from the new `WebsocketInfo`, its subscriptions
+    /// re-issued and the connection re-established.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The REST client whose session the streamer follows
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `StreamerClient` instance or an error if initialization fails.
+    pub async fn from_client(client: &Client) -> Result<Self, AppError> {
+        Self::with_config(client, StreamerConfig::default()).await
+    }
+
+    /// Creates a new streaming client linked to an existing REST client with a
+    /// custom configuration.
+    ///
+    /// Behaves like [`StreamerClient::from_client`], but the pricing adapter, the
+    /// forced transport and the item prefixes are taken from `config` instead of
+    /// the defaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The REST client whose session the streamer follows
+    /// * `config` - Adapter, transport and item prefix settings
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `StreamerClient` instance or an error if initialization fails.
+    /// Transient failures to obtain the streaming credentials are retried
+    /// `WS_INFO_RETRIES` times first; authentication failures are not.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let config = StreamerConfig::default().with_pricing_adapter("PricingV2");
+    /// let streamer = StreamerClient::with_config(&client, config).await?;
+    /// ```
+    pub async fn with_config(client: &Client, config: StreamerConfig) -> Result<Self, AppError> {
+        let ws_info = client
+            .get_ws_info_with_retry(
+                WS_INFO_RETRIES,
+                Duration::from_millis(WS_INFO_RETRY_DELAY_MS),
+            )
+            .await?;
+        let ws_info = match &config.lightstreamer_endpoint {
+            Some(endpoint) => ws_info.with_server(endpoint),
+            None => ws_info,
+        };
+        let password = ws_info.get_ws_password()?;
 
         // Market data client (no adapter specified - uses default)
-        let market_streamer_client = Arc::new(Mutex::new(LightstreamerClient::new(
+        let market_streamer_client = LightstreamerClient::new(
             Some(ws_info.server.as_str()),
             None,
             Some(&ws_info.account_id),
             Some(&password),
-        )?));
+        )?;
+        let market_relay = SubscriptionRelay::spawn(&market_streamer_client);
+        let market_streamer_client = Arc::new(Mutex::new(market_streamer_client));
 
-        let price_streamer_client = Arc::new(Mutex::new(LightstreamerClient::new(
+        let price_streamer_client = LightstreamerClient::new(
             Some(ws_info.server.as_str()),
             None,
             Some(&ws_info.account_id),
             Some(&password),
-        )?));
+        )?;
+        let price_relay = SubscriptionRelay::spawn(&price_streamer_client);
+        let price_streamer_client = Arc::new(Mutex::new(price_streamer_client));
 
-        // Force WebSocket streaming transport on both clients to satisfy IG requirements
+        // Force the configured transport (WebSocket streaming by default, as IG requires)
         {
             let mut client = market_streamer_client.lock().await;
             client
                 .connection_options
-                .set_forced_transport(Some(Transport::WsStreaming));
+                .set_forced_transport(config.forced_transport);
         }
         {
             let mut client = price_streamer_client.lock().await;
             client
                 .connection_options
-                .set_forced_transport(Some(Transport::WsStreaming));
+                .set_forced_transport(config.forced_transport);
         }
 
         Ok(Self {
@@ -784,9 +2785,69 @@ impl StreamerClient {
             price_streamer_client: Some(price_streamer_client),
             has_market_stream_subs: false,
             has_price_stream_subs: false,
+            market_subscriptions: Arc::new(Mutex::new(Vec::new())),
+            price_subscriptions: Arc::new(Mutex::new(Vec::new())),
+            credentials: Some(client.session_handle().subscribe()),
+            stats: StreamStatsRecorder::new(),
+            market_relay: Some(market_relay),
+            price_relay: Some(price_relay),
+            config: Arc::new(config),
+            events: SubscriptionEventHub::new(),
+            backfill_client: None,
+            market_reconnects: Arc::new(watch::channel(0).0),
+            price_reconnects: Arc::new(watch::channel(0).0),
         })
     }
 
+    /// Sets the REST client used to backfill the trade stream after a reconnection.
+    ///
+    /// With `StreamerConfig::backfill_on_reconnect` enabled, every reconnection makes
+    /// trade subscriptions query the account activity between the last trade update
+    /// and the moment the connection settled, `TRADE_BACKFILL_SETTLE_SECS` later, and
+    /// emit a synthesized CONFIRMS update for each activity not already streamed live
+    /// meanwhile. An activity recorded in the same second as the last update before
+    /// the drop may be emitted twice, so consumers should deduplicate by deal reference.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - REST client of the same account
+    #[must_use]
+    pub fn with_backfill_client(mut self, client: Arc<Client>) -> Self {
+        self.backfill_client = Some(client);
+        self
+    }
+
+    /// Gets a handle that can change market subscriptions while connected.
+    #[must_use]
+    pub fn handle(&self) -> StreamerHandle {
+        StreamerHandle {
+            market_relay: self.market_relay.clone(),
+            price_relay: self.price_relay.clone(),
+            market_subscriptions: Arc::clone(&self.market_subscriptions),
+            price_subscriptions: Arc::clone(&self.price_subscriptions),
+            stats: self.stats.clone(),
+            config: Arc::clone(&self.config),
+            events: self.events.clone(),
+        }
+    }
+
+    /// Issues a subscription on the given Lightstreamer client and retains its spec.
+    async fn register_subscription(
+        relay: Option<&SubscriptionRelay>,
+        subscriptions: &Arc<Mutex<Vec<SubscriptionSpec>>>,
+        spec: SubscriptionSpec,
+        client_name: &str,
+    ) -> Result<(), AppError> {
+        let relay = relay.ok_or_else(|| {
+            AppError::WebSocketError(format!("{client_name} streamer client not initialized"))
+        })?;
+
+        let mut spec = spec;
+        spec.issue_via(relay)?;
+        subscriptions.lock().await.push(spec);
+        Ok(())
+    }
+
     /// Creates a default streaming client instance.
     pub async fn default() -> Result<Self, AppError> {
         Self::new().await
@@ -805,7 +2866,7 @@ impl StreamerClient {
     /// # Returns
     ///
     /// Returns a receiver channel for `PriceData` updates, or an error if
-    /// the subscription setup failed.
+    /// the subscription setup failed, e.g. `AppError::InvalidInput` when `fields` is empty.
     ///
     /// # Examples
     ///
@@ -826,52 +2887,200 @@ impl StreamerClient {
         epics: Vec<String>,
         fields: HashSet<StreamingMarketField>,
     ) -> Result<mpsc::UnboundedReceiver<PriceData>, AppError> {
+        let item_receiver = self.issue_market_subscription(epics, fields).await?;
+        Ok(Self::forward_unbounded(
+            &self.stats,
+            item_receiver,
+            |item_update| PriceData::from(item_update),
+        ))
+    }
+
+    /// Subscribes to market data updates through a bounded channel.
+    ///
+    /// Behaves like [`Self::market_subscribe`] but buffers at most `capacity` updates.
+    /// When the buffer is full the incoming update is dropped (drop-newest) and
+    /// counted in [`Self::dropped_updates`], so a slow consumer cannot grow memory
+    /// without bound.
+    ///
+    /// # Arguments
+    ///
+    /// * `epics` - List of instrument EPICs to subscribe to
+    /// * `fields` - Set of market data fields to receive
+    /// * `capacity` - Maximum number of buffered updates (at least 1)
+    ///
+    /// # Returns
+    ///
+    /// Returns a bounded receiver for `PriceData` updates, or an error if
+    /// the subscription setup failed.
+    pub async fn market_subscribe_bounded(
+        &mut self,
+        epics: Vec<String>,
+        fields: HashSet<StreamingMarketField>,
+        capacity: usize,
+    ) -> Result<mpsc::Receiver<PriceData>, AppError> {
+        let item_receiver = self.issue_market_subscription(epics, fields).await?;
+        Ok(Self::forward_bounded(
+            &self.stats,
+            item_receiver,
+            capacity,
+            |item_update| PriceData::from(item_update),
+        ))
+    }
+
+    /// Issues a MARKET subscription and returns the raw update channel.
+    async fn issue_market_subscription(
+        &mut self,
+        epics: Vec<String>,
+        fields: HashSet<StreamingMarketField>,
+    ) -> Result<mpsc::UnboundedReceiver<ItemUpdate>, AppError> {
         // Mark that we have at least one subscription on the market streamer
         self.has_market_stream_subs = true;
 
-        let fields = get_streaming_market_fields(&fields);
-        let market_epics: Vec<String> = epics
-            .iter()
-            .map(|epic| "MARKET:".to_string() + epic)
-            .collect();
-        let mut subscription =
-            Subscription::new(SubscriptionMode::Merge, Some(market_epics), Some(fields))?;
+        Self::issue_market_items(
+            self.market_relay.as_ref(),
+            &self.market_subscriptions,
+            &self.config,
+            &self.events,
+            epics,
+            fields,
+        )
+        .await
+    }
 
-        subscription.set_data_adapter(None)?;
-        subscription.set_requested_snapshot(Some(Snapshot::Yes))?;
+    /// Issues a MARKET subscription through the relay and retains its spec.
+    async fn issue_market_items(
+        relay: Option<&SubscriptionRelay>,
+        subscriptions: &Arc<Mutex<Vec<SubscriptionSpec>>>,
+        config: &StreamerConfig,
+        events: &SubscriptionEventHub,
+        epics: Vec<String>,
+        fields: HashSet<StreamingMarketField>,
+    ) -> Result<mpsc::UnboundedReceiver<ItemUpdate>, AppError> {
+        let fields = get_streaming_market_fields(&fields);
+        let market_epics: Vec<String> = epics.iter().map(|epic| config.market_item(epic)).collect();
+        // Issue the subscription; raw updates arrive on item_receiver
+        let (item_tx, item_receiver) = mpsc::unbounded_channel();
+        let spec = SubscriptionSpec {
+            mode: SubscriptionMode::Merge,
+            items: market_epics,
+            fields,
+            adapter: None,
+            sink: item_tx,
+            id: None,
+            events: events.clone(),
+        };
+        Self::register_subscription(relay, subscriptions, spec, "market").await?;
 
-        // Create channel listener that converts ItemUpdate to PriceData
-        let (listener, item_receiver) = ChannelSubscriptionListener::create_channel();
-        subscription.add_listener(Box::new(listener));
+        info!(
+            "Market subscription created for {} instruments",
+            epics.len()
+        );
+        Ok(item_receiver)
+    }
 
-        // Configure client and add subscription
+    /// Fetches one snapshot per epic from the market stream and unsubscribes.
+    ///
+    /// The subscription is not retained, so it is not re-issued on reconnect. If
+    /// `connect` is not running, a short-lived market connection is opened for the
+    /// duration of the call and closed afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `epics` - Instrument EPICs to quote; duplicates are requested once
+    /// * `fields` - Market data fields to request
+    /// * `timeout` - Maximum time to wait for the snapshots
+    ///
+    /// # Returns
+    ///
+    /// The first update received per epic, keyed by epic. Epics that did not
+    /// respond within `timeout` are missing from the map and logged.
+    pub async fn snapshot_once(
+        &mut self,
+        epics: Vec<String>,
+        fields: HashSet<StreamingMarketField>,
+        timeout: Duration,
+    ) -> Result<HashMap<String, PriceData>, AppError> {
+        let relay = self.market_relay.as_ref().ok_or_else(|| {
+            AppError::WebSocketError("market streamer client not initialized".to_string())
+        })?;
         let client = self.market_streamer_client.as_ref().ok_or_else(|| {
             AppError::WebSocketError("market streamer client not initialized".to_string())
         })?;
 
-        {
-            let mut client = client.lock().await;
-            client
-                .connection_options
-                .set_forced_transport(Some(Transport::WsStreaming));
-            LightstreamerClient::subscribe(client.subscription_sender.clone(), subscription).await;
+        // A duplicate would never get its own snapshot, keeping the loop below waiting
+        let mut seen = HashSet::new();
+        let epics: Vec<String> = epics
+            .into_iter()
+            .filter(|epic| seen.insert(epic.clone()))
+            .collect();
+
+        let (item_tx, mut item_receiver) = mpsc::unbounded_channel();
+        let mut spec = SubscriptionSpec {
+            mode: SubscriptionMode::Merge,
+            items: epics
+                .iter()
+                .map(|epic| self.config.market_item(epic))
+                .collect(),
+            fields: get_streaming_market_fields(&fields),
+            adapter: None,
+            sink: item_tx,
+            id: None,
+            events: self.events.clone(),
+        };
+        spec.issue_via(relay)?;
+
+        // `connect` holds the client lock while connected; otherwise connect here
+        let own_connection = match Arc::clone(client).try_lock_owned() {
+            Ok(mut client) => {
+                let stop = Arc::new(Notify::new());
+                let run_signal = Arc::clone(&stop);
+                let task = tokio::spawn(async move {
+                    client
+                        .connect_direct(run_signal)
+                        .await
+                        .map_err(|e| format!("{:?}", e))
+                });
+                Some((stop, task))
+            }
+            Err(_) => None,
+        };
+
+        let deadline = Instant::now() + timeout;
+        let mut snapshots = HashMap::new();
+        while snapshots.len() < epics.len() {
+            match timeout_at(deadline, item_receiver.recv()).await {
+                Ok(Some(item_update)) => {
+                    let data = PriceData::from(&item_update);
+                    if let Some(epic) = self.config.item_epic(&data.item_name) {
+                        snapshots.entry(epic.to_string()).or_insert(data);
+                    }
+                }
+                Ok(None) | Err(_) => break,
+            }
         }
 
-        // Create a channel for PriceData and spawn a task to convert ItemUpdate to PriceData
-        let (price_tx, price_rx) = mpsc::unbounded_channel();
-        tokio::spawn(async move {
-            let mut receiver = item_receiver;
-            while let Some(item_update) = receiver.recv().await {
-                let price_data = PriceData::from(&item_update);
-                let _ = price_tx.send(price_data);
+        if let Some(id) = spec.id {
+            relay.send(SubscriptionCommand::Unsubscribe(id))?;
+        }
+        if let Some((stop, task)) = own_connection {
+            stop.notify_one();
+            if let Ok(Err(e)) = task.await {
+                warn!("Snapshot connection closed with error: {}", e);
             }
-        });
+        }
 
-        info!(
-            "Market subscription created for {} instruments",
-            epics.len()
-        );
-        Ok(price_rx)
+        let missing: Vec<&String> = epics
+            .iter()
+            .filter(|epic| !snapshots.contains_key(epic.as_str()))
+            .collect();
+        if !missing.is_empty() {
+            warn!(
+                "No snapshot received within {} ms for: {:?}",
+                timeout.as_millis(),
+                missing
+            );
+        }
+        Ok(snapshots)
     }
 
     /// Subscribes to trade updates for the account.
@@ -880,6 +3089,9 @@ impl StreamerClient {
     /// order updates (OPU), and working order updates (WOU) for the account,
     /// and returns a channel receiver for consuming the updates.
     ///
+    /// Events replayed by IG when the subscription starts have
+    /// `TradeFields::is_snapshot` set.
+    ///
     /// # Returns
     ///
     /// Returns a receiver channel for `TradeFields` updates, or an error if
@@ -892,6 +3104,9 @@ impl StreamerClient {
     ///
     /// tokio::spawn(async move {
     ///     while let Some(trade_fields) = receiver.recv().await {
+    ///         if trade_fields.is_snapshot {
+    ///             continue;
+    ///         }
     ///         println!("Trade update: {:?}", trade_fields);
     ///     }
     /// });
@@ -899,52 +3114,141 @@ impl StreamerClient {
     pub async fn trade_subscribe(
         &mut self,
     ) -> Result<mpsc::UnboundedReceiver<TradeFields>, AppError> {
+        let item_receiver = self.issue_trade_subscription().await?;
+        Ok(Self::forward_unbounded(
+            &self.stats,
+            item_receiver,
+            |item_update| TradeData::from(item_update).fields,
+        ))
+    }
+
+    /// Subscribes to trade updates through a bounded channel.
+    ///
+    /// Behaves like [`Self::trade_subscribe`] but buffers at most `capacity` updates,
+    /// dropping the incoming update when the buffer is full (drop-newest).
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of buffered updates (at least 1)
+    ///
+    /// # Returns
+    ///
+    /// Returns a bounded receiver for `TradeFields` updates, or an error if
+    /// the subscription setup failed.
+    pub async fn trade_subscribe_bounded(
+        &mut self,
+        capacity: usize,
+    ) -> Result<mpsc::Receiver<TradeFields>, AppError> {
+        let item_receiver = self.issue_trade_subscription().await?;
+        Ok(Self::forward_bounded(
+            &self.stats,
+            item_receiver,
+            capacity,
+            |item_update| TradeData::from(item_update).fields,
+        ))
+    }
+
+    /// Issues a TRADE subscription and returns the raw update channel.
+    async fn issue_trade_subscription(
+        &mut self,
+    ) -> Result<mpsc::UnboundedReceiver<ItemUpdate>, AppError> {
         // Mark that we have at least one subscription on the market streamer
         self.has_market_stream_subs = true;
 
         let account_id = self.account_id.clone();
-        let fields = Some(vec![
-            "CONFIRMS".to_string(),
-            "OPU".to_string(),
-            "WOU".to_string(),
-        ]);
-        let trade_items = vec![format!("TRADE:{account_id}")];
-
-        let mut subscription =
-            Subscription::new(SubscriptionMode::Distinct, Some(trade_items), fields)?;
-
-        subscription.set_data_adapter(None)?;
-        subscription.set_requested_snapshot(Some(Snapshot::Yes))?;
-
-        // Create channel listener
-        let (listener, item_receiver) = ChannelSubscriptionListener::create_channel();
-        subscription.add_listener(Box::new(listener));
-
-        // Configure client and add subscription (reusing market_streamer_client)
-        let client = self.market_streamer_client.as_ref().ok_or_else(|| {
-            AppError::WebSocketError("market streamer client not initialized".to_string())
-        })?;
+        let fields = vec!["CONFIRMS".to_string(), "OPU".to_string(), "WOU".to_string()];
+        let trade_items = vec![self.config.trade_item(&account_id)];
+
+        // Issue the subscription (reusing market_streamer_client)
+        let (item_tx, item_receiver) = mpsc::unbounded_channel();
+        let spec = SubscriptionSpec {
+            mode: SubscriptionMode::Distinct,
+            items: trade_items,
+            fields,
+            adapter: None,
+            sink: item_tx,
+            id: None,
+            events: self.events.clone(),
+        };
+        Self::register_subscription(
+            self.market_relay.as_ref(),
+            &self.market_subscriptions,
+            spec,
+            "market",
+        )
+        .await?;
 
-        {
-            let mut client = client.lock().await;
-            client
-                .connection_options
-                .set_forced_transport(Some(Transport::WsStreaming));
-            LightstreamerClient::subscribe(client.subscription_sender.clone(), subscription).await;
+        info!("Trade subscription created for account: {}", account_id);
+        match self.backfill_client.as_ref() {
+            Some(client) if self.config.backfill_on_reconnect => Ok(Self::spawn_trade_backfill(
+                Arc::clone(client),
+                // Trades flow on the market connection only
+                self.market_reconnects.subscribe(),
+                self.config.trade_item(&account_id),
+                item_receiver,
+            )),
+            _ => Ok(item_receiver),
         }
+    }
 
-        // Create a channel for TradeFields and spawn a task to convert ItemUpdate to TradeFields
-        let (trade_tx, trade_rx) = mpsc::unbounded_channel();
+    /// Spawns a task relaying trade updates and replaying missed activity on reconnection.
+    ///
+    /// Each time `reconnects` changes, the activity missed while reconnecting is fetched
+    /// and emitted as CONFIRMS updates, as described on `relay_trades`.
+    fn spawn_trade_backfill(
+        client: Arc<Client>,
+        reconnects: watch::Receiver<u64>,
+        item: String,
+        item_receiver: mpsc::UnboundedReceiver<ItemUpdate>,
+    ) -> mpsc::UnboundedReceiver<ItemUpdate> {
+        let (tx, rx) = mpsc::unbounded_channel();
         tokio::spawn(async move {
-            let mut receiver = item_receiver;
-            while let Some(item_update) = receiver.recv().await {
-                let trade_data = crate::presentation::trade::TradeData::from(&item_update);
-                let _ = trade_tx.send(trade_data.fields);
-            }
+            relay_trades(reconnects, item_receiver, tx, |from, to| {
+                Self::missed_trades(&client, &item, from, to)
+            })
+            .await;
         });
+        rx
+    }
 
-        info!("Trade subscription created for account: {}", account_id);
-        Ok(trade_rx)
+    /// Fetches the account activity of a period as synthesized trade updates.
+    ///
+    /// Errors are logged and yield no update, so a failed backfill never stops the stream.
+    async fn missed_trades(
+        client: &Client,
+        item: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<ItemUpdate> {
+        let from = from.format(ACTIVITY_QUERY_DATE_FORMAT).to_string();
+        let to = to.format(ACTIVITY_QUERY_DATE_FORMAT).to_string();
+        let activities = match client.get_activity_with_details(&from, &to).await {
+            Ok(response) => response.activities,
+            Err(e) => {
+                warn!("Trade backfill from {} to {} failed: {}", from, to, e);
+                return Vec::new();
+            }
+        };
+        info!(
+            "Trade backfill from {} to {}: {} activities",
+            from,
+            to,
+            activities.len()
+        );
+
+        activities
+            .iter()
+            .filter_map(|activity| {
+                let confirms = serde_json::to_string(&TradeConfirmation::from(activity)).ok()?;
+                Some(ItemUpdate {
+                    item_name: Some(item.to_string()),
+                    item_pos: 1,
+                    is_snapshot: false,
+                    fields: HashMap::from([("CONFIRMS".to_string(), Some(confirms.clone()))]),
+                    changed_fields: HashMap::from([("CONFIRMS".to_string(), confirms)]),
+                })
+            })
+            .collect()
     }
 
     /// Subscribes to account data updates.
@@ -977,6 +3281,47 @@ impl StreamerClient {
         &mut self,
         fields: HashSet<StreamingAccountDataField>,
     ) -> Result<mpsc::UnboundedReceiver<AccountFields>, AppError> {
+        let item_receiver = self.issue_account_subscription(fields).await?;
+        Ok(Self::forward_unbounded(
+            &self.stats,
+            item_receiver,
+            |item_update| AccountData::from(item_update).fields,
+        ))
+    }
+
+    /// Subscribes to account data updates through a bounded channel.
+    ///
+    /// Behaves like [`Self::account_subscribe`] but buffers at most `capacity` updates,
+    /// dropping the incoming update when the buffer is full (drop-newest).
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - Set of account data fields to receive
+    /// * `capacity` - Maximum number of buffered updates (at least 1)
+    ///
+    /// # Returns
+    ///
+    /// Returns a bounded receiver for `AccountFields` updates, or an error if
+    /// the subscription setup failed.
+    pub async fn account_subscribe_bounded(
+        &mut self,
+        fields: HashSet<StreamingAccountDataField>,
+        capacity: usize,
+    ) -> Result<mpsc::Receiver<AccountFields>, AppError> {
+        let item_receiver = self.issue_account_subscription(fields).await?;
+        Ok(Self::forward_bounded(
+            &self.stats,
+            item_receiver,
+            capacity,
+            |item_update| AccountData::from(item_update).fields,
+        ))
+    }
+
+    /// Issues an ACCOUNT subscription and returns the raw update channel.
+    async fn issue_account_subscription(
+        &mut self,
+        fields: HashSet<StreamingAccountDataField>,
+    ) -> Result<mpsc::UnboundedReceiver<ItemUpdate>, AppError> {
         // Mark that we have at least one subscription on the market streamer
         self.has_market_stream_subs = true;
 
@@ -984,41 +3329,27 @@ impl StreamerClient {
         let account_id = self.account_id.clone();
         let account_items = vec![format!("ACCOUNT:{account_id}")];
 
-        let mut subscription =
-            Subscription::new(SubscriptionMode::Merge, Some(account_items), Some(fields))?;
-
-        subscription.set_data_adapter(None)?;
-        subscription.set_requested_snapshot(Some(Snapshot::Yes))?;
-
-        // Create channel listener
-        let (listener, item_receiver) = ChannelSubscriptionListener::create_channel();
-        subscription.add_listener(Box::new(listener));
-
-        // Configure client and add subscription (reusing market_streamer_client)
-        let client = self.market_streamer_client.as_ref().ok_or_else(|| {
-            AppError::WebSocketError("market streamer client not initialized".to_string())
-        })?;
-
-        {
-            let mut client = client.lock().await;
-            client
-                .connection_options
-                .set_forced_transport(Some(Transport::WsStreaming));
-            LightstreamerClient::subscribe(client.subscription_sender.clone(), subscription).await;
-        }
-
-        // Create a channel for AccountFields and spawn a task to convert ItemUpdate to AccountFields
-        let (account_tx, account_rx) = mpsc::unbounded_channel();
-        tokio::spawn(async move {
-            let mut receiver = item_receiver;
-            while let Some(item_update) = receiver.recv().await {
-                let account_data = crate::presentation::account::AccountData::from(&item_update);
-                let _ = account_tx.send(account_data.fields);
-            }
-        });
+        // Issue the subscription (reusing market_streamer_client)
+        let (item_tx, item_receiver) = mpsc::unbounded_channel();
+        let spec = SubscriptionSpec {
+            mode: SubscriptionMode::Merge,
+            items: account_items,
+            fields,
+            adapter: None,
+            sink: item_tx,
+            id: None,
+            events: self.events.clone(),
+        };
+        Self::register_subscription(
+            self.market_relay.as_ref(),
+            &self.market_subscriptions,
+            spec,
+            "market",
+        )
+        .await?;
 
         info!("Account subscription created for account: {}", account_id);
-        Ok(account_rx)
+        Ok(item_receiver)
     }
 
     /// Subscribes to price data updates for the specified instruments.
@@ -1035,7 +3366,7 @@ impl StreamerClient {
     /// # Returns
     ///
     /// Returns a receiver channel for `PriceData` updates, or an error if
-    /// the subscription setup failed.
+    /// the subscription setup failed, e.g. `AppError::InvalidInput` when `fields` is empty.
     ///
     /// # Examples
     ///
@@ -1056,6 +3387,132 @@ impl StreamerClient {
         epics: Vec<String>,
         fields: HashSet<StreamingPriceField>,
     ) -> Result<mpsc::UnboundedReceiver<PriceData>, AppError> {
+        let item_receiver = self.issue_price_subscription(epics, fields).await?;
+        Ok(Self::forward_unbounded(
+            &self.stats,
+            item_receiver,
+            |item_update| PriceData::from(item_update),
+        ))
+    }
+
+    /// Subscribes to price data updates through a bounded channel.
+    ///
+    /// Behaves like [`Self::price_subscribe`] but buffers at most `capacity` updates,
+    /// dropping the incoming update when the buffer is full (drop-newest).
+    ///
+    /// # Arguments
+    ///
+    /// * `epics` - List of instrument EPICs to subscribe to
+    /// * `fields` - Set of price data fields to receive
+    /// * `capacity` - Maximum number of buffered updates (at least 1)
+    ///
+    /// # Returns
+    ///
+    /// Returns a bounded receiver for `PriceData` updates, or an error if
+    /// the subscription setup failed.
+    pub async fn price_subscribe_bounded(
+        &mut self,
+        epics: Vec<String>,
+        fields: HashSet<StreamingPriceField>,
+        capacity: usize,
+    ) -> Result<mpsc::Receiver<PriceData>, AppError> {
+        let item_receiver = self.issue_price_subscription(epics, fields).await?;
+        Ok(Self::forward_bounded(
+            &self.stats,
+            item_receiver,
+            capacity,
+            |item_update| PriceData::from(item_update),
+        ))
+    }
+
+    /// Subscribes to consolidated quotes built from the market and price streams.
+    ///
+    /// Issues a MARKET subscription (`BID`, `OFFER`, `MARKET_STATE`, `UPDATE_TIME`)
+    /// and a PRICE subscription (`BIDPRICE1`, `ASKPRICE1`, `TIMESTAMP`, `DLG_FLAG`)
+    /// for the same epics and merges both through a [`QuoteAggregator`]. A quote is
+    /// emitted on every update once the epic's bid and offer are known.
+    ///
+    /// # Arguments
+    ///
+    /// * `epics` - List of instrument EPICs to subscribe to
+    ///
+    /// # Returns
+    ///
+    /// Returns a receiver channel for `Quote` updates, or an error if either
+    /// subscription could not be set up.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut quotes = client
+    ///     .subscribe_quotes(vec!["OP.D.OTCDAX1.24000C.IP".to_string()])
+    ///     .await?;
+    /// while let Some(quote) = quotes.recv().await {
+    ///     println!("{} {} / {}", quote.epic, quote.bid, quote.offer);
+    /// }
+    /// ```
+    pub async fn subscribe_quotes(
+        &mut self,
+        epics: Vec<String>,
+    ) -> Result<mpsc::UnboundedReceiver<Quote>, AppError> {
+        let market_fields = HashSet::from([
+            StreamingMarketField::Bid,
+            StreamingMarketField::Offer,
+            StreamingMarketField::MarketState,
+            StreamingMarketField::UpdateTime,
+        ]);
+        let price_fields = HashSet::from([
+            StreamingPriceField::BidPrice1,
+            StreamingPriceField::AskPrice1,
+            StreamingPriceField::Timestamp,
+            StreamingPriceField::DlgFlag,
+        ]);
+        let mut market_receiver = self.market_subscribe(epics.clone(), market_fields).await?;
+        let mut price_receiver = self.price_subscribe(epics, price_fields).await?;
+
+        let config = Arc::clone(&self.config);
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut aggregator = QuoteAggregator::new();
+            let mut market_open = true;
+            let mut price_open = true;
+            while market_open || price_open {
+                let quote = tokio::select! {
+                    update = market_receiver.recv(), if market_open => match update {
+                        Some(update) => config
+                            .item_epic(&update.item_name)
+                            .and_then(|epic| aggregator.apply_market(epic, &update)),
+                        None => {
+                            market_open = false;
+                            None
+                        }
+                    },
+                    update = price_receiver.recv(), if price_open => match update {
+                        Some(update) => config
+                            .item_epic(&update.item_name)
+                            .and_then(|epic| aggregator.apply_price(epic, &update)),
+                        None => {
+                            price_open = false;
+                            None
+                        }
+                    },
+                };
+                if let Some(quote) = quote
+                    && tx.send(quote).is_err()
+                {
+                    break;
+                }
+            }
+        });
+        Ok(rx)
+    }
+
+    /// Issues a PRICE subscription on the Pricing adapter and returns the raw update channel.
+    async fn issue_price_subscription(
+        &mut self,
+        epics: Vec<String>,
+        fields: HashSet<StreamingPriceField>,
+    ) -> Result<mpsc::UnboundedReceiver<ItemUpdate>, AppError> {
         // Mark that we have at least one subscription on the price streamer
         self.has_price_stream_subs = true;
 
@@ -1063,56 +3520,171 @@ impl StreamerClient {
         let account_id = self.account_id.clone();
         let price_epics: Vec<String> = epics
             .iter()
-            .map(|epic| format!("PRICE:{account_id}:{epic}"))
+            .map(|epic| self.config.price_item(&account_id, epic))
             .collect();
 
         // Debug what we are about to subscribe to (items and fields)
         tracing::debug!("Pricing subscribe items: {:?}", price_epics);
         tracing::debug!("Pricing subscribe fields: {:?}", fields);
 
-        let mut subscription =
-            Subscription::new(SubscriptionMode::Merge, Some(price_epics), Some(fields))?;
-
-        // Allow overriding the Pricing adapter name via env var to match server config
-        let pricing_adapter =
-            std::env::var("IG_PRICING_ADAPTER").unwrap_or_else(|_| "Pricing".to_string());
+        // Configured adapter; the default honours the IG_PRICING_ADAPTER env var
+        let pricing_adapter = self.config.pricing_adapter.clone();
         tracing::debug!("Using Pricing data adapter: {}", pricing_adapter);
-        subscription.set_data_adapter(Some(pricing_adapter))?;
-        subscription.set_requested_snapshot(Some(Snapshot::Yes))?;
 
-        // Create channel listener
-        let (listener, item_receiver) = ChannelSubscriptionListener::create_channel();
-        subscription.add_listener(Box::new(listener));
+        // Issue the subscription
+        let (item_tx, item_receiver) = mpsc::unbounded_channel();
+        let spec = SubscriptionSpec {
+            mode: SubscriptionMode::Merge,
+            items: price_epics,
+            fields,
+            adapter: Some(pricing_adapter),
+            sink: item_tx,
+            id: None,
+            events: self.events.clone(),
+        };
+        Self::register_subscription(
+            self.price_relay.as_ref(),
+            &self.price_subscriptions,
+            spec,
+            "price",
+        )
+        .await?;
 
-        // Configure client and add subscription
-        let client = self.price_streamer_client.as_ref().ok_or_else(|| {
-            AppError::WebSocketError("price streamer client not initialized".to_string())
-        })?;
+        info!(
+            "Price subscription created for {} instruments (account: {})",
+            epics.len(),
+            account_id
+        );
+        Ok(item_receiver)
+    }
 
-        {
-            let mut client = client.lock().await;
-            client
-                .connection_options
-                .set_forced_transport(Some(Transport::WsStreaming));
-            LightstreamerClient::subscribe(client.subscription_sender.clone(), subscription).await;
-        }
+    /// Gets the number of updates dropped by bounded subscriptions.
+    ///
+    /// The counter covers every `*_subscribe_bounded` receiver created by this client.
+    #[must_use]
+    pub fn dropped_updates(&self) -> u64 {
+        self.stats.snapshot().total_dropped()
+    }
+
+    /// Gets the received/dropped counters and last update time per subscribed item.
+    ///
+    /// # Returns
+    ///
+    /// A snapshot of the metrics; its `Display` renders the Prometheus text format.
+    #[must_use]
+    pub fn stream_stats(&self) -> StreamStats {
+        self.stats.snapshot()
+    }
+
+    /// Checks whether the stream is alive.
+    ///
+    /// A connected but silent session is not healthy: at least one subscribed item
+    /// must have received an update within the configured staleness window
+    /// (`StreamerConfig::staleness_window`, 30 s by default). As `connect` borrows
+    /// the client while streaming, probes usually go through [`Self::handle`].
+    ///
+    /// # Returns
+    ///
+    /// `true` if an update arrived within the staleness window.
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.stats.snapshot().is_fresh(self.config.staleness_window)
+    }
+
+    /// Waits until the stream is alive, for readiness probes.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` once [`Self::is_healthy`] holds, or `AppError::Timeout` if no
+    /// update arrived within `timeout`.
+    pub async fn wait_until_healthy(&self, timeout: Duration) -> Result<(), AppError> {
+        self.handle().wait_until_healthy(timeout).await
+    }
+
+    /// Opens a channel reporting the server's answer to each subscribed item.
+    ///
+    /// Emits `Subscribed`, `Rejected` or `Unsubscribed` for every item of every
+    /// subscription issued by this client or its handles, including re-issues after a
+    /// reconnect. Events are only published while a receiver is open; calling this
+    /// again closes the previous receiver.
+    ///
+    /// # Returns
+    ///
+    /// A receiver of `SubscriptionEvent`s.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut events = streamer.subscription_events();
+    /// tokio::spawn(async move {
+    ///     while let Some(event) = events.recv().await {
+    ///         if let SubscriptionEvent::Rejected { item, reason } = event {
+    ///             warn!("{item} rejected: {reason}");
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    #[must_use]
+    pub fn subscription_events(&self) -> mpsc::UnboundedReceiver<SubscriptionEvent> {
+        self.events.subscribe()
+    }
 
-        // Create a channel for PriceData and spawn a task to convert ItemUpdate to PriceData
-        let (price_tx, price_rx) = mpsc::unbounded_channel();
+    /// Spawns a task converting raw updates into an unbounded channel.
+    fn forward_unbounded<T, F>(
+        stats: &StreamStatsRecorder,
+        mut item_receiver: mpsc::UnboundedReceiver<ItemUpdate>,
+        convert: F,
+    ) -> mpsc::UnboundedReceiver<T>
+    where
+        T: Send + 'static,
+        F: Fn(&ItemUpdate) -> T + Send + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let stats = stats.clone();
         tokio::spawn(async move {
-            let mut receiver = item_receiver;
-            while let Some(item_update) = receiver.recv().await {
-                let price_data = PriceData::from(&item_update);
-                let _ = price_tx.send(price_data);
+            while let Some(item_update) = item_receiver.recv().await {
+                stats.record_received(item_update.item_name.as_deref().unwrap_or_default());
+                if tx.send(convert(&item_update)).is_err() {
+                    break;
+                }
             }
         });
+        rx
+    }
 
-        info!(
-            "Price subscription created for {} instruments (account: {})",
-            epics.len(),
-            account_id
-        );
-        Ok(price_rx)
+    /// Spawns a task converting raw updates into a bounded channel.
+    ///
+    /// Updates arriving while the buffer is full are dropped and counted.
+    fn forward_bounded<T, F>(
+        stats: &StreamStatsRecorder,
+        mut item_receiver: mpsc::UnboundedReceiver<ItemUpdate>,
+        capacity: usize,
+        convert: F,
+    ) -> mpsc::Receiver<T>
+    where
+        T: Send + 'static,
+        F: Fn(&ItemUpdate) -> T + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        let stats = stats.clone();
+        tokio::spawn(async move {
+            while let Some(item_update) = item_receiver.recv().await {
+                let item = item_update.item_name.as_deref().unwrap_or_default();
+                stats.record_received(item);
+                match tx.try_send(convert(&item_update)) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(_)) => {
+                        stats.record_dropped(item);
+                    }
+                    Err(TrySendError::Closed(_)) => break,
+                }
+            }
+        });
+        rx
     }
 
     /// Subscribes to chart data updates for the specified instruments and scale.
@@ -1163,43 +3735,34 @@ impl StreamerClient {
             .map(|epic| format!("CHART:{epic}:{scale}",))
             .collect();
 
-        // Candle data uses MERGE mode, tick data uses DISTINCT
-        let mode = if matches!(scale, ChartScale::Tick) {
-            SubscriptionMode::Distinct
-        } else {
-            SubscriptionMode::Merge
-        };
-
-        let mut subscription = Subscription::new(mode, Some(chart_items), Some(fields))?;
-
-        subscription.set_data_adapter(None)?;
-        subscription.set_requested_snapshot(Some(Snapshot::Yes))?;
-
-        // Create channel listener
-        let (listener, item_receiver) = ChannelSubscriptionListener::create_channel();
-        subscription.add_listener(Box::new(listener));
-
-        // Configure client and add subscription (reusing market_streamer_client)
-        let client = self.market_streamer_client.as_ref().ok_or_else(|| {
-            AppError::WebSocketError("market streamer client not initialized".to_string())
-        })?;
-
-        {
-            let mut client = client.lock().await;
-            client
-                .connection_options
-                .set_forced_transport(Some(Transport::WsStreaming));
-            LightstreamerClient::subscribe(client.subscription_sender.clone(), subscription).await;
-        }
+        // Candle data uses MERGE mode, tick data uses DISTINCT
+        let mode = if matches!(scale, ChartScale::Tick) {
+            SubscriptionMode::Distinct
+        } else {
+            SubscriptionMode::Merge
+        };
 
-        // Create a channel for ChartData and spawn a task to convert ItemUpdate to ChartData
-        let (chart_tx, chart_rx) = mpsc::unbounded_channel();
-        tokio::spawn(async move {
-            let mut receiver = item_receiver;
-            while let Some(item_update) = receiver.recv().await {
-                let chart_data = ChartData::from(&item_update);
-                let _ = chart_tx.send(chart_data);
-            }
+        // Issue the subscription (reusing market_streamer_client)
+        let (item_tx, item_receiver) = mpsc::unbounded_channel();
+        let spec = SubscriptionSpec {
+            mode,
+            items: chart_items,
+            fields,
+            adapter: None,
+            sink: item_tx,
+            id: None,
+            events: self.events.clone(),
+        };
+        Self::register_subscription(
+            self.market_relay.as_ref(),
+            &self.market_subscriptions,
+            spec,
+            "market",
+        )
+        .await?;
+
+        let chart_rx = Self::forward_unbounded(&self.stats, item_receiver, |item_update| {
+            ChartData::from(item_update)
         });
 
         info!(
@@ -1224,10 +3787,19 @@ impl StreamerClient {
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` when all connections are closed gracefully, or an error if
-    /// any connection fails after maximum retry attempts.
+    /// Returns `StreamClose::Graceful` when every connection was closed on shutdown
+    /// or because no subscription was left, `StreamClose::Unexpected` with the close
+    /// reasons when a connection gave up after exhausting its reconnect attempts, or
+    /// an error if a connection task failed outright.
     ///
-    pub async fn connect(&mut self, shutdown_signal: Option<Arc<Notify>>) -> Result<(), AppError> {
+    /// `Unexpected` is returned as `Ok`, so `connect(None).await?` alone does not
+    /// catch a stream that gave up; chain [`StreamClose::into_result`] to treat it as
+    /// an error.
+    ///
+    pub async fn connect(
+        &mut self,
+        shutdown_signal: Option<Arc<Notify>>,
+    ) -> Result<StreamClose, AppError> {
         // Use provided signal or create a new one with signal hooks
         let signal = if let Some(sig) = shutdown_signal {
             sig
@@ -1244,10 +3816,26 @@ impl StreamerClient {
             if let Some(client) = self.market_streamer_client.as_ref() {
                 let client = Arc::clone(client);
                 let signal = Arc::clone(&signal);
-                let task =
-                    tokio::spawn(
-                        async move { Self::connect_client(client, signal, "Market").await },
-                    );
+                let credentials = self.credentials.clone();
+                let subscriptions = Arc::clone(&self.market_subscriptions);
+                let reconnect = self.config.reconnect;
+                let reconnects = Arc::clone(&self.market_reconnects);
+                let stats = self.stats.clone();
+                let idle_timeout = self.config.idle_timeout;
+                let task = tokio::spawn(async move {
+                    Self::connect_client(
+                        client,
+                        signal,
+                        "Market",
+                        credentials,
+                        subscriptions,
+                        reconnect,
+                        reconnects,
+                        stats,
+                        idle_timeout,
+                    )
+                    .await
+                });
                 tasks.push(task);
             }
         } else {
@@ -1259,10 +3847,26 @@ impl StreamerClient {
             if let Some(client) = self.price_streamer_client.as_ref() {
                 let client = Arc::clone(client);
                 let signal = Arc::clone(&signal);
-                let task =
-                    tokio::spawn(
-                        async move { Self::connect_client(client, signal, "Price").await },
-                    );
+                let credentials = self.credentials.clone();
+                let subscriptions = Arc::clone(&self.price_subscriptions);
+                let reconnect = self.config.reconnect;
+                let reconnects = Arc::clone(&self.price_reconnects);
+                let stats = self.stats.clone();
+                let idle_timeout = self.config.idle_timeout;
+                let task = tokio::spawn(async move {
+                    Self::connect_client(
+                        client,
+                        signal,
+                        "Price",
+                        credentials,
+                        subscriptions,
+                        reconnect,
+                        reconnects,
+                        stats,
+                        idle_timeout,
+                    )
+                    .await
+                });
                 tasks.push(task);
             }
         } else {
@@ -1271,7 +3875,7 @@ impl StreamerClient {
 
         if tasks.is_empty() {
             warn!("No streaming clients selected for connection (no active subscriptions)");
-            return Ok(());
+            return Ok(StreamClose::Graceful);
         }
 
         info!("Connecting {} streaming client(s)...", tasks.len());
@@ -1281,11 +3885,16 @@ impl StreamerClient {
 
         // Check if any task failed
         let mut has_error = false;
+        let mut unexpected = Vec::new();
         for (idx, result) in results.iter().enumerate() {
             match result {
-                Ok(Ok(_)) => {
+                Ok(Ok(StreamClose::Graceful)) => {
                     debug!("Streaming client {} completed successfully", idx);
                 }
+                Ok(Ok(StreamClose::Unexpected(reason))) => {
+                    error!("Streaming client {} closed unexpectedly: {}", idx, reason);
+                    unexpected.push(reason.clone());
+                }
                 Ok(Err(e)) => {
                     error!("Streaming client {} failed: {:?}", idx, e);
                     has_error = true;
@@ -1302,81 +3911,436 @@ impl StreamerClient {
                 "one or more streaming connections failed".to_string(),
             ));
         }
+        if !unexpected.is_empty() {
+            return Ok(StreamClose::Unexpected(unexpected.join("; ")));
+        }
 
         info!("All streaming connections closed gracefully");
-        Ok(())
+        Ok(StreamClose::Graceful)
     }
 
     /// Internal helper to connect a single Lightstreamer client with retry logic.
+    ///
+    /// Failed attempts are retried following `reconnect`; once they are exhausted the
+    /// last close reason is returned as `StreamClose::Unexpected`. When `credentials` is
+    /// provided, a change on it closes the current connection, applies the new
+    /// password, re-issues the retained subscriptions and reconnects without consuming
+    /// a retry attempt. When `idle_timeout` is provided, a connection whose
+    /// subscriptions stay silent for the timeout is rebuilt the same way. A run that
+    /// received updates counts as established, so the failure counter starts over
+    /// when it drops. `reconnects` is bumped every time the connection is rebuilt.
+    #[allow(clippy::too_many_arguments)]
     async fn connect_client(
         client: Arc<Mutex<LightstreamerClient>>,
         signal: Arc<Notify>,
         client_type: &str,
-    ) -> Result<(), AppError> {
-        let mut retry_interval_millis: u64 = 0;
-        let mut retry_counter: u64 = 0;
+        mut credentials: Option<watch::Receiver<Option<WebsocketInfo>>>,
+        subscriptions: Arc<Mutex<Vec<SubscriptionSpec>>>,
+        reconnect: StreamReconnectPolicy,
+        reconnects: Arc<watch::Sender<u64>>,
+        stats: StreamStatsRecorder,
+        idle_timeout: Option<Duration>,
+    ) -> Result<StreamClose, AppError> {
+        let max_attempts = reconnect
+            .max_attempts
+            .map_or_else(|| "unlimited".to_string(), |max| max.to_string());
+        let mut failures: u32 = 0;
+
+        loop {
+            let (run_signal, refreshed, watcher) =
+                Self::watch_credentials(&signal, credentials.clone());
+            let (run_signal, idled, idle_watcher) = Self::watch_idle(
+                &run_signal,
+                idle_timeout,
+                stats.clone(),
+                Arc::clone(&subscriptions),
+            );
+            let received_before = Self::received_updates(&subscriptions, &stats)
+                .await
+                .unwrap_or(0);
 
-        while retry_counter < MAX_CONNECTION_ATTEMPTS {
             let connect_result = {
                 let mut client = client.lock().await;
-                client.connect_direct(Arc::clone(&signal)).await
+                client.connect_direct(Arc::clone(&run_signal)).await
             };
 
+            if let Some(watcher) = watcher {
+                watcher.abort();
+            }
+            if let Some(idle_watcher) = idle_watcher {
+                idle_watcher.abort();
+            }
+
             // Convert error to String immediately to avoid Send issues
             let result_with_string_error = connect_result.map_err(|e| format!("{:?}", e));
 
             match result_with_string_error {
+                Ok(_) if refreshed.load(Ordering::SeqCst) => {
+                    if let Some(credentials) = credentials.as_mut() {
+                        Self::apply_credentials(&client, credentials, &subscriptions, client_type)
+                            .await?;
+                    }
+                    failures = 0;
+                    reconnects.send_modify(|count| *count += 1);
+                    continue;
+                }
+                Ok(_) if idled.load(Ordering::SeqCst) => {
+                    // Replaces the subscriptions of the silent connection, never duplicating them
+                    Self::reissue_subscriptions(&client, &subscriptions, client_type).await?;
+                    failures = 0;
+                    reconnects.send_modify(|count| *count += 1);
+                    continue;
+                }
                 Ok(_) => {
                     info!("{} streamer connected successfully", client_type);
                     break;
                 }
                 Err(error_msg) => {
-                    // If server closed because there are no active subscriptions, treat as graceful
-                    if error_msg.contains("No more requests to fulfill") {
+                    let close = StreamClose::from_reason(&error_msg);
+                    if close.is_graceful() {
                         info!(
-                            "{} streamer closed gracefully: no active subscriptions (server reason: No more requests to fulfill)",
-                            client_type
+                            "{} streamer closed gracefully (server reason: {})",
+                            client_type, error_msg
                         );
-                        return Ok(());
+                        return Ok(close);
                     }
 
                     error!("{} streamer connection failed: {}", client_type, error_msg);
 
-                    if retry_counter < MAX_CONNECTION_ATTEMPTS - 1 {
-                        tokio::time::sleep(std::time::Duration::from_millis(retry_interval_millis))
-                            .await;
-                        retry_interval_millis =
-                            (retry_interval_millis + (200 * retry_counter)).min(5000);
-                        retry_counter += 1;
-                        warn!(
-                            "{} streamer retrying (attempt {}/{}) in {:.2} seconds...",
-                            client_type,
-                            retry_counter + 1,
-                            MAX_CONNECTION_ATTEMPTS,
-                            retry_interval_millis as f64 / 1000.0
+                    // Only consecutive failures count: a run that streamed was established
+                    let received_after = Self::received_updates(&subscriptions, &stats)
+                        .await
+                        .unwrap_or(0);
+                    if received_after > received_before {
+                        failures = 0;
+                    }
+                    failures = failures.saturating_add(1);
+                    if !reconnect.allows_attempt(failures) {
+                        error!(
+                            "{} streamer failed after {} attempts",
+                            client_type, failures
                         );
-                    } else {
-                        retry_counter += 1;
+                        return Ok(StreamClose::Unexpected(format!(
+                            "{} streamer: maximum connection attempts ({}) exceeded, last error: {}",
+                            client_type, failures, error_msg
+                        )));
                     }
+
+                    let delay = reconnect.delay(failures - 1);
+                    warn!(
+                        "{} streamer retrying (attempt {}/{}) in {:.2} seconds...",
+                        client_type,
+                        failures + 1,
+                        max_attempts,
+                        delay.as_secs_f64()
+                    );
+                    tokio::time::sleep(delay).await;
+                    // Subscriptions do not survive a dropped connection
+                    Self::reissue_subscriptions(&client, &subscriptions, client_type).await?;
+                    reconnects.send_modify(|count| *count += 1);
                 }
             }
         }
 
-        if retry_counter >= MAX_CONNECTION_ATTEMPTS {
-            error!(
-                "{} streamer failed after {} attempts",
-                client_type, MAX_CONNECTION_ATTEMPTS
-            );
-            return Err(AppError::WebSocketError(format!(
-                "{} streamer: maximum connection attempts ({}) exceeded",
-                client_type, MAX_CONNECTION_ATTEMPTS
-            )));
+        info!("{} streamer connection closed gracefully", client_type);
+        Ok(StreamClose::Graceful)
+    }
+
+    /// Builds the signal driving a single connection run.
+    ///
+    /// Without credentials the shutdown signal is used as is. Otherwise a forwarding
+    /// task fires the returned signal either on shutdown or when fresh credentials are
+    /// published, in which case the returned flag is set.
+    fn watch_credentials(
+        signal: &Arc<Notify>,
+        credentials: Option<watch::Receiver<Option<WebsocketInfo>>>,
+    ) -> (Arc<Notify>, Arc<AtomicBool>, Option<JoinHandle<()>>) {
+        let refreshed = Arc::new(AtomicBool::new(false));
+        let Some(mut credentials) = credentials else {
+            return (Arc::clone(signal), refreshed, None);
+        };
+
+        let run_signal = Arc::new(Notify::new());
+        let watcher = {
+            let signal = Arc::clone(signal);
+            let run_signal = Arc::clone(&run_signal);
+            let refreshed = Arc::clone(&refreshed);
+            tokio::spawn(async move {
+                tokio::select! {
+                    _ = signal.notified() => {}
+                    changed = credentials.changed() => {
+                        if changed.is_ok() {
+                            refreshed.store(true, Ordering::SeqCst);
+                        } else {
+                            // Session handle dropped: only shutdown can end the run
+                            signal.notified().await;
+                        }
+                    }
+                }
+                run_signal.notify_one();
+            })
+        };
+
+        (run_signal, refreshed, Some(watcher))
+    }
+
+    /// Builds the idle watchdog of a single connection run.
+    ///
+    /// Without an idle timeout `run_signal` is returned as is. Otherwise a task fires
+    /// the returned signal either when `run_signal` fires or when none of the items
+    /// of `subscriptions` received an update within the timeout, in which case the
+    /// returned flag is set. The received counters are polled `IDLE_WATCHDOG_POLLS`
+    /// times per timeout, and silence is measured from the start of the run at most.
+    /// While nothing is subscribed the watchdog keeps waiting.
+    fn watch_idle(
+        run_signal: &Arc<Notify>,
+        idle_timeout: Option<Duration>,
+        stats: StreamStatsRecorder,
+        subscriptions: Arc<Mutex<Vec<SubscriptionSpec>>>,
+    ) -> (Arc<Notify>, Arc<AtomicBool>, Option<JoinHandle<()>>) {
+        let idled = Arc::new(AtomicBool::new(false));
+        let Some(timeout) = idle_timeout else {
+            return (Arc::clone(run_signal), idled, None);
+        };
+
+        let idle_signal = Arc::new(Notify::new());
+        let watcher = {
+            let run_signal = Arc::clone(run_signal);
+            let idle_signal = Arc::clone(&idle_signal);
+            let idled = Arc::clone(&idled);
+            tokio::spawn(async move {
+                let poll = (timeout / IDLE_WATCHDOG_POLLS).max(Duration::from_millis(1));
+                let silence = async {
+                    // Received count when last polled, `None` while nothing is subscribed
+                    let mut last_received: Option<u64> = None;
+                    let mut silent_since = Instant::now();
+                    loop {
+                        if let Some(received) = Self::received_updates(&subscriptions, &stats).await
+                        {
+                            if last_received != Some(received) {
+                                last_received = Some(received);
+                                silent_since = Instant::now();
+                            }
+                            let silent_for = silent_since.elapsed();
+                            if silent_for >= timeout {
+                                return silent_for;
+                            }
+                        } else {
+                            last_received = None;
+                        }
+                        sleep(poll).await;
+                    }
+                };
+                tokio::select! {
+                    _ = run_signal.notified() => {}
+                    silent_for = silence => {
+                        warn!(
+                            "No streaming update for {:.1} seconds, rebuilding the connection",
+                            silent_for.as_secs_f64()
+                        );
+                        idled.store(true, Ordering::SeqCst);
+                    }
+                }
+                idle_signal.notify_one();
+            })
+        };
+
+        (idle_signal, idled, Some(watcher))
+    }
+
+    /// Sums the updates received for the items of the given subscriptions.
+    ///
+    /// # Returns
+    ///
+    /// `None` while nothing is subscribed.
+    async fn received_updates(
+        subscriptions: &Arc<Mutex<Vec<SubscriptionSpec>>>,
+        stats: &StreamStatsRecorder,
+    ) -> Option<u64> {
+        let specs = subscriptions.lock().await;
+        if specs.iter().all(|spec| spec.items.is_empty()) {
+            return None;
         }
+        let snapshot = stats.snapshot();
+        Some(
+            specs
+                .iter()
+                .flat_map(|spec| spec.items.iter())
+                .filter_map(|item| snapshot.get(item))
+                .map(|item_stats| item_stats.received)
+                .sum(),
+        )
+    }
 
-        info!("{} streamer connection closed gracefully", client_type);
+    /// Applies freshly published credentials and re-issues the retained subscriptions.
+    async fn apply_credentials(
+        client: &Arc<Mutex<LightstreamerClient>>,
+        credentials: &mut watch::Receiver<Option<WebsocketInfo>>,
+        subscriptions: &Arc<Mutex<Vec<SubscriptionSpec>>>,
+        client_type: &str,
+    ) -> Result<(), AppError> {
+        let ws_info = credentials.borrow_and_update().clone();
+        let Some(ws_info) = ws_info else {
+            return Ok(());
+        };
+
+        let password = ws_info.get_ws_password()?;
+        {
+            let mut client = client.lock().await;
+            client.connection_details.set_password(Some(password));
+        }
+
+        Self::reissue_subscriptions(client, subscriptions, client_type).await?;
+        info!("{} streamer credentials refreshed", client_type);
         Ok(())
     }
 
+    /// Re-issues every retained subscription on the given Lightstreamer client.
+    ///
+    /// The subscription previously issued for each spec is unsubscribed first, so
+    /// the client keeps exactly one live subscription per spec.
+    async fn reissue_subscriptions(
+        client: &Arc<Mutex<LightstreamerClient>>,
+        subscriptions: &Arc<Mutex<Vec<SubscriptionSpec>>>,
+        client_type: &str,
+    ) -> Result<usize, AppError> {
+        let sender = client.lock().await.subscription_sender.clone();
+        let mut specs = subscriptions.lock().await;
+        for spec in specs.iter_mut() {
+            spec.issue(&sender)?;
+        }
+
+        debug!(
+            "{} streamer: {} subscription(s) re-issued",
+            client_type,
+            specs.len()
+        );
+        Ok(specs.len())
+    }
+
+    /// Removes epics from every subscription on the given client.
+    ///
+    /// Lightstreamer cannot drop a single item from a live subscription, so each
+    /// affected subscription is unsubscribed and, if other items remain, re-issued
+    /// once without any of the epics. Updates for the remaining items keep flowing to the
+    /// original receivers.
+    ///
+    /// If a subscription cannot be changed, it and the ones not processed yet are
+    /// retained unchanged, so they are still restored on reconnect, and the first
+    /// error is returned.
+    ///
+    /// # Returns
+    ///
+    /// The number of subscriptions that referenced one of the epics.
+    async fn remove_epics(
+        relay: Option<&SubscriptionRelay>,
+        subscriptions: &Arc<Mutex<Vec<SubscriptionSpec>>>,
+        config: &StreamerConfig,
+        epics: &[&str],
+    ) -> Result<usize, AppError> {
+        let Some(relay) = relay else {
+            return Ok(0);
+        };
+
+        let mut specs = subscriptions.lock().await;
+        let (affected, kept): (Vec<_>, Vec<_>) = specs
+            .drain(..)
+            .partition(|spec| spec.covers_any_epic(epics, config));
+        *specs = kept;
+        if affected.is_empty() {
+            return Ok(0);
+        }
+
+        let removed = affected.len();
+        let mut first_error = None;
+        for spec in affected {
+            if first_error.is_some() {
+                specs.push(spec);
+                continue;
+            }
+
+            let mut trimmed = spec.clone();
+            trimmed.items.retain(|item| {
+                !config
+                    .item_epic(item)
+                    .is_some_and(|epic| epics.contains(&epic))
+            });
+            let result = if !trimmed.items.is_empty() {
+                // Unsubscribes the previous subscription before issuing the new one
+                trimmed.issue_via(relay)
+            } else if let Some(id) = trimmed.id.take() {
+                relay.send(SubscriptionCommand::Unsubscribe(id))
+            } else {
+                Ok(())
+            };
+
+            match result {
+                Ok(()) if !trimmed.items.is_empty() => specs.push(trimmed),
+                Ok(()) => {}
+                Err(e) => {
+                    specs.push(spec);
+                    first_error = Some(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(removed),
+        }
+    }
+
+    /// Unsubscribes an epic from every market, price and chart subscription.
+    ///
+    /// Subscriptions that only carried this epic are removed; subscriptions shared
+    /// with other epics are re-issued without it, so their receivers stay open.
+    /// When the last subscription on a client is gone the client is no longer
+    /// connected by [`Self::connect`].
+    ///
+    /// # Arguments
+    ///
+    /// * `epic` - Instrument EPIC to stop streaming
+    ///
+    /// # Returns
+    ///
+    /// `true` if at least one subscription referenced the epic.
+    pub async fn unsubscribe(&mut self, epic: &str) -> Result<bool, AppError> {
+        let removed = self.handle().unsubscribe(epic).await?;
+
+        self.has_market_stream_subs = !self.market_subscriptions.lock().await.is_empty();
+        self.has_price_stream_subs = !self.price_subscriptions.lock().await.is_empty();
+        Ok(removed)
+    }
+
+    /// Re-issues all subscriptions created by this client.
+    ///
+    /// Every subscription made through `market_subscribe`, `trade_subscribe`,
+    /// `account_subscribe`, `price_subscribe` or `chart_subscribe` is retained with its
+    /// items, fields, adapter and mode. `connect` already re-issues them whenever a
+    /// dropped connection is retried; this helper allows doing it explicitly, e.g.
+    /// after the server recycled the session. Updates keep flowing to the receivers
+    /// returned by the original subscribe calls.
+    ///
+    /// # Returns
+    ///
+    /// The number of subscriptions re-issued.
+    pub async fn resubscribe_all(&self) -> Result<usize, AppError> {
+        let mut reissued = 0;
+
+        if let Some(client) = self.market_streamer_client.as_ref() {
+            reissued +=
+                Self::reissue_subscriptions(client, &self.market_subscriptions, "Market").await?;
+        }
+
+        if let Some(client) = self.price_streamer_client.as_ref() {
+            reissued +=
+                Self::reissue_subscriptions(client, &self.price_subscriptions, "Price").await?;
+        }
+
+        info!("Re-issued {} subscription(s)", reissued);
+        Ok(reissued)
+    }
+
     /// Disconnects all active Lightstreamer clients.
     ///
     /// This method gracefully closes all streaming connections (market and price).
@@ -1405,3 +4369,649 @@ impl StreamerClient {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    fn market_spec(items: &[&str]) -> (SubscriptionSpec, mpsc::UnboundedReceiver<ItemUpdate>) {
+        let (sink, receiver) = mpsc::unbounded_channel();
+        let spec = SubscriptionSpec {
+            mode: SubscriptionMode::Merge,
+            items: items.iter().map(|item| item.to_string()).collect(),
+            fields: vec!["BID".to_string(), "OFFER".to_string()],
+            adapter: None,
+            sink,
+            id: None,
+            events: SubscriptionEventHub::new(),
+        };
+        (spec, receiver)
+    }
+
+    /// Applies the relayed commands to a set of live subscription ids, assigning
+    /// ids the way the client does
+    fn drain_live(
+        commands: &mut mpsc::UnboundedReceiver<SubscriptionCommand>,
+        live: &mut HashSet<usize>,
+    ) {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+        while let Ok(command) = commands.try_recv() {
+            match command {
+                SubscriptionCommand::Subscribe(_, id_sender) => {
+                    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+                    id_sender.send_replace(Some(id));
+                    assert!(live.insert(id));
+                }
+                SubscriptionCommand::Unsubscribe(id) => {
+                    let id = id
+                        .borrow()
+                        .expect("unsubscribed before the id was assigned");
+                    assert!(live.remove(&id));
+                }
+            }
+        }
+    }
+
+    /// Gets the id assigned to the subscription currently issued for a spec
+    fn assigned_id(spec: &SubscriptionSpec) -> Option<usize> {
+        spec.id.as_ref().and_then(|id| *id.borrow())
+    }
+
+    #[test]
+    fn test_symbol_representatives_skip_entries_without_epic() {
+        let entry = |symbol: &str, epic: &str| DBEntryResponse {
+            symbol: symbol.to_string(),
+            epic: epic.to_string(),
+            ..DBEntryResponse::default()
+        };
+        let entries = vec![
+            entry("DAX", ""),
+            entry("DAX", "OP.D.DAX.1.IP"),
+            entry("DAX", "OP.D.DAX.2.IP"),
+            entry("", "OP.D.OTHER.IP"),
+            entry("FTSE", "OP.D.FTSE.1.IP"),
+        ];
+
+        let epics: Vec<&str> = symbol_representatives(&entries)
+            .into_iter()
+            .map(|entry| entry.epic.as_str())
+            .collect();
+        assert_eq!(epics, vec!["OP.D.DAX.1.IP", "OP.D.FTSE.1.IP"]);
+    }
+
+    #[tokio::test]
+    async fn test_reissue_keeps_one_live_subscription_per_spec() {
+        let (sender, mut commands) = mpsc::unbounded_channel();
+        let relay = SubscriptionRelay { commands: sender };
+        let (mut first, _first_rx) = market_spec(&["MARKET:IX.D.DAX.DAILY.IP"]);
+        let (mut second, _second_rx) = market_spec(&["MARKET:CS.D.EURUSD.CFD.IP"]);
+        let mut live = HashSet::new();
+
+        first.issue_via(&relay).unwrap();
+        second.issue_via(&relay).unwrap();
+        drain_live(&mut commands, &mut live);
+        assert_eq!(live.len(), 2);
+
+        for _ in 0..3 {
+            first.issue_via(&relay).unwrap();
+            second.issue_via(&relay).unwrap();
+            drain_live(&mut commands, &mut live);
+            assert_eq!(live.len(), 2);
+        }
+        assert!(live.contains(&assigned_id(&first).unwrap()));
+        assert!(live.contains(&assigned_id(&second).unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_reissue_drops_a_subscription_still_waiting_for_its_id() {
+        let (sender, mut commands) = mpsc::unbounded_channel();
+        let relay = SubscriptionRelay { commands: sender };
+        let (mut spec, _rx) = market_spec(&["MARKET:IX.D.DAX.DAILY.IP"]);
+
+        // Re-issued, e.g. after a failed attempt, before the client assigned an id
+        spec.issue_via(&relay).unwrap();
+        spec.issue_via(&relay).unwrap();
+
+        let Ok(SubscriptionCommand::Subscribe(_, pending)) = commands.try_recv() else {
+            panic!("expected the first subscription");
+        };
+        let Ok(SubscriptionCommand::Unsubscribe(dropped)) = commands.try_recv() else {
+            panic!("expected the first subscription to be dropped");
+        };
+        assert!(matches!(
+            commands.try_recv(),
+            Ok(SubscriptionCommand::Subscribe(..))
+        ));
+
+        // The unsubscribe targets whichever id the client assigns later
+        pending.send_replace(Some(7));
+        assert_eq!(*dropped.borrow(), Some(7));
+        assert_eq!(assigned_id(&spec), None);
+    }
+
+    /// Run signal, stats, subscriptions, idle signal, idle flag and task of a watchdog
+    type IdleWatchdog = (
+        Arc<Notify>,
+        StreamStatsRecorder,
+        Arc<Mutex<Vec<SubscriptionSpec>>>,
+        Arc<Notify>,
+        Arc<AtomicBool>,
+        JoinHandle<()>,
+    );
+
+    fn idle_watchdog(subscriptions: Vec<SubscriptionSpec>) -> IdleWatchdog {
+        let run_signal = Arc::new(Notify::new());
+        let stats = StreamStatsRecorder::new();
+        let subscriptions = Arc::new(Mutex::new(subscriptions));
+        let (idle_signal, idled, watcher) = StreamerClient::watch_idle(
+            &run_signal,
+            Some(Duration::from_secs(10)),
+            stats.clone(),
+            Arc::clone(&subscriptions),
+        );
+        (
+            run_signal,
+            stats,
+            subscriptions,
+            idle_signal,
+            idled,
+            watcher.expect("watchdog enabled"),
+        )
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_idle_fires_after_silence() {
+        let (spec, _rx) = market_spec(&["MARKET:IX.D.DAX.DAILY.IP"]);
+        let (_run_signal, _stats, _subscriptions, idle_signal, idled, watcher) =
+            idle_watchdog(vec![spec]);
+
+        sleep(Duration::from_secs(9)).await;
+        assert!(!idled.load(Ordering::SeqCst));
+
+        watcher.await.unwrap();
+        assert!(idled.load(Ordering::SeqCst));
+        idle_signal.notified().await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_idle_is_reset_by_updates() {
+        let (spec, _rx) = market_spec(&["MARKET:IX.D.DAX.DAILY.IP"]);
+        let (_run_signal, stats, _subscriptions, _idle_signal, idled, watcher) =
+            idle_watchdog(vec![spec]);
+
+        for _ in 0..5 {
+            sleep(Duration::from_secs(8)).await;
+            stats.record_received("MARKET:IX.D.DAX.DAILY.IP");
+        }
+        assert!(!idled.load(Ordering::SeqCst));
+        assert!(!watcher.is_finished());
+        watcher.abort();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_idle_waits_while_nothing_is_subscribed() {
+        let (_run_signal, _stats, subscriptions, _idle_signal, idled, watcher) =
+            idle_watchdog(Vec::new());
+
+        sleep(Duration::from_secs(60)).await;
+        assert!(!idled.load(Ordering::SeqCst));
+
+        let (spec, _rx) = market_spec(&["MARKET:IX.D.DAX.DAILY.IP"]);
+        subscriptions.lock().await.push(spec);
+        sleep(Duration::from_secs(9)).await;
+        assert!(!idled.load(Ordering::SeqCst));
+        watcher.await.unwrap();
+        assert!(idled.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_idle_exits_when_run_signal_fires() {
+        let (spec, _rx) = market_spec(&["MARKET:IX.D.DAX.DAILY.IP"]);
+        let (run_signal, _stats, _subscriptions, idle_signal, idled, watcher) =
+            idle_watchdog(vec![spec]);
+
+        sleep(Duration::from_secs(5)).await;
+        run_signal.notify_one();
+        watcher.await.unwrap();
+        assert!(!idled.load(Ordering::SeqCst));
+        idle_signal.notified().await;
+    }
+
+    #[tokio::test]
+    async fn test_remove_epic_failure_retains_every_spec() {
+        let (sender, commands) = mpsc::unbounded_channel();
+        drop(commands);
+        let relay = SubscriptionRelay { commands: sender };
+        let (first, _first_rx) =
+            market_spec(&["MARKET:IX.D.DAX.DAILY.IP", "MARKET:IX.D.FTSE.DAILY.IP"]);
+        let (second, _second_rx) = market_spec(&["MARKET:IX.D.DAX.DAILY.IP"]);
+        let subscriptions = Arc::new(Mutex::new(vec![first, second]));
+
+        let result = StreamerClient::remove_epics(
+            Some(&relay),
+            &subscriptions,
+            &StreamerConfig::default(),
+            &["IX.D.DAX.DAILY.IP"],
+        )
+        .await;
+
+        assert!(result.is_err());
+        let specs = subscriptions.lock().await;
+        assert_eq!(specs.len(), 2);
+        assert!(
+            specs
+                .iter()
+                .all(|spec| spec.items.contains(&"MARKET:IX.D.DAX.DAILY.IP".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_epics_reissues_each_spec_once() {
+        let (sender, mut commands) = mpsc::unbounded_channel();
+        let relay = SubscriptionRelay { commands: sender };
+        let (mut spec, _rx) = market_spec(&[
+            "MARKET:IX.D.DAX.DAILY.IP",
+            "MARKET:IX.D.FTSE.DAILY.IP",
+            "MARKET:IX.D.SPTRD.DAILY.IP",
+        ]);
+        spec.issue_via(&relay).unwrap();
+        let subscriptions = Arc::new(Mutex::new(vec![spec]));
+        let mut live = HashSet::new();
+        drain_live(&mut commands, &mut live);
+
+        let removed = StreamerClient::remove_epics(
+            Some(&relay),
+            &subscriptions,
+            &StreamerConfig::default(),
+            &["IX.D.DAX.DAILY.IP", "IX.D.FTSE.DAILY.IP"],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(removed, 1);
+        let mut issued = 0;
+        while let Ok(command) = commands.try_recv() {
+            if let SubscriptionCommand::Subscribe(..) = command {
+                issued += 1;
+            }
+        }
+        assert_eq!(issued, 1);
+        let specs = subscriptions.lock().await;
+        assert_eq!(
+            specs[0].items,
+            vec!["MARKET:IX.D.SPTRD.DAILY.IP".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_epics_drops_chart_subscriptions() {
+        let (sender, mut commands) = mpsc::unbounded_channel();
+        let relay = SubscriptionRelay { commands: sender };
+        let (mut chart, _chart_rx) = market_spec(&["CHART:IX.D.DAX.DAILY.IP:1MINUTE"]);
+        let (mut market, _market_rx) = market_spec(&["MARKET:IX.D.FTSE.DAILY.IP"]);
+        chart.issue_via(&relay).unwrap();
+        market.issue_via(&relay).unwrap();
+        let subscriptions = Arc::new(Mutex::new(vec![chart, market]));
+        let mut live = HashSet::new();
+        drain_live(&mut commands, &mut live);
+
+        let removed = StreamerClient::remove_epics(
+            Some(&relay),
+            &subscriptions,
+            &StreamerConfig::default(),
+            &["IX.D.DAX.DAILY.IP"],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(removed, 1);
+        drain_live(&mut commands, &mut live);
+        assert_eq!(live.len(), 1);
+        let specs = subscriptions.lock().await;
+        assert_eq!(specs.len(), 1);
+        assert_eq!(
+            specs[0].items,
+            vec!["MARKET:IX.D.FTSE.DAILY.IP".to_string()]
+        );
+    }
+
+    fn confirms_update(deal_reference: &str) -> ItemUpdate {
+        let confirms = serde_json::json!({ "dealReference": deal_reference }).to_string();
+        ItemUpdate {
+            item_name: Some("TRADE:ACCOUNT".to_string()),
+            item_pos: 1,
+            is_snapshot: false,
+            fields: HashMap::from([("CONFIRMS".to_string(), Some(confirms.clone()))]),
+            changed_fields: HashMap::from([("CONFIRMS".to_string(), confirms)]),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_relay_trades_backfills_once_the_reconnection_settled() {
+        let (reconnects, reconnects_rx) = watch::channel(0u64);
+        let (item_tx, item_rx) = mpsc::unbounded_channel();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let relay = tokio::spawn({
+            let fetches = Arc::clone(&fetches);
+            relay_trades(reconnects_rx, item_rx, tx, move |_, _| {
+                fetches.fetch_add(1, Ordering::SeqCst);
+                async { vec![confirms_update("LIVE"), confirms_update("MISSED")] }
+            })
+        });
+
+        reconnects.send_modify(|count| *count += 1);
+        sleep(Duration::from_secs(1)).await;
+        assert_eq!(fetches.load(Ordering::SeqCst), 0);
+
+        // Streamed live while the connection settles, so not replayed by the backfill
+        item_tx.send(confirms_update("LIVE")).unwrap();
+        let reference = |update: Option<ItemUpdate>| confirmed_deal_reference(&update.unwrap());
+        assert_eq!(reference(rx.recv().await).as_deref(), Some("LIVE"));
+        assert_eq!(reference(rx.recv().await).as_deref(), Some("MISSED"));
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        drop(item_tx);
+        relay.await.unwrap();
+        assert!(rx.recv().await.is_none());
+    }
+
+    fn utc(raw: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(raw)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    /// Prices at the start and end of a window given in `PRICES_PATH_DATE_FORMAT`
+    fn window_prices(start_date: &str, end_date: &str) -> HistoricalPricesResponse {
+        let price = |date: &str| {
+            serde_json::json!({
+                "snapshotTime": format!("{}Z", date.replace(' ', "T")),
+                "openPrice": {"bid": 1.0, "ask": 1.1, "lastTraded": null},
+                "highPrice": {"bid": 1.0, "ask": 1.1, "lastTraded": null},
+                "lowPrice": {"bid": 1.0, "ask": 1.1, "lastTraded": null},
+                "closePrice": {"bid": 1.0, "ask": 1.1, "lastTraded": null},
+                "lastTradedVolume": 10
+            })
+        };
+        serde_json::from_value(serde_json::json!({
+            "prices": [price(start_date), price(end_date)],
+            "instrumentType": "CURRENCIES"
+        }))
+        .unwrap()
+    }
+
+    fn allowance(remaining: i64, expiry: i64) -> PriceAllowance {
+        PriceAllowance {
+            remaining_allowance: remaining,
+            total_allowance: 10000,
+            allowance_expiry: expiry,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backfill_pages_through_chunks_without_boundary_duplicates() {
+        let monitor = PriceAllowanceMonitor::new();
+        let calls = std::sync::Mutex::new(Vec::new());
+        let prices: Vec<HistoricalPrice> = backfill_chunks(
+            &monitor,
+            "CS.D.EURUSD.CFD.IP",
+            utc("2024-01-01T00:00:00Z"),
+            utc("2024-01-01T10:00:00Z"),
+            chrono::Duration::hours(4),
+            |start_date, end_date| {
+                calls
+                    .lock()
+                    .unwrap()
+                    .push((start_date.clone(), end_date.clone()));
+                async move { Ok(window_prices(&start_date, &end_date)) }
+            },
+        )
+        .try_collect()
+        .await
+        .unwrap();
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                (
+                    "2024-01-01 00:00:00".to_string(),
+                    "2024-01-01 04:00:00".to_string()
+                ),
+                (
+                    "2024-01-01 04:00:00".to_string(),
+                    "2024-01-01 08:00:00".to_string()
+                ),
+                (
+                    "2024-01-01 08:00:00".to_string(),
+                    "2024-01-01 10:00:00".to_string()
+                ),
+            ]
+        );
+        let times: Vec<_> = prices
+            .iter()
+            .map(|price| price.snapshot_time_utc().unwrap())
+            .collect();
+        assert_eq!(
+            times,
+            vec![
+                utc("2024-01-01T00:00:00Z"),
+                utc("2024-01-01T04:00:00Z"),
+                utc("2024-01-01T08:00:00Z"),
+                utc("2024-01-01T10:00:00Z"),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_backfill_resumes_the_chunk_after_allowance_exceeded() {
+        let monitor = PriceAllowanceMonitor::new();
+        let calls = std::sync::Mutex::new(0);
+        let started = Instant::now();
+        let prices: Vec<HistoricalPrice> = backfill_chunks(
+            &monitor,
+            "CS.D.EURUSD.CFD.IP",
+            utc("2024-01-01T00:00:00Z"),
+            utc("2024-01-01T01:00:00Z"),
+            chrono::Duration::hours(1),
+            |start_date, end_date| {
+                let mut calls = calls.lock().unwrap();
+                *calls += 1;
+                let first = *calls == 1;
+                async move {
+                    if first {
+                        Err(AppError::AllowanceExceeded {
+                            resets_in: Some(Duration::from_secs(30)),
+                        })
+                    } else {
+                        Ok(window_prices(&start_date, &end_date))
+                    }
+                }
+            },
+        )
+        .try_collect()
+        .await
+        .unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), 2);
+        assert_eq!(prices.len(), 2);
+        assert!(started.elapsed() >= Duration::from_secs(30));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_backfill_waits_only_for_the_rest_of_the_allowance_period() {
+        let monitor = PriceAllowanceMonitor::new();
+        monitor.observe(&allowance(10, 120));
+        sleep(Duration::from_secs(100)).await;
+        assert_eq!(monitor.resets_in(), Some(Duration::from_secs(20)));
+
+        let started = Instant::now();
+        let prices: Vec<HistoricalPrice> = backfill_chunks(
+            &monitor,
+            "CS.D.EURUSD.CFD.IP",
+            utc("2024-01-01T00:00:00Z"),
+            utc("2024-01-01T01:00:00Z"),
+            chrono::Duration::hours(1),
+            |start_date, end_date| {
+                let monitor = &monitor;
+                async move {
+                    monitor.observe(&allowance(5000, 3600));
+                    Ok(window_prices(&start_date, &end_date))
+                }
+            },
+        )
+        .try_collect()
+        .await
+        .unwrap();
+
+        assert_eq!(prices.len(), 2);
+        let waited = started.elapsed();
+        assert!(waited >= Duration::from_secs(20));
+        assert!(waited < Duration::from_secs(21));
+    }
+
+    fn batch_epics(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("EPIC{i}")).collect()
+    }
+
+    #[tokio::test]
+    async fn test_market_details_batches_split_an_exact_multiple_into_full_batches() {
+        let epics = batch_epics(2 * MAX_MARKET_DETAILS_EPICS);
+        let sizes = std::sync::Mutex::new(Vec::new());
+        let outcomes = market_details_batches(&epics, |batch| {
+            sizes.lock().unwrap().push(batch.len());
+            async { Ok(MultipleMarketDetailsResponse::default()) }.boxed()
+        })
+        .await;
+
+        assert_eq!(
+            *sizes.lock().unwrap(),
+            vec![MAX_MARKET_DETAILS_EPICS, MAX_MARKET_DETAILS_EPICS]
+        );
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].epics, epics[..MAX_MARKET_DETAILS_EPICS]);
+        assert_eq!(outcomes[1].epics, epics[MAX_MARKET_DETAILS_EPICS..]);
+        assert!(outcomes.iter().all(MarketDetailsBatchOutcome::is_success));
+    }
+
+    #[tokio::test]
+    async fn test_market_details_batches_keep_the_remainder_in_a_last_batch() {
+        let epics = batch_epics(2 * MAX_MARKET_DETAILS_EPICS + 1);
+        let sizes = std::sync::Mutex::new(Vec::new());
+        let outcomes = market_details_batches(&epics, |batch| {
+            sizes.lock().unwrap().push(batch.len());
+            async { Ok(MultipleMarketDetailsResponse::default()) }.boxed()
+        })
+        .await;
+
+        assert_eq!(
+            *sizes.lock().unwrap(),
+            vec![MAX_MARKET_DETAILS_EPICS, MAX_MARKET_DETAILS_EPICS, 1]
+        );
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(
+            outcomes[2].epics,
+            vec![epics[2 * MAX_MARKET_DETAILS_EPICS].clone()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_market_details_bulk_fails_with_the_error_of_one_batch() {
+        let epics = batch_epics(2 * MAX_MARKET_DETAILS_EPICS + 10);
+        let failing = epics[MAX_MARKET_DETAILS_EPICS].clone();
+        let outcomes = market_details_batches(&epics, |batch| {
+            let fails = batch[0] == failing;
+            async move {
+                if fails {
+                    Err(AppError::InvalidInput("batch failed".to_string()))
+                } else {
+                    Ok(MultipleMarketDetailsResponse::default())
+                }
+            }
+            .boxed()
+        })
+        .await;
+
+        let successes: Vec<bool> = outcomes
+            .iter()
+            .map(|outcome| outcome.is_success())
+            .collect();
+        assert_eq!(successes, vec![true, false, true]);
+        match collect_market_details(outcomes) {
+            Err(AppError::InvalidInput(msg)) => assert_eq!(msg, "batch failed"),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    fn deal_confirmation(deal_status: Option<&str>) -> OrderConfirmationResponse {
+        serde_json::from_value(serde_json::json!({
+            "date": "2025-10-30T17:13:53",
+            "status": null,
+            "dealReference": "REF1",
+            "dealStatus": deal_status,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_poll_deal_confirmation_waits_for_a_final_outcome() {
+        let attempts = AtomicUsize::new(0);
+        let confirmation = poll_deal_confirmation(
+            "REF1",
+            Duration::from_secs(10),
+            Duration::from_millis(500),
+            || {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match attempt {
+                        0 => Err(AppError::NotFound),
+                        1 => Ok(deal_confirmation(None)),
+                        _ => Ok(deal_confirmation(Some("ACCEPTED"))),
+                    }
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(confirmation.is_accepted());
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_poll_deal_confirmation_times_out_while_pending() {
+        let attempts = AtomicUsize::new(0);
+        let result = poll_deal_confirmation(
+            "REF1",
+            Duration::from_secs(2),
+            Duration::from_millis(500),
+            || {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async { Ok(deal_confirmation(None)) }
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::Timeout(_))));
+        assert_eq!(attempts.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_poll_deal_confirmation_returns_other_errors_at_once() {
+        let attempts = AtomicUsize::new(0);
+        let result = poll_deal_confirmation(
+            "REF1",
+            Duration::from_secs(10),
+            Duration::from_millis(500),
+            || {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async { Err(AppError::Unauthorized) }
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::Unauthorized)));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,4 +1,8 @@
-use crate::constants::{DAYS_TO_BACK_LOOK, DEFAULT_PAGE_SIZE, DEFAULT_SLEEP_TIME};
+use crate::constants::{
+    DAYS_TO_BACK_LOOK, DEFAULT_PAGE_SIZE, DEFAULT_SLEEP_TIME, DEMO_LIGHTSTREAMER_URL,
+    DEMO_REST_BASE_URL, LIVE_LIGHTSTREAMER_URL, LIVE_REST_BASE_URL, REQUIRED_ENV_VARS,
+};
+use crate::error::AppError;
 use crate::storage::config::DatabaseConfig;
 use crate::utils::config::get_env_or_default;
 use dotenv::dotenv;
@@ -9,6 +13,73 @@ use std::env;
 use tracing::error;
 use tracing::log::debug;
 
+/// IG trading environment a client talks to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum IgEnvironment {
+    /// Demo accounts (`demo-api.ig.com`), no real money involved
+    #[default]
+    Demo,
+    /// Live accounts (`api.ig.com`), orders are real
+    Live,
+}
+
+impl IgEnvironment {
+    /// Parses an environment name
+    ///
+    /// Accepts `demo` and `live`, case-insensitively.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "demo" => Some(IgEnvironment::Demo),
+            "live" => Some(IgEnvironment::Live),
+            _ => None,
+        }
+    }
+
+    /// Infers the environment from a REST base URL
+    ///
+    /// Only IG's demo host is recognised as `Demo`: any other URL is assumed to be
+    /// live, so that the live-order safety warnings err on the side of caution.
+    #[must_use]
+    pub fn from_base_url(base_url: &str) -> Self {
+        if base_url.contains("demo-api.ig.com") {
+            IgEnvironment::Demo
+        } else {
+            IgEnvironment::Live
+        }
+    }
+
+    /// Gets the REST API base URL of the environment
+    #[must_use]
+    pub fn rest_base_url(&self) -> &'static str {
+        match self {
+            IgEnvironment::Demo => DEMO_REST_BASE_URL,
+            IgEnvironment::Live => LIVE_REST_BASE_URL,
+        }
+    }
+
+    /// Gets the Lightstreamer endpoint of the environment
+    ///
+    /// IG normally returns it on login; this is the fallback used to override a
+    /// missing or wrong endpoint, e.g. with `StreamerConfig::with_lightstreamer_endpoint`.
+    #[must_use]
+    pub fn lightstreamer_url(&self) -> &'static str {
+        match self {
+            IgEnvironment::Demo => DEMO_LIGHTSTREAMER_URL,
+            IgEnvironment::Live => LIVE_LIGHTSTREAMER_URL,
+        }
+    }
+}
+
+impl std::fmt::Display for IgEnvironment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IgEnvironment::Demo => write!(f, "demo"),
+            IgEnvironment::Live => write!(f, "live"),
+        }
+    }
+}
+
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone)]
 /// Authentication credentials for the IG Markets API
 pub struct Credentials {
@@ -131,10 +202,7 @@ impl Config {
                 account_token: None,
             },
             rest_api: RestApiConfig {
-                base_url: get_env_or_default(
-                    "IG_REST_BASE_URL",
-                    String::from("https://demo-api.ig.com/gateway/deal"),
-                ),
+                base_url: get_env_or_default("IG_REST_BASE_URL", String::from(DEMO_REST_BASE_URL)),
                 timeout: get_env_or_default("IG_REST_TIMEOUT", 30),
             },
             websocket: WebSocketConfig {
@@ -167,6 +235,66 @@ impl Config {
         }
     }
 
+    /// Creates a configuration from the environment, failing on missing credentials
+    ///
+    /// Unlike [`Config::new`], which falls back to placeholder credentials, this
+    /// requires `IG_API_KEY`, `IG_USERNAME`, `IG_PASSWORD` and `IG_ACCOUNT_ID`, and
+    /// selects the REST host from `IG_ENV` (`demo`, the default, or `live`). A `.env`
+    /// file is loaded first. Every other setting is read as in [`Config::new`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Config)` - Configuration with the credentials and REST URL resolved
+    /// * `Err(AppError::Config)` - Listing every missing variable and an invalid `IG_ENV`
+    pub fn from_env() -> Result<Self, AppError> {
+        if let Err(e) = dotenv() {
+            debug!("Failed to load .env file: {e}");
+        }
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    /// Creates a configuration from a variable lookup, as [`Config::from_env`] does
+    ///
+    /// # Arguments
+    ///
+    /// * `var` - Returns the value of a variable, `None` if unset
+    pub fn from_vars<F>(var: F) -> Result<Self, AppError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let value = |name: &str| var(name).filter(|value| !value.trim().is_empty());
+
+        let mut problems: Vec<String> = REQUIRED_ENV_VARS
+            .iter()
+            .filter(|name| value(name).is_none())
+            .map(|name| format!("{name} is not set"))
+            .collect();
+        let environment = match value("IG_ENV") {
+            None => IgEnvironment::Demo,
+            Some(env) => IgEnvironment::parse(&env).unwrap_or_else(|| {
+                problems.push(format!("IG_ENV must be demo or live, got {env}"));
+                IgEnvironment::Demo
+            }),
+        };
+        if !problems.is_empty() {
+            return Err(AppError::Config(problems.join(", ")));
+        }
+
+        let mut config = Self::new();
+        config.credentials.api_key = value("IG_API_KEY").unwrap_or_default();
+        config.credentials.username = value("IG_USERNAME").unwrap_or_default();
+        config.credentials.password = value("IG_PASSWORD").unwrap_or_default();
+        config.credentials.account_id = value("IG_ACCOUNT_ID").unwrap_or_default();
+        config.rest_api.base_url = environment.rest_base_url().to_string();
+        Ok(config)
+    }
+
+    /// Gets the environment targeted by the REST base URL
+    #[must_use]
+    pub fn environment(&self) -> IgEnvironment {
+        IgEnvironment::from_base_url(&self.rest_api.base_url)
+    }
+
     /// Creates a PostgreSQL connection pool using the database configuration
     ///
     /// # Returns
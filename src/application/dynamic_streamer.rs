@@ -9,14 +9,118 @@
 //! This module provides a wrapper around `StreamerClient` that allows dynamic
 //! addition and removal of market subscriptions from multiple threads.
 
-use crate::application::client::StreamerClient;
+use crate::application::client::{Client, StreamClose, StreamerClient, StreamerHandle};
+use crate::application::interfaces::market::MarketService;
+use crate::application::streamer_config::StreamerConfig;
 use crate::error::AppError;
 use crate::model::streaming::StreamingMarketField;
 use crate::presentation::price::PriceData;
-use std::collections::HashSet;
+use reqwest::StatusCode;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{Notify, RwLock, mpsc};
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
+
+/// Sender of price updates paired with their EPIC
+type KeyedPriceSender = mpsc::UnboundedSender<(String, PriceData)>;
+
+/// Callback fired when the streamer gives up reconnecting
+pub type FatalErrorCallback = Arc<dyn Fn(&AppError) + Send + Sync>;
+
+/// Reconnection ceiling applied by `DynamicMarketStreamer`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// Maximum number of consecutive failed connection attempts before giving up
+    pub max_attempts: u32,
+    /// Delay between two connection attempts
+    pub delay: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            delay: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Decision taken after a failed connection attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconnectDecision {
+    /// Try again after the given delay
+    Retry(Duration),
+    /// The reconnect ceiling has been reached, the failure is terminal
+    GiveUp,
+}
+
+/// Tracks consecutive connection failures against a `ReconnectPolicy`
+#[derive(Debug, Clone)]
+pub struct ReconnectTracker {
+    policy: ReconnectPolicy,
+    failures: u32,
+}
+
+impl ReconnectTracker {
+    /// Creates a new tracker with no recorded failures
+    #[must_use]
+    pub fn new(policy: ReconnectPolicy) -> Self {
+        Self {
+            policy,
+            failures: 0,
+        }
+    }
+
+    /// Records a failed attempt and decides whether to retry
+    ///
+    /// # Returns
+    ///
+    /// `ReconnectDecision::GiveUp` once `max_attempts` consecutive failures have been
+    /// recorded, `ReconnectDecision::Retry` otherwise
+    pub fn on_failure(&mut self) -> ReconnectDecision {
+        self.failures = self.failures.saturating_add(1);
+        if self.failures >= self.policy.max_attempts {
+            ReconnectDecision::GiveUp
+        } else {
+            ReconnectDecision::Retry(self.policy.delay)
+        }
+    }
+
+    /// Clears the recorded failures after a successful connection
+    pub fn reset(&mut self) {
+        self.failures = 0;
+    }
+
+    /// Gets the number of consecutive failures recorded
+    #[must_use]
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+}
+
+/// Lifecycle state of the `DynamicMarketStreamer` connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConnectionState {
+    /// A connection is being set up
+    Connecting,
+    /// The connection is established and subscribed
+    Connected,
+    /// The connection was lost and is being rebuilt
+    Reconnecting,
+    /// No connection task is running
+    #[default]
+    Stopped,
+}
+
+/// Restarts of the connection task allowed after it gives up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoRestart {
+    /// Maximum number of restarts per `start` call
+    pub max_restarts: u32,
+    /// Delay before each restart
+    pub delay: Duration,
+}
 
 /// Dynamic market streamer with thread-safe subscription management.
 ///
@@ -64,8 +168,10 @@ use tracing::{debug, info, warn};
 /// }
 /// ```
 pub struct DynamicMarketStreamer {
-    /// Internal streamer client (recreated on epic changes)
+    /// Internal streamer client (recreated only when the connection is rebuilt)
     client: Arc<RwLock<Option<StreamerClient>>>,
+    /// Handle of the current client, used to change subscriptions while connected
+    handle: Arc<RwLock<Option<StreamerHandle>>>,
     /// Set of EPICs currently subscribed
     epics: Arc<RwLock<HashSet<String>>>,
     /// Market fields to subscribe to
@@ -74,10 +180,26 @@ pub struct DynamicMarketStreamer {
     price_tx: Arc<RwLock<Option<mpsc::UnboundedSender<PriceData>>>>,
     /// Channel receiver for price updates (taken on first get_receiver call)
     price_rx: Arc<RwLock<Option<mpsc::UnboundedReceiver<PriceData>>>>,
+    /// Channel sender for epic-keyed updates (created by get_keyed_receiver)
+    keyed_tx: Arc<RwLock<Option<KeyedPriceSender>>>,
+    /// Most recent update per EPIC
+    latest: Arc<RwLock<HashMap<String, PriceData>>>,
     /// Flag indicating if the streamer is connected
     is_connected: Arc<RwLock<bool>>,
     /// Shutdown signal for current connection
     shutdown_signal: Arc<RwLock<Option<Arc<Notify>>>>,
+    /// Reconnection ceiling for the background connection task
+    reconnect_policy: Arc<RwLock<ReconnectPolicy>>,
+    /// Callback fired when reconnect attempts are exhausted
+    fatal_callback: Arc<RwLock<Option<FatalErrorCallback>>>,
+    /// Current lifecycle state of the connection
+    state: Arc<RwLock<ConnectionState>>,
+    /// Restarts allowed once the reconnect ceiling is hit, disabled by default
+    auto_restart: Option<AutoRestart>,
+    /// REST client used to validate EPICs before subscribing
+    validator: Option<Arc<Client>>,
+    /// Configuration of the streamer clients, whose item prefixes name the updates
+    streamer_config: StreamerConfig,
 }
 
 impl DynamicMarketStreamer {
@@ -105,18 +227,146 @@ impl DynamicMarketStreamer {
 
         Ok(Self {
             client: Arc::new(RwLock::new(None)),
+            handle: Arc::new(RwLock::new(None)),
             epics: Arc::new(RwLock::new(HashSet::new())),
             fields,
             price_tx: Arc::new(RwLock::new(Some(price_tx))),
             price_rx: Arc::new(RwLock::new(Some(price_rx))),
+            keyed_tx: Arc::new(RwLock::new(None)),
+            latest: Arc::new(RwLock::new(HashMap::new())),
             is_connected: Arc::new(RwLock::new(false)),
             shutdown_signal: Arc::new(RwLock::new(None)),
+            reconnect_policy: Arc::new(RwLock::new(ReconnectPolicy::default())),
+            fatal_callback: Arc::new(RwLock::new(None)),
+            state: Arc::new(RwLock::new(ConnectionState::Stopped)),
+            auto_restart: None,
+            validator: None,
+            streamer_config: StreamerConfig::default(),
         })
     }
 
+    /// Restarts the connection task when it gives up while EPICs remain.
+    ///
+    /// Once the reconnect ceiling is reached, the connection is rebuilt after `delay`
+    /// instead of stopping, up to `max_restarts` times per `start` call. The fatal
+    /// callback only fires when no restart is left. Closing the connection with
+    /// `disconnect` never triggers a restart.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_restarts` - Maximum number of restarts, `u32::MAX` for no limit
+    /// * `delay` - Delay before each restart
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let streamer = DynamicMarketStreamer::new(fields)
+    ///     .await?
+    ///     .with_auto_restart(u32::MAX, Duration::from_secs(30));
+    /// ```
+    #[must_use]
+    pub fn with_auto_restart(mut self, max_restarts: u32, delay: Duration) -> Self {
+        self.auto_restart = Some(AutoRestart {
+            max_restarts,
+            delay,
+        });
+        self
+    }
+
+    /// Validates EPICs with the REST API before subscribing to them.
+    ///
+    /// `add` then fetches the market details of each new EPIC and rejects it when the
+    /// market does not exist or does not stream prices. Without a validator, EPICs
+    /// are subscribed as given.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Logged in REST client
+    #[must_use]
+    pub fn with_validator(mut self, client: Arc<Client>) -> Self {
+        self.validator = Some(client);
+        self
+    }
+
+    /// Sets the configuration of the streamer clients created on connect.
+    ///
+    /// The item prefixes of `config` are also used to key updates by EPIC.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Adapter, transport and item prefix settings
+    #[must_use]
+    pub fn with_streamer_config(mut self, config: StreamerConfig) -> Self {
+        self.streamer_config = config;
+        self
+    }
+
+    /// Checks that an EPIC exists and streams prices, when a validator is set.
+    async fn validate_epic(&self, epic: &str) -> Result<(), AppError> {
+        let Some(client) = &self.validator else {
+            return Ok(());
+        };
+        let details = match client.get_market_details(epic).await {
+            Ok(details) => details,
+            Err(
+                AppError::NotFound
+                | AppError::Unexpected(StatusCode::NOT_FOUND | StatusCode::BAD_REQUEST),
+            ) => {
+                return Err(AppError::InvalidInput(format!("unknown epic {epic}")));
+            }
+            Err(e) => return Err(e),
+        };
+        if details.instrument.streaming_prices_available == Some(false) {
+            return Err(AppError::InvalidInput(format!(
+                "epic {epic} does not stream prices"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Gets the current lifecycle state of the connection.
+    pub async fn connection_state(&self) -> ConnectionState {
+        *self.state.read().await
+    }
+
+    /// Sets the reconnection ceiling used by the background connection task.
+    ///
+    /// The policy applies to connections started after this call.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Maximum attempts and delay between attempts
+    pub async fn set_reconnect_policy(&self, policy: ReconnectPolicy) {
+        *self.reconnect_policy.write().await = policy;
+    }
+
+    /// Registers a callback fired when the stream dies permanently.
+    ///
+    /// The callback runs from the background connection task once the reconnect
+    /// ceiling is reached, with the last connection error. Long-running applications
+    /// can use it to alert or restart instead of silently losing all data.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - Function invoked with the terminal error
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// streamer.on_fatal(|err| error!("Market stream died: {}", err)).await;
+    /// ```
+    pub async fn on_fatal<F>(&self, callback: F)
+    where
+        F: Fn(&AppError) + Send + Sync + 'static,
+    {
+        *self.fatal_callback.write().await = Some(Arc::new(callback));
+    }
+
     /// Adds a market EPIC to the subscription list.
     ///
-    /// If the streamer is already connected, this will reconnect with the updated list.
+    /// If the streamer is already connected, a subscription for just this EPIC is
+    /// issued on the live connection. A full reconnect is only used as a fallback
+    /// when that is not possible.
     ///
     /// # Arguments
     ///
@@ -124,7 +374,8 @@ impl DynamicMarketStreamer {
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` if the EPIC was added successfully.
+    /// Returns `Ok(())` if the EPIC was added successfully, or
+    /// `AppError::InvalidInput` if a validator is set and rejects it.
     ///
     /// # Examples
     ///
@@ -132,21 +383,97 @@ impl DynamicMarketStreamer {
     /// streamer.add("IX.D.DAX.DAILY.IP".to_string()).await?;
     /// ```
     pub async fn add(&self, epic: String) -> Result<(), AppError> {
-        let mut epics = self.epics.write().await;
+        self.add_many(vec![epic]).await
+    }
 
-        if epics.contains(&epic) {
-            debug!("EPIC {} already subscribed", epic);
+    /// Adds several market EPICs to the subscription list at once.
+    ///
+    /// The EPIC set is updated once and, if the streamer is connected, the new EPICs
+    /// are subscribed with a single subscription on the live connection. At most one
+    /// reconnect is used as a fallback. When a validator is set, every new EPIC is
+    /// validated first and none is added if one is rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `epics` - The market EPICs to subscribe to
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the EPICs were added successfully, or
+    /// `AppError::InvalidInput` if a validator is set and rejects one of them.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// streamer
+    ///     .add_many(vec![
+    ///         "OP.D.OTCSPX3.6880P.IP".to_string(),
+    ///         "OP.D.OTCSPX3.6880C.IP".to_string(),
+    ///     ])
+    ///     .await?;
+    /// ```
+    pub async fn add_many(&self, epics: Vec<String>) -> Result<(), AppError> {
+        let candidates: Vec<String> = {
+            let current = self.epics.read().await;
+            let mut seen = HashSet::new();
+            epics
+                .into_iter()
+                .filter(|epic| {
+                    let is_new = !current.contains(epic) && seen.insert(epic.clone());
+                    if !is_new {
+                        debug!("EPIC {} already subscribed", epic);
+                    }
+                    is_new
+                })
+                .collect()
+        };
+        if candidates.is_empty() {
             return Ok(());
         }
 
-        epics.insert(epic.clone());
-        info!("Added EPIC {} to subscription list", epic);
+        for epic in &candidates {
+            self.validate_epic(epic).await?;
+        }
+
+        let mut epics = self.epics.write().await;
+        let added: Vec<String> = candidates
+            .into_iter()
+            .filter(|epic| epics.insert(epic.clone()))
+            .collect();
         drop(epics); // Release lock
+        if added.is_empty() {
+            return Ok(());
+        }
+        info!("Added EPICs {:?} to subscription list", added);
 
-        // If already connected, reconnect with new list
         let is_connected = *self.is_connected.read().await;
-        if is_connected {
-            self.reconnect().await?;
+        if !is_connected {
+            return Ok(());
+        }
+
+        let handle = self.handle.read().await.clone();
+        let subscribed = match handle {
+            Some(handle) => {
+                handle
+                    .market_subscribe(added.clone(), self.fields.clone())
+                    .await
+            }
+            None => Err(AppError::WebSocketError(
+                "no live streamer handle".to_string(),
+            )),
+        };
+        match subscribed {
+            Ok(receiver) => {
+                self.forward_updates(receiver).await;
+                debug!("Subscribed EPICs {:?} on the live connection", added);
+            }
+            Err(e) => {
+                warn!(
+                    "Live subscribe for {:?} failed ({}), reconnecting",
+                    added, e
+                );
+                self.reconnect().await?;
+            }
         }
 
         Ok(())
@@ -154,7 +481,9 @@ impl DynamicMarketStreamer {
 
     /// Removes a market EPIC from the subscription list.
     ///
-    /// If the streamer is already connected, this will reconnect with the updated list.
+    /// If the streamer is already connected, only this EPIC is unsubscribed from the
+    /// live connection. A full reconnect is only used as a fallback when that is not
+    /// possible.
     ///
     /// # Arguments
     ///
@@ -170,22 +499,72 @@ impl DynamicMarketStreamer {
     /// streamer.remove("IX.D.DAX.DAILY.IP".to_string()).await?;
     /// ```
     pub async fn remove(&self, epic: String) -> Result<(), AppError> {
-        let mut epics = self.epics.write().await;
+        self.remove_many(vec![epic]).await
+    }
 
-        let was_removed = epics.remove(&epic);
-        if was_removed {
-            info!("Removed EPIC {} from subscription list", epic);
-        } else {
-            debug!("EPIC {} was not in subscription list", epic);
+    /// Removes several market EPICs from the subscription list at once.
+    ///
+    /// The EPIC set is updated once and, if the streamer is connected, the removed
+    /// EPICs are unsubscribed from the live connection with a single
+    /// re-subscription. If that fails, a single reconnect is used as a fallback.
+    ///
+    /// # Arguments
+    ///
+    /// * `epics` - The market EPICs to remove
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the EPICs were removed successfully.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// streamer
+    ///     .remove_many(vec![
+    ///         "OP.D.OTCSPX3.6875P.IP".to_string(),
+    ///         "OP.D.OTCSPX3.6875C.IP".to_string(),
+    ///     ])
+    ///     .await?;
+    /// ```
+    pub async fn remove_many(&self, epics: Vec<String>) -> Result<(), AppError> {
+        let mut current = self.epics.write().await;
+        let removed: Vec<String> = epics
+            .into_iter()
+            .filter(|epic| {
+                let was_removed = current.remove(epic);
+                if !was_removed {
+                    debug!("EPIC {} was not in subscription list", epic);
+                }
+                was_removed
+            })
+            .collect();
+        drop(current); // Release lock
+        if removed.is_empty() {
+            return Ok(());
         }
-        drop(epics); // Release lock
+        let mut latest = self.latest.write().await;
+        for epic in &removed {
+            latest.remove(epic);
+        }
+        drop(latest);
+        info!("Removed EPICs {:?} from subscription list", removed);
 
-        // If already connected and something was removed, reconnect
-        if was_removed {
-            let is_connected = *self.is_connected.read().await;
-            if is_connected {
-                self.reconnect().await?;
-            }
+        if !*self.is_connected.read().await {
+            return Ok(());
+        }
+
+        let handle = self.handle.read().await.clone();
+        let Some(handle) = handle else {
+            warn!("No live streamer handle, reconnecting");
+            return self.reconnect().await;
+        };
+        let removed: Vec<&str> = removed.iter().map(String::as_str).collect();
+        if let Err(e) = handle.unsubscribe_many(&removed).await {
+            warn!(
+                "Live unsubscribe for {:?} failed ({}), reconnecting",
+                removed, e
+            );
+            self.reconnect().await?;
         }
 
         Ok(())
@@ -209,6 +588,7 @@ impl DynamicMarketStreamer {
         let mut epics = self.epics.write().await;
         let count = epics.len();
         epics.clear();
+        self.latest.write().await.clear();
         info!("Cleared {} EPICs from subscription list", count);
         Ok(())
     }
@@ -256,10 +636,56 @@ impl DynamicMarketStreamer {
             .ok_or_else(|| AppError::InvalidInput("Receiver already taken".to_string()))
     }
 
+    /// Gets a receiver for price updates keyed by EPIC.
+    ///
+    /// Each update is paired with its EPIC, stripped of the configured item prefix.
+    /// This method can only be called once. Subsequent calls will return an error.
+    ///
+    /// # Returns
+    ///
+    /// Returns a receiver channel for `(epic, PriceData)` updates, or an error if the
+    /// receiver has already been taken.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut receiver = streamer.get_keyed_receiver().await?;
+    /// while let Some((epic, price_data)) = receiver.recv().await {
+    ///     println!("{}: {:?}", epic, price_data.fields.bid);
+    /// }
+    /// ```
+    pub async fn get_keyed_receiver(
+        &self,
+    ) -> Result<mpsc::UnboundedReceiver<(String, PriceData)>, AppError> {
+        let mut tx_lock = self.keyed_tx.write().await;
+        if tx_lock.is_some() {
+            return Err(AppError::InvalidInput(
+                "Keyed receiver already taken".to_string(),
+            ));
+        }
+        let (keyed_tx, keyed_rx) = mpsc::unbounded_channel();
+        *tx_lock = Some(keyed_tx);
+        Ok(keyed_rx)
+    }
+
+    /// Gets the most recent update received for an EPIC.
+    ///
+    /// # Arguments
+    ///
+    /// * `epic` - The market EPIC, without the item prefix
+    ///
+    /// # Returns
+    ///
+    /// The latest `PriceData`, or `None` if no update has been received yet.
+    pub async fn latest(&self, epic: &str) -> Option<PriceData> {
+        self.latest.read().await.get(epic).cloned()
+    }
+
     /// Reconnects the streamer with the current list of EPICs.
     ///
     /// This method disconnects the current client and creates a new one with
-    /// the updated EPIC list.
+    /// the updated EPIC list. It is only used when a subscription change cannot be
+    /// applied to the live connection.
     async fn reconnect(&self) -> Result<(), AppError> {
         info!("Reconnecting with updated EPIC list...");
 
@@ -285,38 +711,56 @@ impl DynamicMarketStreamer {
 
     /// Internal method to start connection.
     async fn start_internal(&self) -> Result<(), AppError> {
+        *self.state.write().await = ConnectionState::Connecting;
+        let signal = match self.prepare_client().await {
+            Ok(Some(signal)) => signal,
+            Ok(None) => {
+                *self.state.write().await = ConnectionState::Stopped;
+                return Ok(());
+            }
+            Err(e) => {
+                *self.state.write().await = ConnectionState::Stopped;
+                return Err(e);
+            }
+        };
+
+        // Spawn connection task in background
+        let streamer = self.clone();
+        tokio::spawn(async move {
+            streamer.run_connection(signal).await;
+        });
+
+        info!("Connection task started in background");
+        Ok(())
+    }
+
+    /// Creates a new client subscribed to all EPICs and stores it.
+    ///
+    /// Returns the shutdown signal of the new connection, or `None` when there
+    /// are no EPICs to subscribe to.
+    async fn prepare_client(&self) -> Result<Option<Arc<Notify>>, AppError> {
         let epics = self.get_epics().await;
 
         if epics.is_empty() {
             warn!("No EPICs to subscribe to");
-            return Ok(());
+            return Ok(None);
         }
 
         info!("Starting connection with {} EPICs", epics.len());
 
         // Create new client
-        let mut new_client = StreamerClient::new().await?;
+        let mut new_client =
+            StreamerClient::with_config(&Client::new(), self.streamer_config.clone()).await?;
 
         // Subscribe to all EPICs
         let fields = self.fields.clone();
-        let mut receiver = new_client.market_subscribe(epics.clone(), fields).await?;
+        let receiver = new_client.market_subscribe(epics.clone(), fields).await?;
 
         // Forward updates to the main channel
-        let price_tx = self.price_tx.read().await;
-        if let Some(tx) = price_tx.as_ref() {
-            let tx = tx.clone();
-            tokio::spawn(async move {
-                while let Some(price_data) = receiver.recv().await {
-                    if tx.send(price_data).is_err() {
-                        warn!("Failed to send price update: receiver dropped");
-                        break;
-                    }
-                }
-                debug!("Subscription forwarding task ended");
-            });
-        }
+        self.forward_updates(receiver).await;
 
-        // Store the new client
+        // Store the new client and its handle
+        *self.handle.write().await = Some(new_client.handle());
         *self.client.write().await = Some(new_client);
 
         // Create new shutdown signal
@@ -325,32 +769,185 @@ impl DynamicMarketStreamer {
 
         // Mark as connected
         *self.is_connected.write().await = true;
+        *self.state.write().await = ConnectionState::Connected;
 
-        // Spawn connection task in background
-        let client = Arc::clone(&self.client);
-        let is_connected = Arc::clone(&self.is_connected);
+        Ok(Some(signal))
+    }
 
+    /// Spawns a task forwarding a subscription's updates to the main and keyed channels.
+    ///
+    /// The task also caches the latest update per EPIC, so it keeps running while
+    /// either channel is dropped.
+    async fn forward_updates(&self, mut receiver: mpsc::UnboundedReceiver<PriceData>) {
+        let mut price_tx = self.price_tx.read().await.clone();
+        let keyed_tx = Arc::clone(&self.keyed_tx);
+        let latest = Arc::clone(&self.latest);
+        let config = self.streamer_config.clone();
         tokio::spawn(async move {
-            let result = {
-                let mut client_guard = client.write().await;
+            while let Some(price_data) = receiver.recv().await {
+                let epic = config
+                    .item_epic(&price_data.item_name)
+                    .unwrap_or(&price_data.item_name)
+                    .to_string();
+
+                latest
+                    .write()
+                    .await
+                    .insert(epic.clone(), price_data.clone());
+
+                if let Some(tx) = keyed_tx.read().await.as_ref() {
+                    let _ = tx.send((epic, price_data.clone()));
+                }
+
+                if let Some(tx) = price_tx.as_ref()
+                    && tx.send(price_data).is_err()
+                {
+                    warn!("Failed to send price update: receiver dropped");
+                    price_tx = None;
+                }
+            }
+            debug!("Subscription forwarding task ended");
+        });
+    }
+
+    /// Maintains the connection, rebuilding the client until the reconnect ceiling is hit.
+    ///
+    /// With auto restart enabled, hitting the ceiling while EPICs remain starts over
+    /// with a fresh tracker after the restart delay.
+    async fn run_connection(&self, signal: Arc<Notify>) {
+        let mut signal = Some(signal);
+        self.supervise(|| {
+            let signal = signal.take();
+            async move { self.attempt_connection(signal).await }
+        })
+        .await;
+    }
+
+    /// Runs one connection attempt.
+    ///
+    /// `signal` belongs to the client already prepared by `start`; without it a fresh
+    /// client is prepared first.
+    async fn attempt_connection(&self, signal: Option<Arc<Notify>>) -> Result<(), AppError> {
+        let prepared = match signal {
+            Some(signal) => Ok(Some(signal)),
+            None => self.prepare_client().await,
+        };
+
+        match prepared {
+            Ok(Some(signal)) => {
+                let mut client_guard = self.client.write().await;
                 if let Some(ref mut c) = *client_guard {
-                    c.connect(Some(signal)).await
+                    // An unexpected close is a failure to reconnect from
+                    c.connect(Some(signal))
+                        .await
+                        .and_then(StreamClose::into_result)
                 } else {
                     Ok(())
                 }
-            };
+            }
+            Ok(None) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Marks the connection as reconnecting unless `disconnect` stopped it.
+    ///
+    /// # Returns
+    ///
+    /// `false` when the streamer is stopped, in which case the state is left as is.
+    async fn mark_reconnecting(&self) -> bool {
+        let mut state = self.state.write().await;
+        if *state == ConnectionState::Stopped {
+            return false;
+        }
+        *state = ConnectionState::Reconnecting;
+        *self.is_connected.write().await = false;
+        true
+    }
 
-            // Mark as disconnected
-            *is_connected.write().await = false;
+    /// Drives connection attempts until one completes or the reconnect ceiling is hit.
+    ///
+    /// Once the ceiling is hit and no restart is left, the fatal callback fires with
+    /// the last error and no further attempt is made. A `disconnect` ends the loop
+    /// before the next attempt, including during a retry or restart delay.
+    async fn supervise<F, Fut>(&self, mut attempt: F)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<(), AppError>>,
+    {
+        let policy = *self.reconnect_policy.read().await;
+        let mut tracker = ReconnectTracker::new(policy);
+        let mut restarts = 0u32;
 
-            match result {
-                Ok(_) => info!("Connection task completed successfully"),
-                Err(e) => tracing::error!("Connection task failed: {:?}", e),
+        loop {
+            match attempt().await {
+                Ok(_) => {
+                    info!("Connection task completed successfully");
+                    break;
+                }
+                Err(e) => {
+                    error!("Connection task failed: {:?}", e);
+                    match tracker.on_failure() {
+                        ReconnectDecision::Retry(delay) => {
+                            warn!(
+                                "Reconnecting in {:?} (attempt {}/{})",
+                                delay,
+                                tracker.failures() + 1,
+                                policy.max_attempts
+                            );
+                            if !self.mark_reconnecting().await {
+                                info!("Streamer stopped, not reconnecting");
+                                break;
+                            }
+                            tokio::time::sleep(delay).await;
+                            if self.connection_state().await == ConnectionState::Stopped {
+                                info!("Streamer stopped during the reconnect delay");
+                                break;
+                            }
+                        }
+                        ReconnectDecision::GiveUp => {
+                            error!(
+                                "Giving up after {} failed connection attempts",
+                                tracker.failures()
+                            );
+                            let stopped = self.connection_state().await == ConnectionState::Stopped;
+                            let has_epics = !self.epics.read().await.is_empty();
+                            if let Some(auto_restart) = self.auto_restart
+                                && restarts < auto_restart.max_restarts
+                                && !stopped
+                                && has_epics
+                            {
+                                restarts += 1;
+                                warn!(
+                                    "Restarting connection task in {:?} (restart {}/{})",
+                                    auto_restart.delay, restarts, auto_restart.max_restarts
+                                );
+                                if !self.mark_reconnecting().await {
+                                    info!("Streamer stopped, not restarting");
+                                    break;
+                                }
+                                tokio::time::sleep(auto_restart.delay).await;
+                                if self.connection_state().await == ConnectionState::Stopped {
+                                    info!("Streamer stopped during the restart delay");
+                                    break;
+                                }
+                                tracker = ReconnectTracker::new(policy);
+                                continue;
+                            }
+                            let callback = self.fatal_callback.read().await.clone();
+                            if let Some(callback) = callback {
+                                callback(&e);
+                            }
+                            break;
+                        }
+                    }
+                }
             }
-        });
+        }
 
-        info!("Connection task started in background");
-        Ok(())
+        // Mark as disconnected
+        *self.is_connected.write().await = false;
+        *self.state.write().await = ConnectionState::Stopped;
     }
 
     /// Starts the connection to the Lightstreamer server and subscribes to all initial EPICs.
@@ -419,6 +1016,9 @@ impl DynamicMarketStreamer {
     /// streamer.disconnect().await?;
     /// ```
     pub async fn disconnect(&mut self) -> Result<(), AppError> {
+        // Mark as stopped first so that the connection task does not restart
+        *self.state.write().await = ConnectionState::Stopped;
+
         // Signal shutdown
         {
             let shutdown_lock = self.shutdown_signal.read().await;
@@ -433,6 +1033,7 @@ impl DynamicMarketStreamer {
             client.disconnect().await?;
         }
         *client_lock = None;
+        *self.handle.write().await = None;
 
         *self.is_connected.write().await = false;
         info!("Disconnected from Lightstreamer server");
@@ -444,12 +1045,152 @@ impl Clone for DynamicMarketStreamer {
     fn clone(&self) -> Self {
         Self {
             client: Arc::clone(&self.client),
+            handle: Arc::clone(&self.handle),
             epics: Arc::clone(&self.epics),
             fields: self.fields.clone(),
             price_tx: Arc::clone(&self.price_tx),
             price_rx: Arc::clone(&self.price_rx),
+            keyed_tx: Arc::clone(&self.keyed_tx),
+            latest: Arc::clone(&self.latest),
             is_connected: Arc::clone(&self.is_connected),
             shutdown_signal: Arc::clone(&self.shutdown_signal),
+            reconnect_policy: Arc::clone(&self.reconnect_policy),
+            fatal_callback: Arc::clone(&self.fatal_callback),
+            state: Arc::clone(&self.state),
+            auto_restart: self.auto_restart,
+            validator: self.validator.clone(),
+            streamer_config: self.streamer_config.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fatal_close_fires_callback_and_stops_retrying() {
+        let streamer = DynamicMarketStreamer::new(HashSet::new()).await.unwrap();
+        streamer
+            .set_reconnect_policy(ReconnectPolicy {
+                max_attempts: 3,
+                delay: Duration::from_secs(1),
+            })
+            .await;
+        *streamer.state.write().await = ConnectionState::Connecting;
+        let fatal_errors = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&fatal_errors);
+        streamer
+            .on_fatal(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+
+        let attempts = AtomicU32::new(0);
+        streamer
+            .supervise(|| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async {
+                    StreamClose::Unexpected("session closed by server".to_string()).into_result()
+                }
+            })
+            .await;
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(fatal_errors.load(Ordering::SeqCst), 1);
+        assert_eq!(streamer.connection_state().await, ConnectionState::Stopped);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_disconnect_during_retry_delay_stops_reconnecting() {
+        let streamer = DynamicMarketStreamer::new(HashSet::new()).await.unwrap();
+        streamer
+            .set_reconnect_policy(ReconnectPolicy {
+                max_attempts: 3,
+                delay: Duration::from_secs(1),
+            })
+            .await;
+        *streamer.state.write().await = ConnectionState::Connecting;
+        let mut stopper = streamer.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            stopper.disconnect().await.unwrap();
+        });
+
+        let attempts = AtomicU32::new(0);
+        streamer
+            .supervise(|| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async { Err(AppError::WebSocketError("connection lost".to_string())) }
+            })
+            .await;
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+        assert_eq!(streamer.connection_state().await, ConnectionState::Stopped);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_disconnect_during_restart_delay_stops_restarting() {
+        let streamer = DynamicMarketStreamer::new(HashSet::new())
+            .await
+            .unwrap()
+            .with_auto_restart(u32::MAX, Duration::from_secs(30));
+        streamer
+            .set_reconnect_policy(ReconnectPolicy {
+                max_attempts: 1,
+                delay: Duration::from_secs(1),
+            })
+            .await;
+        streamer
+            .epics
+            .write()
+            .await
+            .insert("IX.D.DAX.DAILY.IP".to_string());
+        *streamer.state.write().await = ConnectionState::Connecting;
+        let mut stopper = streamer.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            stopper.disconnect().await.unwrap();
+        });
+
+        let attempts = AtomicU32::new(0);
+        streamer
+            .supervise(|| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async { Err(AppError::WebSocketError("connection lost".to_string())) }
+            })
+            .await;
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+        assert_eq!(streamer.connection_state().await, ConnectionState::Stopped);
+    }
+
+    #[tokio::test]
+    async fn test_removed_epics_are_evicted_from_latest() {
+        let streamer = DynamicMarketStreamer::new(HashSet::new()).await.unwrap();
+        let (tx, rx) = mpsc::unbounded_channel();
+        streamer.forward_updates(rx).await;
+        let mut receiver = streamer.get_receiver().await.unwrap();
+        for epic in ["IX.D.DAX.DAILY.IP", "IX.D.FTSE.DAILY.IP"] {
+            streamer.epics.write().await.insert(epic.to_string());
+            tx.send(PriceData {
+                item_name: format!("MARKET:{epic}"),
+                ..Default::default()
+            })
+            .unwrap();
+            receiver.recv().await.unwrap();
+        }
+        assert!(streamer.latest("IX.D.DAX.DAILY.IP").await.is_some());
+
+        streamer
+            .remove_many(vec!["IX.D.DAX.DAILY.IP".to_string()])
+            .await
+            .unwrap();
+        assert!(streamer.latest("IX.D.DAX.DAILY.IP").await.is_none());
+        assert!(streamer.latest("IX.D.FTSE.DAILY.IP").await.is_some());
+
+        streamer.clear().await.unwrap();
+        assert!(streamer.latest("IX.D.FTSE.DAILY.IP").await.is_none());
+    }
+}
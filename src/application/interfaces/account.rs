@@ -1,7 +1,8 @@
-use crate::error::AppError;
+use crate::IgResult;
+use crate::model::requests::ActivityFilter;
 use crate::prelude::{
-    AccountActivityResponse, AccountsResponse, PositionsResponse, TransactionHistoryResponse,
-    WorkingOrdersResponse,
+    AccountActivityResponse, AccountsResponse, Position, PositionsResponse,
+    TransactionHistoryResponse, WorkingOrdersResponse,
 };
 use async_trait::async_trait;
 
@@ -9,16 +10,44 @@ use async_trait::async_trait;
 #[async_trait]
 pub trait AccountService: Send + Sync {
     /// Gets information about all user accounts
-    async fn get_accounts(&self) -> Result<AccountsResponse, AppError>;
+    async fn get_accounts(&self) -> IgResult<AccountsResponse>;
+
+    /// Switches the active account used for subsequent requests
+    ///
+    /// Calls `PUT /session` without changing the default account. Streaming
+    /// credentials fetched afterwards refer to the new account.
+    ///
+    /// # Arguments
+    /// * `account_id` - Account to switch to; must be one of `get_accounts()`
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the account is now active
+    /// * `Err(AppError::InvalidInput)` - If the account is not owned by the user
+    async fn set_active_account(&self, account_id: &str) -> IgResult<()>;
+
+    /// Gets the id of the account used for requests
+    ///
+    /// # Returns
+    /// * The active account id, or an empty string before login
+    async fn active_account_id(&self) -> String;
 
     /// Gets open positions
-    async fn get_positions(&self) -> Result<PositionsResponse, AppError>;
+    async fn get_positions(&self) -> IgResult<PositionsResponse>;
+
+    /// Gets a single open position
+    ///
+    /// # Arguments
+    /// * `deal_id` - Deal ID of the position
+    ///
+    /// # Returns
+    /// * `Err(AppError::NotFound)` - If no open position has that deal ID
+    async fn get_position_by_deal_id(&self, deal_id: &str) -> IgResult<Position>;
 
     /// Gets open positions base in filter
-    async fn get_positions_w_filter(&self, filter: &str) -> Result<PositionsResponse, AppError>;
+    async fn get_positions_w_filter(&self, filter: &str) -> IgResult<PositionsResponse>;
 
     /// Gets working orders
-    async fn get_working_orders(&self) -> Result<WorkingOrdersResponse, AppError>;
+    async fn get_working_orders(&self) -> IgResult<WorkingOrdersResponse>;
 
     /// Gets account activity
     ///
@@ -29,8 +58,39 @@ pub trait AccountService: Send + Sync {
     ///
     /// # Returns
     /// * Account activity for the specified period
-    async fn get_activity(&self, from: &str, to: &str)
-    -> Result<AccountActivityResponse, AppError>;
+    async fn get_activity(&self, from: &str, to: &str) -> IgResult<AccountActivityResponse>;
+
+    /// Gets all account activity for a period, following IG's paging links
+    ///
+    /// Pages are requested until `metadata.paging.next` is exhausted. Paging stops
+    /// early, with a warning, if a `next` link points back to a page already fetched.
+    ///
+    /// # Arguments
+    /// * `from` - Start date in ISO format (e.g. "2023-01-01T00:00:00Z")
+    /// * `to` - End date in ISO format (e.g. "2023-02-01T00:00:00Z")
+    ///
+    /// # Returns
+    /// * Every activity in the period, with the metadata of the last page
+    async fn get_all_activity(&self, from: &str, to: &str) -> IgResult<AccountActivityResponse>;
+
+    /// Gets account activity matching a FIQL filter
+    ///
+    /// The filter is evaluated server-side, so only matching activities are
+    /// transferred.
+    ///
+    /// # Arguments
+    /// * `from` - Start date in ISO format (e.g. "2023-01-01T00:00:00Z")
+    /// * `to` - End date in ISO format (e.g. "2023-02-01T00:00:00Z")
+    /// * `filter` - Filter built with `ActivityFilter`
+    ///
+    /// # Returns
+    /// * Matching activities for the specified period
+    async fn get_activity_filtered(
+        &self,
+        from: &str,
+        to: &str,
+        filter: &ActivityFilter,
+    ) -> IgResult<AccountActivityResponse>;
 
     /// Gets detailed account activity
     ///
@@ -49,12 +109,8 @@ pub trait AccountService: Send + Sync {
 
         from: &str,
         to: &str,
-    ) -> Result<AccountActivityResponse, AppError>;
+    ) -> IgResult<AccountActivityResponse>;
 
     /// Gets transaction history for a given period, handling pagination automatically.
-    async fn get_transactions(
-        &self,
-        from: &str,
-        to: &str,
-    ) -> Result<TransactionHistoryResponse, AppError>;
+    async fn get_transactions(&self, from: &str, to: &str) -> IgResult<TransactionHistoryResponse>;
 }
@@ -1,20 +1,54 @@
-use crate::error::AppError;
-use crate::model::requests::RecentPricesRequest;
+use crate::IgResult;
+use crate::model::requests::{MarketDetailFilter, MarketSearchFilter, RecentPricesRequest};
 use crate::model::responses::{
-    CategoriesResponse, CategoryInstrumentsResponse, DBEntryResponse, HistoricalPricesResponse,
-    MarketNavigationResponse, MarketSearchResponse, MultipleMarketDetailsResponse,
+    CategoriesResponse, CategoryInstrumentsResponse, ClientSentimentListResponse,
+    ClientSentimentResponse, DBEntryResponse, HistoricalPricesResponse, MarketDetailsBatchOutcome,
+    MarketHierarchyStats, MarketNavigationResponse, MarketSearchResponse,
+    MultipleMarketDetailsResponse,
 };
-use crate::presentation::market::{MarketData, MarketDetails};
+use crate::presentation::market::{FilteredMarketDetails, MarketData, MarketDetails, Resolution};
+use crate::presentation::option_chain::OptionChain;
 use async_trait::async_trait;
 
 /// Interface for the market service
 #[async_trait]
 pub trait MarketService: Send + Sync {
     /// Searches markets by search term
-    async fn search_markets(&self, search_term: &str) -> Result<MarketSearchResponse, AppError>;
+    async fn search_markets(&self, search_term: &str) -> IgResult<MarketSearchResponse>;
+
+    /// Searches markets by search term and prunes the results locally
+    ///
+    /// IG's search is fuzzy and ignores filters, so the server results are fetched
+    /// first and `filter` (instrument type, expiry, tradeable status, epic prefix)
+    /// is applied afterwards.
+    ///
+    /// # Arguments
+    /// * `search_term` - Term sent to the search endpoint
+    /// * `filter` - Criteria the returned markets must meet
+    async fn search_markets_filtered(
+        &self,
+        search_term: &str,
+        filter: MarketSearchFilter,
+    ) -> IgResult<MarketSearchResponse>;
 
     /// Gets details of a specific market by its EPIC
-    async fn get_market_details(&self, epic: &str) -> Result<MarketDetails, AppError>;
+    async fn get_market_details(&self, epic: &str) -> IgResult<MarketDetails>;
+
+    /// Gets details of a specific market with an explicit detail filter
+    ///
+    /// # Arguments
+    /// * `epic` - Instrument epic
+    /// * `filter` - `MarketDetailFilter::All` for full details, or
+    ///   `MarketDetailFilter::SnapshotOnly` to fetch just the snapshot
+    ///
+    /// # Returns
+    /// The details with `instrument` and `dealing_rules` left empty for snapshot-only
+    /// requests
+    async fn get_market_details_filtered(
+        &self,
+        epic: &str,
+        filter: MarketDetailFilter,
+    ) -> IgResult<FilteredMarketDetails>;
 
     /// Gets details of multiple markets by their EPICs in a single request
     ///
@@ -30,22 +64,68 @@ pub trait MarketService: Send + Sync {
     async fn get_multiple_market_details(
         &self,
         epics: &[String],
-    ) -> Result<MultipleMarketDetailsResponse, AppError>;
+    ) -> IgResult<MultipleMarketDetailsResponse>;
+
+    /// Gets details of any number of markets, 50 epics per request
+    ///
+    /// The epics are split into batches of 50 that are requested concurrently,
+    /// through the rate limiter, with at most `DEFAULT_MARKET_DETAILS_BULK_CONCURRENCY`
+    /// requests in flight.
+    ///
+    /// # Arguments
+    /// * `epics` - EPICs to get details for
+    ///
+    /// # Returns
+    /// * One `MarketDetailsBatchOutcome` per batch, in input order, so a failed
+    ///   batch does not discard the others
+    async fn get_market_details_batches(&self, epics: &[String]) -> Vec<MarketDetailsBatchOutcome>;
+
+    /// Gets details of any number of markets, failing if any batch fails
+    ///
+    /// See [`MarketService::get_market_details_batches`] to keep partial results.
+    ///
+    /// # Arguments
+    /// * `epics` - EPICs to get details for
+    ///
+    /// # Returns
+    /// * `Ok(Vec<MarketDetails>)` - Details in input order; epics IG does not
+    ///   return are left out
+    /// * `Err(AppError)` - The error of the first failed batch
+    async fn get_market_details_bulk(&self, epics: &[String]) -> IgResult<Vec<MarketDetails>>;
 
     /// Gets historical prices for a market
+    ///
+    /// # Arguments
+    /// * `epic` - Instrument epic
+    /// * `resolution` - Price resolution token as listed by [`Resolution`], e.g. `MINUTE_5`.
+    ///   Tokens are not checked; a misspelt one is rejected by IG with a 400
+    /// * `from` - Start date (yyyy-MM-ddTHH:mm:ss)
+    /// * `to` - End date (yyyy-MM-ddTHH:mm:ss)
     async fn get_historical_prices(
         &self,
         epic: &str,
         resolution: &str,
         from: &str,
         to: &str,
-    ) -> Result<HistoricalPricesResponse, AppError>;
+    ) -> IgResult<HistoricalPricesResponse>;
+
+    /// Gets historical prices for a market with a typed resolution
+    ///
+    /// Same as [`MarketService::get_historical_prices`], which takes the raw token.
+    async fn get_historical_prices_typed(
+        &self,
+        epic: &str,
+        resolution: Resolution,
+        from: &str,
+        to: &str,
+    ) -> IgResult<HistoricalPricesResponse>;
 
     /// Gets historical prices for a market using path parameters (API v2)
     ///
     /// # Arguments
     /// * `epic` - Instrument epic
-    /// * `resolution` - Price resolution (SECOND, MINUTE, MINUTE_2, MINUTE_3, MINUTE_5, MINUTE_10, MINUTE_15, MINUTE_30, HOUR, HOUR_2, HOUR_3, HOUR_4, DAY, WEEK, MONTH)
+    /// * `resolution` - Price resolution (SECOND, MINUTE, MINUTE_2, MINUTE_3, MINUTE_5, MINUTE_10, MINUTE_15, MINUTE_30, HOUR, HOUR_2, HOUR_3, HOUR_4, DAY, WEEK, MONTH),
+    ///   see [`Resolution`] for a typed alternative
     /// * `start_date` - Start date (yyyy-MM-dd HH:mm:ss)
     /// * `end_date` - End date (yyyy-MM-dd HH:mm:ss). Must be later than the start date
     async fn get_historical_prices_by_date_range(
@@ -54,7 +134,19 @@ pub trait MarketService: Send + Sync {
         resolution: &str,
         start_date: &str,
         end_date: &str,
-    ) -> Result<HistoricalPricesResponse, AppError>;
+    ) -> IgResult<HistoricalPricesResponse>;
+
+    /// Gets historical prices for a date range (API v2) with a typed resolution
+    ///
+    /// Same as [`MarketService::get_historical_prices_by_date_range`], which takes
+    /// the raw token.
+    async fn get_historical_prices_by_date_range_typed(
+        &self,
+        epic: &str,
+        resolution: Resolution,
+        start_date: &str,
+        end_date: &str,
+    ) -> IgResult<HistoricalPricesResponse>;
 
     /// Gets recent historical prices with custom parameters
     ///
@@ -66,13 +158,13 @@ pub trait MarketService: Send + Sync {
     async fn get_recent_prices(
         &self,
         params: &RecentPricesRequest<'_>,
-    ) -> Result<HistoricalPricesResponse, AppError>;
+    ) -> IgResult<HistoricalPricesResponse>;
 
     /// Gets historical prices by number of data points (API v1)
     ///
     /// # Arguments
     /// * `epic` - Instrument epic
-    /// * `resolution` - Price resolution
+    /// * `resolution` - Price resolution token as listed by [`Resolution`]
     /// * `num_points` - Number of data points required
     async fn get_historical_prices_by_count_v1(
         &self,
@@ -80,26 +172,37 @@ pub trait MarketService: Send + Sync {
         epic: &str,
         resolution: &str,
         num_points: i32,
-    ) -> Result<HistoricalPricesResponse, AppError>;
+    ) -> IgResult<HistoricalPricesResponse>;
 
     /// Gets historical prices by number of data points (API v2)
     ///
     /// # Arguments
     /// * `epic` - Instrument epic
-    /// * `resolution` - Price resolution
+    /// * `resolution` - Price resolution token as listed by [`Resolution`]
     /// * `num_points` - Number of data points required
     async fn get_historical_prices_by_count_v2(
         &self,
         epic: &str,
         resolution: &str,
         num_points: i32,
-    ) -> Result<HistoricalPricesResponse, AppError>;
+    ) -> IgResult<HistoricalPricesResponse>;
+
+    /// Gets historical prices by number of data points (API v2) with a typed resolution
+    ///
+    /// Same as [`MarketService::get_historical_prices_by_count_v2`], which takes the
+    /// raw token.
+    async fn get_historical_prices_by_count_typed(
+        &self,
+        epic: &str,
+        resolution: Resolution,
+        num_points: i32,
+    ) -> IgResult<HistoricalPricesResponse>;
 
     /// Gets the top-level market navigation nodes
     ///
     /// This method returns the root nodes of the market hierarchy, which can be used
     /// to navigate through the available markets.
-    async fn get_market_navigation(&self) -> Result<MarketNavigationResponse, AppError>;
+    async fn get_market_navigation(&self) -> IgResult<MarketNavigationResponse>;
 
     /// Gets the market navigation node with the specified ID
     ///
@@ -107,36 +210,85 @@ pub trait MarketService: Send + Sync {
     ///
     /// # Arguments
     /// * `node_id` - The ID of the navigation node to retrieve
-    async fn get_market_navigation_node(
-        &self,
-        node_id: &str,
-    ) -> Result<MarketNavigationResponse, AppError>;
+    async fn get_market_navigation_node(&self, node_id: &str)
+    -> IgResult<MarketNavigationResponse>;
 
     /// Navigates through all levels of the market hierarchy and collects all MarketData
     ///
     /// This method performs a comprehensive traversal of the IG Markets hierarchy,
-    /// starting from the root navigation and going through multiple levels to collect
-    /// all available market instruments.
+    /// starting from the root navigation and going down `DEFAULT_MARKET_TRAVERSAL_DEPTH`
+    /// levels to collect all available market instruments.
+    ///
+    /// # Returns
+    /// * `IgResult<Vec<MarketData>>` - Vector containing all found market instruments
+    async fn get_all_markets(&self) -> IgResult<Vec<MarketData>>;
+
+    /// Navigates the market hierarchy down to a given depth and collects all MarketData
     ///
     /// # Arguments
-    /// * `session` - The authenticated IG session
-    /// * `max_levels` - Maximum depth to traverse (default: 5 levels)
+    /// * `max_depth` - Maximum number of levels walked below the root
+    ///
+    /// # Returns
+    /// * `IgResult<Vec<MarketData>>` - Vector containing all found market instruments
+    async fn get_all_markets_with_depth(&self, max_depth: usize) -> IgResult<Vec<MarketData>>;
+
+    /// Navigates the market hierarchy and reports how the traversal went
+    ///
+    /// Use [`MarketHierarchyStats::hit_depth_limit`] to tell whether `max_depth`
+    /// stopped the traversal before the tree was exhausted.
+    ///
+    /// # Arguments
+    /// * `max_depth` - Maximum number of levels walked below the root
     ///
     /// # Returns
-    /// * `Result<Vec<MarketData>, AppError>` - Vector containing all found market instruments
-    async fn get_all_markets(&self) -> Result<Vec<MarketData>, AppError>;
+    /// * `IgResult<(Vec<MarketData>, MarketHierarchyStats)>` - The markets found and
+    ///   the traversal statistics
+    async fn get_all_markets_verbose(
+        &self,
+        max_depth: usize,
+    ) -> IgResult<(Vec<MarketData>, MarketHierarchyStats)>;
+
+    /// Navigates the market hierarchy fetching the nodes of each level concurrently
+    ///
+    /// Nodes that fail to load are logged and skipped; the traversal carries on with
+    /// the remaining nodes. The order of the returned markets is not specified.
+    ///
+    /// # Arguments
+    /// * `max_concurrency` - Maximum number of node requests in flight (minimum 1)
+    ///
+    /// # Returns
+    /// * `IgResult<Vec<MarketData>>` - Vector containing all found market instruments
+    async fn get_all_markets_with_concurrency(
+        &self,
+        max_concurrency: usize,
+    ) -> IgResult<Vec<MarketData>>;
+
+    /// Builds the option chain of an underlying from its navigation node
+    ///
+    /// Every market below the node is collected, level by level, and grouped by
+    /// expiry and strike with `OptionChain::from_markets`.
+    ///
+    /// # Arguments
+    /// * `underlying_node_id` - Navigation node holding the underlying's options
+    ///
+    /// # Returns
+    /// * The chain, empty if no option market was found below the node
+    async fn get_option_chain(&self, underlying_node_id: &str) -> IgResult<OptionChain>;
 
     /// Gets all markets converted to database entries format
     ///
     /// This method retrieves all available markets and converts them to a standardized
-    /// database entry format for storage or further processing.
+    /// database entry format for storage or further processing. Expiry dates are
+    /// taken from the details of one market per symbol, fetched concurrently with
+    /// [`MarketService::get_market_details_batches`]; a symbol whose details cannot
+    /// be fetched keeps the expiry reported by the navigation hierarchy.
     ///
     /// # Arguments
     /// * `session` - The authenticated IG session
     ///
     /// # Returns
-    /// * `Result<Vec<DBEntry>, AppError>` - Vector of database entries representing all markets
-    async fn get_vec_db_entries(&self) -> Result<Vec<DBEntryResponse>, AppError>;
+    /// * `IgResult<Vec<DBEntry>>` - Vector of database entries representing all markets
+    async fn get_vec_db_entries(&self) -> IgResult<Vec<DBEntryResponse>>;
 
     /// Gets all categories of instruments enabled for the IG account
     ///
@@ -144,8 +296,8 @@ pub trait MarketService: Send + Sync {
     /// available for trading on the account.
     ///
     /// # Returns
-    /// * `Result<CategoriesResponse, AppError>` - List of available categories
-    async fn get_categories(&self) -> Result<CategoriesResponse, AppError>;
+    /// * `IgResult<CategoriesResponse>` - List of available categories
+    async fn get_categories(&self) -> IgResult<CategoriesResponse>;
 
     /// Gets all instruments for a specific category
     ///
@@ -158,11 +310,44 @@ pub trait MarketService: Send + Sync {
     /// * `page_size` - Optional page size (default: 150, max: 1000)
     ///
     /// # Returns
-    /// * `Result<CategoryInstrumentsResponse, AppError>` - List of instruments in the category
+    /// * `IgResult<CategoryInstrumentsResponse>` - List of instruments in the category
+    /// * `AppError::NotSupported` - If IG does not serve this endpoint for the account,
+    ///   as happens with demo accounts
     async fn get_category_instruments(
         &self,
         category_id: &str,
         page_number: Option<i32>,
         page_size: Option<i32>,
-    ) -> Result<CategoryInstrumentsResponse, AppError>;
+    ) -> IgResult<CategoryInstrumentsResponse>;
+
+    /// Gets the client sentiment (long/short split) for a market
+    ///
+    /// # Arguments
+    /// * `market_id` - Market identifier (as found in the instrument details)
+    ///
+    /// # Returns
+    /// * `IgResult<ClientSentimentResponse>` - Long and short position percentages
+    async fn get_client_sentiment(&self, market_id: &str) -> IgResult<ClientSentimentResponse>;
+
+    /// Gets the client sentiment of the markets related to a market
+    ///
+    /// # Arguments
+    /// * `market_id` - Market identifier
+    ///
+    /// # Returns
+    /// * `IgResult<ClientSentimentListResponse>` - Sentiment of each related market
+    async fn get_related_sentiment(&self, market_id: &str)
+    -> IgResult<ClientSentimentListResponse>;
+
+    /// Gets the client sentiment of several markets in a single request
+    ///
+    /// # Arguments
+    /// * `market_ids` - Market identifiers, between 1 and 50
+    ///
+    /// # Returns
+    /// * `IgResult<ClientSentimentListResponse>` - Sentiment of each requested market
+    async fn get_client_sentiment_batch(
+        &self,
+        market_ids: &[String],
+    ) -> IgResult<ClientSentimentListResponse>;
 }
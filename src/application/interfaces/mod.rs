@@ -6,3 +6,5 @@ pub mod listener;
 pub mod market;
 /// Order service interface
 pub mod order;
+/// Watchlist service interface
+pub mod watchlist;
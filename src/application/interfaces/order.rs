@@ -1,13 +1,15 @@
-use crate::error::AppError;
+use crate::IgResult;
 use crate::model::requests::{
     ClosePositionRequest, CreateOrderRequest, CreateWorkingOrderRequest, UpdatePositionRequest,
+    UpdateWorkingOrderRequest,
 };
 use crate::model::responses::{
-    ClosePositionResponse, CreateOrderResponse, CreateWorkingOrderResponse,
-    OrderConfirmationResponse, UpdatePositionResponse,
+    ClosePositionOutcome, ClosePositionResponse, CreateOrderResponse, CreateWorkingOrderResponse,
+    OrderConfirmationResponse, OrderOutcome, UpdatePositionResponse,
 };
 
 use async_trait::async_trait;
+use std::time::Duration;
 
 #[async_trait]
 /// Service for creating, updating, and managing trading orders with the IG Markets API
@@ -17,16 +19,13 @@ use async_trait::async_trait;
 /// and close positions.
 pub trait OrderService: Send + Sync {
     /// Creates a new order
-    async fn create_order(
-        &self,
-        order: &CreateOrderRequest,
-    ) -> Result<CreateOrderResponse, AppError>;
+    async fn create_order(&self, order: &CreateOrderRequest) -> IgResult<CreateOrderResponse>;
 
     /// Gets the confirmation of an order
     async fn get_order_confirmation(
         &self,
         deal_reference: &str,
-    ) -> Result<OrderConfirmationResponse, AppError>;
+    ) -> IgResult<OrderConfirmationResponse>;
 
     /// Gets the confirmation of an order with retry logic
     async fn get_order_confirmation_w_retry(
@@ -34,14 +33,48 @@ pub trait OrderService: Send + Sync {
         deal_reference: &str,
         retries: u64,
         delay_ms: u64,
-    ) -> Result<OrderConfirmationResponse, AppError>;
+    ) -> IgResult<OrderConfirmationResponse>;
+
+    /// Waits until a deal has been accepted or rejected
+    ///
+    /// Polls the confirmation endpoint while the deal is unknown or pending.
+    /// A rejected deal is returned as `Ok`, with `reason` populated.
+    ///
+    /// # Arguments
+    /// * `deal_reference` - Reference returned when the deal was requested
+    /// * `timeout` - Maximum time to wait for a final outcome
+    ///
+    /// # Returns
+    /// * `Ok(OrderConfirmationResponse)` - The accepted or rejected confirmation
+    /// * `Err(AppError::Timeout)` - If no final outcome arrived in time
+    async fn wait_for_deal(
+        &self,
+        deal_reference: &str,
+        timeout: Duration,
+    ) -> IgResult<OrderConfirmationResponse>;
+
+    /// Creates an order and waits for its final confirmation
+    ///
+    /// # Arguments
+    /// * `order` - The order to place
+    /// * `timeout` - Maximum time to wait for the confirmation
+    ///
+    /// # Returns
+    /// * `Ok(OrderOutcome)` - The accepted or rejected outcome
+    /// * `Err(AppError::DealConfirmationTimeout)` - If the confirmation did not arrive
+    ///   in time; carries the deal reference for later reconciliation
+    async fn place_order_and_confirm(
+        &self,
+        order: &CreateOrderRequest,
+        timeout: Duration,
+    ) -> IgResult<OrderOutcome>;
 
     /// Updates an existing position
     async fn update_position(
         &self,
         deal_id: &str,
         update: &UpdatePositionRequest,
-    ) -> Result<UpdatePositionResponse, AppError>;
+    ) -> IgResult<UpdatePositionResponse>;
 
     ///  Asynchronously updates the limit level of a position in a specified deal.
     ///  
@@ -49,10 +82,10 @@ pub trait OrderService: Send + Sync {
     ///  - `deal_id`: A reference to a string slice representing the unique identifier of the deal
     ///    whose position is to be updated.
     ///  - `limit_level`: An optional `f64` value specifying the new limit level for the position.
-    ///    If `None`, the limit level will not be updated.
+    ///    If `None`, the existing limit is removed from the position.
     ///  
     ///  # Returns
-    ///  - `Result<UpdatePositionResponse, AppError>`:
+    ///  - `IgResult<UpdatePositionResponse>`:
     ///    - On success, returns an `UpdatePositionResponse` containing details of the updated position.
     ///    - On failure, returns an `AppError` indicating the error encountered during the operation.
     ///  
@@ -72,19 +105,70 @@ pub trait OrderService: Send + Sync {
         &self,
         deal_id: &str,
         limit_level: Option<f64>,
-    ) -> Result<UpdatePositionResponse, AppError>;
+    ) -> IgResult<UpdatePositionResponse>;
 
     /// Closes an existing position
     async fn close_position(
         &self,
         close_request: &ClosePositionRequest,
-    ) -> Result<ClosePositionResponse, AppError>;
+    ) -> IgResult<ClosePositionResponse>;
+
+    /// Closes an open position at market, inferring the closing direction
+    ///
+    /// # Arguments
+    /// * `deal_id` - Deal ID of the position to close
+    /// * `size` - Size to close, or `None` to close the whole position
+    ///
+    /// # Errors
+    /// * `AppError::NotFound` - If no open position has that deal ID
+    /// * `AppError::InvalidInput` - If `size` is not positive or exceeds the position size
+    async fn close_position_by_deal_id(
+        &self,
+        deal_id: &str,
+        size: Option<f64>,
+    ) -> IgResult<ClosePositionResponse>;
+
+    /// Closes every open position at market
+    ///
+    /// Open positions are fetched and a market close request is issued for each one,
+    /// selling to close buys and buying to close sells, for the full position size.
+    /// Requests run with bounded concurrency.
+    ///
+    /// # Returns
+    /// * One `ClosePositionOutcome` per position, so failed closes can be identified
+    async fn close_all_positions(&self) -> IgResult<Vec<ClosePositionOutcome>>;
+
+    /// Closes every open position on a single instrument at market
+    ///
+    /// # Arguments
+    /// * `epic` - Instrument EPIC whose positions are closed
+    ///
+    /// # Returns
+    /// * One `ClosePositionOutcome` per position on the instrument
+    async fn close_positions_for_epic(&self, epic: &str) -> IgResult<Vec<ClosePositionOutcome>>;
 
     /// Creates a new working order
     async fn create_working_order(
         &self,
         order: &CreateWorkingOrderRequest,
-    ) -> Result<CreateWorkingOrderResponse, AppError>;
+    ) -> IgResult<CreateWorkingOrderResponse>;
+
+    /// Amends an existing working order in place
+    ///
+    /// # Arguments
+    /// * `deal_id` - Deal ID of the working order to amend
+    /// * `update` - Fields to change; at least one must be set
+    ///
+    /// # Returns
+    /// * The deal reference of the amendment, to be confirmed with `get_order_confirmation`
+    ///
+    /// # Errors
+    /// * `AppError::InvalidInput` - If `update` sets no field
+    async fn update_working_order(
+        &self,
+        deal_id: &str,
+        update: &UpdateWorkingOrderRequest,
+    ) -> IgResult<CreateWorkingOrderResponse>;
 
     /// Deletes a working order based on the provided deal ID.
     ///
@@ -92,8 +176,9 @@ pub trait OrderService: Send + Sync {
     /// - `deal_id`: A `String` representing the deal ID of the working order that needs to be deleted.
     ///
     /// # Returns
-    /// - `Result<(), AppError>`:
-    ///   - On success, the function returns `Ok(())` indicating that the working order was successfully deleted.
+    /// - `IgResult<String>`:
+    ///   - On success, the deal reference of the deletion, which can be passed to
+    ///     `get_order_confirmation` to confirm the cancellation went through.
     ///   - On failure, it returns `Err(AppError)` containing the error details that occurred during the deletion process.
     ///
     /// # Errors
@@ -102,5 +187,5 @@ pub trait OrderService: Send + Sync {
     /// - If there are connectivity issues with the database or external services.
     /// - If the calling user does not have permission to delete the specified working order.
     ///
-    async fn delete_working_order(&self, deal_id: &str) -> Result<(), AppError>;
+    async fn delete_working_order(&self, deal_id: &str) -> IgResult<String>;
 }
@@ -0,0 +1,41 @@
+use crate::IgResult;
+use crate::model::responses::{WatchlistResponse, WatchlistsResponse};
+use async_trait::async_trait;
+
+/// Interface for the watchlist service
+#[async_trait]
+pub trait WatchlistService: Send + Sync {
+    /// Gets all watchlists of the active account
+    async fn get_watchlists(&self) -> IgResult<WatchlistsResponse>;
+
+    /// Creates a new watchlist
+    ///
+    /// # Arguments
+    /// * `name` - Watchlist name
+    /// * `epics` - EPICs of the markets to add to the watchlist
+    ///
+    /// # Returns
+    /// * The identifier of the new watchlist, or `AppError::WatchlistNotCreated`
+    ///   when IG reports `SUCCESS_NOT_CREATED`
+    async fn create_watchlist(&self, name: &str, epics: &[String]) -> IgResult<String>;
+
+    /// Gets the markets of a watchlist
+    ///
+    /// # Arguments
+    /// * `watchlist_id` - Watchlist identifier
+    async fn get_watchlist(&self, watchlist_id: &str) -> IgResult<WatchlistResponse>;
+
+    /// Adds a market to a watchlist
+    ///
+    /// # Arguments
+    /// * `watchlist_id` - Watchlist identifier
+    /// * `epic` - EPIC of the market to add
+    async fn add_market_to_watchlist(&self, watchlist_id: &str, epic: &str) -> IgResult<()>;
+
+    /// Removes a market from a watchlist
+    ///
+    /// # Arguments
+    /// * `watchlist_id` - Watchlist identifier
+    /// * `epic` - EPIC of the market to remove
+    async fn remove_market_from_watchlist(&self, watchlist_id: &str, epic: &str) -> IgResult<()>;
+}
@@ -0,0 +1,97 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Market details cache
+//!
+//! `MarketDetailsCache` keeps the market details fetched by `Client` for a fixed time
+//! so that near-static instrument metadata is not requested again on every call.
+//! Cached entries include the price snapshot of the time they were fetched, which
+//! can therefore be up to one TTL old.
+
+use crate::presentation::market::MarketDetails;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// Thread-safe cache of market details keyed by epic, with a time to live
+#[derive(Debug)]
+pub struct MarketDetailsCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<String, (Instant, MarketDetails)>>,
+}
+
+impl MarketDetailsCache {
+    /// Creates an empty cache
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - How long an entry is served after being fetched
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Gets the time to live of the entries
+    #[must_use]
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Gets the details of an epic if they were fetched within the TTL
+    ///
+    /// # Arguments
+    ///
+    /// * `epic` - Instrument epic
+    #[must_use]
+    pub fn get(&self, epic: &str) -> Option<MarketDetails> {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(epic)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, details)| details.clone())
+    }
+
+    /// Stores the details of a market under its epic
+    ///
+    /// # Arguments
+    ///
+    /// * `details` - Details returned by IG
+    pub fn insert(&self, details: &MarketDetails) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, (fetched_at, _)| fetched_at.elapsed() < self.ttl);
+        entries.insert(
+            details.instrument.epic.clone(),
+            (Instant::now(), details.clone()),
+        );
+    }
+
+    /// Removes every entry
+    pub fn clear(&self) {
+        self.entries
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Gets the number of entries still within the TTL
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        entries
+            .values()
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .count()
+    }
+
+    /// Checks whether no entry is within the TTL
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
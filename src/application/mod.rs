@@ -8,5 +8,15 @@ pub mod config;
 pub mod dynamic_streamer;
 /// Service interfaces and traits
 pub mod interfaces;
+/// Market details cache with time to live
+pub mod market_cache;
+/// Historical price allowance monitoring
+pub mod price_allowance;
 /// Rate limiter module for API request throttling
 pub mod rate_limiter;
+/// Streaming subscription metrics
+pub mod stream_stats;
+/// Streaming client configuration
+pub mod streamer_config;
+/// Streaming subscription status events
+pub mod subscription_events;
@@ -0,0 +1,192 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Historical price allowance monitoring
+//!
+//! IG returns the remaining historical-data allowance with every prices response.
+//! This module watches those values so callers are warned before the allowance is
+//! exhausted and, optionally, so subsequent historical requests are slowed down.
+
+use crate::presentation::market::PriceAllowance;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::warn;
+
+/// Callback fired when the remaining price allowance drops below the threshold
+pub type PriceAllowanceCallback = Arc<dyn Fn(&PriceAllowance) + Send + Sync>;
+
+/// Threshold registration for the allowance monitor
+#[derive(Clone)]
+struct AllowanceWatch {
+    threshold: i64,
+    callback: PriceAllowanceCallback,
+}
+
+/// Monitor for the historical price allowance reported by IG
+///
+/// The monitor keeps the last observed allowance, fires the registered callback
+/// every time an observation is below the threshold, and exposes the delay that
+/// should be applied before the next historical request while the allowance is low.
+#[derive(Default)]
+pub struct PriceAllowanceMonitor {
+    watch: RwLock<Option<AllowanceWatch>>,
+    auto_delay: RwLock<Option<Duration>>,
+    // Last allowance and when it was observed, as `allowanceExpiry` counts from there
+    last: RwLock<Option<(PriceAllowance, Instant)>>,
+    warning_threshold: RwLock<Option<i64>>,
+    low: AtomicBool,
+}
+
+impl PriceAllowanceMonitor {
+    /// Creates a new monitor without threshold or delay
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the low-allowance threshold and the callback to fire
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - Remaining allowance below which the callback fires
+    /// * `callback` - Function invoked with the observed allowance
+    pub fn set_threshold<F>(&self, threshold: i64, callback: F)
+    where
+        F: Fn(&PriceAllowance) + Send + Sync + 'static,
+    {
+        let mut watch = self.watch.write().unwrap_or_else(|e| e.into_inner());
+        *watch = Some(AllowanceWatch {
+            threshold,
+            callback: Arc::new(callback),
+        });
+    }
+
+    /// Sets the remaining allowance below which each observation logs a warning
+    ///
+    /// Unlike [`set_threshold`](Self::set_threshold) no callback is involved, so the
+    /// warning can be enabled alongside a callback with a different threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - Remaining allowance below which to warn, or `None` to disable
+    pub fn set_warning_threshold(&self, threshold: Option<i64>) {
+        let mut warning = self
+            .warning_threshold
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        *warning = threshold;
+    }
+
+    /// Sets the delay applied before historical requests while the allowance is low
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Delay to apply, or `None` to disable automatic throttling
+    pub fn set_auto_delay(&self, delay: Option<Duration>) {
+        let mut auto_delay = self.auto_delay.write().unwrap_or_else(|e| e.into_inner());
+        *auto_delay = delay;
+    }
+
+    /// Records an allowance reported by IG and fires the callback when it is low
+    ///
+    /// # Arguments
+    ///
+    /// * `allowance` - Allowance returned with a historical prices response
+    ///
+    /// # Returns
+    ///
+    /// `true` if the remaining allowance is below the registered threshold
+    pub fn observe(&self, allowance: &PriceAllowance) -> bool {
+        {
+            let mut last = self.last.write().unwrap_or_else(|e| e.into_inner());
+            *last = Some((allowance.clone(), Instant::now()));
+        }
+
+        let warning = *self
+            .warning_threshold
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        if allowance.is_exhausted() {
+            warn!(
+                "Historical price allowance exhausted, resets in {}s",
+                allowance.resets_in().as_secs()
+            );
+        } else if let Some(threshold) = warning
+            && allowance.remaining_allowance < threshold
+        {
+            warn!(
+                "Historical price allowance below {}: {} of {} remaining, resets in {}s",
+                threshold,
+                allowance.remaining_allowance,
+                allowance.total_allowance,
+                allowance.resets_in().as_secs()
+            );
+        }
+
+        let watch = self.watch.read().unwrap_or_else(|e| e.into_inner()).clone();
+        let Some(watch) = watch else {
+            self.low.store(false, Ordering::SeqCst);
+            return false;
+        };
+
+        let is_low = allowance.remaining_allowance < watch.threshold;
+        self.low.store(is_low, Ordering::SeqCst);
+        if is_low {
+            warn!(
+                "Historical price allowance low: {} of {} remaining (threshold {})",
+                allowance.remaining_allowance, allowance.total_allowance, watch.threshold
+            );
+            (watch.callback)(allowance);
+        }
+        is_low
+    }
+
+    /// Gets the last allowance observed
+    #[must_use]
+    pub fn last_allowance(&self) -> Option<PriceAllowance> {
+        self.last
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|(allowance, _)| allowance.clone())
+    }
+
+    /// Gets the time left until the last observed allowance resets
+    ///
+    /// Unlike `PriceAllowance::resets_in`, which is the value reported by IG, the time
+    /// elapsed since the observation is subtracted.
+    #[must_use]
+    pub fn resets_in(&self) -> Option<Duration> {
+        self.last
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|(allowance, observed_at)| {
+                allowance.resets_in().saturating_sub(observed_at.elapsed())
+            })
+    }
+
+    /// Checks whether the last observed allowance was below the threshold
+    #[must_use]
+    pub fn is_low(&self) -> bool {
+        self.low.load(Ordering::SeqCst)
+    }
+
+    /// Gets the delay to apply before the next historical request
+    ///
+    /// # Returns
+    ///
+    /// The configured delay while the allowance is low, `None` otherwise
+    #[must_use]
+    pub fn pending_delay(&self) -> Option<Duration> {
+        if !self.is_low() {
+            return None;
+        }
+        *self.auto_delay.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
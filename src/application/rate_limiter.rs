@@ -15,9 +15,11 @@ use governor::{
     clock::QuantaClock,
     state::{InMemoryState, NotKeyed},
 };
+use reqwest::Method;
 use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::RwLock;
 
 /// Rate limiter for controlling API request rates
 ///
@@ -71,6 +73,45 @@ impl RateLimiter {
         }
     }
 
+    /// Creates a rate limiter allowing `max_requests` per `period`
+    ///
+    /// Up to `max_requests` requests may be made in a burst, after which requests
+    /// are spaced evenly over the period.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_requests` - Number of requests allowed per period (minimum 1)
+    /// * `period` - Length of the period
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // 30 requests per minute
+    /// let limiter = RateLimiter::per_period(30, Duration::from_secs(60));
+    /// ```
+    #[must_use]
+    pub fn per_period(max_requests: u32, period: Duration) -> Self {
+        let limiter = GovernorRateLimiter::direct(Self::quota(max_requests, period));
+        Self {
+            limiter: Arc::new(limiter),
+        }
+    }
+
+    /// Builds the quota used by `per_period`
+    ///
+    /// # Arguments
+    ///
+    /// * `max_requests` - Number of requests allowed per period (minimum 1)
+    /// * `period` - Length of the period
+    #[must_use]
+    pub fn quota(max_requests: u32, period: Duration) -> Quota {
+        let max_requests = NonZeroU32::new(max_requests).unwrap_or(NonZeroU32::MIN);
+        let interval = (period / max_requests.get()).max(Duration::from_nanos(1));
+        Quota::with_period(interval)
+            .expect("Valid period")
+            .allow_burst(max_requests)
+    }
+
     /// Waits until a request can be made according to the rate limit
     ///
     /// This method blocks until the rate limiter allows the request to proceed.
@@ -110,6 +151,107 @@ impl RateLimiter {
     }
 }
 
+/// Kind of REST request, metered separately by IG
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestKind {
+    /// Dealing requests: creating, updating or closing positions and working orders
+    Trading,
+    /// Every other request (market data, account, history, ...)
+    NonTrading,
+}
+
+impl RequestKind {
+    /// Classifies a request by its method and path
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - HTTP method of the request
+    /// * `path` - Request path, relative to the REST base URL
+    #[must_use]
+    pub fn classify(method: &Method, path: &str) -> Self {
+        let path = path.trim_start_matches('/');
+        let dealing_path =
+            path.starts_with("positions/otc") || path.starts_with("workingorders/otc");
+        if dealing_path && *method != Method::GET {
+            RequestKind::Trading
+        } else {
+            RequestKind::NonTrading
+        }
+    }
+}
+
+/// Rate limiters for trading and non-trading requests
+///
+/// IG meters dealing requests separately from the rest of the API, so each kind
+/// of request acquires a permit from its own bucket.
+#[derive(Clone, Debug)]
+pub struct RateLimiters {
+    trading: Arc<RwLock<RateLimiter>>,
+    non_trading: Arc<RwLock<RateLimiter>>,
+}
+
+impl RateLimiters {
+    /// Creates the buckets from one limiter per request kind
+    ///
+    /// # Arguments
+    ///
+    /// * `trading` - Limiter for dealing requests
+    /// * `non_trading` - Limiter for every other request
+    #[must_use]
+    pub fn new(trading: RateLimiter, non_trading: RateLimiter) -> Self {
+        Self {
+            trading: Arc::new(RwLock::new(trading)),
+            non_trading: Arc::new(RwLock::new(non_trading)),
+        }
+    }
+
+    /// Creates the buckets using the configured limit for non-trading requests
+    /// and the default limit for trading requests
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Rate limiter configuration for non-trading requests
+    #[must_use]
+    pub fn from_config(config: &RateLimiterConfig) -> Self {
+        Self::new(Self::default_trading(), RateLimiter::new(config))
+    }
+
+    /// Gets the limiter for a kind of request
+    #[must_use]
+    pub fn for_kind(&self, kind: RequestKind) -> Arc<RwLock<RateLimiter>> {
+        match kind {
+            RequestKind::Trading => Arc::clone(&self.trading),
+            RequestKind::NonTrading => Arc::clone(&self.non_trading),
+        }
+    }
+
+    /// Gets the limiter for a request
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - HTTP method of the request
+    /// * `path` - Request path, relative to the REST base URL
+    #[must_use]
+    pub fn for_request(&self, method: &Method, path: &str) -> Arc<RwLock<RateLimiter>> {
+        self.for_kind(RequestKind::classify(method, path))
+    }
+
+    /// Default trading limit: 1 request per second
+    fn default_trading() -> RateLimiter {
+        RateLimiter::per_period(1, Duration::from_secs(1))
+    }
+}
+
+impl Default for RateLimiters {
+    /// 1 trading request per second and 30 non-trading requests per minute
+    fn default() -> Self {
+        Self::new(
+            Self::default_trading(),
+            RateLimiter::per_period(30, Duration::from_secs(60)),
+        )
+    }
+}
+
 impl std::fmt::Debug for RateLimiter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RateLimiter")
@@ -121,6 +263,65 @@ impl std::fmt::Debug for RateLimiter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use governor::clock::FakeRelativeClock;
+
+    #[test]
+    fn test_quota_spaces_requests_on_fake_clock() {
+        let clock = FakeRelativeClock::default();
+        let limiter = GovernorRateLimiter::direct_with_clock(
+            RateLimiter::quota(30, Duration::from_secs(60)),
+            clock.clone(),
+        );
+
+        // The full burst is available immediately
+        for _ in 0..30 {
+            assert!(limiter.check().is_ok());
+        }
+        assert!(limiter.check().is_err());
+
+        // Afterwards one request every 2 seconds
+        clock.advance(Duration::from_millis(1999));
+        assert!(limiter.check().is_err());
+        clock.advance(Duration::from_millis(1));
+        assert!(limiter.check().is_ok());
+        assert!(limiter.check().is_err());
+    }
+
+    #[test]
+    fn test_trading_quota_allows_one_request_per_second() {
+        let clock = FakeRelativeClock::default();
+        let limiter = GovernorRateLimiter::direct_with_clock(
+            RateLimiter::quota(1, Duration::from_secs(1)),
+            clock.clone(),
+        );
+
+        assert!(limiter.check().is_ok());
+        assert!(limiter.check().is_err());
+        clock.advance(Duration::from_millis(500));
+        assert!(limiter.check().is_err());
+        clock.advance(Duration::from_millis(500));
+        assert!(limiter.check().is_ok());
+    }
+
+    #[test]
+    fn test_request_kind_classification() {
+        assert_eq!(
+            RequestKind::classify(&Method::POST, "positions/otc"),
+            RequestKind::Trading
+        );
+        assert_eq!(
+            RequestKind::classify(&Method::DELETE, "/workingorders/otc/DIAAAA"),
+            RequestKind::Trading
+        );
+        assert_eq!(
+            RequestKind::classify(&Method::GET, "positions"),
+            RequestKind::NonTrading
+        );
+        assert_eq!(
+            RequestKind::classify(&Method::GET, "markets/IX.D.DAX.DAILY.IP"),
+            RequestKind::NonTrading
+        );
+    }
 
     #[tokio::test]
     async fn test_rate_limiter_allows_requests() {
@@ -0,0 +1,182 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Streaming subscription metrics
+//!
+//! The forwarding tasks spawned by `StreamerClient` record, per Lightstreamer item,
+//! how many updates arrived, how many were dropped because a bounded consumer fell
+//! behind, and when the last update was seen.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Counters for a single subscribed item
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ItemStats {
+    /// Updates received from the server
+    pub received: u64,
+    /// Updates discarded because the consumer channel was full
+    pub dropped: u64,
+    /// When the last update was received
+    pub last_update_at: Option<Instant>,
+}
+
+/// Snapshot of the streaming metrics, keyed by item name (e.g. `MARKET:IX.D.DAX.DAILY.IP`)
+///
+/// The `Display` implementation renders the Prometheus text exposition format.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamStats {
+    /// Counters per item name
+    pub items: BTreeMap<String, ItemStats>,
+}
+
+impl StreamStats {
+    /// Gets the counters for an item
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Full Lightstreamer item name
+    #[must_use]
+    pub fn get(&self, item: &str) -> Option<&ItemStats> {
+        self.items.get(item)
+    }
+
+    /// Gets the number of updates received across all items
+    #[must_use]
+    pub fn total_received(&self) -> u64 {
+        self.items.values().map(|stats| stats.received).sum()
+    }
+
+    /// Gets the number of updates dropped across all items
+    #[must_use]
+    pub fn total_dropped(&self) -> u64 {
+        self.items.values().map(|stats| stats.dropped).sum()
+    }
+
+    /// Gets the time of the most recent update across all items
+    #[must_use]
+    pub fn last_update_at(&self) -> Option<Instant> {
+        self.items
+            .values()
+            .filter_map(|stats| stats.last_update_at)
+            .max()
+    }
+
+    /// Checks whether any item received an update within the given window
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Maximum age of the most recent update
+    #[must_use]
+    pub fn is_fresh(&self, window: Duration) -> bool {
+        self.last_update_at()
+            .is_some_and(|last| last.elapsed() <= window)
+    }
+}
+
+impl fmt::Display for StreamStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# HELP ig_stream_updates_received_total Updates received per streaming item"
+        )?;
+        writeln!(f, "# TYPE ig_stream_updates_received_total counter")?;
+        for (item, stats) in &self.items {
+            writeln!(
+                f,
+                "ig_stream_updates_received_total{{item=\"{}\"}} {}",
+                escape_label(item),
+                stats.received
+            )?;
+        }
+
+        writeln!(
+            f,
+            "# HELP ig_stream_updates_dropped_total Updates dropped per streaming item"
+        )?;
+        writeln!(f, "# TYPE ig_stream_updates_dropped_total counter")?;
+        for (item, stats) in &self.items {
+            writeln!(
+                f,
+                "ig_stream_updates_dropped_total{{item=\"{}\"}} {}",
+                escape_label(item),
+                stats.dropped
+            )?;
+        }
+
+        writeln!(
+            f,
+            "# HELP ig_stream_seconds_since_last_update Seconds since the last update per streaming item"
+        )?;
+        writeln!(f, "# TYPE ig_stream_seconds_since_last_update gauge")?;
+        for (item, stats) in &self.items {
+            if let Some(last) = stats.last_update_at {
+                writeln!(
+                    f,
+                    "ig_stream_seconds_since_last_update{{item=\"{}\"}} {:.3}",
+                    escape_label(item),
+                    last.elapsed().as_secs_f64()
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Escapes a Prometheus label value
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Thread-safe recorder shared by the streaming forwarding tasks
+#[derive(Debug, Clone, Default)]
+pub struct StreamStatsRecorder {
+    items: Arc<Mutex<BTreeMap<String, ItemStats>>>,
+}
+
+impl StreamStatsRecorder {
+    /// Creates an empty recorder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an update received for an item
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Full Lightstreamer item name
+    pub fn record_received(&self, item: &str) {
+        let mut items = self.items.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = items.entry(item.to_string()).or_default();
+        stats.received += 1;
+        stats.last_update_at = Some(Instant::now());
+    }
+
+    /// Records an update dropped for an item
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Full Lightstreamer item name
+    pub fn record_dropped(&self, item: &str) {
+        let mut items = self.items.lock().unwrap_or_else(|e| e.into_inner());
+        items.entry(item.to_string()).or_default().dropped += 1;
+    }
+
+    /// Takes a snapshot of the current counters
+    #[must_use]
+    pub fn snapshot(&self) -> StreamStats {
+        let items = self.items.lock().unwrap_or_else(|e| e.into_inner());
+        StreamStats {
+            items: items.clone(),
+        }
+    }
+}
@@ -0,0 +1,272 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Streaming client configuration
+//!
+//! `StreamerConfig` carries the settings `StreamerClient` applies to its Lightstreamer
+//! connections: the data adapter used for PRICE subscriptions, the forced transport,
+//! the reconnection backoff and the item name prefixes, which only need changing on
+//! non-standard IG setups.
+//!
+//! Demo accounts have a few known quirks:
+//! - the endpoint returned on login is occasionally the live one or missing, so the
+//!   handshake fails; `with_lightstreamer_endpoint(IgEnvironment::Demo.lightstreamer_url())`
+//!   pins the demo server;
+//! - some demo setups reject the WebSocket streaming handshake and only accept
+//!   polling; `with_forced_transport(Some(Transport::WsPolling))` switches to it.
+
+use crate::constants::DEFAULT_STREAM_STALENESS_SECS;
+use lightstreamer_rs::client::Transport;
+use std::time::Duration;
+
+/// Default data adapter for PRICE subscriptions
+pub const DEFAULT_PRICING_ADAPTER: &str = "Pricing";
+
+/// Environment variable overriding the default PRICE data adapter
+pub const PRICING_ADAPTER_ENV: &str = "IG_PRICING_ADAPTER";
+
+/// Reconnection backoff used by `StreamerClient::connect`
+///
+/// After the `n`-th consecutive failure (zero-based) the client waits `delay(n)`,
+/// where `delay(0)` is zero and `delay(n) = delay(n - 1) * multiplier + base_delay * (n - 1)`,
+/// capped at `max_delay`. The default keeps the historical behaviour: three attempts
+/// with immediate retries, and with more attempts 200 ms, 600 ms, 1.2 s, ... up to 5 s.
+/// Not to be confused with `dynamic_streamer::ReconnectPolicy`, which drives the
+/// rebuilds of `DynamicMarketStreamer` on top of this.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StreamReconnectPolicy {
+    /// Maximum number of connection attempts, `None` to retry forever
+    pub max_attempts: Option<u32>,
+    /// Step added to the delay after every failure, times the number of earlier failures
+    pub base_delay: Duration,
+    /// Upper bound for the delay between reconnections
+    pub max_delay: Duration,
+    /// Factor applied to the previous delay after every failure
+    pub multiplier: f64,
+}
+
+impl Default for StreamReconnectPolicy {
+    /// Three attempts, with delays growing by 200 ms steps up to 5 s
+    fn default() -> Self {
+        Self {
+            max_attempts: Some(3),
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+            multiplier: 1.0,
+        }
+    }
+}
+
+impl StreamReconnectPolicy {
+    /// Creates a policy that never gives up, keeping the default backoff
+    #[must_use]
+    pub fn unlimited() -> Self {
+        Self {
+            max_attempts: None,
+            ..Self::default()
+        }
+    }
+
+    /// Gets the delay before reconnecting after a failure
+    ///
+    /// # Arguments
+    ///
+    /// * `failure` - Zero-based index of the consecutive failure
+    #[must_use]
+    pub fn delay(&self, failure: u32) -> Duration {
+        let step = self.base_delay.as_secs_f64();
+        let multiplier = self.multiplier.max(0.0);
+        let max_delay = self.max_delay.as_secs_f64();
+
+        let mut delay = 0.0;
+        for earlier in 0..failure {
+            delay = delay * multiplier + step * f64::from(earlier);
+            if !delay.is_finite() || delay >= max_delay {
+                return self.max_delay;
+            }
+            if step == 0.0 {
+                // Without a step the delay never leaves zero
+                break;
+            }
+        }
+        Duration::from_secs_f64(delay)
+    }
+
+    /// Checks whether another attempt is allowed after `attempts` failed ones
+    #[must_use]
+    pub fn allows_attempt(&self, attempts: u32) -> bool {
+        self.max_attempts.is_none_or(|max| attempts < max)
+    }
+}
+
+/// Settings applied by `StreamerClient` to its connections and subscriptions
+#[derive(Debug, Clone)]
+pub struct StreamerConfig {
+    /// Data adapter used for PRICE subscriptions
+    pub pricing_adapter: String,
+    /// Transport forced on both Lightstreamer clients (`None` lets the server negotiate)
+    pub forced_transport: Option<Transport>,
+    /// Lightstreamer endpoint used instead of the one returned on login
+    pub lightstreamer_endpoint: Option<String>,
+    /// Reconnection backoff of each connection
+    pub reconnect: StreamReconnectPolicy,
+    /// Maximum age of the latest update for the stream to be considered healthy
+    pub staleness_window: Duration,
+    /// Silence after which a connection is dropped and rebuilt (`None` disables it)
+    ///
+    /// Measured per connection across all of its subscriptions, so it should be
+    /// longer than the quietest expected period of the subscribed markets.
+    pub idle_timeout: Option<Duration>,
+    /// Whether trade activity missed while reconnecting is fetched from the account
+    /// history and replayed on the trade stream (requires a backfill client)
+    pub backfill_on_reconnect: bool,
+    /// Item prefix for market data subscriptions (`MARKET:{epic}`)
+    pub market_item_prefix: String,
+    /// Item prefix for price subscriptions (`PRICE:{account}:{epic}`)
+    pub price_item_prefix: String,
+    /// Item prefix for trade subscriptions (`TRADE:{account}`)
+    pub trade_item_prefix: String,
+}
+
+impl Default for StreamerConfig {
+    /// Uses the `Pricing` adapter, unless `IG_PRICING_ADAPTER` is set, WebSocket
+    /// streaming transport, the endpoint returned on login, the default reconnection
+    /// backoff, a 30 s staleness window, no idle timeout, no trade backfill and the
+    /// standard IG item prefixes.
+    fn default() -> Self {
+        Self {
+            pricing_adapter: std::env::var(PRICING_ADAPTER_ENV)
+                .unwrap_or_else(|_| DEFAULT_PRICING_ADAPTER.to_string()),
+            forced_transport: Some(Transport::WsStreaming),
+            lightstreamer_endpoint: None,
+            reconnect: StreamReconnectPolicy::default(),
+            staleness_window: Duration::from_secs(DEFAULT_STREAM_STALENESS_SECS),
+            idle_timeout: None,
+            backfill_on_reconnect: false,
+            market_item_prefix: "MARKET".to_string(),
+            price_item_prefix: "PRICE".to_string(),
+            trade_item_prefix: "TRADE".to_string(),
+        }
+    }
+}
+
+impl StreamerConfig {
+    /// Sets the data adapter used for PRICE subscriptions
+    #[must_use]
+    pub fn with_pricing_adapter(mut self, adapter: impl Into<String>) -> Self {
+        self.pricing_adapter = adapter.into();
+        self
+    }
+
+    /// Sets the transport forced on both Lightstreamer clients
+    #[must_use]
+    pub fn with_forced_transport(mut self, transport: Option<Transport>) -> Self {
+        self.forced_transport = transport;
+        self
+    }
+
+    /// Sets the Lightstreamer endpoint, overriding the one returned on login
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Host or URL, with or without the `/lightstreamer` path
+    #[must_use]
+    pub fn with_lightstreamer_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.lightstreamer_endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Sets the reconnection backoff
+    #[must_use]
+    pub fn with_reconnect_policy(mut self, reconnect: StreamReconnectPolicy) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Sets how long the stream may stay silent before it is reported unhealthy
+    #[must_use]
+    pub fn with_staleness_window(mut self, window: Duration) -> Self {
+        self.staleness_window = window;
+        self
+    }
+
+    /// Sets the silence after which a connection is proactively rebuilt
+    ///
+    /// When no subscription of a connection receives an update within `timeout`,
+    /// the connection is closed and goes through the reconnect path, re-issuing its
+    /// subscriptions. Idle reconnections do not consume reconnect attempts.
+    #[must_use]
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether missed trade activity is replayed after a reconnection
+    ///
+    /// Only takes effect once `StreamerClient::with_backfill_client` provided the
+    /// REST client used to query the activity history.
+    #[must_use]
+    pub fn with_backfill_on_reconnect(mut self, enabled: bool) -> Self {
+        self.backfill_on_reconnect = enabled;
+        self
+    }
+
+    /// Sets the MARKET, PRICE and TRADE item prefixes
+    ///
+    /// # Arguments
+    ///
+    /// * `market` - Prefix for market data items
+    /// * `price` - Prefix for price items
+    /// * `trade` - Prefix for trade items
+    #[must_use]
+    pub fn with_item_prefixes(
+        mut self,
+        market: impl Into<String>,
+        price: impl Into<String>,
+        trade: impl Into<String>,
+    ) -> Self {
+        self.market_item_prefix = market.into();
+        self.price_item_prefix = price.into();
+        self.trade_item_prefix = trade.into();
+        self
+    }
+
+    /// Gets the market data item name for an epic
+    #[must_use]
+    pub fn market_item(&self, epic: &str) -> String {
+        format!("{}:{epic}", self.market_item_prefix)
+    }
+
+    /// Gets the price item name for an account and epic
+    #[must_use]
+    pub fn price_item(&self, account_id: &str, epic: &str) -> String {
+        format!("{}:{account_id}:{epic}", self.price_item_prefix)
+    }
+
+    /// Gets the trade item name for an account
+    #[must_use]
+    pub fn trade_item(&self, account_id: &str) -> String {
+        format!("{}:{account_id}", self.trade_item_prefix)
+    }
+
+    /// Extracts the epic from a market, price or CHART item name
+    ///
+    /// # Returns
+    ///
+    /// `None` for account-level items such as `TRADE:{account}`.
+    #[must_use]
+    pub fn item_epic<'a>(&self, item: &'a str) -> Option<&'a str> {
+        let mut parts = item.split(':');
+        let prefix = parts.next()?;
+        if prefix == self.market_item_prefix || prefix == "CHART" {
+            parts.next()
+        } else if prefix == self.price_item_prefix {
+            parts.nth(1)
+        } else {
+            None
+        }
+    }
+}
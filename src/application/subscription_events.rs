@@ -0,0 +1,139 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Streaming subscription status events
+//!
+//! Every subscription issued by `StreamerClient` carries a `SubscriptionStatusListener`
+//! that reports the server's answer for each of its items: confirmed, rejected or
+//! removed. Events are only published once `StreamerClient::subscription_events` has
+//! been called, so unobserved clients pay nothing for them.
+
+use lightstreamer_rs::subscription::{ItemUpdate, SubscriptionListener};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+/// Status change of a subscribed item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionEvent {
+    /// The server accepted the subscription of the item
+    Subscribed {
+        /// Full Lightstreamer item name (e.g. `MARKET:IX.D.DAX.DAILY.IP`)
+        item: String,
+    },
+    /// The server refused the subscription of the item
+    Rejected {
+        /// Full Lightstreamer item name
+        item: String,
+        /// Error code and message reported by the server
+        reason: String,
+    },
+    /// The item is no longer subscribed
+    Unsubscribed {
+        /// Full Lightstreamer item name
+        item: String,
+    },
+}
+
+impl SubscriptionEvent {
+    /// Gets the item the event refers to
+    #[must_use]
+    pub fn item(&self) -> &str {
+        match self {
+            Self::Subscribed { item }
+            | Self::Rejected { item, .. }
+            | Self::Unsubscribed { item } => item,
+        }
+    }
+}
+
+/// Thread-safe publisher shared by the subscription status listeners
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionEventHub {
+    sender: Arc<Mutex<Option<mpsc::UnboundedSender<SubscriptionEvent>>>>,
+}
+
+impl SubscriptionEventHub {
+    /// Creates a hub without subscriber
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opens the event channel
+    ///
+    /// Only one receiver is fed at a time: a new call closes the previous channel.
+    #[must_use]
+    pub fn subscribe(&self) -> mpsc::UnboundedReceiver<SubscriptionEvent> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        *self.sender.lock().unwrap_or_else(|e| e.into_inner()) = Some(tx);
+        rx
+    }
+
+    /// Publishes an event, dropping the channel once its receiver is gone
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - Event to publish
+    pub fn publish(&self, event: SubscriptionEvent) {
+        let mut sender = self.sender.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(tx) = sender.as_ref()
+            && tx.send(event).is_err()
+        {
+            *sender = None;
+        }
+    }
+}
+
+/// Listener reporting the status of a subscription for each of its items
+///
+/// Lightstreamer confirms or rejects a subscription as a whole, so a status change is
+/// published once per item of the subscription.
+pub struct SubscriptionStatusListener {
+    items: Vec<String>,
+    hub: SubscriptionEventHub,
+}
+
+impl SubscriptionStatusListener {
+    /// Creates a listener for the given items
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - Full Lightstreamer item names of the subscription
+    /// * `hub` - Hub the events are published to
+    #[must_use]
+    pub fn new(items: Vec<String>, hub: SubscriptionEventHub) -> Self {
+        Self { items, hub }
+    }
+
+    fn publish_each(&self, event: impl Fn(String) -> SubscriptionEvent) {
+        for item in &self.items {
+            self.hub.publish(event(item.clone()));
+        }
+    }
+}
+
+impl SubscriptionListener for SubscriptionStatusListener {
+    fn on_item_update(&self, _update: &ItemUpdate) {}
+
+    fn on_subscription(&mut self) {
+        self.publish_each(|item| SubscriptionEvent::Subscribed { item });
+    }
+
+    fn on_subscription_error(&mut self, code: i32, message: Option<&str>) {
+        let reason = match message {
+            Some(message) => format!("{code}: {message}"),
+            None => code.to_string(),
+        };
+        self.publish_each(|item| SubscriptionEvent::Rejected {
+            item,
+            reason: reason.clone(),
+        });
+    }
+
+    fn on_unsubscription(&mut self) {
+        self.publish_each(|item| SubscriptionEvent::Unsubscribed { item });
+    }
+}
@@ -6,14 +6,74 @@ pub const MAX_CONSECUTIVE_ERRORS: u32 = 3;
 pub const ERROR_COOLDOWN_SECONDS: u64 = 300;
 /// Default sleep time in hours if not specified in environment (24 hours)
 pub const DEFAULT_SLEEP_TIME: u64 = 24;
+/// Base URL of IG's demo REST API
+pub const DEMO_REST_BASE_URL: &str = "https://demo-api.ig.com/gateway/deal";
+/// Base URL of IG's live REST API
+pub const LIVE_REST_BASE_URL: &str = "https://api.ig.com/gateway/deal";
+/// Lightstreamer endpoint of IG's demo environment
+pub const DEMO_LIGHTSTREAMER_URL: &str = "https://demo-apd.marketdatasystems.com";
+/// Lightstreamer endpoint of IG's live environment
+pub const LIVE_LIGHTSTREAMER_URL: &str = "https://apd.marketdatasystems.com";
+/// Environment variables `Config::from_env` requires
+pub const REQUIRED_ENV_VARS: &[&str] =
+    &["IG_API_KEY", "IG_USERNAME", "IG_PASSWORD", "IG_ACCOUNT_ID"];
 /// Default page size for API requests
 pub const DEFAULT_PAGE_SIZE: u32 = 50;
+/// Default number of navigation nodes fetched concurrently when walking the market hierarchy
+pub const DEFAULT_MARKET_TRAVERSAL_CONCURRENCY: usize = 8;
+/// Default number of navigation levels walked below the root when collecting every market
+pub const DEFAULT_MARKET_TRAVERSAL_DEPTH: usize = 6;
+/// Maximum number of navigation levels walked below a node when building an option chain
+pub const MAX_OPTION_CHAIN_DEPTH: usize = 4;
+/// Maximum number of epics IG accepts in a single multiple market details request,
+/// also the limit of market ids in a client sentiment batch
+pub const MAX_MARKET_DETAILS_EPICS: usize = 50;
+/// Maximum number of multiple market details requests in flight when fetching in bulk
+pub const DEFAULT_MARKET_DETAILS_BULK_CONCURRENCY: usize = 4;
+/// Maximum number of close requests in flight when closing positions in bulk
+pub const DEFAULT_BULK_CLOSE_CONCURRENCY: usize = 4;
+/// Interval in milliseconds between deal confirmation polls while waiting for a deal
+pub const DEFAULT_DEAL_POLL_INTERVAL_MS: u64 = 250;
+/// Seconds without streaming updates after which `StreamerClient::is_healthy` reports a stale stream
+pub const DEFAULT_STREAM_STALENESS_SECS: u64 = 30;
+/// Times per idle timeout the streaming idle watchdog polls the received counters
+pub const IDLE_WATCHDOG_POLLS: u32 = 4;
+/// Interval in milliseconds between health checks while waiting for a stream to become healthy
+pub const STREAM_HEALTH_POLL_INTERVAL_MS: u64 = 250;
+/// Retries of the streaming credentials request when a `StreamerClient` is created
+pub const WS_INFO_RETRIES: u32 = 3;
+/// Delay in milliseconds between two streaming credentials requests
+pub const WS_INFO_RETRY_DELAY_MS: u64 = 1000;
+/// Interval in milliseconds between flushes of a JSON-lines export file
+pub const JSONL_FLUSH_INTERVAL_MS: u64 = 1000;
+/// Page size used when paging through the transaction history
+pub const TRANSACTIONS_PAGE_SIZE: u32 = 200;
+/// Seconds a reconnected trade stream is given to settle before missed activity is fetched
+pub const TRADE_BACKFILL_SETTLE_SECS: u64 = 10;
+/// Date format of the `from`/`to` parameters of the activity history endpoint
+pub const ACTIVITY_QUERY_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+/// Date format of the start and end path segments of the historical prices endpoint (v2)
+pub const PRICES_PATH_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+/// Remaining historical price allowance below which a backfill pauses until the reset
+pub const BACKFILL_ALLOWANCE_RESERVE: i64 = 100;
+/// Pause in seconds when a backfill hits the allowance and IG gave no reset time
+pub const BACKFILL_ALLOWANCE_PAUSE_SECS: u64 = 60;
+/// Prefix of the deal references returned by a client in dry-run mode
+pub const DRY_RUN_DEAL_REFERENCE_PREFIX: &str = "DRYRUN-";
+/// Clock drift in seconds between the local clock and IG's server time above which a warning is logged
+pub const MAX_CLOCK_DRIFT_SECS: i64 = 2;
 /// Base delay in milliseconds used for proximity-based delays in the rate limiter
 /// This value is used to calculate wait times when approaching rate limits
 pub const BASE_DELAY_MS: u64 = 1000;
 /// Additional safety buffer in milliseconds added to wait times
 /// This provides extra margin to ensure rate limits are not exceeded
 pub const SAFETY_BUFFER_MS: u64 = 1000;
+/// Default time allowed to establish a connection to the IG API, in seconds
+pub const DEFAULT_HTTP_CONNECT_TIMEOUT_SECS: u64 = 10;
+/// Default time allowed for a complete REST request, in seconds
+pub const DEFAULT_HTTP_REQUEST_TIMEOUT_SECS: u64 = 30;
+/// Default time idle pooled connections are kept alive, in seconds
+pub const DEFAULT_HTTP_POOL_IDLE_TIMEOUT_SECS: u64 = 90;
 /// User agent string used in HTTP requests to identify this client to the IG Markets API
 pub const USER_AGENT: &str = "Rust-IG-Client/0.1.9";
 /// A constant representing the default sell level for orders.
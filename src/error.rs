@@ -5,8 +5,140 @@
 ******************************************************************************/
 use reqwest::StatusCode;
 use std::fmt::{Display, Formatter};
+use std::time::Duration;
 use std::{fmt, io};
 
+/// Allowance that was exceeded when IG rate limited a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitScope {
+    /// Per-account trading allowance (`exceeded-account-trading-allowance`)
+    AccountTrading,
+    /// Per-account non-trading allowance (`exceeded-account-allowance`)
+    AccountNonTrading,
+    /// Per-application allowance (`exceeded-api-key-allowance`)
+    App,
+    /// Weekly historical price data allowance (`exceeded-account-historical-data-allowance`)
+    HistoricalData,
+    /// Rate limited without a recognised IG error code (e.g. a bare HTTP 429)
+    Unknown,
+}
+
+impl RateLimitScope {
+    /// Maps an IG error code, or a response body containing one, to its scope
+    ///
+    /// # Arguments
+    /// * `error_code` - IG error code such as `error.public-api.exceeded-account-allowance`
+    ///
+    /// # Returns
+    /// The matching scope, or `None` if the code is not a rate limit error
+    #[must_use]
+    pub fn from_error_code(error_code: &str) -> Option<Self> {
+        if error_code.contains("exceeded-account-trading-allowance") {
+            Some(RateLimitScope::AccountTrading)
+        } else if error_code.contains("exceeded-account-historical-data-allowance") {
+            Some(RateLimitScope::HistoricalData)
+        } else if error_code.contains("exceeded-account-allowance") {
+            Some(RateLimitScope::AccountNonTrading)
+        } else if error_code.contains("exceeded-api-key-allowance") {
+            Some(RateLimitScope::App)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for RateLimitScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RateLimitScope::AccountTrading => write!(f, "account trading"),
+            RateLimitScope::AccountNonTrading => write!(f, "account non-trading"),
+            RateLimitScope::App => write!(f, "app"),
+            RateLimitScope::HistoricalData => write!(f, "historical data"),
+            RateLimitScope::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Reason IG gave for rejecting an order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderRejectCode {
+    /// The market is closed (`MARKET_CLOSED`)
+    MarketClosed,
+    /// The market is offline (`MARKET_OFFLINE`)
+    MarketOffline,
+    /// Only closing trades are accepted on the market (`CLOSING_ONLY_TRADES_ACCEPTED_ON_THIS_MARKET`)
+    ClosingOnly,
+    /// The account does not have enough funds (`INSUFFICIENT_FUNDS`)
+    InsufficientFunds,
+    /// The stop or limit level is invalid, e.g. too close to the market (`ATTACHED_ORDER_LEVEL_ERROR`)
+    AttachedOrderLevelError,
+    /// The trailing stop is invalid (`ATTACHED_ORDER_TRAILING_STOP_ERROR`)
+    AttachedOrderTrailingStopError,
+    /// The size is below the minimum deal size (`MINIMUM_ORDER_SIZE_ERROR`)
+    MinimumOrderSizeError,
+    /// The size exceeds the maximum size dealt automatically (`MAX_AUTO_SIZE_EXCEEDED`)
+    MaxAutoSizeExceeded,
+    /// The position cannot be closed (`POSITION_NOT_AVAILABLE_TO_CLOSE`)
+    PositionNotAvailableToClose,
+    /// The order level is on the wrong side of the market (`WRONG_SIDE_OF_MARKET`)
+    WrongSideOfMarket,
+    /// Any other reason, as reported by IG
+    Other(String),
+}
+
+impl OrderRejectCode {
+    /// Maps an IG rejection reason or error code to its variant
+    ///
+    /// Accepts deal confirmation reasons (`MARKET_CLOSED`) as well as REST error
+    /// codes, whose last dotted segment is compared after normalising dashes to
+    /// underscores (`error.trading.market-closed`).
+    ///
+    /// # Arguments
+    /// * `code` - Reason or error code reported by IG
+    #[must_use]
+    pub fn from_code(code: &str) -> Self {
+        let normalized = code
+            .trim()
+            .rsplit('.')
+            .next()
+            .unwrap_or_default()
+            .replace('-', "_")
+            .to_ascii_uppercase();
+        match normalized.as_str() {
+            "MARKET_CLOSED" | "MARKET_CLOSED_WITH_EDITS" => Self::MarketClosed,
+            "MARKET_OFFLINE" => Self::MarketOffline,
+            "CLOSING_ONLY_TRADES_ACCEPTED_ON_THIS_MARKET" => Self::ClosingOnly,
+            "INSUFFICIENT_FUNDS" => Self::InsufficientFunds,
+            "ATTACHED_ORDER_LEVEL_ERROR" => Self::AttachedOrderLevelError,
+            "ATTACHED_ORDER_TRAILING_STOP_ERROR" => Self::AttachedOrderTrailingStopError,
+            "MINIMUM_ORDER_SIZE_ERROR" => Self::MinimumOrderSizeError,
+            "MAX_AUTO_SIZE_EXCEEDED" => Self::MaxAutoSizeExceeded,
+            "POSITION_NOT_AVAILABLE_TO_CLOSE" => Self::PositionNotAvailableToClose,
+            "WRONG_SIDE_OF_MARKET" => Self::WrongSideOfMarket,
+            _ => Self::Other(code.trim().to_string()),
+        }
+    }
+}
+
+impl Display for OrderRejectCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let code = match self {
+            Self::MarketClosed => "MARKET_CLOSED",
+            Self::MarketOffline => "MARKET_OFFLINE",
+            Self::ClosingOnly => "CLOSING_ONLY_TRADES_ACCEPTED_ON_THIS_MARKET",
+            Self::InsufficientFunds => "INSUFFICIENT_FUNDS",
+            Self::AttachedOrderLevelError => "ATTACHED_ORDER_LEVEL_ERROR",
+            Self::AttachedOrderTrailingStopError => "ATTACHED_ORDER_TRAILING_STOP_ERROR",
+            Self::MinimumOrderSizeError => "MINIMUM_ORDER_SIZE_ERROR",
+            Self::MaxAutoSizeExceeded => "MAX_AUTO_SIZE_EXCEEDED",
+            Self::PositionNotAvailableToClose => "POSITION_NOT_AVAILABLE_TO_CLOSE",
+            Self::WrongSideOfMarket => "WRONG_SIDE_OF_MARKET",
+            Self::Other(code) => code.as_str(),
+        };
+        write!(f, "{code}")
+    }
+}
+
 /// Error type for fetch operations
 #[derive(Debug)]
 pub enum FetchError {
@@ -139,10 +271,35 @@ pub enum AppError {
     Unauthorized,
     /// OAuth token expired error (requires token refresh)
     OAuthTokenExpired,
+    /// CST/X-SECURITY-TOKEN session rejected by IG (requires re-authentication)
+    SessionExpired,
     /// Resource not found error
     NotFound,
     /// API rate limit exceeded
     RateLimitExceeded,
+    /// IG rate limited the request once retries were exhausted
+    RateLimited {
+        /// Allowance that was exceeded
+        scope: RateLimitScope,
+        /// Delay requested by the `Retry-After` header, if any
+        retry_after: Option<Duration>,
+    },
+    /// The historical price allowance is used up; requests fail until it resets
+    AllowanceExceeded {
+        /// Time until the allowance resets, if IG reported it
+        resets_in: Option<Duration>,
+    },
+    /// The request did not complete within the configured timeout
+    Timeout(String),
+    /// A deal was requested but its confirmation did not arrive in time
+    ///
+    /// The deal may still have been executed; use the reference to reconcile.
+    DealConfirmationTimeout {
+        /// Reference returned when the deal was requested
+        deal_reference: String,
+        /// Time waited for the confirmation
+        timeout: Duration,
+    },
     /// Error during serialization or deserialization
     SerializationError(String),
     /// WebSocket communication error
@@ -161,6 +318,21 @@ pub enum AppError {
     ///     that were violated.
     ///
     InvalidInput(String),
+    /// Invalid or incomplete client configuration (e.g. an unreadable certificate)
+    Config(String),
+    /// IG refused an order for a business reason (market closed, stop too close, ...)
+    OrderRejected {
+        /// Typed rejection reason
+        code: OrderRejectCode,
+        /// Error code or reason as reported by IG
+        message: String,
+    },
+    /// The endpoint is not available for this account type or environment.
+    /// Contains a hint on how to proceed.
+    NotSupported(String),
+    /// IG accepted the watchlist request but did not create the watchlist
+    /// (`SUCCESS_NOT_CREATED`). Contains the watchlist name.
+    WatchlistNotCreated(String),
     /// Generic error for cases that don't fit into other specific error categories.
     /// Contains a descriptive error message.
     Generic(String),
@@ -176,12 +348,44 @@ impl Display for AppError {
             AppError::Db(e) => write!(f, "db error: {e}"),
             AppError::Unauthorized => write!(f, "unauthorized"),
             AppError::OAuthTokenExpired => write!(f, "oauth token expired"),
+            AppError::SessionExpired => write!(f, "session expired"),
             AppError::NotFound => write!(f, "not found"),
             AppError::RateLimitExceeded => write!(f, "rate limit exceeded"),
+            AppError::RateLimited { scope, retry_after } => match retry_after {
+                Some(delay) => write!(
+                    f,
+                    "rate limited ({scope} allowance), retry after {}s",
+                    delay.as_secs()
+                ),
+                None => write!(f, "rate limited ({scope} allowance)"),
+            },
+            AppError::AllowanceExceeded { resets_in } => match resets_in {
+                Some(delay) => write!(
+                    f,
+                    "historical price allowance exceeded, resets in {}s",
+                    delay.as_secs()
+                ),
+                None => write!(f, "historical price allowance exceeded"),
+            },
+            AppError::Timeout(s) => write!(f, "timeout: {s}"),
+            AppError::DealConfirmationTimeout {
+                deal_reference,
+                timeout,
+            } => write!(
+                f,
+                "deal {deal_reference} not confirmed within {} ms",
+                timeout.as_millis()
+            ),
             AppError::SerializationError(s) => write!(f, "serialization error: {s}"),
             AppError::WebSocketError(s) => write!(f, "websocket error: {s}"),
             AppError::Deserialization(s) => write!(f, "deserialization error: {s}"),
             AppError::InvalidInput(s) => write!(f, "invalid input: {s}"),
+            AppError::Config(s) => write!(f, "configuration error: {s}"),
+            AppError::OrderRejected { code, message } => {
+                write!(f, "order rejected ({code}): {message}")
+            }
+            AppError::NotSupported(s) => write!(f, "not supported: {s}"),
+            AppError::WatchlistNotCreated(s) => write!(f, "watchlist not created: {s}"),
             AppError::Generic(s) => write!(f, "generic error: {s}"),
         }
     }
@@ -191,7 +395,11 @@ impl std::error::Error for AppError {}
 
 impl From<reqwest::Error> for AppError {
     fn from(e: reqwest::Error) -> Self {
-        AppError::Network(e)
+        if e.is_timeout() {
+            AppError::Timeout(e.to_string())
+        } else {
+            AppError::Network(e)
+        }
     }
 }
 impl From<io::Error> for AppError {
@@ -212,7 +420,7 @@ impl From<sqlx::Error> for AppError {
 impl From<AuthError> for AppError {
     fn from(e: AuthError) -> Self {
         match e {
-            AuthError::Network(e) => AppError::Network(e),
+            AuthError::Network(e) => AppError::from(e),
             AuthError::Io(e) => AppError::Io(e),
             AuthError::Json(e) => AppError::Json(e),
             AuthError::BadCredentials => AppError::Unauthorized,
@@ -522,6 +522,11 @@ pub mod prelude;
 /// Utility functions and helpers
 pub mod utils;
 
+/// Result type alias for IG client operations
+///
+/// This is a convenience type alias that uses `AppError` as the error type
+pub type IgResult<T> = Result<T, error::AppError>;
+
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -4,7 +4,7 @@
    Date: 19/10/25
 ******************************************************************************/
 use crate::application::auth::Session;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
@@ -166,6 +166,38 @@ impl OAuthToken {
     }
 }
 
+/// OAuth (API v3) session tokens with their absolute expiry
+#[derive(Debug, Clone, PartialEq)]
+pub struct OAuthSession {
+    /// Account the tokens are bound to (sent as `IG-ACCOUNT-ID`)
+    pub account_id: String,
+    /// OAuth access token
+    pub access_token: String,
+    /// OAuth refresh token
+    pub refresh_token: String,
+    /// Token type (typically "Bearer")
+    pub token_type: String,
+    /// Instant at which the access token expires
+    pub expires_at: DateTime<Utc>,
+}
+
+impl OAuthSession {
+    /// Checks if the access token is expired or will expire within the margin
+    ///
+    /// # Arguments
+    /// * `margin_seconds` - Safety margin in seconds before actual expiry
+    #[must_use]
+    pub fn is_expired(&self, margin_seconds: i64) -> bool {
+        self.expires_at - chrono::Duration::seconds(margin_seconds) <= Utc::now()
+    }
+
+    /// Gets the value of the `Authorization` header for this session
+    #[must_use]
+    pub fn authorization_header(&self) -> String {
+        format!("Bearer {}", self.access_token)
+    }
+}
+
 /// API v2 session response
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
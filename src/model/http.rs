@@ -6,19 +6,119 @@
 
 use crate::application::auth::{Auth, Session, WebsocketInfo};
 use crate::application::config::Config;
-use crate::application::rate_limiter::RateLimiter;
-use crate::error::AppError;
+use crate::application::rate_limiter::{RateLimiter, RateLimiters, RequestKind};
+use crate::constants::{
+    DEFAULT_HTTP_CONNECT_TIMEOUT_SECS, DEFAULT_HTTP_POOL_IDLE_TIMEOUT_SECS,
+    DEFAULT_HTTP_REQUEST_TIMEOUT_SECS,
+};
+use crate::error::{AppError, OrderRejectCode, RateLimitScope};
+use crate::model::http_observer::{
+    HttpObserver, HttpRequestInfo, HttpResponseInfo, NoopHttpObserver,
+};
 use crate::model::retry::RetryConfig;
+use chrono::{DateTime, Utc};
 use reqwest::Client as HttpInternalClient;
-use reqwest::{Client, Method, Response, StatusCode};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Certificate, Client, Method, Proxy, Response, StatusCode};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
-use std::sync::Arc;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock as StdRwLock};
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{debug, error, warn};
 
 const USER_AGENT: &str = "ig-client/0.6.0";
 
+/// Settings for the underlying reqwest client
+///
+/// Requests exceeding `request_timeout` fail with `AppError::Timeout`.
+///
+/// Proxy precedence: an explicit `https_proxy` wins; otherwise reqwest honours the
+/// `HTTPS_PROXY`/`HTTP_PROXY`/`NO_PROXY` environment variables. Both only apply to
+/// REST requests: lightstreamer-rs exposes no proxy setting, so the streaming
+/// connection always goes direct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpClientConfig {
+    /// Maximum time allowed to establish a connection
+    pub connect_timeout: Duration,
+    /// Maximum time allowed for a whole request, until the body is read
+    pub request_timeout: Duration,
+    /// How long idle pooled connections are kept, `None` to keep them indefinitely
+    pub pool_idle_timeout: Option<Duration>,
+    /// `User-Agent` header sent with every request
+    pub user_agent: String,
+    /// Proxy URL for HTTPS requests (e.g. `http://proxy.corp:3128`), overriding `HTTPS_PROXY`
+    pub https_proxy: Option<String>,
+    /// PEM files with additional trusted root certificates, e.g. a TLS inspection CA
+    pub extra_root_certs: Vec<PathBuf>,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(DEFAULT_HTTP_CONNECT_TIMEOUT_SECS),
+            request_timeout: Duration::from_secs(DEFAULT_HTTP_REQUEST_TIMEOUT_SECS),
+            pool_idle_timeout: Some(Duration::from_secs(DEFAULT_HTTP_POOL_IDLE_TIMEOUT_SECS)),
+            user_agent: USER_AGENT.to_string(),
+            https_proxy: None,
+            extra_root_certs: Vec::new(),
+        }
+    }
+}
+
+impl HttpClientConfig {
+    /// Sets the proxy used for HTTPS requests
+    #[must_use]
+    pub fn with_https_proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.https_proxy = Some(proxy.into());
+        self
+    }
+
+    /// Adds a PEM file of trusted root certificates
+    #[must_use]
+    pub fn with_root_cert(mut self, path: impl Into<PathBuf>) -> Self {
+        self.extra_root_certs.push(path.into());
+        self
+    }
+
+    /// Builds a reqwest client with these settings
+    ///
+    /// # Returns
+    /// * `Ok(reqwest::Client)` - The configured client
+    /// * `Err(AppError::Config)` - If the proxy URL is invalid or a certificate file
+    ///   cannot be read or parsed
+    /// * `Err(AppError)` - If the settings are rejected, e.g. an invalid user agent
+    pub fn build_client(&self) -> Result<HttpInternalClient, AppError> {
+        let mut builder = HttpInternalClient::builder()
+            .user_agent(self.user_agent.as_str())
+            .connect_timeout(self.connect_timeout)
+            .timeout(self.request_timeout)
+            .pool_idle_timeout(self.pool_idle_timeout);
+
+        if let Some(url) = &self.https_proxy {
+            let proxy = Proxy::https(url)
+                .map_err(|e| AppError::Config(format!("invalid https proxy {url}: {e}")))?;
+            builder = builder.proxy(proxy);
+        }
+        for path in &self.extra_root_certs {
+            let pem = std::fs::read(path).map_err(|e| {
+                AppError::Config(format!(
+                    "cannot read root certificate {}: {e}",
+                    path.display()
+                ))
+            })?;
+            let certificate = Certificate::from_pem(&pem).map_err(|e| {
+                AppError::Config(format!("invalid root certificate {}: {e}", path.display()))
+            })?;
+            builder = builder.add_root_certificate(certificate);
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
 /// Simplified client for IG Markets API with automatic authentication
 ///
 /// This client handles all authentication complexity internally, including:
@@ -31,7 +131,8 @@ pub struct HttpClient {
     auth: Arc<Auth>,
     http_client: HttpInternalClient,
     config: Arc<Config>,
-    rate_limiter: Arc<RwLock<RateLimiter>>,
+    rate_limiters: RateLimiters,
+    observer: StdRwLock<Arc<dyn HttpObserver>>,
 }
 
 impl HttpClient {
@@ -47,13 +148,11 @@ impl HttpClient {
         let config = Arc::new(config);
 
         // Create HTTP client and rate limiter first
-        let http_client = HttpInternalClient::builder()
-            .user_agent(USER_AGENT)
-            .build()?;
-        let rate_limiter = Arc::new(RwLock::new(RateLimiter::new(&config.rate_limiter)));
+        let http_client = HttpClientConfig::default().build_client()?;
+        let rate_limiters = RateLimiters::from_config(&config.rate_limiter);
 
         // Create Auth instance
-        let auth = Arc::new(Auth::new(config.clone()));
+        let auth = Arc::new(Auth::with_client(config.clone(), http_client.clone()));
 
         // Perform initial login
         auth.login().await?;
@@ -62,30 +161,100 @@ impl HttpClient {
             auth,
             http_client,
             config,
-            rate_limiter,
+            rate_limiters,
+            observer: StdRwLock::new(Arc::new(NoopHttpObserver)),
         })
     }
 
     /// Creates a new client without performing initial authentication
     pub fn new_lazy(config: Config) -> Self {
+        Self::lazy_with_http_config(config, HttpClientConfig::default())
+            .expect("Failed to create HTTP client")
+    }
+
+    /// Creates a new client with custom connection and timeout settings
+    ///
+    /// The configuration is read from the environment as with `HttpClient::default()`
+    /// and no authentication is performed until the first request.
+    ///
+    /// # Arguments
+    /// * `http_config` - Timeouts and user agent for the underlying reqwest client
+    ///
+    /// # Returns
+    /// * `Ok(HttpClient)` - Client using the given settings for REST and login requests
+    /// * `Err(AppError)` - If the reqwest client cannot be built
+    pub fn with_config(http_config: HttpClientConfig) -> Result<Self, AppError> {
+        Self::lazy_with_http_config(Config::default(), http_config)
+    }
+
+    pub(crate) fn lazy_with_http_config(
+        config: Config,
+        http_config: HttpClientConfig,
+    ) -> Result<Self, AppError> {
         let config = Arc::new(config);
 
         // Create HTTP client and rate limiter first
-        let http_client = HttpInternalClient::builder()
-            .user_agent(USER_AGENT)
-            .build()
-            .expect("Failed to create HTTP client");
-        let rate_limiter = Arc::new(RwLock::new(RateLimiter::new(&config.rate_limiter)));
+        let http_client = http_config.build_client()?;
+        let rate_limiters = RateLimiters::from_config(&config.rate_limiter);
 
         // Create Auth instance
-        let auth = Arc::new(Auth::new(config.clone()));
+        let auth = Arc::new(Auth::with_client(config.clone(), http_client.clone()));
 
-        Self {
+        Ok(Self {
             auth,
             http_client,
             config,
-            rate_limiter,
-        }
+            rate_limiters,
+            observer: StdRwLock::new(Arc::new(NoopHttpObserver)),
+        })
+    }
+
+    /// Replaces the rate limiters applied to REST requests
+    ///
+    /// Every request acquires a permit from the trading or non-trading bucket
+    /// before being sent, depending on the endpoint.
+    ///
+    /// # Arguments
+    /// * `rate_limiters` - Buckets for trading and non-trading requests
+    #[must_use]
+    pub fn with_rate_limiter(mut self, rate_limiters: RateLimiters) -> Self {
+        self.rate_limiters = rate_limiters;
+        self
+    }
+
+    /// Replaces the observer notified of every REST call
+    ///
+    /// # Arguments
+    /// * `observer` - Receives the method, path, status and latency of each call
+    #[must_use]
+    pub fn with_observer(self, observer: Arc<dyn HttpObserver>) -> Self {
+        self.set_observer(observer);
+        self
+    }
+
+    /// Replaces the observer notified of every REST call on a shared client
+    ///
+    /// # Arguments
+    /// * `observer` - Receives the method, path, status and latency of each call
+    pub fn set_observer(&self, observer: Arc<dyn HttpObserver>) {
+        *self.observer.write().unwrap_or_else(|e| e.into_inner()) = observer;
+    }
+
+    /// Sends a request, reporting it and its outcome to the observer
+    async fn send_observed<Fut>(
+        &self,
+        request: HttpRequestInfo,
+        send: Fut,
+    ) -> Result<Response, AppError>
+    where
+        Fut: Future<Output = Result<Response, AppError>>,
+    {
+        let observer = Arc::clone(&*self.observer.read().unwrap_or_else(|e| e.into_inner()));
+        observer.on_request(&request);
+        let started = Instant::now();
+        let result = send.await;
+        observer.on_response(&HttpResponseInfo::new(&request, &result, started.elapsed()));
+        result
     }
 
     /// Gets WebSocket connection information for Lightstreamer
@@ -93,9 +262,22 @@ impl HttpClient {
     /// # Returns
     /// * `WebsocketInfo` containing server endpoint, authentication tokens, and account ID
     pub async fn get_ws_info(&self) -> WebsocketInfo {
+        let limiter = self.rate_limiters.for_kind(RequestKind::NonTrading);
+        limiter.read().await.wait().await;
         self.auth.get_ws_info().await
     }
 
+    /// Gets WebSocket connection information, reporting login failures
+    ///
+    /// # Returns
+    /// * `Ok(WebsocketInfo)` - Server endpoint, authentication tokens and account ID
+    /// * `Err(AppError)` - If the login needed to obtain the tokens fails
+    pub async fn try_get_ws_info(&self) -> Result<WebsocketInfo, AppError> {
+        let limiter = self.rate_limiters.for_kind(RequestKind::NonTrading);
+        limiter.read().await.wait().await;
+        self.auth.try_get_ws_info().await
+    }
+
     /// Makes a GET request
     pub async fn get<T: DeserializeOwned>(
         &self,
@@ -105,6 +287,19 @@ impl HttpClient {
         self.request(Method::GET, path, None::<()>, version).await
     }
 
+    /// Makes a GET request and returns the raw response
+    ///
+    /// Useful when the headers matter more than the body, e.g. to read the
+    /// server `Date` or measure latency.
+    pub async fn get_response(
+        &self,
+        path: &str,
+        version: Option<u8>,
+    ) -> Result<Response, AppError> {
+        self.send_with_reauth(|| self.request_internal(Method::GET, path, &None::<()>, version))
+            .await
+    }
+
     /// Makes a POST request
     pub async fn post<B: Serialize, T: DeserializeOwned>(
         &self,
@@ -153,21 +348,9 @@ impl HttpClient {
         body: B,
         version: Option<u8>,
     ) -> Result<T, AppError> {
-        match self
-            .request_internal_with_delete_method(path, &body, version)
+        let body = &body;
+        self.with_reauth(move || self.request_internal_with_delete_method(path, body, version))
             .await
-        {
-            Ok(response) => self.parse_response(response).await,
-            Err(AppError::OAuthTokenExpired) => {
-                warn!("OAuth token expired, refreshing and retrying");
-                self.auth.refresh_token().await?;
-                let response = self
-                    .request_internal_with_delete_method(path, &body, version)
-                    .await?;
-                self.parse_response(response).await
-            }
-            Err(e) => Err(e),
-        }
     }
 
     /// Makes a request with custom API version
@@ -178,21 +361,61 @@ impl HttpClient {
         body: Option<B>,
         version: Option<u8>,
     ) -> Result<T, AppError> {
-        match self
-            .request_internal(method.clone(), path, &body, version)
+        let body = &body;
+        self.with_reauth(move || self.request_internal(method.clone(), path, body, version))
             .await
-        {
-            Ok(response) => self.parse_response(response).await,
-            Err(AppError::OAuthTokenExpired) => {
-                warn!("OAuth token expired, refreshing and retrying");
-                self.auth.refresh_token().await?;
-                let response = self.request_internal(method, path, &body, version).await?;
-                self.parse_response(response).await
+    }
+
+    /// Runs a request, re-authenticating and replaying it once if IG rejects the tokens
+    ///
+    /// OAuth sessions are refreshed, CST/X-SECURITY-TOKEN sessions are logged in again.
+    /// The request is replayed at most once per call; if re-authentication fails the
+    /// original error is returned.
+    async fn with_reauth<T, F, Fut>(&self, send: F) -> Result<T, AppError>
+    where
+        T: DeserializeOwned,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<Response, AppError>>,
+    {
+        let response = self.send_with_reauth(send).await?;
+        self.parse_response(response).await
+    }
+
+    /// Sends a request with the same re-authentication rules as `with_reauth`,
+    /// returning the raw response
+    async fn send_with_reauth<F, Fut>(&self, mut send: F) -> Result<Response, AppError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<Response, AppError>>,
+    {
+        match send().await {
+            Ok(response) => Ok(response),
+            Err(e @ (AppError::OAuthTokenExpired | AppError::SessionExpired)) => {
+                warn!("{}, re-authenticating and retrying", e);
+                if let Err(reauth_error) = self.reauthenticate().await {
+                    error!("Re-authentication failed: {}", reauth_error);
+                    return Err(e);
+                }
+                send().await
             }
             Err(e) => Err(e),
         }
     }
 
+    /// Obtains fresh tokens for the current session type
+    async fn reauthenticate(&self) -> Result<Session, AppError> {
+        let is_oauth = self
+            .auth
+            .current_session()
+            .await
+            .is_some_and(|session| session.is_oauth());
+        if is_oauth {
+            self.auth.refresh_oauth().await
+        } else {
+            self.auth.login().await
+        }
+    }
+
     /// Internal method to make HTTP requests
     async fn request_internal<B: Serialize>(
         &self,
@@ -236,14 +459,18 @@ impl HttpClient {
             headers.push(("X-SECURITY-TOKEN", x_security_token.as_str()));
         }
 
-        make_http_request(
-            &self.http_client,
-            self.rate_limiter.clone(),
-            method,
-            &url,
-            headers,
-            body,
-            RetryConfig::infinite(),
+        let request = HttpRequestInfo::new(method.clone(), path, version.unwrap_or(1), &headers);
+        self.send_observed(
+            request,
+            make_http_request(
+                &self.http_client,
+                self.rate_limiters.for_request(&method, path),
+                method,
+                &url,
+                headers,
+                body,
+                RetryConfig::infinite(),
+            ),
         )
         .await
     }
@@ -293,14 +520,18 @@ impl HttpClient {
             headers.push(("X-SECURITY-TOKEN", x_security_token.as_str()));
         }
 
-        make_http_request(
-            &self.http_client,
-            self.rate_limiter.clone(),
-            Method::POST, // Always POST for this method
-            &url,
-            headers,
-            &Some(body),
-            RetryConfig::infinite(),
+        let request = HttpRequestInfo::new(Method::POST, path, version.unwrap_or(1), &headers);
+        self.send_observed(
+            request,
+            make_http_request(
+                &self.http_client,
+                self.rate_limiters.for_request(&Method::DELETE, path),
+                Method::POST, // Always POST for this method
+                &url,
+                headers,
+                &Some(body),
+                RetryConfig::infinite(),
+            ),
         )
         .await
     }
@@ -322,6 +553,18 @@ impl HttpClient {
         Ok(())
     }
 
+    /// Gets the account id sent in the `IG-ACCOUNT-ID` header
+    ///
+    /// # Returns
+    /// The account of the stored session, or an empty string before login
+    pub async fn active_account_id(&self) -> String {
+        self.auth
+            .current_session()
+            .await
+            .map(|session| session.account_id)
+            .unwrap_or_default()
+    }
+
     /// Gets the current session
     pub async fn get_session(&self) -> Result<Session, AppError> {
         self.auth.get_session().await
@@ -336,6 +579,16 @@ impl HttpClient {
     pub fn auth(&self) -> &Auth {
         &self.auth
     }
+
+    /// Gets the configuration the client was created with
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Gets the rate limiters applied to REST requests
+    pub fn rate_limiters(&self) -> &RateLimiters {
+        &self.rate_limiters
+    }
 }
 
 impl Default for HttpClient {
@@ -363,7 +616,9 @@ impl Default for HttpClient {
 /// # Returns
 ///
 /// * `Ok(Response)` - Successful HTTP response
-/// * `Err(AppError)` - Error if request fails (excluding rate limit errors which are retried)
+/// * `Err(AppError)` - Error if request fails (excluding rate limit errors which are retried).
+///   The weekly historical data allowance is not retried: exceeding it fails at once
+///   with `AppError::RateLimited`
 ///
 /// # Example
 ///
@@ -469,13 +724,25 @@ pub async fn make_http_request<B: Serialize>(
         }
 
         match status {
-            StatusCode::FORBIDDEN => {
+            StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => {
+                let retry_after = response
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(parse_retry_after);
                 let body_text = response.text().await.unwrap_or_default();
-                if body_text.contains("exceeded-api-key-allowance")
-                    || body_text.contains("exceeded-account-allowance")
-                    || body_text.contains("exceeded-account-trading-allowance")
-                    || body_text.contains("exceeded-account-historical-data-allowance")
-                {
+                let scope = RateLimitScope::from_error_code(&body_text)
+                    .or((status == StatusCode::TOO_MANY_REQUESTS)
+                        .then_some(RateLimitScope::Unknown));
+                if scope == Some(RateLimitScope::HistoricalData) {
+                    // The weekly allowance does not come back within a retry delay
+                    error!("Historical data allowance exceeded: {}", body_text);
+                    return Err(AppError::RateLimited {
+                        scope: RateLimitScope::HistoricalData,
+                        retry_after,
+                    });
+                }
+                if let Some(scope) = scope {
                     retry_count += 1;
 
                     // Check if we've exceeded max retries (0 = infinite)
@@ -485,14 +752,18 @@ pub async fn make_http_request<B: Serialize>(
                             retry_count - 1,
                             max_retries
                         );
-                        return Err(AppError::RateLimitExceeded);
+                        return Err(AppError::RateLimited { scope, retry_after });
                     }
 
+                    let wait = retry_after.unwrap_or(Duration::from_secs(delay_secs));
                     warn!(
-                        "Rate limit exceeded (attempt {}): {}. Waiting {} seconds before retry...",
-                        retry_count, body_text, delay_secs
+                        "Rate limit exceeded (attempt {}, {} allowance): {}. Waiting {} seconds before retry...",
+                        retry_count,
+                        scope,
+                        body_text,
+                        wait.as_secs()
                     );
-                    tokio::time::sleep(tokio::time::Duration::from_secs(delay_secs)).await;
+                    tokio::time::sleep(wait).await;
                     continue; // Retry the request
                 }
                 error!("Forbidden: {}", body_text);
@@ -500,17 +771,94 @@ pub async fn make_http_request<B: Serialize>(
             }
             StatusCode::UNAUTHORIZED => {
                 let body_text = response.text().await.unwrap_or_default();
-                if body_text.contains("oauth-token-invalid") {
-                    return Err(AppError::OAuthTokenExpired);
+                let error = unauthorized_error(&body_text);
+                if matches!(error, AppError::Unauthorized) {
+                    error!("Unauthorized: {}", body_text);
                 }
-                error!("Unauthorized: {}", body_text);
-                return Err(AppError::Unauthorized);
+                return Err(error);
             }
             _ => {
                 let body = response.text().await.unwrap_or_default();
                 error!("Request failed with status {}: {}", status, body);
+                if status.is_client_error()
+                    && let Some(rejection) = order_rejection(url, &body)
+                {
+                    return Err(rejection);
+                }
                 return Err(AppError::Unexpected(status));
             }
         }
     }
 }
+
+/// Maps the body of a failed dealing request to `AppError::OrderRejected`
+///
+/// Only requests to the position and working order endpoints are considered, so
+/// that error codes of other endpoints keep surfacing as `AppError::Unexpected`.
+///
+/// # Arguments
+/// * `url` - URL of the request
+/// * `body_text` - Body of the error response, e.g. `{"errorCode":"error.trading.market-closed"}`
+///
+/// # Returns
+/// The rejection, or `None` if the request is not a dealing request or the body
+/// carries no `errorCode`
+#[must_use]
+pub fn order_rejection(url: &str, body_text: &str) -> Option<AppError> {
+    if !(url.contains("positions/otc") || url.contains("workingorders/otc")) {
+        return None;
+    }
+    let body: serde_json::Value = serde_json::from_str(body_text).ok()?;
+    let error_code = body.get("errorCode")?.as_str()?;
+    Some(AppError::OrderRejected {
+        code: OrderRejectCode::from_code(error_code),
+        message: error_code.to_string(),
+    })
+}
+
+/// Maps the body of a 401 response to the matching error
+///
+/// Rejected OAuth tokens map to `AppError::OAuthTokenExpired` and rejected
+/// CST/X-SECURITY-TOKEN pairs to `AppError::SessionExpired`, both of which are
+/// recovered by re-authenticating. Any other body maps to `AppError::Unauthorized`.
+///
+/// # Arguments
+/// * `body_text` - Body of the 401 response
+#[must_use]
+pub fn unauthorized_error(body_text: &str) -> AppError {
+    if body_text.contains("oauth-token-invalid")
+        || body_text.contains("error.security.token-invalid")
+    {
+        AppError::OAuthTokenExpired
+    } else if body_text.contains("client-token-invalid") {
+        AppError::SessionExpired
+    } else {
+        AppError::Unauthorized
+    }
+}
+
+/// Parses a `Retry-After` header expressed in seconds
+///
+/// # Arguments
+/// * `value` - Header value, e.g. `"30"`
+///
+/// # Returns
+/// The delay, or `None` if the value is not a number of seconds
+#[must_use]
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Parses an HTTP `Date` header, e.g. `"Sun, 06 Nov 1994 08:49:37 GMT"`
+///
+/// # Arguments
+/// * `value` - Header value in RFC 2822 / IMF-fixdate format
+///
+/// # Returns
+/// The date in UTC, or `None` if the value cannot be parsed
+#[must_use]
+pub fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
@@ -0,0 +1,152 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! REST request observation
+//!
+//! `HttpClient` reports every REST call it sends to an `HttpObserver`: method, path,
+//! API version and headers before the call, status and latency after it. Credentials
+//! never reach the observer: the API key and session token headers are redacted.
+
+use crate::error::AppError;
+use reqwest::{Method, Response, StatusCode};
+use std::time::Duration;
+
+/// Headers whose values are replaced by [`REDACTED`] before reaching an observer
+pub const SENSITIVE_HEADERS: &[&str] =
+    &["X-IG-API-KEY", "Authorization", "CST", "X-SECURITY-TOKEN"];
+
+/// Placeholder for redacted header values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// REST call about to be sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequestInfo {
+    /// HTTP method actually sent (closing positions is a POST with `_method: DELETE`)
+    pub method: Method,
+    /// Endpoint path relative to the REST base URL (e.g. `positions/otc`)
+    pub path: String,
+    /// IG API version header
+    pub version: u8,
+    /// Request headers, with credentials redacted
+    pub headers: Vec<(String, String)>,
+}
+
+impl HttpRequestInfo {
+    /// Describes a request, redacting its sensitive headers
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - HTTP method
+    /// * `path` - Endpoint path
+    /// * `version` - IG API version
+    /// * `headers` - Headers as sent
+    #[must_use]
+    pub fn new(method: Method, path: &str, version: u8, headers: &[(&str, &str)]) -> Self {
+        Self {
+            method,
+            path: path.trim_start_matches('/').to_string(),
+            version,
+            headers: redact_headers(headers),
+        }
+    }
+}
+
+/// Outcome of a REST call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponseInfo {
+    /// HTTP method of the request
+    pub method: Method,
+    /// Endpoint path of the request
+    pub path: String,
+    /// Response status, `None` if no response was received
+    pub status: Option<StatusCode>,
+    /// Time until the response headers or the error, including rate limiter waits
+    /// and rate limit retries
+    pub latency: Duration,
+    /// Error reported for the call, if any
+    pub error: Option<String>,
+}
+
+impl HttpResponseInfo {
+    /// Describes the outcome of a request
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The observed request
+    /// * `result` - Response or error returned for it
+    /// * `latency` - Time the call took
+    #[must_use]
+    pub fn new(
+        request: &HttpRequestInfo,
+        result: &Result<Response, AppError>,
+        latency: Duration,
+    ) -> Self {
+        let (status, error) = match result {
+            Ok(response) => (Some(response.status()), None),
+            Err(AppError::Unexpected(status)) => (Some(*status), Some(status.to_string())),
+            Err(
+                e @ (AppError::Unauthorized
+                | AppError::OAuthTokenExpired
+                | AppError::SessionExpired),
+            ) => (Some(StatusCode::UNAUTHORIZED), Some(e.to_string())),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        Self {
+            method: request.method.clone(),
+            path: request.path.clone(),
+            status,
+            latency,
+            error,
+        }
+    }
+
+    /// Checks whether the call returned a 2xx status
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.status.is_some_and(|status| status.is_success())
+    }
+}
+
+/// Receives every REST call sent by `HttpClient`
+///
+/// Both methods default to doing nothing. They are called inline, on the request
+/// path, so implementations should be quick and must not block.
+pub trait HttpObserver: Send + Sync {
+    /// Called before a request is sent
+    fn on_request(&self, _request: &HttpRequestInfo) {}
+
+    /// Called once a response or an error is received
+    fn on_response(&self, _response: &HttpResponseInfo) {}
+}
+
+/// Observer that ignores every call, used by default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopHttpObserver;
+
+impl HttpObserver for NoopHttpObserver {}
+
+/// Copies headers, replacing the values of [`SENSITIVE_HEADERS`] by [`REDACTED`]
+///
+/// # Arguments
+///
+/// * `headers` - Header names and values
+#[must_use]
+pub fn redact_headers(headers: &[(&str, &str)]) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if SENSITIVE_HEADERS
+                .iter()
+                .any(|sensitive| sensitive.eq_ignore_ascii_case(name))
+            {
+                REDACTED
+            } else {
+                value
+            };
+            (name.to_string(), value.to_string())
+        })
+        .collect()
+}
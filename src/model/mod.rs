@@ -7,6 +7,8 @@
 pub mod auth;
 /// HTTP request utilities with rate limiting and retry
 pub mod http;
+/// Observation hook for the REST requests sent by `HttpClient`
+pub mod http_observer;
 /// Request models for API calls
 pub mod requests;
 /// Response models from API calls
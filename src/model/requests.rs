@@ -3,10 +3,16 @@
    Email: jb@taunais.com
    Date: 19/10/25
 ******************************************************************************/
-use crate::constants::{DEFAULT_ORDER_BUY_LEVEL, DEFAULT_ORDER_SELL_LEVEL};
-use crate::prelude::{Deserialize, Serialize, WorkingOrder};
+use crate::constants::{
+    ACTIVITY_QUERY_DATE_FORMAT, DEFAULT_ORDER_BUY_LEVEL, DEFAULT_ORDER_SELL_LEVEL,
+};
+use crate::error::AppError;
+use crate::prelude::{Deserialize, Position, Serialize, WorkingOrder};
+use crate::presentation::account::ActivityType;
+use crate::presentation::instrument::InstrumentType;
+use crate::presentation::market::{MarketData, MarketState};
 use crate::presentation::order::{Direction, OrderType, TimeInForce};
-use chrono::{Duration, Utc};
+use chrono::{Duration, NaiveDateTime, Utc};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use std::fmt;
 use std::fmt::{Debug, Display};
@@ -39,7 +45,7 @@ impl<'a> RecentPricesRequest<'a> {
         }
     }
 
-    /// Set the resolution
+    /// Set the resolution, e.g. `Resolution::Minute5.as_str()`
     pub fn with_resolution(mut self, resolution: &'a str) -> Self {
         self.resolution = Some(resolution);
         self
@@ -74,6 +80,120 @@ impl<'a> RecentPricesRequest<'a> {
         self.page_number = Some(page_number);
         self
     }
+
+    /// Starts a validating builder for the given epic
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let params = RecentPricesRequest::builder("CS.D.EURUSD.CFD.IP")
+    ///     .resolution(Resolution::Minute5.as_str())
+    ///     .max_points(20)
+    ///     .build()?;
+    /// ```
+    pub fn builder(epic: &'a str) -> RecentPricesRequestBuilder<'a> {
+        RecentPricesRequestBuilder {
+            request: Self::new(epic),
+        }
+    }
+
+    /// Checks the parameters against the combinations IG rejects or ignores
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the request can be sent
+    /// * `Err(AppError::InvalidInput)` - Describing the first invalid combination found
+    pub fn validate(&self) -> Result<(), AppError> {
+        let invalid = |reason: String| Err(AppError::InvalidInput(reason));
+        let parse = |date: &str| {
+            NaiveDateTime::parse_from_str(date, ACTIVITY_QUERY_DATE_FORMAT).map_err(|_| {
+                AppError::InvalidInput(format!("date {date} is not in yyyy-MM-ddTHH:mm:ss format"))
+            })
+        };
+
+        if self.epic.is_empty() {
+            return invalid("epic is required".to_string());
+        }
+        if self.max_points.is_some() && (self.from.is_some() || self.to.is_some()) {
+            return invalid("max points cannot be combined with a from/to date range".to_string());
+        }
+        if let Some(max_points) = self.max_points
+            && max_points <= 0
+        {
+            return invalid(format!("max points must be positive, got {max_points}"));
+        }
+        if let Some(page_size) = self.page_size
+            && page_size < 0
+        {
+            return invalid(format!("page size cannot be negative, got {page_size}"));
+        }
+        if let Some(page_number) = self.page_number
+            && page_number < 1
+        {
+            return invalid(format!("page number starts at 1, got {page_number}"));
+        }
+        let from = self.from.map(parse).transpose()?;
+        let to = self.to.map(parse).transpose()?;
+        if let (Some(from), Some(to)) = (from, to)
+            && from > to
+        {
+            return invalid(format!("from {from} is after to {to}"));
+        }
+        Ok(())
+    }
+}
+
+/// Builder for [`RecentPricesRequest`] that validates the parameters on `build`
+#[derive(Debug, Clone)]
+pub struct RecentPricesRequestBuilder<'a> {
+    request: RecentPricesRequest<'a>,
+}
+
+impl<'a> RecentPricesRequestBuilder<'a> {
+    /// Sets the resolution, e.g. `Resolution::Minute5.as_str()`
+    pub fn resolution(mut self, resolution: &'a str) -> Self {
+        self.request.resolution = Some(resolution);
+        self
+    }
+
+    /// Sets the start date (yyyy-MM-ddTHH:mm:ss)
+    pub fn from(mut self, from: &'a str) -> Self {
+        self.request.from = Some(from);
+        self
+    }
+
+    /// Sets the end date (yyyy-MM-ddTHH:mm:ss)
+    pub fn to(mut self, to: &'a str) -> Self {
+        self.request.to = Some(to);
+        self
+    }
+
+    /// Sets the maximum number of price points; not allowed with a date range
+    pub fn max_points(mut self, max_points: i32) -> Self {
+        self.request.max_points = Some(max_points);
+        self
+    }
+
+    /// Sets the page size (0 disables paging)
+    pub fn page_size(mut self, page_size: i32) -> Self {
+        self.request.page_size = Some(page_size);
+        self
+    }
+
+    /// Sets the page number, starting at 1
+    pub fn page_number(mut self, page_number: i32) -> Self {
+        self.request.page_number = Some(page_number);
+        self
+    }
+
+    /// Validates the parameters and builds the request
+    ///
+    /// # Returns
+    /// * `Ok(RecentPricesRequest)` - The validated request
+    /// * `Err(AppError::InvalidInput)` - See [`RecentPricesRequest::validate`]
+    pub fn build(self) -> Result<RecentPricesRequest<'a>, AppError> {
+        self.request.validate()?;
+        Ok(self.request)
+    }
 }
 
 impl Display for RecentPricesRequest<'_> {
@@ -91,6 +211,218 @@ impl Debug for RecentPricesRequest<'_> {
     }
 }
 
+/// Level of detail requested from the market details endpoint
+#[derive(Debug, Clone, Copy, DisplaySimple, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MarketDetailFilter {
+    /// Full market details (instrument, dealing rules and snapshot)
+    #[default]
+    All,
+    /// Only the market snapshot, cheaper for latency-sensitive callers
+    SnapshotOnly,
+}
+
+impl MarketDetailFilter {
+    /// Gets the value sent in the `filter` query parameter
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketDetailFilter::All => "ALL",
+            MarketDetailFilter::SnapshotOnly => "SNAPSHOT_ONLY",
+        }
+    }
+
+    /// Builds the market details path for an EPIC with this filter
+    ///
+    /// # Arguments
+    /// * `epic` - Instrument epic
+    ///
+    /// # Returns
+    /// The relative path including the `filter` query parameter
+    #[must_use]
+    pub fn market_details_path(&self, epic: &str) -> String {
+        format!("markets/{}?filter={}", epic, self.as_str())
+    }
+}
+
+/// Local filter applied to market search results
+///
+/// IG's search endpoint is fuzzy and does not accept filters, so every criterion is
+/// applied after the results arrive. Unset criteria match everything.
+///
+/// # Example
+/// ```ignore
+/// let filter = MarketSearchFilter::new()
+///     .epic_prefix("OP.D.OTCDAX1.")
+///     .tradeable_only(true);
+/// let options = client.search_markets_filtered("DAX", filter).await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketSearchFilter {
+    /// Keep only markets of this instrument type
+    pub instrument_type: Option<InstrumentType>,
+    /// Keep only markets with this expiry (e.g. `DFB`, `DEC-25`), case-insensitively
+    pub expiry: Option<String>,
+    /// Keep only markets currently open for new positions
+    pub tradeable_only: bool,
+    /// Keep only markets whose epic starts with this prefix
+    pub epic_prefix: Option<String>,
+}
+
+impl MarketSearchFilter {
+    /// Creates a filter that keeps every market
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps only markets of the given instrument type
+    #[must_use]
+    pub fn instrument_type(mut self, instrument_type: InstrumentType) -> Self {
+        self.instrument_type = Some(instrument_type);
+        self
+    }
+
+    /// Keeps only markets with the given expiry
+    #[must_use]
+    pub fn expiry(mut self, expiry: impl Into<String>) -> Self {
+        self.expiry = Some(expiry.into());
+        self
+    }
+
+    /// Keeps only markets whose status is `TRADEABLE`
+    #[must_use]
+    pub fn tradeable_only(mut self, tradeable_only: bool) -> Self {
+        self.tradeable_only = tradeable_only;
+        self
+    }
+
+    /// Keeps only markets whose epic starts with `prefix`
+    #[must_use]
+    pub fn epic_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.epic_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Checks whether a market passes every criterion
+    #[must_use]
+    pub fn matches(&self, market: &MarketData) -> bool {
+        self.instrument_type
+            .as_ref()
+            .is_none_or(|instrument_type| market.instrument_type == *instrument_type)
+            && self
+                .expiry
+                .as_deref()
+                .is_none_or(|expiry| market.expiry.eq_ignore_ascii_case(expiry))
+            && (!self.tradeable_only
+                || MarketState::from_status_str(&market.market_status).is_tradeable())
+            && self
+                .epic_prefix
+                .as_deref()
+                .is_none_or(|prefix| market.epic.starts_with(prefix))
+    }
+}
+
+/// Builder for FIQL filters accepted by the activity history endpoint
+///
+/// Clauses are combined with `;` (logical AND), e.g.
+/// `epic==IX.D.DAX.*;dealId==DIAAAAA`. A `*` in a value acts as a wildcard.
+///
+/// # Example
+/// ```ignore
+/// let filter = ActivityFilter::new()
+///     .epic("IX.D.DAX.*")
+///     .activity_type(ActivityType::Position);
+/// let activity = client.get_activity_filtered(from, to, &filter).await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActivityFilter {
+    clauses: Vec<String>,
+}
+
+impl ActivityFilter {
+    /// Creates an empty filter
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the activity to an EPIC, wildcards allowed
+    #[must_use]
+    pub fn epic(self, epic: &str) -> Self {
+        self.clause("epic", epic)
+    }
+
+    /// Restricts the activity to a deal id
+    #[must_use]
+    pub fn deal_id(self, deal_id: &str) -> Self {
+        self.clause("dealId", deal_id)
+    }
+
+    /// Restricts the activity to a type
+    #[must_use]
+    pub fn activity_type(self, activity_type: ActivityType) -> Self {
+        let value = serde_json::to_value(activity_type)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        self.clause("type", &value)
+    }
+
+    fn clause(mut self, field: &str, value: &str) -> Self {
+        self.clauses.push(format!("{}=={}", field, value));
+        self
+    }
+
+    /// Checks whether no clause has been added
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    /// Gets the FIQL expression, unencoded
+    #[must_use]
+    pub fn to_fiql(&self) -> String {
+        self.clauses.join(";")
+    }
+
+    /// Gets the FIQL expression percent-encoded for use as a query parameter value
+    #[must_use]
+    pub fn to_query_value(&self) -> String {
+        percent_encode(&self.to_fiql())
+    }
+}
+
+/// Percent-encodes everything outside the RFC 3986 unreserved set
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Model for creating a new watchlist
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, Default)]
+pub struct CreateWatchlistRequest {
+    /// Watchlist name
+    pub name: String,
+    /// EPICs of the markets to add to the watchlist
+    pub epics: Vec<String>,
+}
+
+/// Model for adding a market to an existing watchlist
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, Default)]
+pub struct AddToWatchlistRequest {
+    /// EPIC of the market to add
+    pub epic: String,
+}
+
 /// Model for creating a new order
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct CreateOrderRequest {
@@ -508,16 +840,76 @@ impl CreateOrderRequest {
     }
 
     /// Adds a limit distance to the order
+    ///
+    /// IG accepts either a limit level or a limit distance, not both.
     pub fn with_limit_distance(mut self, limit_distance: f64) -> Self {
         self.limit_distance = Some(limit_distance);
         self
     }
 
-    /// Adds a guaranteed stop to the order
-    pub fn with_guaranteed_stop(mut self, guaranteed: bool) -> Self {
-        self.guaranteed_stop = guaranteed;
+    /// Adds a guaranteed stop at `distance` points from the opening level
+    ///
+    /// Sets `guaranteedStop` and `stopDistance` together. A guaranteed stop cannot
+    /// be combined with a trailing stop.
+    pub fn with_guaranteed_stop(mut self, distance: f64) -> Self {
+        self.guaranteed_stop = true;
+        self.stop_distance = Some(distance);
         self
     }
+
+    /// Adds a trailing stop at `distance` points that moves in steps of `increment`
+    ///
+    /// Sets `trailingStop`, `stopDistance` and `trailingStopIncrement` together.
+    pub fn with_trailing_stop(mut self, distance: f64, increment: f64) -> Self {
+        self.trailing_stop = Some(true);
+        self.stop_distance = Some(distance);
+        self.trailing_stop_increment = Some(increment);
+        self
+    }
+
+    /// Checks the order against the field combinations IG rejects
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the order can be sent
+    /// * `Err(AppError::InvalidInput)` - Describing the first invalid combination found
+    pub fn validate(&self) -> Result<(), AppError> {
+        let invalid = |reason: &str| Err(AppError::InvalidInput(reason.to_string()));
+        let trailing = self.trailing_stop.unwrap_or(false);
+        let has_stop = self.stop_level.is_some() || self.stop_distance.is_some();
+        let has_limit = self.limit_level.is_some() || self.limit_distance.is_some();
+
+        if self.size <= 0.0 {
+            return invalid("size must be positive");
+        }
+        if self.order_type == OrderType::Limit && self.level.is_none() {
+            return invalid("limit orders require a level");
+        }
+        if self.stop_level.is_some() && self.stop_distance.is_some() {
+            return invalid("set either stopLevel or stopDistance, not both");
+        }
+        if self.limit_level.is_some() && self.limit_distance.is_some() {
+            return invalid("set either limitLevel or limitDistance, not both");
+        }
+        if self.guaranteed_stop && trailing {
+            return invalid("a guaranteed stop cannot be a trailing stop");
+        }
+        if self.guaranteed_stop && !has_stop {
+            return invalid("a guaranteed stop requires stopLevel or stopDistance");
+        }
+        if trailing && (self.stop_distance.is_none() || self.trailing_stop_increment.is_none()) {
+            return invalid("a trailing stop requires stopDistance and trailingStopIncrement");
+        }
+        if trailing && self.stop_level.is_some() {
+            return invalid("a trailing stop is set with stopDistance, not stopLevel");
+        }
+        if !trailing && self.trailing_stop_increment.is_some() {
+            return invalid("trailingStopIncrement is only allowed with a trailing stop");
+        }
+        if (has_stop || has_limit) && !self.force_open {
+            return invalid("stops and limits require forceOpen");
+        }
+        Ok(())
+    }
 }
 
 /// Model for updating an existing position (PUT /positions/otc/{dealId})
@@ -556,6 +948,23 @@ pub struct UpdatePositionRequest {
     pub trailing_stop_increment: Option<f64>,
 }
 
+impl UpdatePositionRequest {
+    /// Creates a request that only sets the take profit level
+    ///
+    /// Stop and trailing fields are left unset. With `None` the `limitLevel`
+    /// field is omitted, which removes the existing limit from the position.
+    ///
+    /// # Arguments
+    /// * `limit_level` - New take profit level, or `None` to clear it
+    #[must_use]
+    pub fn limit_only(limit_level: Option<f64>) -> Self {
+        Self {
+            limit_level,
+            ..Default::default()
+        }
+    }
+}
+
 /// Model for closing an existing position
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct ClosePositionRequest {
@@ -687,6 +1096,18 @@ impl ClosePositionRequest {
     }
 }
 
+impl From<&Position> for ClosePositionRequest {
+    /// Builds a market close request for the full size of an open position,
+    /// selling to close buys and buying to close sells
+    fn from(value: &Position) -> Self {
+        Self::market(
+            value.position.deal_id.clone(),
+            value.position.direction.opposite(),
+            value.position.size,
+        )
+    }
+}
+
 /// Model for creating a new working order
 #[derive(DebugPretty, DisplaySimple, Clone, Deserialize, Serialize, Default)]
 pub struct CreateWorkingOrderRequest {
@@ -896,3 +1317,123 @@ impl CreateWorkingOrderRequest {
         self
     }
 }
+
+/// Model for amending an existing working order
+///
+/// Only the fields that are set are sent; at least one must be set. Build it with
+/// `UpdateWorkingOrderRequest::new()` and the `with_*` methods.
+#[derive(DebugPretty, DisplaySimple, Clone, Deserialize, Serialize, Default)]
+pub struct UpdateWorkingOrderRequest {
+    /// New price level for the order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<f64>,
+    /// New type of working order (LIMIT or STOP)
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub order_type: Option<OrderType>,
+    /// New order duration
+    #[serde(rename = "timeInForce", skip_serializing_if = "Option::is_none")]
+    pub time_in_force: Option<TimeInForce>,
+    /// Whether to use a guaranteed stop
+    #[serde(rename = "guaranteedStop", skip_serializing_if = "Option::is_none")]
+    pub guaranteed_stop: Option<bool>,
+    /// New price level for stop loss
+    #[serde(rename = "stopLevel", skip_serializing_if = "Option::is_none")]
+    pub stop_level: Option<f64>,
+    /// New distance for stop loss
+    #[serde(rename = "stopDistance", skip_serializing_if = "Option::is_none")]
+    pub stop_distance: Option<f64>,
+    /// New price level for take profit
+    #[serde(rename = "limitLevel", skip_serializing_if = "Option::is_none")]
+    pub limit_level: Option<f64>,
+    /// New distance for take profit
+    #[serde(rename = "limitDistance", skip_serializing_if = "Option::is_none")]
+    pub limit_distance: Option<f64>,
+    /// New expiry date for GTD orders
+    #[serde(rename = "goodTillDate", skip_serializing_if = "Option::is_none")]
+    pub good_till_date: Option<String>,
+}
+
+impl UpdateWorkingOrderRequest {
+    /// Creates an empty update
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the new price level of the order
+    pub fn with_level(mut self, level: f64) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Sets the new type of the order
+    pub fn with_order_type(mut self, order_type: OrderType) -> Self {
+        self.order_type = Some(order_type);
+        self
+    }
+
+    /// Sets a new stop loss level
+    pub fn with_stop_loss(mut self, stop_level: f64) -> Self {
+        self.stop_level = Some(stop_level);
+        self
+    }
+
+    /// Sets a new stop loss distance in points
+    pub fn with_stop_distance(mut self, stop_distance: f64) -> Self {
+        self.stop_distance = Some(stop_distance);
+        self
+    }
+
+    /// Sets a new take profit level
+    pub fn with_take_profit(mut self, limit_level: f64) -> Self {
+        self.limit_level = Some(limit_level);
+        self
+    }
+
+    /// Sets a new take profit distance in points
+    pub fn with_limit_distance(mut self, limit_distance: f64) -> Self {
+        self.limit_distance = Some(limit_distance);
+        self
+    }
+
+    /// Sets a new expiration date and switches the order to `GoodTillDate`
+    ///
+    /// # Arguments
+    /// * `date` - Expiration date in IG format (`YYYY/MM/DD HH:MM:SS`)
+    pub fn expires_at(mut self, date: String) -> Self {
+        self.time_in_force = Some(TimeInForce::GoodTillDate);
+        self.good_till_date = Some(date);
+        self
+    }
+
+    /// Switches the order to `GoodTillCancelled`, dropping any expiration date
+    pub fn good_till_cancelled(mut self) -> Self {
+        self.time_in_force = Some(TimeInForce::GoodTillCancelled);
+        self.good_till_date = None;
+        self
+    }
+
+    /// Checks that the update changes something
+    ///
+    /// # Returns
+    /// * `Ok(())` - If at least one field is set
+    /// * `Err(AppError::InvalidInput)` - If the update is empty
+    pub fn validate(&self) -> Result<(), AppError> {
+        let any_set = self.level.is_some()
+            || self.order_type.is_some()
+            || self.time_in_force.is_some()
+            || self.guaranteed_stop.is_some()
+            || self.stop_level.is_some()
+            || self.stop_distance.is_some()
+            || self.limit_level.is_some()
+            || self.limit_distance.is_some()
+            || self.good_till_date.is_some();
+        if any_set {
+            Ok(())
+        } else {
+            Err(AppError::InvalidInput(
+                "working order update must set at least one field".to_string(),
+            ))
+        }
+    }
+}
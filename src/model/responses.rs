@@ -3,21 +3,29 @@
    Email: jb@taunais.com
    Date: 19/10/25
 ******************************************************************************/
+use crate::error::{AppError, OrderRejectCode};
 use crate::prelude::{Account, Activity, MarketDetails};
 use crate::presentation::account::{
-    AccountTransaction, ActivityMetadata, Position, TransactionMetadata, WorkingOrder,
+    AccountTransaction, ActivityMetadata, OptionSummary, Position, TransactionMetadata,
+    WorkingOrder,
 };
 use crate::presentation::instrument::InstrumentType;
 use crate::presentation::market::{
     Category, CategoryInstrument, CategoryInstrumentsMetadata, HistoricalPrice, MarketData,
-    MarketNavigationNode, MarketNode, PriceAllowance,
+    MarketNavigationNode, MarketNode, Ohlc, PriceAllowance, PriceSide,
+};
+use crate::presentation::order::{Direction, Status, signed_size};
+use crate::utils::parsing::{
+    deserialize_null_as_empty_vec, deserialize_nullable_status, parse_epic, parse_instrument_name,
 };
-use crate::presentation::order::{Direction, Status};
-use crate::utils::parsing::{deserialize_null_as_empty_vec, deserialize_nullable_status};
 use chrono::{DateTime, Utc};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use tracing::warn;
 
 /// Database entry response for market instruments
 #[derive(
@@ -45,15 +53,10 @@ impl From<MarketNode> for DBEntryResponse {
         let mut entry = DBEntryResponse::default();
         if !value.markets.is_empty() {
             let market = &value.markets[0];
-            entry.symbol = market
-                .epic
-                .split('.')
-                .nth(2)
-                .unwrap_or_default()
-                .to_string();
+            entry.symbol = epic_symbol(&market.epic);
             entry.epic = market.epic.clone();
             entry.name = market.instrument_name.clone();
-            entry.instrument_type = market.instrument_type;
+            entry.instrument_type = market.instrument_type.clone();
             entry.exchange = "IG".to_string();
             entry.expiry = market.expiry.clone();
             entry.last_update = Utc::now();
@@ -65,12 +68,7 @@ impl From<MarketNode> for DBEntryResponse {
 impl From<MarketData> for DBEntryResponse {
     fn from(market: MarketData) -> Self {
         DBEntryResponse {
-            symbol: market
-                .epic
-                .split('.')
-                .nth(2)
-                .unwrap_or_default()
-                .to_string(),
+            symbol: epic_symbol(&market.epic),
             epic: market.epic.clone(),
             name: market.instrument_name.clone(),
             instrument_type: market.instrument_type,
@@ -81,6 +79,13 @@ impl From<MarketData> for DBEntryResponse {
     }
 }
 
+/// Symbol segment of an epic, empty when the epic is malformed
+fn epic_symbol(epic: &str) -> String {
+    parse_epic(epic)
+        .map(|parsed| parsed.symbol)
+        .unwrap_or_default()
+}
+
 impl From<&MarketNode> for DBEntryResponse {
     fn from(value: &MarketNode) -> Self {
         DBEntryResponse::from(value.clone())
@@ -186,6 +191,79 @@ impl HistoricalPricesResponse {
     pub fn iter(&self) -> impl Iterator<Item = &HistoricalPrice> {
         self.prices.iter()
     }
+
+    /// Converts the price points to typed OHLC candles
+    ///
+    /// Price points whose snapshot time cannot be parsed, or that lack a price
+    /// for the requested side, are skipped with a warning.
+    ///
+    /// # Arguments
+    /// * `side` - Side of the quote to use; `Mid` averages bid and ask
+    ///
+    /// # Returns
+    /// Candles in the same order as the price points
+    #[must_use]
+    pub fn to_ohlc(&self, side: PriceSide) -> Vec<Ohlc> {
+        self.prices
+            .iter()
+            .filter_map(|price| {
+                let candle = price.to_ohlc(side);
+                if candle.is_none() {
+                    warn!(
+                        "Skipping historical price at '{}': unparseable time or missing {:?} prices",
+                        price.snapshot_time, side
+                    );
+                }
+                candle
+            })
+            .collect()
+    }
+
+    /// Writes the price points as CSV candles
+    ///
+    /// The output has a `timestamp,open,high,low,close,volume` header followed by
+    /// one row per candle, with RFC3339 timestamps and an empty volume when unknown.
+    /// Price points skipped by [`Self::to_ohlc`] are not written.
+    ///
+    /// # Arguments
+    /// * `w` - Destination writer
+    /// * `side` - Side of the quote to use; `Mid` averages bid and ask
+    ///
+    /// # Returns
+    /// * `Ok(())` - If every row was written and the writer flushed
+    /// * `Err(AppError::Io)` - If writing or flushing fails
+    pub fn write_csv<W: Write>(&self, w: &mut W, side: PriceSide) -> Result<(), AppError> {
+        writeln!(w, "timestamp,open,high,low,close,volume")?;
+        for candle in self.to_ohlc(side) {
+            let volume = candle.volume.map(|v| v.to_string()).unwrap_or_default();
+            writeln!(
+                w,
+                "{},{},{},{},{},{}",
+                candle.timestamp.to_rfc3339(),
+                candle.open,
+                candle.high,
+                candle.low,
+                candle.close,
+                volume
+            )?;
+        }
+        w.flush()?;
+        Ok(())
+    }
+
+    /// Writes the price points as CSV candles to a file
+    ///
+    /// # Arguments
+    /// * `path` - File to create or truncate
+    /// * `side` - Side of the quote to use; `Mid` averages bid and ask
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the file was written
+    /// * `Err(AppError::Io)` - If the file cannot be created or written
+    pub fn write_csv_file<P: AsRef<Path>>(&self, path: P, side: PriceSide) -> Result<(), AppError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_csv(&mut writer, side)
+    }
 }
 
 /// Model for market search results
@@ -243,6 +321,31 @@ pub struct MarketNavigationResponse {
     pub markets: Vec<MarketData>,
 }
 
+/// Statistics of a market hierarchy traversal, as returned by `get_all_markets_verbose`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct MarketHierarchyStats {
+    /// Number of levels below the root that were walked
+    pub levels_processed: usize,
+    /// Number of navigation nodes fetched successfully
+    pub nodes_visited: usize,
+    /// Number of markets collected, including the root level
+    pub markets_found: usize,
+    /// Number of navigation nodes that failed to load and were skipped
+    pub node_errors: usize,
+    /// Number of nodes left unvisited because the depth limit was reached
+    pub unvisited_nodes: usize,
+}
+
+impl MarketHierarchyStats {
+    /// Checks whether the traversal stopped at the depth limit with nodes left
+    ///
+    /// When true, deeper markets may be missing; retry with a larger `max_depth`.
+    #[must_use]
+    pub fn hit_depth_limit(&self) -> bool {
+        self.unvisited_nodes > 0
+    }
+}
+
 /// Response containing all categories of instruments enabled for the IG account
 #[derive(DebugPretty, DisplaySimple, Clone, Deserialize, Serialize, Default)]
 pub struct CategoriesResponse {
@@ -373,6 +476,73 @@ impl PositionsResponse {
 
         epic_map.into_values().collect()
     }
+
+    /// Sums the profit and loss of every position
+    ///
+    /// Uses `Position::pnl`, so positions in different currencies are added as is;
+    /// see [`Self::total_pnl_by_currency`] for a per-currency total.
+    #[must_use]
+    pub fn total_pnl(&self) -> f64 {
+        self.positions.iter().map(Position::pnl).sum()
+    }
+
+    /// Sums the profit and loss of the positions, grouped by position currency
+    ///
+    /// # Returns
+    /// A map from currency code (e.g. `EUR`) to the total PnL in that currency
+    #[must_use]
+    pub fn total_pnl_by_currency(&self) -> HashMap<String, f64> {
+        let mut totals: HashMap<String, f64> = HashMap::new();
+        for position in &self.positions {
+            *totals
+                .entry(position.position.currency.clone())
+                .or_default() += position.pnl();
+        }
+        totals
+    }
+
+    /// Nets the option positions per underlying, separating calls from puts
+    ///
+    /// Positions are first combined by epic with [`Self::compact_by_epic`], then
+    /// grouped by the underlying parsed from the instrument name. Non-option
+    /// positions are ignored.
+    ///
+    /// # Returns
+    /// The net call and put sizes of every underlying, signed by direction
+    #[must_use]
+    pub fn option_summary(&self) -> OptionSummary {
+        let mut summary = OptionSummary::default();
+        for position in Self::compact_by_epic(self.positions.clone()) {
+            let (is_call, is_put) = (position.market.is_call(), position.market.is_put());
+            if !is_call && !is_put {
+                continue;
+            }
+            let signed_size = signed_size(&position.position.direction, position.position.size);
+            let underlying = parse_instrument_name(&position.market.instrument_name).asset_name;
+            let exposure = summary.underlyings.entry(underlying).or_default();
+            if is_call {
+                exposure.net_calls += signed_size;
+                exposure.call_epics += 1;
+            } else {
+                exposure.net_puts += signed_size;
+                exposure.put_epics += 1;
+            }
+        }
+        summary
+    }
+
+    /// Sums the gross exposure of every position, `size * level * contract_size`
+    ///
+    /// Long and short positions both add to the total.
+    #[must_use]
+    pub fn total_exposure(&self) -> f64 {
+        self.positions
+            .iter()
+            .map(|position| {
+                position.position.size * position.position.level * position.position.contract_size
+            })
+            .sum()
+    }
 }
 
 /// Working orders
@@ -392,6 +562,19 @@ pub struct AccountActivityResponse {
     pub metadata: Option<ActivityMetadata>,
 }
 
+impl AccountActivityResponse {
+    /// Gets the request path of the next page, if IG reported one
+    ///
+    /// # Returns
+    /// The `metadata.paging.next` path without its leading slash, or `None` on the last page
+    #[must_use]
+    pub fn next_page_path(&self) -> Option<String> {
+        let next = self.metadata.as_ref()?.paging.as_ref()?.next.as_deref()?;
+        let next = next.trim().trim_start_matches('/');
+        (!next.is_empty()).then(|| next.to_string())
+    }
+}
+
 /// Transaction history
 #[derive(DebugPretty, DisplaySimple, Clone, Deserialize, Serialize)]
 pub struct TransactionHistoryResponse {
@@ -401,6 +584,77 @@ pub struct TransactionHistoryResponse {
     pub metadata: TransactionMetadata,
 }
 
+/// Client sentiment for a single market
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ClientSentimentResponse {
+    /// Market identifier the sentiment refers to
+    #[serde(rename = "marketId")]
+    pub market_id: String,
+    /// Percentage of clients holding long positions
+    #[serde(rename = "longPositionPercentage")]
+    pub long_position_percentage: f64,
+    /// Percentage of clients holding short positions
+    #[serde(rename = "shortPositionPercentage")]
+    pub short_position_percentage: f64,
+}
+
+/// Client sentiment for several markets (related markets or batch request)
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ClientSentimentListResponse {
+    /// Sentiment of each market
+    #[serde(rename = "clientSentiments", default)]
+    pub client_sentiments: Vec<ClientSentimentResponse>,
+}
+
+/// Summary of a watchlist
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Watchlist {
+    /// Watchlist identifier
+    pub id: String,
+    /// Watchlist name
+    pub name: String,
+    /// Whether markets can be added to or removed from the watchlist
+    pub editable: bool,
+    /// Whether the watchlist can be deleted
+    pub deleteable: bool,
+    /// Whether this is one of the default system watchlists
+    #[serde(rename = "defaultSystemWatchlist")]
+    pub default_system_watchlist: bool,
+}
+
+/// Response containing all watchlists of the account
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, Default)]
+pub struct WatchlistsResponse {
+    /// List of watchlists
+    #[serde(default)]
+    pub watchlists: Vec<Watchlist>,
+}
+
+/// Response containing the markets of a watchlist
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, Default)]
+pub struct WatchlistResponse {
+    /// Markets in the watchlist
+    #[serde(default)]
+    pub markets: Vec<MarketData>,
+}
+
+/// Response to watchlist creation
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+pub struct CreateWatchlistResponse {
+    /// Identifier of the new watchlist
+    #[serde(rename = "watchlistId")]
+    pub watchlist_id: Option<String>,
+    /// Creation status (`SUCCESS`, `SUCCESS_NOT_ALL_INSTRUMENTS_ADDED`, `SUCCESS_NOT_CREATED`)
+    pub status: String,
+}
+
+/// Status response to watchlist updates
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+pub struct WatchlistStatusResponse {
+    /// Update status
+    pub status: String,
+}
+
 /// Response to order creation
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct CreateOrderResponse {
@@ -417,6 +671,42 @@ pub struct ClosePositionResponse {
     pub deal_reference: String,
 }
 
+/// Outcome of closing one position in a bulk close
+#[derive(Debug)]
+pub struct ClosePositionOutcome {
+    /// Deal identifier of the position
+    pub deal_id: String,
+    /// Instrument EPIC of the position
+    pub epic: String,
+    /// Close response, or the error returned for this position
+    pub result: Result<ClosePositionResponse, AppError>,
+}
+
+impl ClosePositionOutcome {
+    /// Checks whether the close request was accepted
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Result of one request issued by `get_market_details_batches`
+#[derive(Debug)]
+pub struct MarketDetailsBatchOutcome {
+    /// Epics requested in the batch, in input order
+    pub epics: Vec<String>,
+    /// Details in the order of `epics`, or the error of the request
+    pub result: Result<Vec<MarketDetails>, AppError>,
+}
+
+impl MarketDetailsBatchOutcome {
+    /// Checks whether the batch was fetched
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
 /// Response to updating a position
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct UpdatePositionResponse {
@@ -485,6 +775,105 @@ pub struct OrderConfirmationResponse {
     pub direction: Option<Direction>,
 }
 
+impl OrderConfirmationResponse {
+    /// Checks whether IG accepted the deal
+    ///
+    /// Uses `dealStatus` when present and falls back to `status`.
+    #[must_use]
+    pub fn is_accepted(&self) -> bool {
+        match &self.deal_status {
+            Some(deal_status) => deal_status.eq_ignore_ascii_case("ACCEPTED"),
+            None => self.status == Status::Accepted,
+        }
+    }
+
+    /// Checks whether IG rejected the deal; `reason` explains why
+    #[must_use]
+    pub fn is_rejected(&self) -> bool {
+        match &self.deal_status {
+            Some(deal_status) => deal_status.eq_ignore_ascii_case("REJECTED"),
+            None => self.status == Status::Rejected,
+        }
+    }
+
+    /// Checks whether the confirmation has reached a final outcome
+    #[must_use]
+    pub fn is_terminal(&self) -> bool {
+        self.is_accepted() || self.is_rejected()
+    }
+
+    /// Gets the typed rejection reason when IG rejected the deal
+    #[must_use]
+    pub fn reject_code(&self) -> Option<OrderRejectCode> {
+        if !self.is_rejected() {
+            return None;
+        }
+        Some(OrderRejectCode::from_code(
+            self.reason.as_deref().unwrap_or("UNKNOWN"),
+        ))
+    }
+}
+
+/// Final outcome of an order placed with `place_order_and_confirm`
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+pub struct OrderOutcome {
+    /// Client-generated reference for the deal
+    pub deal_reference: String,
+    /// Deal identifier, present when the deal was accepted
+    pub deal_id: Option<String>,
+    /// `Status::Accepted` or `Status::Rejected`
+    pub status: Status,
+    /// Reason given by IG, e.g. why the deal was rejected
+    pub reason: Option<String>,
+    /// Fill level
+    pub level: Option<f64>,
+    /// Filled size
+    pub size: Option<f64>,
+}
+
+impl OrderOutcome {
+    /// Checks whether the order was accepted
+    #[must_use]
+    pub fn is_accepted(&self) -> bool {
+        self.status == Status::Accepted
+    }
+
+    /// Turns a rejected outcome into `AppError::OrderRejected`
+    ///
+    /// # Returns
+    /// The outcome itself unless it was rejected
+    pub fn into_result(self) -> Result<Self, AppError> {
+        if self.status != Status::Rejected {
+            return Ok(self);
+        }
+        let message = self.reason.unwrap_or_else(|| "UNKNOWN".to_string());
+        Err(AppError::OrderRejected {
+            code: OrderRejectCode::from_code(&message),
+            message,
+        })
+    }
+}
+
+impl From<OrderConfirmationResponse> for OrderOutcome {
+    fn from(confirmation: OrderConfirmationResponse) -> Self {
+        let status = if confirmation.is_accepted() {
+            Status::Accepted
+        } else if confirmation.is_rejected() {
+            Status::Rejected
+        } else {
+            confirmation.status.clone()
+        };
+        Self {
+            deal_reference: confirmation.deal_reference,
+            deal_id: confirmation.deal_id,
+            status,
+            reason: confirmation.reason,
+            level: confirmation.level,
+            size: confirmation.size,
+        }
+    }
+}
+
 impl std::fmt::Display for MultipleMarketDetailsResponse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         use prettytable::format;
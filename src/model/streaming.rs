@@ -12,15 +12,65 @@
 //! - Price data (detailed bid/ask levels)
 //! - Account data (P&L, margin, equity)
 
+use crate::error::AppError;
 use crate::prelude::{Deserialize, Serialize};
+use serde::{Deserializer, de};
 use std::collections::HashSet;
 use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// Finds the field whose Lightstreamer name matches `name`, ignoring case
+fn parse_field_name<T: Debug + Clone>(fields: &[T], name: &str) -> Result<T, AppError> {
+    let name = name.trim();
+    fields
+        .iter()
+        .find(|field| format!("{field:?}").eq_ignore_ascii_case(name))
+        .cloned()
+        .ok_or_else(|| AppError::InvalidInput(format!("unknown streaming field: {name}")))
+}
+
+/// Parses a list of field names, case-insensitively, into a set of fields.
+///
+/// Works for `StreamingMarketField`, `StreamingPriceField` and
+/// `StreamingAccountDataField`, e.g. for field lists loaded from a config file.
+///
+/// # Arguments
+///
+/// * `names` - Field names such as `bid`, `BID` or `Mid_Open`
+///
+/// # Returns
+///
+/// The set of fields, or `AppError::InvalidInput` listing every unknown name.
+pub fn parse_fields<T>(names: &[String]) -> Result<HashSet<T>, AppError>
+where
+    T: FromStr + Eq + Hash,
+{
+    let mut fields = HashSet::with_capacity(names.len());
+    let mut unknown = Vec::new();
+    for name in names {
+        match name.parse::<T>() {
+            Ok(field) => {
+                fields.insert(field);
+            }
+            Err(_) => unknown.push(name.trim()),
+        }
+    }
+    if unknown.is_empty() {
+        Ok(fields)
+    } else {
+        Err(AppError::InvalidInput(format!(
+            "unknown streaming fields: {}",
+            unknown.join(", ")
+        )))
+    }
+}
 
 /// Streaming market fields available for market subscriptions.
 ///
 /// These fields represent the various market data points that can be subscribed to
 /// in the IG Markets streaming API for market updates.
-#[derive(Clone, Deserialize, Serialize, PartialEq, Eq, Default, Hash)]
+#[derive(Clone, Serialize, PartialEq, Eq, Default, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum StreamingMarketField {
     /// Mid open price
@@ -70,6 +120,49 @@ impl Display for StreamingMarketField {
     }
 }
 
+impl FromStr for StreamingMarketField {
+    type Err = AppError;
+
+    /// Parses a Lightstreamer field name, case-insensitively
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        parse_field_name(Self::ALL, name)
+    }
+}
+
+impl<'de> Deserialize<'de> for StreamingMarketField {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(de::Error::custom)
+    }
+}
+
+impl StreamingMarketField {
+    /// Every field, in declaration order
+    pub const ALL: &'static [Self] = &[
+        StreamingMarketField::MidOpen,
+        StreamingMarketField::High,
+        StreamingMarketField::Low,
+        StreamingMarketField::Change,
+        StreamingMarketField::ChangePct,
+        StreamingMarketField::UpdateTime,
+        StreamingMarketField::MarketDelay,
+        StreamingMarketField::MarketState,
+        StreamingMarketField::Bid,
+        StreamingMarketField::Offer,
+    ];
+
+    /// Fields needed to follow the best quote: bid, offer, update time and market state.
+    #[must_use]
+    pub fn top_of_book() -> HashSet<Self> {
+        HashSet::from([
+            StreamingMarketField::Bid,
+            StreamingMarketField::Offer,
+            StreamingMarketField::UpdateTime,
+            StreamingMarketField::MarketState,
+        ])
+    }
+}
+
 /// Constructs a vector of serialized streaming market field names from a given set of `StreamingMarketField`.
 ///
 /// # Arguments
@@ -102,7 +195,7 @@ pub(crate) fn get_streaming_market_fields(fields: &HashSet<StreamingMarketField>
 ///
 /// These fields represent the various price data points that can be subscribed to
 /// in the IG Markets streaming API for price updates.
-#[derive(Clone, Deserialize, Serialize, PartialEq, Eq, Default, Hash)]
+#[derive(Clone, Serialize, PartialEq, Eq, Default, Hash)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum StreamingPriceField {
     /// Mid open price
@@ -373,6 +466,156 @@ impl Display for StreamingPriceField {
     }
 }
 
+impl FromStr for StreamingPriceField {
+    type Err = AppError;
+
+    /// Parses a Lightstreamer field name, case-insensitively
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        parse_field_name(Self::ALL, name)
+    }
+}
+
+impl<'de> Deserialize<'de> for StreamingPriceField {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(de::Error::custom)
+    }
+}
+
+impl StreamingPriceField {
+    /// Every field, in declaration order
+    pub const ALL: &'static [Self] = &[
+        StreamingPriceField::MidOpen,
+        StreamingPriceField::High,
+        StreamingPriceField::Low,
+        StreamingPriceField::BidQuoteId,
+        StreamingPriceField::AskQuoteId,
+        StreamingPriceField::BidPrice1,
+        StreamingPriceField::BidPrice2,
+        StreamingPriceField::BidPrice3,
+        StreamingPriceField::BidPrice4,
+        StreamingPriceField::BidPrice5,
+        StreamingPriceField::AskPrice1,
+        StreamingPriceField::AskPrice2,
+        StreamingPriceField::AskPrice3,
+        StreamingPriceField::AskPrice4,
+        StreamingPriceField::AskPrice5,
+        StreamingPriceField::BidSize1,
+        StreamingPriceField::BidSize2,
+        StreamingPriceField::BidSize3,
+        StreamingPriceField::BidSize4,
+        StreamingPriceField::BidSize5,
+        StreamingPriceField::AskSize1,
+        StreamingPriceField::AskSize2,
+        StreamingPriceField::AskSize3,
+        StreamingPriceField::AskSize4,
+        StreamingPriceField::AskSize5,
+        StreamingPriceField::Currency0,
+        StreamingPriceField::Currency1,
+        StreamingPriceField::C1BidSize1,
+        StreamingPriceField::C1BidSize2,
+        StreamingPriceField::C1BidSize3,
+        StreamingPriceField::C1BidSize4,
+        StreamingPriceField::C1BidSize5,
+        StreamingPriceField::C1AskSize1,
+        StreamingPriceField::C1AskSize2,
+        StreamingPriceField::C1AskSize3,
+        StreamingPriceField::C1AskSize4,
+        StreamingPriceField::C1AskSize5,
+        StreamingPriceField::Currency2,
+        StreamingPriceField::C2BidSize1,
+        StreamingPriceField::C2BidSize2,
+        StreamingPriceField::C2BidSize3,
+        StreamingPriceField::C2BidSize4,
+        StreamingPriceField::C2BidSize5,
+        StreamingPriceField::C2AskSize1,
+        StreamingPriceField::C2AskSize2,
+        StreamingPriceField::C2AskSize3,
+        StreamingPriceField::C2AskSize4,
+        StreamingPriceField::C2AskSize5,
+        StreamingPriceField::Currency3,
+        StreamingPriceField::C3BidSize1,
+        StreamingPriceField::C3BidSize2,
+        StreamingPriceField::C3BidSize3,
+        StreamingPriceField::C3BidSize4,
+        StreamingPriceField::C3BidSize5,
+        StreamingPriceField::C3AskSize1,
+        StreamingPriceField::C3AskSize2,
+        StreamingPriceField::C3AskSize3,
+        StreamingPriceField::C3AskSize4,
+        StreamingPriceField::C3AskSize5,
+        StreamingPriceField::Currency4,
+        StreamingPriceField::C4BidSize1,
+        StreamingPriceField::C4BidSize2,
+        StreamingPriceField::C4BidSize3,
+        StreamingPriceField::C4BidSize4,
+        StreamingPriceField::C4BidSize5,
+        StreamingPriceField::C4AskSize1,
+        StreamingPriceField::C4AskSize2,
+        StreamingPriceField::C4AskSize3,
+        StreamingPriceField::C4AskSize4,
+        StreamingPriceField::C4AskSize5,
+        StreamingPriceField::Currency5,
+        StreamingPriceField::C5BidSize1,
+        StreamingPriceField::C5BidSize2,
+        StreamingPriceField::C5BidSize3,
+        StreamingPriceField::C5BidSize4,
+        StreamingPriceField::C5BidSize5,
+        StreamingPriceField::C5AskSize1,
+        StreamingPriceField::C5AskSize2,
+        StreamingPriceField::C5AskSize3,
+        StreamingPriceField::C5AskSize4,
+        StreamingPriceField::C5AskSize5,
+        StreamingPriceField::Timestamp,
+        StreamingPriceField::DlgFlag,
+    ];
+
+    /// The five bid and ask levels with their sizes, plus timestamp and dealing flag.
+    ///
+    /// Sizes are the ones in the account currency; the per-currency `C{n}` sizes are
+    /// left out.
+    #[must_use]
+    pub fn full_ladder() -> HashSet<Self> {
+        HashSet::from([
+            StreamingPriceField::BidPrice1,
+            StreamingPriceField::BidPrice2,
+            StreamingPriceField::BidPrice3,
+            StreamingPriceField::BidPrice4,
+            StreamingPriceField::BidPrice5,
+            StreamingPriceField::AskPrice1,
+            StreamingPriceField::AskPrice2,
+            StreamingPriceField::AskPrice3,
+            StreamingPriceField::AskPrice4,
+            StreamingPriceField::AskPrice5,
+            StreamingPriceField::BidSize1,
+            StreamingPriceField::BidSize2,
+            StreamingPriceField::BidSize3,
+            StreamingPriceField::BidSize4,
+            StreamingPriceField::BidSize5,
+            StreamingPriceField::AskSize1,
+            StreamingPriceField::AskSize2,
+            StreamingPriceField::AskSize3,
+            StreamingPriceField::AskSize4,
+            StreamingPriceField::AskSize5,
+            StreamingPriceField::Timestamp,
+            StreamingPriceField::DlgFlag,
+        ])
+    }
+
+    /// The first bid and ask level with their sizes, plus timestamp and dealing flag.
+    #[must_use]
+    pub fn best_bid_offer() -> HashSet<Self> {
+        HashSet::from([
+            StreamingPriceField::BidPrice1,
+            StreamingPriceField::AskPrice1,
+            StreamingPriceField::BidSize1,
+            StreamingPriceField::AskSize1,
+            StreamingPriceField::Timestamp,
+            StreamingPriceField::DlgFlag,
+        ])
+    }
+}
+
 /// Constructs a vector of serialized streaming price field names from a given set of `StreamingPriceField`.
 ///
 /// # Arguments
@@ -516,7 +759,7 @@ pub(crate) fn get_streaming_price_fields(fields: &HashSet<StreamingPriceField>)
 ///
 /// These fields represent the various account data points that can be subscribed to
 /// in the IG Markets streaming API for account updates.
-#[derive(Clone, Deserialize, Serialize, PartialEq, Eq, Default, Hash)]
+#[derive(Clone, Serialize, PartialEq, Eq, Default, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum StreamingAccountDataField {
     /// Profit and loss
@@ -572,6 +815,54 @@ impl Display for StreamingAccountDataField {
     }
 }
 
+impl FromStr for StreamingAccountDataField {
+    type Err = AppError;
+
+    /// Parses a Lightstreamer field name, case-insensitively
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        parse_field_name(Self::ALL, name)
+    }
+}
+
+impl<'de> Deserialize<'de> for StreamingAccountDataField {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(de::Error::custom)
+    }
+}
+
+impl StreamingAccountDataField {
+    /// Every field, in declaration order
+    pub const ALL: &'static [Self] = &[
+        StreamingAccountDataField::Pnl,
+        StreamingAccountDataField::Deposit,
+        StreamingAccountDataField::AvailableCash,
+        StreamingAccountDataField::PnlLr,
+        StreamingAccountDataField::PnlNlr,
+        StreamingAccountDataField::Funds,
+        StreamingAccountDataField::Margin,
+        StreamingAccountDataField::MarginLr,
+        StreamingAccountDataField::MarginNlr,
+        StreamingAccountDataField::AvailableToDeal,
+        StreamingAccountDataField::Equity,
+        StreamingAccountDataField::EquityUsed,
+    ];
+
+    /// Fields for monitoring account risk: P&L, funds, margin, equity and the
+    /// amount still available to deal.
+    #[must_use]
+    pub fn risk_set() -> HashSet<Self> {
+        HashSet::from([
+            StreamingAccountDataField::Pnl,
+            StreamingAccountDataField::Funds,
+            StreamingAccountDataField::Margin,
+            StreamingAccountDataField::Equity,
+            StreamingAccountDataField::EquityUsed,
+            StreamingAccountDataField::AvailableToDeal,
+        ])
+    }
+}
+
 /// Constructs a vector of serialized streaming account data field names from a given set of `StreamingAccountDataField`.
 ///
 /// # Arguments
@@ -48,7 +48,12 @@ pub fn build_market_hierarchy<'a>(
                     Err(e) => {
                         error!("Error getting node {}: {:?}", id, e);
                         // If we hit a rate limit, return empty results instead of failing
-                        if matches!(e, AppError::RateLimitExceeded | AppError::Unexpected(_)) {
+                        if matches!(
+                            e,
+                            AppError::RateLimitExceeded
+                                | AppError::RateLimited { .. }
+                                | AppError::Unexpected(_)
+                        ) {
                             info!("Rate limit or API error encountered, returning partial results");
                             return Ok(Vec::new());
                         }
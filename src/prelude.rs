@@ -21,26 +21,37 @@
 pub use crate::application::client::Client;
 
 // HTTP client
-pub use crate::model::http::HttpClient;
+pub use crate::model::http::{HttpClient, HttpClientConfig};
 
 // Authentication
 pub use crate::application::auth::{Auth, Session};
+pub use crate::model::auth::OAuthSession;
 
 // Configuration
 pub use crate::application::config::{
-    Config, Credentials, RateLimiterConfig, RestApiConfig, WebSocketConfig,
+    Config, Credentials, IgEnvironment, RateLimiterConfig, RestApiConfig, WebSocketConfig,
 };
 
 // Rate limiter
-pub use crate::application::rate_limiter::RateLimiter;
+pub use crate::application::rate_limiter::{RateLimiter, RateLimiters, RequestKind};
+
+// Streaming metrics
+pub use crate::application::stream_stats::{ItemStats, StreamStats, StreamStatsRecorder};
+pub use crate::application::streamer_config::StreamerConfig;
+pub use crate::application::subscription_events::{SubscriptionEvent, SubscriptionEventHub};
+
+// Price allowance monitoring
+pub use crate::application::price_allowance::{PriceAllowanceCallback, PriceAllowanceMonitor};
 
 // Service interfaces
 pub use crate::application::interfaces::account::AccountService;
 pub use crate::application::interfaces::listener::ListenerResult;
 pub use crate::application::interfaces::market::MarketService;
 pub use crate::application::interfaces::order::OrderService;
+pub use crate::application::interfaces::watchlist::WatchlistService;
 
 // Error handling
+pub use crate::IgResult;
 pub use crate::error::AppError;
 
 // Common presentation models
@@ -48,7 +59,9 @@ pub use crate::presentation::account::*;
 pub use crate::presentation::chart::*;
 pub use crate::presentation::instrument::*;
 pub use crate::presentation::market::*;
+pub use crate::presentation::option_chain::*;
 pub use crate::presentation::order::*;
+pub use crate::presentation::portfolio::*;
 pub use crate::presentation::trade::*;
 pub use crate::presentation::transaction::*;
 
@@ -69,8 +82,3 @@ pub use crate::presentation::order::{Direction, Status};
 pub use crate::storage::market_database::MarketDatabaseService;
 
 pub use crate::storage::utils::{create_connection_pool, create_database_config_from_env};
-
-/// Result type alias for IG client operations
-///
-/// This is a convenience type alias that uses `AppError` as the error type
-pub type IgResult<T> = Result<T, AppError>;
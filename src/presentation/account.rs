@@ -1,11 +1,13 @@
 use crate::presentation::instrument::InstrumentType;
-use crate::presentation::market::MarketState;
-use crate::presentation::order::{Direction, OrderType, Status, TimeInForce};
+use crate::presentation::market::{MarketSnapshotView, MarketState, apply_scaling};
+use crate::presentation::order::{Direction, OrderType, Status, TimeInForce, signed_size};
 use crate::presentation::serialization::string_as_float_opt;
+use crate::utils::parsing::parse_ig_datetime;
+use chrono::{DateTime, Utc};
 use lightstreamer_rs::subscription::ItemUpdate;
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ops::Add;
 
 /// Account information
@@ -79,6 +81,12 @@ pub enum ActivityType {
     System,
     /// Activity related to working orders
     WorkingOrder,
+    /// Activity type not known to this client
+    ///
+    /// Catches any value IG adds later so the rest of the activity list still
+    /// deserializes.
+    #[serde(other)]
+    Unknown,
 }
 
 /// Individual activity record
@@ -86,6 +94,9 @@ pub enum ActivityType {
 pub struct Activity {
     /// Date and time of the activity
     pub date: String,
+    /// Date and time of the activity in UTC, when IG sends it
+    #[serde(rename = "dateUTC", default)]
+    pub date_utc: Option<String>,
     /// Unique identifier for the deal
     #[serde(rename = "dealId", default)]
     pub deal_id: Option<String>,
@@ -122,6 +133,20 @@ pub struct Activity {
     pub level: Option<String>,
 }
 
+impl Activity {
+    /// Parses the activity time into a UTC timestamp
+    ///
+    /// `dateUTC` is used when IG sends it. Otherwise `date` is read as UTC, which
+    /// only holds for accounts whose local time is UTC.
+    ///
+    /// # Returns
+    /// The activity time, or `None` if the field is in an unknown format
+    #[must_use]
+    pub fn occurred_at_utc(&self) -> Option<DateTime<Utc>> {
+        parse_ig_datetime(self.date_utc.as_deref().unwrap_or(&self.date))
+    }
+}
+
 /// Detailed information about an activity
 /// Only available when using the detailed=true parameter
 #[derive(Debug, Clone, DisplaySimple, Deserialize, Serialize)]
@@ -174,7 +199,7 @@ pub struct ActivityDetails {
 }
 
 /// Types of actions that can be performed on an activity
-#[derive(Debug, Copy, Clone, DisplaySimple, Deserialize, Serialize)]
+#[derive(Debug, Clone, DisplaySimple, Deserialize, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ActionType {
     /// A limit order was deleted
@@ -207,10 +232,11 @@ pub enum ActionType {
     StopOrderOpened,
     /// A stop order was rolled
     StopOrderRolled,
-    /// Unknown action type
-    Unknown,
     /// A working order was deleted
     WorkingOrderDeleted,
+    /// Action type not known to this version of the client, with the value IG sent
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Action associated with an activity
@@ -266,6 +292,11 @@ impl Position {
     /// - `self.position.direction` must be either `Direction::Buy` or
     ///   `Direction::Sell`.
     ///
+    /// # Deprecation
+    ///
+    /// This calculation ignores `contract_size` and `scaling_factor` and is kept for
+    /// backward compatibility only. Use [`Position::net_pnl`] instead.
+    ///
     pub fn pnl(&self) -> f64 {
         if let Some(pnl) = self.pnl {
             pnl
@@ -285,6 +316,35 @@ impl Position {
         }
     }
 
+    /// Calculates the profit and loss of the position in account currency
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Buy:  (bid - level)   * size * contract_size / scaling_factor
+    /// Sell: (level - offer) * size * contract_size / scaling_factor
+    /// ```
+    ///
+    /// A `scaling_factor` of 0 or below is treated as 1, as in
+    /// [`apply_scaling`](crate::presentation::market::apply_scaling).
+    ///
+    /// # Returns
+    ///
+    /// The P&L, or `0.0` when the closing price (bid for Buy, offer for Sell) is
+    /// not available. The cached `pnl` field is not used.
+    #[must_use]
+    pub fn net_pnl(&self) -> f64 {
+        let level = self.position.level;
+        let points = match self.position.direction {
+            Direction::Buy => self.market.bid.map_or(0.0, |bid| bid - level),
+            Direction::Sell => self.market.offer.map_or(0.0, |offer| level - offer),
+        };
+        apply_scaling(
+            points * self.position.size * self.position.contract_size,
+            self.market.scaling_factor,
+        )
+    }
+
     /// Updates the profit and loss (PnL) for the current position in the market.
     ///
     /// The method calculates the PnL based on the position's direction (Buy or Sell),
@@ -381,6 +441,58 @@ impl Add for Position {
     }
 }
 
+/// Net option exposure of one underlying
+///
+/// Sizes are signed by direction: bought options count positive, sold options
+/// negative.
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct OptionExposure {
+    /// Net size of the call positions
+    pub net_calls: f64,
+    /// Net size of the put positions
+    pub net_puts: f64,
+    /// Number of distinct call epics held
+    pub call_epics: usize,
+    /// Number of distinct put epics held
+    pub put_epics: usize,
+}
+
+/// Option positions netted per underlying, as built by `PositionsResponse::option_summary`
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct OptionSummary {
+    /// Exposure keyed by underlying name (e.g. `US 500`)
+    pub underlyings: BTreeMap<String, OptionExposure>,
+}
+
+impl OptionSummary {
+    /// Gets the exposure of an underlying
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying` - Underlying name as parsed from the instrument name
+    #[must_use]
+    pub fn get(&self, underlying: &str) -> Option<&OptionExposure> {
+        self.underlyings.get(underlying)
+    }
+
+    /// Returns the number of underlyings with option positions
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.underlyings.len()
+    }
+
+    /// Returns true if no option position was found
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.underlyings.is_empty()
+    }
+
+    /// Returns an iterator over the underlyings and their exposure, sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &OptionExposure)> {
+        self.underlyings.iter()
+    }
+}
+
 /// Details of a position
 #[derive(DebugPretty, DisplaySimple, Clone, Deserialize, Serialize)]
 pub struct PositionDetails {
@@ -427,31 +539,56 @@ pub struct PositionDetails {
     pub limited_risk_premium: Option<f64>,
 }
 
+impl PositionDetails {
+    /// Parses `createdDateUTC` into a UTC timestamp
+    ///
+    /// # Returns
+    /// The creation time, or `None` if the field is in an unknown format
+    #[must_use]
+    pub fn created_at_utc(&self) -> Option<DateTime<Utc>> {
+        parse_ig_datetime(&self.created_date_utc)
+    }
+}
+
+/// Combines two positions on the same market
+///
+/// - Same direction: sizes and contract sizes are summed and `level` is the
+///   size-weighted average entry price.
+/// - Opposite directions: the positions are netted. Sizes and contract sizes are
+///   the absolute differences, and `direction` and `level` follow the larger
+///   (surviving) side. When both sides have the same size the result is flat and
+///   keeps the left-hand side's direction and level.
 impl Add for PositionDetails {
     type Output = PositionDetails;
 
     fn add(self, other: PositionDetails) -> PositionDetails {
-        let (contract_size, size) = if self.direction != other.direction {
-            (
-                (self.contract_size - other.contract_size).abs(),
-                (self.size - other.size).abs(),
-            )
+        let net_size =
+            signed_size(&self.direction, self.size) + signed_size(&other.direction, other.size);
+        let net_contract_size = signed_size(&self.direction, self.contract_size)
+            + signed_size(&other.direction, other.contract_size);
+        let size = net_size.abs();
+        let (direction, level) = if self.direction == other.direction {
+            let level = if size != 0.0 {
+                (self.level * self.size + other.level * other.size) / size
+            } else {
+                self.level
+            };
+            (self.direction.clone(), level)
+        } else if net_size * other.direction.sign() > 0.0 {
+            (other.direction.clone(), other.level)
         } else {
-            (
-                self.contract_size + other.contract_size,
-                self.size + other.size,
-            )
+            (self.direction.clone(), self.level)
         };
 
         PositionDetails {
-            contract_size,
+            contract_size: net_contract_size.abs(),
             created_date: self.created_date,
             created_date_utc: self.created_date_utc,
             deal_id: self.deal_id,
             deal_reference: self.deal_reference,
-            direction: self.direction,
+            direction,
             limit_level: other.limit_level.or(self.limit_level),
-            level: (self.level + other.level) / 2.0, // Average level
+            level,
             size,
             stop_level: other.stop_level.or(self.stop_level),
             trailing_step: other.trailing_step.or(self.trailing_step),
@@ -514,6 +651,18 @@ pub struct PositionMarket {
 }
 
 impl PositionMarket {
+    /// Checks whether new positions can be opened, based on `market_status`
+    #[must_use]
+    pub fn is_tradeable(&self) -> bool {
+        MarketState::from_status_str(&self.market_status).is_tradeable()
+    }
+
+    /// Checks whether only closing existing positions is allowed, based on `market_status`
+    #[must_use]
+    pub fn is_closing_only(&self) -> bool {
+        MarketState::from_status_str(&self.market_status).is_closing_only()
+    }
+
     /// Checks if the current financial instrument is a call option.
     ///
     /// A call option is a financial derivative that gives the holder the right (but not the obligation)
@@ -546,6 +695,28 @@ impl PositionMarket {
     }
 }
 
+impl MarketSnapshotView for PositionMarket {
+    fn epic(&self) -> &str {
+        &self.epic
+    }
+
+    fn instrument_name(&self) -> &str {
+        &self.instrument_name
+    }
+
+    fn bid(&self) -> Option<f64> {
+        self.bid
+    }
+
+    fn offer(&self) -> Option<f64> {
+        self.offer
+    }
+
+    fn status(&self) -> MarketState {
+        MarketState::from_status_str(&self.market_status)
+    }
+}
+
 /// Working order
 #[derive(DebugPretty, Clone, DisplaySimple, Deserialize, Serialize)]
 pub struct WorkingOrder {
@@ -705,6 +876,28 @@ impl AccountMarketData {
     }
 }
 
+impl MarketSnapshotView for AccountMarketData {
+    fn epic(&self) -> &str {
+        &self.epic
+    }
+
+    fn instrument_name(&self) -> &str {
+        &self.instrument_name
+    }
+
+    fn bid(&self) -> Option<f64> {
+        self.bid
+    }
+
+    fn offer(&self) -> Option<f64> {
+        self.offer
+    }
+
+    fn status(&self) -> MarketState {
+        self.market_status.clone()
+    }
+}
+
 /// Transaction metadata
 #[derive(DebugPretty, Clone, DisplaySimple, Deserialize, Serialize)]
 pub struct TransactionMetadata {
@@ -799,6 +992,71 @@ impl AccountTransaction {
     pub fn is_put(&self) -> bool {
         self.instrument_name.contains("PUT")
     }
+
+    /// Parses `dateUtc` into a UTC timestamp
+    ///
+    /// # Returns
+    /// The transaction time, or `None` if the field is in an unknown format
+    #[must_use]
+    pub fn occurred_at_utc(&self) -> Option<DateTime<Utc>> {
+        parse_ig_datetime(&self.date_utc)
+    }
+
+    /// Parses `openDateUtc` into a UTC timestamp
+    ///
+    /// # Returns
+    /// The opening time of the position, or `None` if the field is in an unknown format
+    #[must_use]
+    pub fn opened_at_utc(&self) -> Option<DateTime<Utc>> {
+        parse_ig_datetime(&self.open_date_utc)
+    }
+
+    /// Parses the profit or loss amount, e.g. `"E-1,234.56"` -> `-1234.56`
+    ///
+    /// # Returns
+    /// * `Some(value)` - The amount without currency symbol and thousands separators
+    /// * `None` - If the field is not a number
+    #[must_use]
+    pub fn profit_and_loss_value(&self) -> Option<f64> {
+        parse_amount(&self.profit_and_loss)
+    }
+
+    /// Parses the opening price level
+    #[must_use]
+    pub fn open_level_value(&self) -> Option<f64> {
+        parse_amount(&self.open_level)
+    }
+
+    /// Parses the closing price level
+    #[must_use]
+    pub fn close_level_value(&self) -> Option<f64> {
+        parse_amount(&self.close_level)
+    }
+
+    /// Parses the transaction size
+    #[must_use]
+    pub fn size_value(&self) -> Option<f64> {
+        parse_amount(&self.size)
+    }
+}
+
+/// Parses an amount reported by the transaction history endpoint
+///
+/// Strips a leading currency symbol (`E`, `$`, `£`, ...) and thousands
+/// separators; the sign may appear before or after the symbol.
+fn parse_amount(raw: &str) -> Option<f64> {
+    let raw = raw.trim();
+    let (negative, rest) = match raw.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, raw),
+    };
+    let number: String = rest
+        .trim_start_matches(|c: char| !(c.is_ascii_digit() || c == '-' || c == '+' || c == '.'))
+        .chars()
+        .filter(|c| *c != ',')
+        .collect();
+    let value = number.parse::<f64>().ok()?;
+    Some(if negative { -value } else { value })
 }
 
 /// Representation of account data received from the IG Markets streaming API
@@ -880,7 +1138,125 @@ pub struct AccountFields {
     equity_used: Option<f64>,
 }
 
+impl AccountFields {
+    /// Gets the profit and loss (`PNL`)
+    #[must_use]
+    pub fn pnl(&self) -> Option<f64> {
+        self.pnl
+    }
+
+    /// Gets the deposit (`DEPOSIT`)
+    #[must_use]
+    pub fn deposit(&self) -> Option<f64> {
+        self.deposit
+    }
+
+    /// Gets the available cash (`AVAILABLE_CASH`)
+    #[must_use]
+    pub fn available_cash(&self) -> Option<f64> {
+        self.available_cash
+    }
+
+    /// Gets the profit and loss of limited-risk positions (`PNL_LR`)
+    #[must_use]
+    pub fn pnl_lr(&self) -> Option<f64> {
+        self.pnl_lr
+    }
+
+    /// Gets the profit and loss of non-limited-risk positions (`PNL_NLR`)
+    #[must_use]
+    pub fn pnl_nlr(&self) -> Option<f64> {
+        self.pnl_nlr
+    }
+
+    /// Gets the funds (`FUNDS`)
+    #[must_use]
+    pub fn funds(&self) -> Option<f64> {
+        self.funds
+    }
+
+    /// Gets the margin (`MARGIN`)
+    #[must_use]
+    pub fn margin(&self) -> Option<f64> {
+        self.margin
+    }
+
+    /// Gets the margin of limited-risk positions (`MARGIN_LR`)
+    #[must_use]
+    pub fn margin_lr(&self) -> Option<f64> {
+        self.margin_lr
+    }
+
+    /// Gets the margin of non-limited-risk positions (`MARGIN_NLR`)
+    #[must_use]
+    pub fn margin_nlr(&self) -> Option<f64> {
+        self.margin_nlr
+    }
+
+    /// Gets the amount available to deal (`AVAILABLE_TO_DEAL`)
+    #[must_use]
+    pub fn available_to_deal(&self) -> Option<f64> {
+        self.available_to_deal
+    }
+
+    /// Gets the equity (`EQUITY`)
+    #[must_use]
+    pub fn equity(&self) -> Option<f64> {
+        self.equity
+    }
+
+    /// Gets the equity used (`EQUITY_USED`)
+    #[must_use]
+    pub fn equity_used(&self) -> Option<f64> {
+        self.equity_used
+    }
+
+    /// Gets every field with its Lightstreamer name, in declaration order
+    fn values(&self) -> [(&'static str, Option<f64>); 12] {
+        [
+            ("PNL", self.pnl),
+            ("DEPOSIT", self.deposit),
+            ("AVAILABLE_CASH", self.available_cash),
+            ("PNL_LR", self.pnl_lr),
+            ("PNL_NLR", self.pnl_nlr),
+            ("FUNDS", self.funds),
+            ("MARGIN", self.margin),
+            ("MARGIN_LR", self.margin_lr),
+            ("MARGIN_NLR", self.margin_nlr),
+            ("AVAILABLE_TO_DEAL", self.available_to_deal),
+            ("EQUITY", self.equity),
+            ("EQUITY_USED", self.equity_used),
+        ]
+    }
+
+    /// Gets the fields present in the update, keyed by their Lightstreamer name
+    ///
+    /// # Returns
+    /// * `HashMap<&'static str, f64>` - e.g. `"EQUITY" -> 10250.5`; absent fields are omitted
+    #[must_use]
+    pub fn to_map(&self) -> HashMap<&'static str, f64> {
+        self.values()
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|value| (name, value)))
+            .collect()
+    }
+}
+
 impl AccountData {
+    /// Iterates over the fields that changed in this update
+    ///
+    /// Snapshot updates (`is_snapshot`) may report every field as changed, even
+    /// if its value is the same as in the previous update.
+    ///
+    /// # Returns
+    /// * `(Lightstreamer field name, new value)` pairs, in declaration order
+    pub fn changed(&self) -> impl Iterator<Item = (&'static str, f64)> {
+        self.changed_fields
+            .values()
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|value| (name, value)))
+    }
+
     /// Converts an ItemUpdate from the Lightstreamer API to an AccountData object
     ///
     /// # Arguments
@@ -1070,6 +1446,61 @@ mod tests {
         assert!((position.pnl() - 0.0).abs() < 1e-12);
     }
 
+    #[test]
+    fn net_pnl_applies_contract_size_and_scaling_factor() {
+        // (bid - level) * size * contract_size / scaling_factor
+        // (5100 - 5000) * 2 * 10 / 100 = 20
+        let mut details = sample_position_details(Direction::Buy, 5000.0, 2.0);
+        details.contract_size = 10.0;
+        let mut market = sample_market(Some(5100.0), Some(5102.0));
+        market.scaling_factor = 100;
+        let position = Position {
+            position: details,
+            market,
+            pnl: None,
+        };
+        assert!((position.net_pnl() - 20.0).abs() < 1e-9);
+        // The legacy calculation ignores both factors
+        assert!((position.pnl() - 200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn net_pnl_sell_uses_offer() {
+        // (level - offer) * size * contract_size / scaling_factor
+        // (5000 - 5102) * 1 * 10 / 100 = -10.2
+        let mut details = sample_position_details(Direction::Sell, 5000.0, 1.0);
+        details.contract_size = 10.0;
+        let mut market = sample_market(Some(5100.0), Some(5102.0));
+        market.scaling_factor = 100;
+        let position = Position {
+            position: details,
+            market,
+            pnl: Some(99.0),
+        };
+        assert!((position.net_pnl() + 10.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn add_same_direction_uses_size_weighted_level() {
+        // (100 * 1 + 130 * 3) / 4 = 122.5
+        let a = sample_position_details(Direction::Buy, 100.0, 1.0);
+        let b = sample_position_details(Direction::Buy, 130.0, 3.0);
+        let merged = a + b;
+        assert_eq!(merged.direction, Direction::Buy);
+        assert!((merged.size - 4.0).abs() < 1e-12);
+        assert!((merged.level - 122.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn add_opposite_directions_follows_surviving_side() {
+        let a = sample_position_details(Direction::Buy, 100.0, 1.0);
+        let b = sample_position_details(Direction::Sell, 130.0, 3.0);
+        let merged = a + b;
+        assert_eq!(merged.direction, Direction::Sell);
+        assert!((merged.size - 2.0).abs() < 1e-12);
+        assert!((merged.level - 130.0).abs() < 1e-12);
+    }
+
     #[test]
     fn pnl_buy_is_zero_when_bid_missing() {
         // When bid is missing for BUY, unwrap_or(value) makes current_value == value => pnl = 0
@@ -2,7 +2,7 @@ use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
 
 #[derive(
-    DebugPretty, DisplaySimple, Copy, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Default,
+    DebugPretty, DisplaySimple, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Default,
 )]
 #[serde(rename_all = "UPPERCASE")]
 /// Types of financial instruments available in the IG Markets API
@@ -66,9 +66,37 @@ pub enum InstrumentType {
     /// Test market instruments
     #[serde(rename = "TEST_MARKET")]
     TestMarket,
-    /// Unknown instrument type
-    Unknown,
     /// Options
     #[default]
     Options,
+    /// Instrument type not known to this version of the client, with the value IG sent
+    ///
+    /// Any `instrumentType` IG introduces after this enum was written deserializes
+    /// here, so new product launches don't break market responses.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+/// Type of an option contract
+#[derive(DebugPretty, DisplaySimple, Copy, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum OptionType {
+    /// Right to buy the underlying at the strike
+    Call,
+    /// Right to sell the underlying at the strike
+    Put,
+}
+
+impl OptionType {
+    /// Parses the option type as written in IG epics and instrument names
+    ///
+    /// Accepts `C`/`CALL` and `P`/`PUT`, case-insensitively.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "C" | "CALL" => Some(OptionType::Call),
+            "P" | "PUT" => Some(OptionType::Put),
+            _ => None,
+        }
+    }
 }
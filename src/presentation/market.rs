@@ -1,9 +1,13 @@
-use crate::presentation::instrument::InstrumentType;
+use crate::error::AppError;
+use crate::presentation::instrument::{InstrumentType, OptionType};
 use crate::presentation::serialization::{string_as_bool_opt, string_as_float_opt};
+use crate::utils::parsing::{parse_ig_datetime, parse_instrument_name};
+use chrono::{DateTime, Utc};
 use lightstreamer_rs::subscription::ItemUpdate;
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 
 /// Model for a market instrument with enhanced deserialization
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, PartialEq)]
@@ -55,6 +59,24 @@ pub struct Instrument {
     #[serde(rename = "chartCode")]
     /// Code used for charting this instrument
     pub chart_code: Option<String>,
+    #[serde(rename = "streamingPricesAvailable", default)]
+    /// Whether prices of this instrument can be streamed
+    pub streaming_prices_available: Option<bool>,
+}
+
+impl Instrument {
+    /// Gets the last dealing date of the instrument as a UTC timestamp
+    ///
+    /// # Returns
+    ///
+    /// The parsed `expiryDetails.lastDealingDate`, or `None` if the instrument has no
+    /// expiry details or the date cannot be parsed
+    #[must_use]
+    pub fn last_dealing_date_utc(&self) -> Option<DateTime<Utc>> {
+        self.expiry_details
+            .as_ref()
+            .and_then(ExpiryDetails::last_dealing_date_utc)
+    }
 }
 
 /// Model for an instrument's currency
@@ -87,6 +109,37 @@ pub struct MarketDetails {
     pub dealing_rules: DealingRules,
 }
 
+/// Market details returned for an explicit detail filter
+///
+/// A `SNAPSHOT_ONLY` response carries neither the instrument nor the dealing rules,
+/// so both are optional here.
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+pub struct FilteredMarketDetails {
+    /// Detailed information about the instrument, absent for snapshot-only requests
+    #[serde(default)]
+    pub instrument: Option<Instrument>,
+    /// Current market snapshot with prices
+    pub snapshot: MarketSnapshot,
+    /// Trading rules for the market, absent for snapshot-only requests
+    #[serde(rename = "dealingRules", default)]
+    pub dealing_rules: Option<DealingRules>,
+}
+
+impl FilteredMarketDetails {
+    /// Converts into full market details when the instrument and dealing rules are present
+    ///
+    /// # Returns
+    /// `None` for snapshot-only details
+    #[must_use]
+    pub fn into_market_details(self) -> Option<MarketDetails> {
+        Some(MarketDetails {
+            instrument: self.instrument?,
+            snapshot: self.snapshot,
+            dealing_rules: self.dealing_rules?,
+        })
+    }
+}
+
 /// Trading rules for a market with enhanced deserialization
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct DealingRules {
@@ -179,6 +232,262 @@ pub struct MarketSnapshot {
     pub controlled_risk_extra_spread: Option<f64>,
 }
 
+impl MarketSnapshot {
+    /// Gets the snapshot prices divided by the scaling factor
+    ///
+    /// A missing scaling factor is treated as 1, leaving prices unchanged.
+    #[must_use]
+    pub fn scaled(&self) -> ScaledSnapshot {
+        let scaling_factor = self.scaling_factor.unwrap_or(1);
+        let scale = |value: Option<f64>| value.map(|v| apply_scaling(v, scaling_factor));
+        ScaledSnapshot {
+            bid: scale(self.bid),
+            offer: scale(self.offer),
+            high: scale(self.high),
+            low: scale(self.low),
+            scaling_factor,
+        }
+    }
+}
+
+impl MarketDetails {
+    /// Gets the snapshot prices divided by the market scaling factor
+    ///
+    /// See [`ScaledSnapshot`] for which IG fields are quoted in scaled units.
+    #[must_use]
+    pub fn scaled_snapshot(&self) -> ScaledSnapshot {
+        self.snapshot.scaled()
+    }
+
+    /// Gets the minimum deal size
+    #[must_use]
+    pub fn min_deal_size(&self) -> Option<f64> {
+        self.dealing_rules.min_deal_size.value
+    }
+
+    /// Gets the maximum deal size, if IG reports one
+    #[must_use]
+    pub fn max_deal_size(&self) -> Option<f64> {
+        self.dealing_rules.max_deal_size
+    }
+
+    /// Gets the minimum step distance in points, e.g. of trailing stop increments
+    #[must_use]
+    pub fn min_step_distance(&self) -> Option<f64> {
+        self.rule_in_points(&self.dealing_rules.min_step_distance)
+    }
+
+    /// Gets the minimum distance of a normal stop or limit in points
+    #[must_use]
+    pub fn min_stop_distance(&self) -> Option<f64> {
+        self.rule_in_points(&self.dealing_rules.min_normal_stop_or_limit_distance)
+    }
+
+    /// Gets the maximum distance of a stop or limit in points
+    #[must_use]
+    pub fn max_stop_distance(&self) -> Option<f64> {
+        self.rule_in_points(&self.dealing_rules.max_stop_or_limit_distance)
+    }
+
+    /// Gets the minimum distance of a controlled risk (guaranteed) stop in points
+    #[must_use]
+    pub fn min_controlled_risk_stop_distance(&self) -> Option<f64> {
+        self.rule_in_points(&self.dealing_rules.min_controlled_risk_stop_distance)
+    }
+
+    /// Checks an order size and stop distance against the dealing rules
+    ///
+    /// The stop distance is checked against the normal stop rules; guaranteed
+    /// stops must also respect `min_controlled_risk_stop_distance`. Rules IG does
+    /// not report, or reports as a percentage while the snapshot has no price, are
+    /// skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Deal size
+    /// * `stop_distance` - Stop distance in points, if any
+    /// * `guaranteed_stop` - Whether the stop is a guaranteed (controlled risk) stop
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())`, or every violated rule
+    pub fn validate_order(
+        &self,
+        size: f64,
+        stop_distance: Option<f64>,
+        guaranteed_stop: bool,
+    ) -> Result<(), Vec<DealingRuleViolation>> {
+        let mut violations = Vec::new();
+
+        if !size.is_finite() || size <= 0.0 {
+            violations.push(DealingRuleViolation::InvalidSize { size });
+        } else {
+            if let Some(min) = self.min_deal_size()
+                && size < min - RULE_TOLERANCE
+            {
+                violations.push(DealingRuleViolation::BelowMinDealSize { size, min });
+            }
+            if let Some(max) = self.max_deal_size()
+                && max > 0.0
+                && size > max + RULE_TOLERANCE
+            {
+                violations.push(DealingRuleViolation::AboveMaxDealSize { size, max });
+            }
+        }
+
+        if let Some(distance) = stop_distance {
+            if let Some(min) = self.min_stop_distance()
+                && distance < min - RULE_TOLERANCE
+            {
+                violations.push(DealingRuleViolation::StopTooClose { distance, min });
+            }
+            if guaranteed_stop
+                && let Some(min) = self.min_controlled_risk_stop_distance()
+                && distance < min - RULE_TOLERANCE
+            {
+                violations.push(DealingRuleViolation::GuaranteedStopTooClose { distance, min });
+            }
+            if let Some(max) = self.max_stop_distance()
+                && max > 0.0
+                && distance > max + RULE_TOLERANCE
+            {
+                violations.push(DealingRuleViolation::StopTooFar { distance, max });
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Converts a dealing rule to points, using the snapshot mid for percentages
+    fn rule_in_points(&self, rule: &StepDistance) -> Option<f64> {
+        let value = rule.value?;
+        match rule.unit {
+            Some(StepUnit::Percentage | StepUnit::Pct) => {
+                let (bid, offer) = (self.snapshot.bid?, self.snapshot.offer?);
+                Some((bid + offer) / 2.0 * value / 100.0)
+            }
+            _ => Some(value),
+        }
+    }
+}
+
+/// Tolerance for floating point comparisons against dealing rules
+const RULE_TOLERANCE: f64 = 1e-9;
+
+/// Dealing rule broken by an order, as reported by `MarketDetails::validate_order`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DealingRuleViolation {
+    /// The size is zero, negative or not a number
+    InvalidSize {
+        /// Requested size
+        size: f64,
+    },
+    /// The size is below the minimum deal size
+    BelowMinDealSize {
+        /// Requested size
+        size: f64,
+        /// Minimum deal size
+        min: f64,
+    },
+    /// The size is above the maximum deal size
+    AboveMaxDealSize {
+        /// Requested size
+        size: f64,
+        /// Maximum deal size
+        max: f64,
+    },
+    /// The stop is closer than the minimum stop distance
+    StopTooClose {
+        /// Requested stop distance in points
+        distance: f64,
+        /// Minimum stop distance in points
+        min: f64,
+    },
+    /// The guaranteed stop is closer than the minimum controlled risk stop distance
+    GuaranteedStopTooClose {
+        /// Requested stop distance in points
+        distance: f64,
+        /// Minimum controlled risk stop distance in points
+        min: f64,
+    },
+    /// The stop is further than the maximum stop distance
+    StopTooFar {
+        /// Requested stop distance in points
+        distance: f64,
+        /// Maximum stop distance in points
+        max: f64,
+    },
+}
+
+impl std::fmt::Display for DealingRuleViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSize { size } => write!(f, "invalid size {size}"),
+            Self::BelowMinDealSize { size, min } => {
+                write!(f, "size {size} is below the minimum deal size {min}")
+            }
+            Self::AboveMaxDealSize { size, max } => {
+                write!(f, "size {size} is above the maximum deal size {max}")
+            }
+            Self::StopTooClose { distance, min } => {
+                write!(f, "stop distance {distance} is below the minimum {min}")
+            }
+            Self::GuaranteedStopTooClose { distance, min } => {
+                write!(
+                    f,
+                    "guaranteed stop distance {distance} is below the minimum {min}"
+                )
+            }
+            Self::StopTooFar { distance, max } => {
+                write!(f, "stop distance {distance} is above the maximum {max}")
+            }
+        }
+    }
+}
+
+/// Snapshot prices converted from IG quote units to instrument price units
+///
+/// IG quotes the REST snapshot `bid`, `offer`, `high`, `low` and `netChange`, the
+/// streaming `BID`/`OFFER`/`HIGH`/`LOW` fields and position levels multiplied by
+/// `scalingFactor`. These are *not* pre-scaled and must be divided by the factor to
+/// obtain the instrument price. `percentageChange` is a ratio and deal sizes are
+/// contract counts, so neither is affected by the scaling factor.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScaledSnapshot {
+    /// Bid price divided by the scaling factor
+    pub bid: Option<f64>,
+    /// Offer price divided by the scaling factor
+    pub offer: Option<f64>,
+    /// Session high divided by the scaling factor
+    pub high: Option<f64>,
+    /// Session low divided by the scaling factor
+    pub low: Option<f64>,
+    /// Scaling factor applied (1 when IG did not report one)
+    pub scaling_factor: i64,
+}
+
+/// Converts a value quoted by IG into instrument price units
+///
+/// # Arguments
+///
+/// * `value` - Value as quoted by IG
+/// * `scaling_factor` - Market scaling factor; values of 0 or below leave `value` unchanged
+///
+/// # Returns
+///
+/// `value / scaling_factor`
+#[must_use]
+pub fn apply_scaling(value: f64, scaling_factor: i64) -> f64 {
+    if scaling_factor <= 0 {
+        return value;
+    }
+    value / scaling_factor as f64
+}
+
 /// Basic market data
 #[derive(DebugPretty, DisplaySimple, Clone, Deserialize, Serialize)]
 pub struct MarketData {
@@ -275,6 +584,150 @@ pub struct HistoricalPrice {
     pub last_traded_volume: Option<i64>,
 }
 
+/// Resolution of historical price data, serialized as IG's exact token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Resolution {
+    /// One second
+    #[serde(rename = "SECOND")]
+    Second,
+    /// One minute
+    #[serde(rename = "MINUTE")]
+    Minute,
+    /// Two minutes
+    #[serde(rename = "MINUTE_2")]
+    Minute2,
+    /// Three minutes
+    #[serde(rename = "MINUTE_3")]
+    Minute3,
+    /// Five minutes
+    #[serde(rename = "MINUTE_5")]
+    Minute5,
+    /// Ten minutes
+    #[serde(rename = "MINUTE_10")]
+    Minute10,
+    /// Fifteen minutes
+    #[serde(rename = "MINUTE_15")]
+    Minute15,
+    /// Thirty minutes
+    #[serde(rename = "MINUTE_30")]
+    Minute30,
+    /// One hour
+    #[serde(rename = "HOUR")]
+    Hour,
+    /// Two hours
+    #[serde(rename = "HOUR_2")]
+    Hour2,
+    /// Three hours
+    #[serde(rename = "HOUR_3")]
+    Hour3,
+    /// Four hours
+    #[serde(rename = "HOUR_4")]
+    Hour4,
+    /// One day
+    #[serde(rename = "DAY")]
+    Day,
+    /// One week
+    #[serde(rename = "WEEK")]
+    Week,
+    /// One month
+    #[serde(rename = "MONTH")]
+    Month,
+}
+
+impl Resolution {
+    /// Every resolution, from the finest to the coarsest
+    pub const ALL: [Resolution; 15] = [
+        Resolution::Second,
+        Resolution::Minute,
+        Resolution::Minute2,
+        Resolution::Minute3,
+        Resolution::Minute5,
+        Resolution::Minute10,
+        Resolution::Minute15,
+        Resolution::Minute30,
+        Resolution::Hour,
+        Resolution::Hour2,
+        Resolution::Hour3,
+        Resolution::Hour4,
+        Resolution::Day,
+        Resolution::Week,
+        Resolution::Month,
+    ];
+
+    /// Gets the token IG expects for this resolution, e.g. `MINUTE_5`
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Resolution::Second => "SECOND",
+            Resolution::Minute => "MINUTE",
+            Resolution::Minute2 => "MINUTE_2",
+            Resolution::Minute3 => "MINUTE_3",
+            Resolution::Minute5 => "MINUTE_5",
+            Resolution::Minute10 => "MINUTE_10",
+            Resolution::Minute15 => "MINUTE_15",
+            Resolution::Minute30 => "MINUTE_30",
+            Resolution::Hour => "HOUR",
+            Resolution::Hour2 => "HOUR_2",
+            Resolution::Hour3 => "HOUR_3",
+            Resolution::Hour4 => "HOUR_4",
+            Resolution::Day => "DAY",
+            Resolution::Week => "WEEK",
+            Resolution::Month => "MONTH",
+        }
+    }
+}
+
+impl std::fmt::Display for Resolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Resolution {
+    type Err = AppError;
+
+    /// Parses an IG resolution token, case-insensitively
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        Resolution::ALL
+            .into_iter()
+            .find(|resolution| resolution.as_str().eq_ignore_ascii_case(token))
+            .ok_or_else(|| AppError::InvalidInput(format!("unknown resolution: {token}")))
+    }
+}
+
+impl HistoricalPrice {
+    /// Parses the snapshot time of the price point as UTC
+    ///
+    /// # Returns
+    ///
+    /// The parsed timestamp, or `None` if the value is malformed
+    #[must_use]
+    pub fn snapshot_time_utc(&self) -> Option<DateTime<Utc>> {
+        parse_ig_datetime(&self.snapshot_time)
+    }
+
+    /// Converts the price point to a typed candle
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - Side of the quote to use for open, high, low and close
+    ///
+    /// # Returns
+    ///
+    /// The candle, or `None` if the timestamp or any required price is missing
+    #[must_use]
+    pub fn to_ohlc(&self, side: PriceSide) -> Option<Ohlc> {
+        Some(Ohlc {
+            timestamp: self.snapshot_time_utc()?,
+            open: self.open_price.value(side)?,
+            high: self.high_price.value(side)?,
+            low: self.low_price.value(side)?,
+            close: self.close_price.value(side)?,
+            volume: self.last_traded_volume.map(|volume| volume as f64),
+        })
+    }
+}
+
 /// Price point with bid, ask and last traded prices
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct PricePoint {
@@ -287,6 +740,54 @@ pub struct PricePoint {
     pub last_traded: Option<f64>,
 }
 
+impl PricePoint {
+    /// Gets the price for the requested side
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - Side to read; `Mid` averages bid and ask
+    ///
+    /// # Returns
+    ///
+    /// The price, or `None` if a required side is missing
+    #[must_use]
+    pub fn value(&self, side: PriceSide) -> Option<f64> {
+        match side {
+            PriceSide::Bid => self.bid,
+            PriceSide::Ask => self.ask,
+            PriceSide::Mid => Some((self.bid? + self.ask?) / 2.0),
+        }
+    }
+}
+
+/// Side of the quote used when converting historical prices to candles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PriceSide {
+    /// Bid prices
+    Bid,
+    /// Ask/offer prices
+    Ask,
+    /// Average of bid and ask prices
+    Mid,
+}
+
+/// Typed OHLC candle built from a historical price point
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ohlc {
+    /// Start of the candle period in UTC
+    pub timestamp: DateTime<Utc>,
+    /// Opening price
+    pub open: f64,
+    /// Highest price
+    pub high: f64,
+    /// Lowest price
+    pub low: f64,
+    /// Closing price
+    pub close: f64,
+    /// Volume traded during the period
+    pub volume: Option<f64>,
+}
+
 /// Information about API usage allowance for price data
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct PriceAllowance {
@@ -301,6 +802,28 @@ pub struct PriceAllowance {
     pub allowance_expiry: i64,
 }
 
+impl PriceAllowance {
+    /// Checks whether no historical data points are left in the current period
+    #[must_use]
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining_allowance <= 0
+    }
+
+    /// Gets the number of historical data points left, never negative
+    #[must_use]
+    pub fn remaining(&self) -> i64 {
+        self.remaining_allowance.max(0)
+    }
+
+    /// Gets the time until the allowance resets
+    ///
+    /// IG reports `allowanceExpiry` in seconds; negative values are treated as zero.
+    #[must_use]
+    pub fn resets_in(&self) -> Duration {
+        Duration::from_secs(self.allowance_expiry.max(0) as u64)
+    }
+}
+
 /// Details about instrument expiry
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ExpiryDetails {
@@ -313,6 +836,27 @@ pub struct ExpiryDetails {
     pub settlement_info: Option<String>,
 }
 
+impl ExpiryDetails {
+    /// Parses the last dealing date into a UTC timestamp
+    ///
+    /// IG reports the date as `yyyy-MM-ddTHH:mm` (e.g. `2024-12-20T16:00`); variants
+    /// with seconds, a space or slash separators, or a full RFC 3339 offset are also
+    /// accepted. Dates without an offset are interpreted as UTC.
+    ///
+    /// # Returns
+    ///
+    /// The parsed timestamp, or `None` if the value is empty or malformed
+    #[must_use]
+    pub fn last_dealing_date_utc(&self) -> Option<DateTime<Utc>> {
+        let raw = self.last_dealing_date.trim();
+        if raw.is_empty() {
+            return None;
+        }
+
+        parse_ig_datetime(raw)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 /// Unit for step distances in trading rules
 pub enum StepUnit {
@@ -385,6 +929,131 @@ pub enum MarketState {
     OnAuction,
     /// Market is in auction phase but editing is not allowed
     OnAuctionNoEdits,
+    /// Status not known to this version of the client, with the value IG sent
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl MarketState {
+    /// Parses a market status string as returned by the REST API
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - Status string such as `TRADEABLE` or `EDITS_ONLY`
+    ///
+    /// # Returns
+    ///
+    /// The matching `MarketState`, or `MarketState::Unknown` with the normalised value
+    #[must_use]
+    pub fn from_status_str(status: &str) -> Self {
+        let status = status.trim().to_uppercase();
+        serde_json::from_value(serde_json::Value::String(status.clone()))
+            .unwrap_or(MarketState::Unknown(status))
+    }
+
+    /// Checks whether new positions can be opened
+    #[must_use]
+    pub fn is_tradeable(&self) -> bool {
+        matches!(self, MarketState::Tradeable)
+    }
+
+    /// Checks whether only existing positions and orders can be edited or closed
+    #[must_use]
+    pub fn is_closing_only(&self) -> bool {
+        matches!(self, MarketState::EditsOnly)
+    }
+}
+
+/// Common read-only view over the market representations returned by the API
+///
+/// `MarketData`, `MarketDetails`, `PositionMarket` and `AccountMarketData` expose the
+/// same core information under different names and types. This trait lets generic
+/// code, such as a price-table renderer, accept any of them.
+///
+/// The trait is not named `MarketSnapshot` to avoid clashing with the
+/// `MarketSnapshot` struct carried by `MarketDetails`.
+pub trait MarketSnapshotView {
+    /// Unique identifier of the market (EPIC code)
+    fn epic(&self) -> &str;
+
+    /// Human-readable name of the instrument
+    fn instrument_name(&self) -> &str;
+
+    /// Current bid price, if available
+    fn bid(&self) -> Option<f64>;
+
+    /// Current offer price, if available
+    fn offer(&self) -> Option<f64>;
+
+    /// Current trading status of the market
+    fn status(&self) -> MarketState;
+
+    /// Mid price, if both sides are available
+    fn mid(&self) -> Option<f64> {
+        Some((self.bid()? + self.offer()?) / 2.0)
+    }
+
+    /// Type of the option, parsed from the instrument name
+    fn option_type(&self) -> Option<OptionType> {
+        parse_instrument_name(self.instrument_name())
+            .option_type
+            .as_deref()
+            .and_then(OptionType::parse)
+    }
+
+    /// Checks whether the instrument is a call option
+    fn is_call(&self) -> bool {
+        self.option_type() == Some(OptionType::Call)
+    }
+
+    /// Checks whether the instrument is a put option
+    fn is_put(&self) -> bool {
+        self.option_type() == Some(OptionType::Put)
+    }
+}
+
+impl MarketSnapshotView for MarketData {
+    fn epic(&self) -> &str {
+        &self.epic
+    }
+
+    fn instrument_name(&self) -> &str {
+        &self.instrument_name
+    }
+
+    fn bid(&self) -> Option<f64> {
+        self.bid
+    }
+
+    fn offer(&self) -> Option<f64> {
+        self.offer
+    }
+
+    fn status(&self) -> MarketState {
+        MarketState::from_status_str(&self.market_status)
+    }
+}
+
+impl MarketSnapshotView for MarketDetails {
+    fn epic(&self) -> &str {
+        &self.instrument.epic
+    }
+
+    fn instrument_name(&self) -> &str {
+        &self.instrument.name
+    }
+
+    fn bid(&self) -> Option<f64> {
+        self.snapshot.bid
+    }
+
+    fn offer(&self) -> Option<f64> {
+        self.snapshot.offer
+    }
+
+    fn status(&self) -> MarketState {
+        MarketState::from_status_str(&self.snapshot.market_status)
+    }
 }
 
 /// Representation of market data received from the IG Markets streaming API
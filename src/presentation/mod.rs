@@ -6,8 +6,12 @@ pub mod chart;
 pub mod instrument;
 /// Market data and navigation models
 pub mod market;
+/// Option chain models
+pub mod option_chain;
 /// Order placement and management models
 pub mod order;
+/// Live portfolio valuation from streaming prices
+pub mod portfolio;
 /// Price and quote models
 pub mod price;
 /// Serialization utilities for API responses
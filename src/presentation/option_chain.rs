@@ -0,0 +1,125 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Option chain models
+//!
+//! `OptionChain` groups the option markets found under a navigation node by expiry
+//! and strike, pairing the call and put epics of each strike. Strikes and option
+//! types are taken from the instrument names via `parse_instrument_name`.
+
+use crate::presentation::instrument::OptionType;
+use crate::presentation::market::MarketData;
+use crate::utils::parsing::parse_instrument_name;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+
+/// Options of a single expiry, ordered by strike
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ExpiryChain {
+    /// Expiry as reported by IG (e.g. `DEC-25`)
+    pub expiry: String,
+    /// `(strike, call epic, put epic)` in ascending strike order
+    pub strikes: Vec<(f64, Option<String>, Option<String>)>,
+}
+
+impl ExpiryChain {
+    /// Gets the call and put epics of a strike
+    #[must_use]
+    pub fn strike(&self, strike: f64) -> Option<&(f64, Option<String>, Option<String>)> {
+        self.strikes.iter().find(|(value, _, _)| *value == strike)
+    }
+}
+
+/// Option chain of an underlying, grouped by expiry
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct OptionChain {
+    /// Name of the underlying asset (e.g. `Germany 40`)
+    pub underlying: String,
+    /// Expiries in the order they were first found
+    pub expiries: Vec<ExpiryChain>,
+}
+
+impl OptionChain {
+    /// Builds the chain from a list of markets
+    ///
+    /// Markets whose instrument name carries no strike and CALL/PUT suffix are
+    /// ignored. If two markets share an expiry, strike and type, the first wins.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying` - Fallback underlying name, used when no option market is found
+    /// * `markets` - Markets to group, typically every market under a navigation node
+    #[must_use]
+    pub fn from_markets(underlying: &str, markets: &[MarketData]) -> Self {
+        let mut chain = OptionChain {
+            underlying: underlying.to_string(),
+            expiries: Vec::new(),
+        };
+        let mut underlying_found = false;
+
+        for market in markets {
+            let info = parse_instrument_name(&market.instrument_name);
+            let Some(strike) = info.strike.as_deref().and_then(|s| s.parse::<f64>().ok()) else {
+                continue;
+            };
+            let Some(option_type) = info.option_type.as_deref().and_then(OptionType::parse) else {
+                continue;
+            };
+            if !underlying_found {
+                chain.underlying = info.asset_name.clone();
+                underlying_found = true;
+            }
+
+            let index = match chain
+                .expiries
+                .iter()
+                .position(|expiry| expiry.expiry == market.expiry)
+            {
+                Some(index) => index,
+                None => {
+                    chain.expiries.push(ExpiryChain {
+                        expiry: market.expiry.clone(),
+                        strikes: Vec::new(),
+                    });
+                    chain.expiries.len() - 1
+                }
+            };
+            let strikes = &mut chain.expiries[index].strikes;
+            let position = match strikes.iter().position(|(value, _, _)| *value == strike) {
+                Some(position) => position,
+                None => {
+                    strikes.push((strike, None, None));
+                    strikes.len() - 1
+                }
+            };
+            let entry = &mut strikes[position];
+            let slot = match option_type {
+                OptionType::Call => &mut entry.1,
+                OptionType::Put => &mut entry.2,
+            };
+            slot.get_or_insert_with(|| market.epic.clone());
+        }
+
+        for expiry in &mut chain.expiries {
+            expiry
+                .strikes
+                .sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        }
+        chain
+    }
+
+    /// Gets the chain of an expiry
+    #[must_use]
+    pub fn expiry(&self, expiry: &str) -> Option<&ExpiryChain> {
+        self.expiries.iter().find(|chain| chain.expiry == expiry)
+    }
+
+    /// Checks whether the chain has no options
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.expiries.iter().all(|expiry| expiry.strikes.is_empty())
+    }
+}
@@ -17,6 +17,39 @@ pub enum Direction {
     Sell,
 }
 
+impl Direction {
+    /// Gets the direction that closes a position opened in this direction
+    #[must_use]
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Buy => Direction::Sell,
+            Direction::Sell => Direction::Buy,
+        }
+    }
+
+    /// Gets the sign of this direction: `1.0` for Buy, `-1.0` for Sell
+    #[must_use]
+    pub fn sign(&self) -> f64 {
+        match self {
+            Direction::Buy => 1.0,
+            Direction::Sell => -1.0,
+        }
+    }
+}
+
+/// Signs a size by direction, positive for Buy and negative for Sell
+///
+/// # Arguments
+/// * `direction` - Direction of the position or order
+/// * `size` - Unsigned size
+///
+/// # Returns
+/// `size` for Buy, `-size` for Sell
+#[must_use]
+pub fn signed_size(direction: &Direction, size: f64) -> f64 {
+    direction.sign() * size
+}
+
 /// Order type
 #[derive(Debug, Clone, DisplaySimple, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "UPPERCASE")]
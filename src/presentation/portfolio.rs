@@ -0,0 +1,120 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Live portfolio valuation
+//!
+//! `LivePortfolio` holds the open positions of an account and revalues them as
+//! streaming prices arrive, so the PnL stays current without polling the positions
+//! endpoint. Prices are matched to positions by epic.
+
+use crate::application::streamer_config::StreamerConfig;
+use crate::model::responses::PositionsResponse;
+use crate::presentation::account::Position;
+use crate::presentation::price::PriceData;
+
+/// Open positions kept up to date with streaming prices
+#[derive(Debug, Clone, Default)]
+pub struct LivePortfolio {
+    positions: Vec<Position>,
+    streamer_config: StreamerConfig,
+}
+
+impl LivePortfolio {
+    /// Creates a portfolio from a list of positions
+    ///
+    /// # Arguments
+    ///
+    /// * `positions` - Open positions, typically from `get_positions`
+    #[must_use]
+    pub fn new(positions: Vec<Position>) -> Self {
+        Self {
+            positions,
+            streamer_config: StreamerConfig::default(),
+        }
+    }
+
+    /// Sets the streamer configuration whose item prefixes name the updates
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Configuration of the `StreamerClient` producing the updates
+    #[must_use]
+    pub fn with_streamer_config(mut self, config: StreamerConfig) -> Self {
+        self.streamer_config = config;
+        self
+    }
+
+    /// Gets the positions with their latest prices and PnL
+    #[must_use]
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// Applies the latest bid and offer of an epic to its positions
+    ///
+    /// A missing side keeps its previous value. The PnL of every matching position
+    /// is recomputed with `Position::update_pnl`.
+    ///
+    /// # Arguments
+    ///
+    /// * `epic` - Instrument epic, without any item prefix
+    /// * `bid` - Latest bid, if it changed
+    /// * `offer` - Latest offer, if it changed
+    ///
+    /// # Returns
+    ///
+    /// The number of positions revalued
+    pub fn apply_price(&mut self, epic: &str, bid: Option<f64>, offer: Option<f64>) -> usize {
+        let mut updated = 0;
+        for position in self
+            .positions
+            .iter_mut()
+            .filter(|position| position.market.epic == epic)
+        {
+            if bid.is_some() {
+                position.market.bid = bid;
+            }
+            if offer.is_some() {
+                position.market.offer = offer;
+            }
+            position.update_pnl();
+            updated += 1;
+        }
+        updated
+    }
+
+    /// Applies a streaming market update to the positions of its epic
+    ///
+    /// The epic is taken from the item name with `StreamerConfig::item_epic`, using the
+    /// configured item prefixes; an unprefixed item name is used as the epic.
+    ///
+    /// # Arguments
+    ///
+    /// * `update` - Update received from `market_subscribe`
+    ///
+    /// # Returns
+    ///
+    /// The number of positions revalued
+    pub fn apply_update(&mut self, update: &PriceData) -> usize {
+        let epic = self
+            .streamer_config
+            .item_epic(&update.item_name)
+            .unwrap_or(&update.item_name);
+        self.apply_price(epic, update.fields.bid, update.fields.offer)
+    }
+
+    /// Sums the profit and loss of every position
+    #[must_use]
+    pub fn total_pnl(&self) -> f64 {
+        self.positions.iter().map(Position::pnl).sum()
+    }
+}
+
+impl From<PositionsResponse> for LivePortfolio {
+    fn from(response: PositionsResponse) -> Self {
+        Self::new(response.positions)
+    }
+}
@@ -1,8 +1,9 @@
 use crate::presentation::serialization::string_as_float_opt;
+use chrono::{DateTime, Utc};
 use lightstreamer_rs::subscription::ItemUpdate;
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Market dealing status flags indicating trading availability
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -27,6 +28,23 @@ pub enum DealingFlag {
     AuctionNoEdit,
     /// Market trading is suspended
     Suspend,
+    /// Flag not known to this version of the client, with the value IG sent
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl DealingFlag {
+    /// Checks whether new positions can be opened
+    #[must_use]
+    pub fn is_tradeable(&self) -> bool {
+        matches!(self, DealingFlag::Deal | DealingFlag::DealNoEdit)
+    }
+
+    /// Checks whether only closing existing positions is allowed
+    #[must_use]
+    pub fn is_closing_only(&self) -> bool {
+        matches!(self, DealingFlag::ClosingOnly)
+    }
 }
 
 /// Structure for price data received from the IG Markets API
@@ -46,7 +64,11 @@ pub struct PriceData {
 }
 
 /// Price field data containing bid, offer, and market status information
+///
+/// Missing fields deserialize as `None`, so documents serialized without their empty
+/// fields, e.g. by `JsonLinesWriter`, read back.
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct PriceFields {
     /// The opening price at the middle of the bid-ask spread
     #[serde(rename = "MID_OPEN")]
@@ -583,9 +605,327 @@ pub struct PriceFields {
     pub dealing_flag: Option<DealingFlag>,
 }
 
+impl PriceFields {
+    /// Gets every numeric field with its Lightstreamer name, in declaration order
+    fn numeric_values(&self) -> [(&'static str, Option<f64>); 79] {
+        [
+            ("MID_OPEN", self.mid_open),
+            ("HIGH", self.high),
+            ("LOW", self.low),
+            ("BID", self.bid),
+            ("OFFER", self.offer),
+            ("CHANGE", self.change),
+            ("CHANGE_PCT", self.change_pct),
+            ("MARKET_DELAY", self.market_delay),
+            ("BIDPRICE1", self.bid_price1),
+            ("BIDPRICE2", self.bid_price2),
+            ("BIDPRICE3", self.bid_price3),
+            ("BIDPRICE4", self.bid_price4),
+            ("BIDPRICE5", self.bid_price5),
+            ("ASKPRICE1", self.ask_price1),
+            ("ASKPRICE2", self.ask_price2),
+            ("ASKPRICE3", self.ask_price3),
+            ("ASKPRICE4", self.ask_price4),
+            ("ASKPRICE5", self.ask_price5),
+            ("BIDSIZE1", self.bid_size1),
+            ("BIDSIZE2", self.bid_size2),
+            ("BIDSIZE3", self.bid_size3),
+            ("BIDSIZE4", self.bid_size4),
+            ("BIDSIZE5", self.bid_size5),
+            ("ASKSIZE1", self.ask_size1),
+            ("ASKSIZE2", self.ask_size2),
+            ("ASKSIZE3", self.ask_size3),
+            ("ASKSIZE4", self.ask_size4),
+            ("ASKSIZE5", self.ask_size5),
+            ("C1BIDSIZE1", self.c1_bid_size_1),
+            ("C1BIDSIZE2", self.c1_bid_size_2),
+            ("C1BIDSIZE3", self.c1_bid_size_3),
+            ("C1BIDSIZE4", self.c1_bid_size_4),
+            ("C1BIDSIZE5", self.c1_bid_size_5),
+            ("C2BIDSIZE1", self.c2_bid_size_1),
+            ("C2BIDSIZE2", self.c2_bid_size_2),
+            ("C2BIDSIZE3", self.c2_bid_size_3),
+            ("C2BIDSIZE4", self.c2_bid_size_4),
+            ("C2BIDSIZE5", self.c2_bid_size_5),
+            ("C3BIDSIZE1", self.c3_bid_size_1),
+            ("C3BIDSIZE2", self.c3_bid_size_2),
+            ("C3BIDSIZE3", self.c3_bid_size_3),
+            ("C3BIDSIZE4", self.c3_bid_size_4),
+            ("C3BIDSIZE5", self.c3_bid_size_5),
+            ("C4BIDSIZE1", self.c4_bid_size_1),
+            ("C4BIDSIZE2", self.c4_bid_size_2),
+            ("C4BIDSIZE3", self.c4_bid_size_3),
+            ("C4BIDSIZE4", self.c4_bid_size_4),
+            ("C4BIDSIZE5", self.c4_bid_size_5),
+            ("C5BIDSIZE1", self.c5_bid_size_1),
+            ("C5BIDSIZE2", self.c5_bid_size_2),
+            ("C5BIDSIZE3", self.c5_bid_size_3),
+            ("C5BIDSIZE4", self.c5_bid_size_4),
+            ("C5BIDSIZE5", self.c5_bid_size_5),
+            ("C1ASKSIZE1", self.c1_ask_size_1),
+            ("C1ASKSIZE2", self.c1_ask_size_2),
+            ("C1ASKSIZE3", self.c1_ask_size_3),
+            ("C1ASKSIZE4", self.c1_ask_size_4),
+            ("C1ASKSIZE5", self.c1_ask_size_5),
+            ("C2ASKSIZE1", self.c2_ask_size_1),
+            ("C2ASKSIZE2", self.c2_ask_size_2),
+            ("C2ASKSIZE3", self.c2_ask_size_3),
+            ("C2ASKSIZE4", self.c2_ask_size_4),
+            ("C2ASKSIZE5", self.c2_ask_size_5),
+            ("C3ASKSIZE1", self.c3_ask_size_1),
+            ("C3ASKSIZE2", self.c3_ask_size_2),
+            ("C3ASKSIZE3", self.c3_ask_size_3),
+            ("C3ASKSIZE4", self.c3_ask_size_4),
+            ("C3ASKSIZE5", self.c3_ask_size_5),
+            ("C4ASKSIZE1", self.c4_ask_size_1),
+            ("C4ASKSIZE2", self.c4_ask_size_2),
+            ("C4ASKSIZE3", self.c4_ask_size_3),
+            ("C4ASKSIZE4", self.c4_ask_size_4),
+            ("C4ASKSIZE5", self.c4_ask_size_5),
+            ("C5ASKSIZE1", self.c5_ask_size_1),
+            ("C5ASKSIZE2", self.c5_ask_size_2),
+            ("C5ASKSIZE3", self.c5_ask_size_3),
+            ("C5ASKSIZE4", self.c5_ask_size_4),
+            ("C5ASKSIZE5", self.c5_ask_size_5),
+            ("TIMESTAMP", self.timestamp),
+        ]
+    }
+
+    /// Gets the numeric fields present in the update, keyed by their Lightstreamer name
+    ///
+    /// Text fields (`MARKET_STATE`, `UPDATE_TIME`, quote ids, currencies and
+    /// `DLG_FLAG`) are not included.
+    #[must_use]
+    pub fn to_map(&self) -> HashMap<&'static str, f64> {
+        self.numeric_values()
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|value| (name, value)))
+            .collect()
+    }
+
+    /// Gets the level-1 bid price
+    #[must_use]
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bid_price1
+    }
+
+    /// Gets the level-1 offer (ask) price
+    #[must_use]
+    pub fn best_offer(&self) -> Option<f64> {
+        self.ask_price1
+    }
+
+    /// Gets the midpoint between the level-1 bid and offer
+    ///
+    /// # Returns
+    ///
+    /// The mid price, or `None` if either side is missing
+    #[must_use]
+    pub fn mid_price(&self) -> Option<f64> {
+        Some((self.best_bid()? + self.best_offer()?) / 2.0)
+    }
+
+    /// Gets the spread between the level-1 offer and bid
+    ///
+    /// # Returns
+    ///
+    /// The spread, or `None` if either side is missing
+    #[must_use]
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_offer()? - self.best_bid()?)
+    }
+
+    /// Gets the size-weighted midpoint of the level-1 quotes
+    ///
+    /// Each price is weighted by the size on the opposite side, so the result
+    /// leans towards the side with less liquidity. Falls back to [`Self::mid_price`]
+    /// when either size is missing or both sizes are zero.
+    ///
+    /// # Returns
+    ///
+    /// The weighted mid price, or `None` if either price is missing
+    #[must_use]
+    pub fn weighted_mid(&self) -> Option<f64> {
+        let bid = self.best_bid()?;
+        let offer = self.best_offer()?;
+        match (self.bid_size1, self.ask_size1) {
+            (Some(bid_size), Some(ask_size)) if bid_size + ask_size > 0.0 => {
+                Some((bid * ask_size + offer * bid_size) / (bid_size + ask_size))
+            }
+            _ => Some((bid + offer) / 2.0),
+        }
+    }
+
+    /// Overlays every field that is present in `other` onto these fields
+    ///
+    /// Fields missing from `other` keep their current value.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Fields whose present values take precedence
+    pub fn merge(&mut self, other: &PriceFields) {
+        macro_rules! overlay {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    if other.$field.is_some() {
+                        self.$field = other.$field.clone();
+                    }
+                )*
+            };
+        }
+
+        overlay!(
+            mid_open,
+            high,
+            low,
+            bid,
+            offer,
+            change,
+            change_pct,
+            market_delay,
+            market_state,
+            update_time,
+            bid_quote_id,
+            ask_quote_id,
+            bid_price1,
+            bid_price2,
+            bid_price3,
+            bid_price4,
+            bid_price5,
+            ask_price1,
+            ask_price2,
+            ask_price3,
+            ask_price4,
+            ask_price5,
+            bid_size1,
+            bid_size2,
+            bid_size3,
+            bid_size4,
+            bid_size5,
+            ask_size1,
+            ask_size2,
+            ask_size3,
+            ask_size4,
+            ask_size5,
+            currency0,
+            currency1,
+            currency2,
+            currency3,
+            currency4,
+            currency5,
+            c1_bid_size_1,
+            c1_bid_size_2,
+            c1_bid_size_3,
+            c1_bid_size_4,
+            c1_bid_size_5,
+            c2_bid_size_1,
+            c2_bid_size_2,
+            c2_bid_size_3,
+            c2_bid_size_4,
+            c2_bid_size_5,
+            c3_bid_size_1,
+            c3_bid_size_2,
+            c3_bid_size_3,
+            c3_bid_size_4,
+            c3_bid_size_5,
+            c4_bid_size_1,
+            c4_bid_size_2,
+            c4_bid_size_3,
+            c4_bid_size_4,
+            c4_bid_size_5,
+            c5_bid_size_1,
+            c5_bid_size_2,
+            c5_bid_size_3,
+            c5_bid_size_4,
+            c5_bid_size_5,
+            c1_ask_size_1,
+            c1_ask_size_2,
+            c1_ask_size_3,
+            c1_ask_size_4,
+            c1_ask_size_5,
+            c2_ask_size_1,
+            c2_ask_size_2,
+            c2_ask_size_3,
+            c2_ask_size_4,
+            c2_ask_size_5,
+            c3_ask_size_1,
+            c3_ask_size_2,
+            c3_ask_size_3,
+            c3_ask_size_4,
+            c3_ask_size_5,
+            c4_ask_size_1,
+            c4_ask_size_2,
+            c4_ask_size_3,
+            c4_ask_size_4,
+            c4_ask_size_5,
+            c5_ask_size_1,
+            c5_ask_size_2,
+            c5_ask_size_3,
+            c5_ask_size_4,
+            c5_ask_size_5,
+            timestamp,
+            dealing_flag
+        );
+    }
+}
+
 impl PriceData {
+    /// Formats a one-line top-of-book view for logs, e.g.
+    /// `CS.D.EURUSD.CFD.IP bid=1.0841 ask=1.0842 spread=0.0001 @14:03:27`
+    ///
+    /// The level-1 prices of the Pricing adapter are used when present, otherwise
+    /// the market stream's `BID`/`OFFER`. Missing values are shown as `-`. Use
+    /// `Display` for the full JSON representation.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let fields = &self.fields;
+        let epic = self.item_name.rsplit(':').next().unwrap_or_default();
+        let bid = fields.best_bid().or(fields.bid);
+        let offer = fields.best_offer().or(fields.offer);
+        let spread = fields.spread().or_else(|| Some(offer? - bid?));
+        let time = fields.update_time.clone().or_else(|| {
+            fields
+                .timestamp
+                .and_then(|millis| DateTime::from_timestamp_millis(millis as i64))
+                .map(|time| time.format("%H:%M:%S").to_string())
+        });
+        let show = |value: Option<f64>| value.map_or_else(|| "-".to_string(), |v| v.to_string());
+        let (bid_text, offer_text) = (show(bid), show(offer));
+        // Round the spread to the quoted precision to hide float noise
+        let decimals = [&bid_text, &offer_text]
+            .iter()
+            .filter_map(|text| text.split_once('.').map(|(_, fraction)| fraction.len()))
+            .max()
+            .unwrap_or(0);
+        let spread_text =
+            spread.map_or_else(|| "-".to_string(), |spread| format!("{spread:.decimals$}"));
+        format!(
+            "{epic} bid={bid_text} ask={offer_text} spread={spread_text} @{}",
+            time.as_deref().unwrap_or("-")
+        )
+    }
+
+    /// Iterates over the numeric fields that changed in this update
+    ///
+    /// Snapshot updates (`is_snapshot`) may report every field as changed, even
+    /// if its value is the same as in the previous update. Text fields such as
+    /// `DLG_FLAG` are not included.
+    ///
+    /// # Returns
+    ///
+    /// `(Lightstreamer field name, new value)` pairs, in declaration order
+    pub fn changed(&self) -> impl Iterator<Item = (&'static str, f64)> {
+        self.changed_fields
+            .numeric_values()
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|value| (name, value)))
+    }
+
     /// Converts a Lightstreamer ItemUpdate to a PriceData object
     ///
+    /// Strict variant: any field that fails to parse fails the whole conversion.
+    /// See [`PriceData::from_item_update_partial`] to keep the other fields.
+    ///
     /// # Arguments
     ///
     /// * `item_update` - The ItemUpdate from Lightstreamer containing price data
@@ -594,6 +934,27 @@ impl PriceData {
     ///
     /// A Result containing either the parsed PriceData or an error message
     pub fn from_item_update(item_update: &ItemUpdate) -> Result<Self, String> {
+        let (price_data, errors) = Self::from_item_update_partial(item_update);
+        if errors.is_empty() {
+            Ok(price_data)
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
+    /// Converts a Lightstreamer ItemUpdate to a PriceData object, field by field
+    ///
+    /// Fields that fail to parse are left as `None` and reported, so one malformed
+    /// value does not discard the rest of the update.
+    ///
+    /// # Arguments
+    ///
+    /// * `item_update` - The ItemUpdate from Lightstreamer containing price data
+    ///
+    /// # Returns
+    ///
+    /// The parsed PriceData and the parse errors, empty if every field parsed
+    pub fn from_item_update_partial(item_update: &ItemUpdate) -> (Self, Vec<String>) {
         // Extract the item_name, defaulting to an empty string if None
         let item_name = item_update.item_name.clone().unwrap_or_default();
 
@@ -604,42 +965,39 @@ impl PriceData {
         let is_snapshot = item_update.is_snapshot;
 
         // Convert fields
-        let fields = Self::create_price_fields(&item_update.fields)?;
+        let mut errors = Vec::new();
+        let fields = Self::create_price_fields(&item_update.fields, &mut errors);
 
         // Convert changed_fields by first creating a HashMap<String, Option<String>>
         let mut changed_fields_map: HashMap<String, Option<String>> = HashMap::new();
         for (key, value) in &item_update.changed_fields {
             changed_fields_map.insert(key.clone(), Some(value.clone()));
         }
-        let changed_fields = Self::create_price_fields(&changed_fields_map)?;
-
-        Ok(PriceData {
-            item_name,
-            item_pos,
-            fields,
-            changed_fields,
-            is_snapshot,
-        })
+        let changed_fields = Self::create_price_fields(&changed_fields_map, &mut errors);
+
+        // Both maps carry changed values, so keep each error once
+        let mut seen = HashSet::new();
+        errors.retain(|error| seen.insert(error.clone()));
+        (
+            PriceData {
+                item_name,
+                item_pos,
+                fields,
+                changed_fields,
+                is_snapshot,
+            },
+            errors,
+        )
     }
 
-    // Helper method to create PriceFields from a HashMap
+    // Helper method to create PriceFields from a HashMap, collecting parse errors
     fn create_price_fields(
         fields_map: &HashMap<String, Option<String>>,
-    ) -> Result<PriceFields, String> {
+        errors: &mut Vec<String>,
+    ) -> PriceFields {
         // Helper function to safely get a field value
         let get_field = |key: &str| -> Option<String> { fields_map.get(key).cloned().flatten() };
 
-        // Helper function to parse float values
-        let parse_float = |key: &str| -> Result<Option<f64>, String> {
-            match get_field(key) {
-                Some(val) if !val.is_empty() => val
-                    .parse::<f64>()
-                    .map(Some)
-                    .map_err(|_| format!("Failed to parse {key} as float: {val}")),
-                _ => Ok(None),
-            }
-        };
-
         // Parse dealing flag (case-insensitive to handle Lightstreamer lowercase conversion)
         let dealing_flag = match get_field("DLG_FLAG")
             .as_deref()
@@ -655,19 +1013,30 @@ impl PriceData {
             Some("AUCTION") => Some(DealingFlag::Auction),
             Some("AUCTIONNOEDIT") => Some(DealingFlag::AuctionNoEdit),
             Some("SUSPEND") => Some(DealingFlag::Suspend),
-            Some(unknown) => return Err(format!("Unknown dealing flag: {unknown}")),
+            Some(unknown) => Some(DealingFlag::Unknown(unknown.to_string())),
             None => None,
         };
 
-        Ok(PriceFields {
-            mid_open: parse_float("MID_OPEN")?,
-            high: parse_float("HIGH")?,
-            low: parse_float("LOW")?,
-            bid: parse_float("BID")?,
-            offer: parse_float("OFFER")?,
-            change: parse_float("CHANGE")?,
-            change_pct: parse_float("CHANGE_PCT")?,
-            market_delay: parse_float("MARKET_DELAY")?,
+        // Helper function to parse float values, recording failures as errors
+        let mut parse_float = |key: &str| -> Option<f64> {
+            match get_field(key) {
+                Some(val) if !val.is_empty() => val
+                    .parse::<f64>()
+                    .map_err(|_| errors.push(format!("Failed to parse {key} as float: {val}")))
+                    .ok(),
+                _ => None,
+            }
+        };
+
+        PriceFields {
+            mid_open: parse_float("MID_OPEN"),
+            high: parse_float("HIGH"),
+            low: parse_float("LOW"),
+            bid: parse_float("BID"),
+            offer: parse_float("OFFER"),
+            change: parse_float("CHANGE"),
+            change_pct: parse_float("CHANGE_PCT"),
+            market_delay: parse_float("MARKET_DELAY"),
             market_state: get_field("MARKET_STATE"),
             update_time: get_field("UPDATE_TIME"),
 
@@ -675,32 +1044,32 @@ impl PriceData {
             ask_quote_id: get_field("ASKQUOTEID"),
 
             // Bid ladder prices
-            bid_price1: parse_float("BIDPRICE1")?,
-            bid_price2: parse_float("BIDPRICE2")?,
-            bid_price3: parse_float("BIDPRICE3")?,
-            bid_price4: parse_float("BIDPRICE4")?,
-            bid_price5: parse_float("BIDPRICE5")?,
+            bid_price1: parse_float("BIDPRICE1"),
+            bid_price2: parse_float("BIDPRICE2"),
+            bid_price3: parse_float("BIDPRICE3"),
+            bid_price4: parse_float("BIDPRICE4"),
+            bid_price5: parse_float("BIDPRICE5"),
 
             // Ask ladder prices
-            ask_price1: parse_float("ASKPRICE1")?,
-            ask_price2: parse_float("ASKPRICE2")?,
-            ask_price3: parse_float("ASKPRICE3")?,
-            ask_price4: parse_float("ASKPRICE4")?,
-            ask_price5: parse_float("ASKPRICE5")?,
+            ask_price1: parse_float("ASKPRICE1"),
+            ask_price2: parse_float("ASKPRICE2"),
+            ask_price3: parse_float("ASKPRICE3"),
+            ask_price4: parse_float("ASKPRICE4"),
+            ask_price5: parse_float("ASKPRICE5"),
 
             // Bid sizes
-            bid_size1: parse_float("BIDSIZE1")?,
-            bid_size2: parse_float("BIDSIZE2")?,
-            bid_size3: parse_float("BIDSIZE3")?,
-            bid_size4: parse_float("BIDSIZE4")?,
-            bid_size5: parse_float("BIDSIZE5")?,
+            bid_size1: parse_float("BIDSIZE1"),
+            bid_size2: parse_float("BIDSIZE2"),
+            bid_size3: parse_float("BIDSIZE3"),
+            bid_size4: parse_float("BIDSIZE4"),
+            bid_size5: parse_float("BIDSIZE5"),
 
             // Ask sizes
-            ask_size1: parse_float("ASKSIZE1")?,
-            ask_size2: parse_float("ASKSIZE2")?,
-            ask_size3: parse_float("ASKSIZE3")?,
-            ask_size4: parse_float("ASKSIZE4")?,
-            ask_size5: parse_float("ASKSIZE5")?,
+            ask_size1: parse_float("ASKSIZE1"),
+            ask_size2: parse_float("ASKSIZE2"),
+            ask_size3: parse_float("ASKSIZE3"),
+            ask_size4: parse_float("ASKSIZE4"),
+            ask_size5: parse_float("ASKSIZE5"),
 
             // Currencies
             currency0: get_field("CURRENCY0"),
@@ -711,75 +1080,229 @@ impl PriceData {
             currency5: get_field("CURRENCY5"),
 
             // Bid size thresholds (expanded 1..5 for C1..C5)
-            c1_bid_size_1: parse_float("C1BIDSIZE1")?,
-            c1_bid_size_2: parse_float("C1BIDSIZE2")?,
-            c1_bid_size_3: parse_float("C1BIDSIZE3")?,
-            c1_bid_size_4: parse_float("C1BIDSIZE4")?,
-            c1_bid_size_5: parse_float("C1BIDSIZE5")?,
-
-            c2_bid_size_1: parse_float("C2BIDSIZE1")?,
-            c2_bid_size_2: parse_float("C2BIDSIZE2")?,
-            c2_bid_size_3: parse_float("C2BIDSIZE3")?,
-            c2_bid_size_4: parse_float("C2BIDSIZE4")?,
-            c2_bid_size_5: parse_float("C2BIDSIZE5")?,
-
-            c3_bid_size_1: parse_float("C3BIDSIZE1")?,
-            c3_bid_size_2: parse_float("C3BIDSIZE2")?,
-            c3_bid_size_3: parse_float("C3BIDSIZE3")?,
-            c3_bid_size_4: parse_float("C3BIDSIZE4")?,
-            c3_bid_size_5: parse_float("C3BIDSIZE5")?,
-
-            c4_bid_size_1: parse_float("C4BIDSIZE1")?,
-            c4_bid_size_2: parse_float("C4BIDSIZE2")?,
-            c4_bid_size_3: parse_float("C4BIDSIZE3")?,
-            c4_bid_size_4: parse_float("C4BIDSIZE4")?,
-            c4_bid_size_5: parse_float("C4BIDSIZE5")?,
-
-            c5_bid_size_1: parse_float("C5BIDSIZE1")?,
-            c5_bid_size_2: parse_float("C5BIDSIZE2")?,
-            c5_bid_size_3: parse_float("C5BIDSIZE3")?,
-            c5_bid_size_4: parse_float("C5BIDSIZE4")?,
-            c5_bid_size_5: parse_float("C5BIDSIZE5")?,
+            c1_bid_size_1: parse_float("C1BIDSIZE1"),
+            c1_bid_size_2: parse_float("C1BIDSIZE2"),
+            c1_bid_size_3: parse_float("C1BIDSIZE3"),
+            c1_bid_size_4: parse_float("C1BIDSIZE4"),
+            c1_bid_size_5: parse_float("C1BIDSIZE5"),
+
+            c2_bid_size_1: parse_float("C2BIDSIZE1"),
+            c2_bid_size_2: parse_float("C2BIDSIZE2"),
+            c2_bid_size_3: parse_float("C2BIDSIZE3"),
+            c2_bid_size_4: parse_float("C2BIDSIZE4"),
+            c2_bid_size_5: parse_float("C2BIDSIZE5"),
+
+            c3_bid_size_1: parse_float("C3BIDSIZE1"),
+            c3_bid_size_2: parse_float("C3BIDSIZE2"),
+            c3_bid_size_3: parse_float("C3BIDSIZE3"),
+            c3_bid_size_4: parse_float("C3BIDSIZE4"),
+            c3_bid_size_5: parse_float("C3BIDSIZE5"),
+
+            c4_bid_size_1: parse_float("C4BIDSIZE1"),
+            c4_bid_size_2: parse_float("C4BIDSIZE2"),
+            c4_bid_size_3: parse_float("C4BIDSIZE3"),
+            c4_bid_size_4: parse_float("C4BIDSIZE4"),
+            c4_bid_size_5: parse_float("C4BIDSIZE5"),
+
+            c5_bid_size_1: parse_float("C5BIDSIZE1"),
+            c5_bid_size_2: parse_float("C5BIDSIZE2"),
+            c5_bid_size_3: parse_float("C5BIDSIZE3"),
+            c5_bid_size_4: parse_float("C5BIDSIZE4"),
+            c5_bid_size_5: parse_float("C5BIDSIZE5"),
 
             // Ask size thresholds (expanded 1..5 for C1..C5)
-            c1_ask_size_1: parse_float("C1ASKSIZE1")?,
-            c1_ask_size_2: parse_float("C1ASKSIZE2")?,
-            c1_ask_size_3: parse_float("C1ASKSIZE3")?,
-            c1_ask_size_4: parse_float("C1ASKSIZE4")?,
-            c1_ask_size_5: parse_float("C1ASKSIZE5")?,
-
-            c2_ask_size_1: parse_float("C2ASKSIZE1")?,
-            c2_ask_size_2: parse_float("C2ASKSIZE2")?,
-            c2_ask_size_3: parse_float("C2ASKSIZE3")?,
-            c2_ask_size_4: parse_float("C2ASKSIZE4")?,
-            c2_ask_size_5: parse_float("C2ASKSIZE5")?,
-
-            c3_ask_size_1: parse_float("C3ASKSIZE1")?,
-            c3_ask_size_2: parse_float("C3ASKSIZE2")?,
-            c3_ask_size_3: parse_float("C3ASKSIZE3")?,
-            c3_ask_size_4: parse_float("C3ASKSIZE4")?,
-            c3_ask_size_5: parse_float("C3ASKSIZE5")?,
-
-            c4_ask_size_1: parse_float("C4ASKSIZE1")?,
-            c4_ask_size_2: parse_float("C4ASKSIZE2")?,
-            c4_ask_size_3: parse_float("C4ASKSIZE3")?,
-            c4_ask_size_4: parse_float("C4ASKSIZE4")?,
-            c4_ask_size_5: parse_float("C4ASKSIZE5")?,
-
-            c5_ask_size_1: parse_float("C5ASKSIZE1")?,
-            c5_ask_size_2: parse_float("C5ASKSIZE2")?,
-            c5_ask_size_3: parse_float("C5ASKSIZE3")?,
-            c5_ask_size_4: parse_float("C5ASKSIZE4")?,
-            c5_ask_size_5: parse_float("C5ASKSIZE5")?,
-
-            timestamp: parse_float("TIMESTAMP")?,
+            c1_ask_size_1: parse_float("C1ASKSIZE1"),
+            c1_ask_size_2: parse_float("C1ASKSIZE2"),
+            c1_ask_size_3: parse_float("C1ASKSIZE3"),
+            c1_ask_size_4: parse_float("C1ASKSIZE4"),
+            c1_ask_size_5: parse_float("C1ASKSIZE5"),
+
+            c2_ask_size_1: parse_float("C2ASKSIZE1"),
+            c2_ask_size_2: parse_float("C2ASKSIZE2"),
+            c2_ask_size_3: parse_float("C2ASKSIZE3"),
+            c2_ask_size_4: parse_float("C2ASKSIZE4"),
+            c2_ask_size_5: parse_float("C2ASKSIZE5"),
+
+            c3_ask_size_1: parse_float("C3ASKSIZE1"),
+            c3_ask_size_2: parse_float("C3ASKSIZE2"),
+            c3_ask_size_3: parse_float("C3ASKSIZE3"),
+            c3_ask_size_4: parse_float("C3ASKSIZE4"),
+            c3_ask_size_5: parse_float("C3ASKSIZE5"),
+
+            c4_ask_size_1: parse_float("C4ASKSIZE1"),
+            c4_ask_size_2: parse_float("C4ASKSIZE2"),
+            c4_ask_size_3: parse_float("C4ASKSIZE3"),
+            c4_ask_size_4: parse_float("C4ASKSIZE4"),
+            c4_ask_size_5: parse_float("C4ASKSIZE5"),
+
+            c5_ask_size_1: parse_float("C5ASKSIZE1"),
+            c5_ask_size_2: parse_float("C5ASKSIZE2"),
+            c5_ask_size_3: parse_float("C5ASKSIZE3"),
+            c5_ask_size_4: parse_float("C5ASKSIZE4"),
+            c5_ask_size_5: parse_float("C5ASKSIZE5"),
+
+            timestamp: parse_float("TIMESTAMP"),
             dealing_flag,
+        }
+    }
+}
+
+/// Coalesced top-of-book view built from successive price updates
+///
+/// Streaming updates only carry the fields that changed, so the tracker keeps the
+/// latest known value of every field. A snapshot update replaces the whole book.
+#[derive(Debug, Clone, Default)]
+pub struct BookTracker {
+    book: PriceFields,
+}
+
+impl BookTracker {
+    /// Creates an empty tracker
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies a price update to the tracked book
+    ///
+    /// # Arguments
+    ///
+    /// * `update` - Price update received from the stream
+    pub fn apply(&mut self, update: &PriceData) {
+        if update.is_snapshot {
+            self.book = PriceFields::default();
+        }
+        self.book.merge(&update.fields);
+        self.book.merge(&update.changed_fields);
+    }
+
+    /// Gets the latest coalesced fields
+    #[must_use]
+    pub fn snapshot(&self) -> &PriceFields {
+        &self.book
+    }
+}
+
+/// Consolidated quote for an instrument
+///
+/// Built by `QuoteAggregator` from the market (default adapter) and price
+/// (Pricing adapter) streams of the same epic.
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Quote {
+    /// Instrument EPIC
+    pub epic: String,
+    /// Best bid price
+    pub bid: f64,
+    /// Best offer (ask) price
+    pub offer: f64,
+    /// Midpoint between bid and offer
+    pub mid: f64,
+    /// Time of the last price update, or when the quote was built if the stream
+    /// reported none
+    pub timestamp: DateTime<Utc>,
+    /// Dealing status reported by the price stream
+    pub dealing_flag: Option<DealingFlag>,
+}
+
+/// Latest market and price stream fields of one epic
+#[derive(Debug, Clone, Default)]
+struct QuoteBooks {
+    market: BookTracker,
+    price: BookTracker,
+}
+
+/// Merges market and price stream updates into one `Quote` per epic
+///
+/// Level-1 prices from the price stream take precedence; the market stream's
+/// `BID`/`OFFER` fill in until the price stream has delivered them.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut aggregator = QuoteAggregator::new();
+/// if let Some(quote) = aggregator.apply_price("IX.D.DAX.DAILY.IP", &price_update) {
+///     println!("{} mid {}", quote.epic, quote.mid);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct QuoteAggregator {
+    books: HashMap<String, QuoteBooks>,
+}
+
+impl QuoteAggregator {
+    /// Creates an empty aggregator
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies a market stream update for an epic
+    ///
+    /// # Returns
+    ///
+    /// The updated quote, or `None` while the bid or offer is still unknown
+    pub fn apply_market(&mut self, epic: &str, update: &PriceData) -> Option<Quote> {
+        self.books
+            .entry(epic.to_string())
+            .or_default()
+            .market
+            .apply(update);
+        self.quote(epic)
+    }
+
+    /// Applies a price stream update for an epic
+    ///
+    /// # Returns
+    ///
+    /// The updated quote, or `None` while the bid or offer is still unknown
+    pub fn apply_price(&mut self, epic: &str, update: &PriceData) -> Option<Quote> {
+        self.books
+            .entry(epic.to_string())
+            .or_default()
+            .price
+            .apply(update);
+        self.quote(epic)
+    }
+
+    /// Gets the current quote of an epic
+    ///
+    /// # Returns
+    ///
+    /// The quote, or `None` if the bid or offer has not been received yet
+    #[must_use]
+    pub fn quote(&self, epic: &str) -> Option<Quote> {
+        let books = self.books.get(epic)?;
+        let price = books.price.snapshot();
+        let market = books.market.snapshot();
+
+        let bid = price.best_bid().or(price.bid).or(market.bid)?;
+        let offer = price.best_offer().or(price.offer).or(market.offer)?;
+        let timestamp = price
+            .timestamp
+            .and_then(|millis| DateTime::from_timestamp_millis(millis as i64))
+            .unwrap_or_else(Utc::now);
+
+        Some(Quote {
+            epic: epic.to_string(),
+            bid,
+            offer,
+            mid: (bid + offer) / 2.0,
+            timestamp,
+            dealing_flag: price.dealing_flag.clone(),
         })
     }
 }
 
 impl From<&ItemUpdate> for PriceData {
     fn from(item_update: &ItemUpdate) -> Self {
-        PriceData::from_item_update(item_update).unwrap_or_default()
+        let (price_data, errors) = PriceData::from_item_update_partial(item_update);
+        if !errors.is_empty() {
+            tracing::warn!(
+                "Partial price update for {}: {}",
+                price_data.item_name,
+                errors.join("; ")
+            );
+        }
+        price_data
     }
 }
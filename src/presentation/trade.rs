@@ -1,3 +1,4 @@
+use crate::presentation::account::Activity;
 use crate::presentation::order::{Direction, OrderType, Status, TimeInForce};
 use crate::presentation::serialization::{option_string_empty_as_none, string_as_float_opt};
 use lightstreamer_rs::subscription::ItemUpdate;
@@ -35,6 +36,196 @@ pub struct TradeFields {
     /// Optional working order update details.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wou: Option<WorkingOrderUpdate>,
+    /// Whether the update belongs to the initial snapshot of the subscription
+    ///
+    /// The TRADE subscription is DISTINCT with a snapshot, so IG may replay recent
+    /// events on subscribe; these are flagged so consumers can skip them.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_snapshot: bool,
+}
+
+impl TradeFields {
+    /// Parses the JSON carried by the CONFIRMS field
+    ///
+    /// # Returns
+    ///
+    /// The confirmation, or `None` if the field is missing or is not a JSON confirmation
+    #[must_use]
+    pub fn confirmation(&self) -> Option<TradeConfirmation> {
+        serde_json::from_str(self.confirms.as_deref()?).ok()
+    }
+}
+
+/// Deal confirmation received through the CONFIRMS field of the trade stream
+///
+/// Mirrors `OrderConfirmationResponse`, returned by the REST confirms endpoint.
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct TradeConfirmation {
+    /// Unique deal identifier.
+    #[serde(rename = "dealId")]
+    #[serde(with = "option_string_empty_as_none")]
+    #[serde(default)]
+    pub deal_id: Option<String>,
+    /// Client-generated reference for the deal.
+    #[serde(rename = "dealReference")]
+    #[serde(with = "option_string_empty_as_none")]
+    #[serde(default)]
+    pub deal_reference: Option<String>,
+    /// Status of the position or order affected by the deal.
+    #[serde(default)]
+    pub status: Option<Status>,
+    /// Outcome of the deal (accepted or rejected).
+    #[serde(rename = "dealStatus")]
+    #[serde(default)]
+    pub deal_status: Option<DealStatus>,
+    /// Reason code, e.g. `SUCCESS` or the cause of a rejection.
+    #[serde(with = "option_string_empty_as_none")]
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Epic identifier for the instrument.
+    #[serde(with = "option_string_empty_as_none")]
+    #[serde(default)]
+    pub epic: Option<String>,
+    /// Direction of the deal (buy or sell).
+    #[serde(default)]
+    pub direction: Option<Direction>,
+    /// Deal size.
+    #[serde(with = "string_as_float_opt")]
+    #[serde(default)]
+    pub size: Option<f64>,
+    /// Price level of the deal.
+    #[serde(with = "string_as_float_opt")]
+    #[serde(default)]
+    pub level: Option<f64>,
+    /// Realised profit or loss, for closing deals.
+    #[serde(with = "string_as_float_opt")]
+    #[serde(default)]
+    pub profit: Option<f64>,
+    /// Currency of the profit.
+    #[serde(rename = "profitCurrency")]
+    #[serde(with = "option_string_empty_as_none")]
+    #[serde(default)]
+    pub profit_currency: Option<String>,
+    /// Expiry of the instrument.
+    #[serde(with = "option_string_empty_as_none")]
+    #[serde(default)]
+    pub expiry: Option<String>,
+    /// Date and time of the confirmation.
+    #[serde(with = "option_string_empty_as_none")]
+    #[serde(default)]
+    pub date: Option<String>,
+}
+
+impl TradeConfirmation {
+    /// Checks whether IG accepted the deal
+    #[must_use]
+    pub fn is_accepted(&self) -> bool {
+        self.deal_status == Some(DealStatus::Accepted)
+    }
+
+    /// Checks whether IG rejected the deal; `reason` explains why
+    #[must_use]
+    pub fn is_rejected(&self) -> bool {
+        self.deal_status == Some(DealStatus::Rejected)
+    }
+}
+
+impl From<&Activity> for TradeConfirmation {
+    /// Rebuilds a confirmation from an account activity, e.g. to fill a streaming gap
+    ///
+    /// Direction, size and level are only known for activities fetched with details.
+    /// Activities carry no reason code, only a free-text description, so `reason`
+    /// is left empty; `deal_status` is only set for accepted or rejected activities.
+    fn from(activity: &Activity) -> Self {
+        let details = activity.details.as_ref();
+        Self {
+            deal_id: activity.deal_id.clone(),
+            deal_reference: activity
+                .deal_reference
+                .clone()
+                .or_else(|| details.and_then(|details| details.deal_reference.clone())),
+            status: None,
+            deal_status: match activity.status {
+                Some(Status::Accepted) => Some(DealStatus::Accepted),
+                Some(Status::Rejected) => Some(DealStatus::Rejected),
+                _ => None,
+            },
+            reason: None,
+            epic: activity.epic.clone(),
+            direction: details.and_then(|details| details.direction.clone()),
+            size: details.and_then(|details| details.size),
+            level: details.and_then(|details| details.level),
+            profit: None,
+            profit_currency: None,
+            expiry: activity.period.clone(),
+            date: Some(activity.date.clone()),
+        }
+    }
+}
+
+impl From<&TradeConfirmation> for TradeFields {
+    /// Wraps a confirmation as the CONFIRMS field of a trade update
+    fn from(confirmation: &TradeConfirmation) -> Self {
+        Self {
+            confirms: serde_json::to_string(confirmation).ok(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Outcome of a deal, carried as `dealStatus` by the trade stream
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DealStatus {
+    /// Deal was accepted
+    Accepted,
+    /// Deal was rejected
+    Rejected,
+    /// Status not known to this version of the client, with the value IG sent
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+/// Status carried by an open position update (OPU) of the trade stream
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OpuStatus {
+    /// Position is open
+    Open,
+    /// Position was opened by this deal
+    Opened,
+    /// Position was updated, e.g. its stop or limit changed
+    Updated,
+    /// Position was amended
+    Amended,
+    /// Position was partially closed
+    PartiallyClosed,
+    /// Position was fully closed
+    FullyClosed,
+    /// Position was closed
+    Closed,
+    /// Position was deleted, i.e. it no longer exists
+    Deleted,
+    /// Status not known to this version of the client, with the value IG sent
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+/// Status carried by a working order update (WOU) of the trade stream
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WouStatus {
+    /// Working order is open
+    Open,
+    /// Working order was updated, e.g. its level changed
+    Updated,
+    /// Working order was amended
+    Amended,
+    /// Working order was deleted, either cancelled or filled
+    Deleted,
+    /// Status not known to this version of the client, with the value IG sent
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Structure representing details of an open position update.
@@ -58,11 +249,11 @@ pub struct OpenPositionUpdate {
     pub epic: Option<String>,
     /// Status of the position.
     #[serde(default)]
-    pub status: Option<Status>,
+    pub status: Option<OpuStatus>,
     /// Deal status of the position.
     #[serde(rename = "dealStatus")]
     #[serde(default)]
-    pub deal_status: Option<Status>,
+    pub deal_status: Option<DealStatus>,
     /// Price level of the position.
     #[serde(with = "string_as_float_opt")]
     #[serde(default)]
@@ -116,11 +307,11 @@ pub struct WorkingOrderUpdate {
     pub epic: Option<String>,
     /// Status of the working order.
     #[serde(default)]
-    pub status: Option<Status>,
+    pub status: Option<WouStatus>,
     /// Deal status of the working order.
     #[serde(rename = "dealStatus")]
     #[serde(default)]
-    pub deal_status: Option<Status>,
+    pub deal_status: Option<DealStatus>,
     /// Price level at which the working order is set.
     #[serde(with = "string_as_float_opt")]
     #[serde(default)]
@@ -194,15 +385,17 @@ impl TradeData {
         // Extract is_snapshot
         let is_snapshot = item_update.is_snapshot;
 
-        // Convert fields
-        let fields = Self::create_trade_fields(&item_update.fields)?;
+        // Convert fields, tagging them with the snapshot flag of the update
+        let mut fields = Self::create_trade_fields(&item_update.fields)?;
+        fields.is_snapshot = is_snapshot;
 
         // Convert changed_fields by first creating a HashMap<String, Option<String>>
         let mut changed_fields_map: HashMap<String, Option<String>> = HashMap::new();
         for (key, value) in &item_update.changed_fields {
             changed_fields_map.insert(key.clone(), Some(value.clone()));
         }
-        let changed_fields = Self::create_trade_fields(&changed_fields_map)?;
+        let mut changed_fields = Self::create_trade_fields(&changed_fields_map)?;
+        changed_fields.is_snapshot = is_snapshot;
 
         Ok(TradeData {
             item_name,
@@ -258,7 +451,12 @@ impl TradeData {
             None
         };
 
-        Ok(TradeFields { confirms, opu, wou })
+        Ok(TradeFields {
+            confirms,
+            opu,
+            wou,
+            is_snapshot: false,
+        })
     }
 }
 
@@ -0,0 +1,203 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! JSON-lines export of streaming data
+//!
+//! `JsonLinesWriter` appends any `Serialize` value to a file as one JSON document per
+//! line. `spawn_tee` sits between a streaming receiver and its consumer, persisting
+//! every item while forwarding it downstream, e.g. to record `PriceData` for a later
+//! replay. It does the file I/O on the blocking pool, so the runtime is never stalled.
+
+use crate::constants::JSONL_FLUSH_INTERVAL_MS;
+use crate::error::AppError;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::task::{JoinHandle, spawn_blocking};
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::error;
+
+/// Buffered writer of newline-delimited JSON
+///
+/// The buffer is flushed when at least `JSONL_FLUSH_INTERVAL_MS` elapsed since the
+/// previous flush, and when the writer is dropped.
+pub struct JsonLinesWriter<T: Serialize> {
+    writer: BufWriter<File>,
+    last_flush: Instant,
+    written: u64,
+    _item: PhantomData<fn(&T)>,
+}
+
+impl<T: Serialize> JsonLinesWriter<T> {
+    /// Opens a file for appending, creating it if needed
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - File to write to
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, AppError> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            last_flush: Instant::now(),
+            written: 0,
+            _item: PhantomData,
+        })
+    }
+
+    /// Appends an item as a single JSON line
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to persist
+    pub fn write(&mut self, item: &T) -> Result<(), AppError> {
+        serde_json::to_writer(&mut self.writer, item)?;
+        self.writer.write_all(b"\n")?;
+        self.written += 1;
+        if self.last_flush.elapsed() >= Duration::from_millis(JSONL_FLUSH_INTERVAL_MS) {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Appends already serialized items, one per line, and flushes them
+    fn append_lines(&mut self, lines: &[String]) -> Result<(), AppError> {
+        for line in lines {
+            self.writer.write_all(line.as_bytes())?;
+            self.writer.write_all(b"\n")?;
+            self.written += 1;
+        }
+        self.flush()
+    }
+
+    /// Writes the buffered lines to the file
+    pub fn flush(&mut self) -> Result<(), AppError> {
+        self.writer.flush()?;
+        self.last_flush = Instant::now();
+        Ok(())
+    }
+
+    /// Gets the number of items written so far
+    #[must_use]
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+}
+
+impl<T: Serialize> Drop for JsonLinesWriter<T> {
+    fn drop(&mut self) {
+        if let Err(e) = self.writer.flush() {
+            error!("Failed to flush JSON-lines file: {}", e);
+        }
+    }
+}
+
+/// Forwards every item of a receiver downstream while appending it to a file
+///
+/// Items are serialized as they arrive and written to the file every
+/// `JSONL_FLUSH_INTERVAL_MS` on the blocking pool. A write failure is logged once and
+/// recording stops, but items keep being forwarded. The task ends, writing the
+/// remaining items, when the source closes or the downstream receiver is dropped.
+///
+/// # Arguments
+///
+/// * `receiver` - Source of items, e.g. from `market_subscribe`
+/// * `path` - File the items are appended to
+///
+/// # Returns
+///
+/// The forwarding task and the downstream receiver, or an error if the file
+/// cannot be opened.
+///
+/// # Examples
+///
+/// ```ignore
+/// let receiver = streamer.market_subscribe(epics, fields).await?;
+/// let (_task, mut receiver) = spawn_tee(receiver, "dax.jsonl").await?;
+/// while let Some(price) = receiver.recv().await {
+///     // same consumer code as without recording
+/// }
+/// ```
+pub async fn spawn_tee<T>(
+    mut receiver: mpsc::UnboundedReceiver<T>,
+    path: impl AsRef<Path>,
+) -> Result<(JoinHandle<()>, mpsc::UnboundedReceiver<T>), AppError>
+where
+    T: Serialize + Send + 'static,
+{
+    let path = path.as_ref().to_path_buf();
+    let writer = spawn_blocking(move || JsonLinesWriter::<T>::create(path))
+        .await
+        .map_err(|e| AppError::Generic(format!("failed to open JSON-lines file: {e}")))??;
+    let mut writer = Some(writer);
+    let (tx, rx) = mpsc::unbounded_channel();
+    let task = tokio::spawn(async move {
+        let mut lines = Vec::new();
+        let mut flush = interval(Duration::from_millis(JSONL_FLUSH_INTERVAL_MS));
+        flush.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                item = receiver.recv() => {
+                    let Some(item) = item else { break };
+                    if writer.is_some() {
+                        match serde_json::to_string(&item) {
+                            Ok(line) => lines.push(line),
+                            Err(e) => {
+                                error!("Failed to record item, recording stopped: {}", e);
+                                writer = None;
+                            }
+                        }
+                    }
+                    if tx.send(item).is_err() {
+                        break;
+                    }
+                }
+                _ = flush.tick() => {
+                    writer = append_lines(writer, std::mem::take(&mut lines)).await;
+                }
+            }
+        }
+        append_lines(writer, lines).await;
+    });
+    Ok((task, rx))
+}
+
+/// Appends serialized items on the blocking pool
+///
+/// # Returns
+///
+/// The writer, or `None` once a write failed and recording stopped
+async fn append_lines<T>(
+    writer: Option<JsonLinesWriter<T>>,
+    lines: Vec<String>,
+) -> Option<JsonLinesWriter<T>>
+where
+    T: Serialize + Send + 'static,
+{
+    let mut writer = writer?;
+    if lines.is_empty() {
+        return Some(writer);
+    }
+    match spawn_blocking(move || {
+        let result = writer.append_lines(&lines);
+        (writer, result)
+    })
+    .await
+    {
+        Ok((writer, Ok(()))) => Some(writer),
+        Ok((_, Err(e))) => {
+            error!("Failed to record items, recording stopped: {}", e);
+            None
+        }
+        Err(e) => {
+            error!("Recording task failed, recording stopped: {}", e);
+            None
+        }
+    }
+}
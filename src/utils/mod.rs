@@ -1,5 +1,7 @@
 /// Configuration utilities
 pub mod config;
+/// Module containing the JSON-lines export of streaming data
+pub mod export;
 /// Module containing financial calculation utilities
 pub mod finance;
 /// Module containing utilities for handling unique identifiers
@@ -8,8 +10,15 @@ pub mod id;
 pub mod logger;
 /// Module containing parsing utilities for instrument names and other data
 pub mod parsing;
+/// Module containing the replay of recorded price data
+pub mod replay;
+/// Module containing a generic retry helper with exponential backoff
+pub mod retry;
 
+pub use export::*;
 pub use finance::*;
 pub use id::*;
 pub use logger::*;
 pub use parsing::*;
+pub use replay::*;
+pub use retry::*;
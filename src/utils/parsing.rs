@@ -1,4 +1,7 @@
+use crate::error::AppError;
+use crate::presentation::instrument::OptionType;
 use crate::presentation::order::Status;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize};
@@ -221,6 +224,149 @@ pub fn parse_instrument_name(instrument_name: &str) -> ParsedOptionInfo {
     }
 }
 
+/// Components of an IG epic such as `IX.D.DAX.DAILY.IP` or `OP.D.OTCSPX3.6910P.IP`
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ParsedEpic {
+    /// Market type prefix (e.g. "IX", "CS", "OP")
+    pub market_type: String,
+    /// Instrument symbol (e.g. "DAX", "OTCSPX3")
+    pub symbol: String,
+    /// Strike price for option epics
+    pub strike: Option<f64>,
+    /// Option type for option epics
+    pub option_type: Option<OptionType>,
+    /// Period segment (e.g. "DAILY", "TODAY", "IFD"); `None` for option epics
+    pub period: Option<String>,
+}
+
+impl ParsedEpic {
+    /// Checks whether the epic refers to an option
+    #[must_use]
+    pub fn is_option(&self) -> bool {
+        self.option_type.is_some()
+    }
+
+    /// Fills the strike and option type from the instrument name when the epic
+    /// does not encode them (e.g. `DO.D.OTCDDAX.41.IP`)
+    ///
+    /// # Arguments
+    /// * `instrument_name` - Name such as "Germany 40 27500 PUT"
+    #[must_use]
+    pub fn with_instrument_name(mut self, instrument_name: &str) -> Self {
+        if self.option_type.is_none() {
+            let info = parse_instrument_name(instrument_name);
+            self.option_type = info.option_type.as_deref().and_then(OptionType::parse);
+            if self.option_type.is_some() {
+                self.strike = self
+                    .strike
+                    .or_else(|| info.strike.and_then(|s| s.parse().ok()));
+            }
+        }
+        self
+    }
+}
+
+/// Parse an epic into market type, symbol, period and, for options, strike and type
+///
+/// # Examples
+///
+/// ```
+/// use ig_client::presentation::instrument::OptionType;
+/// use ig_client::utils::parsing::parse_epic;
+///
+/// let epic = parse_epic("OP.D.OTCSPX3.6910P.IP").unwrap();
+/// assert_eq!(epic.symbol, "OTCSPX3");
+/// assert_eq!(epic.strike, Some(6910.0));
+/// assert_eq!(epic.option_type, Some(OptionType::Put));
+///
+/// let epic = parse_epic("IX.D.DAX.DAILY.IP").unwrap();
+/// assert_eq!(epic.market_type, "IX");
+/// assert_eq!(epic.period.as_deref(), Some("DAILY"));
+/// ```
+///
+/// # Errors
+/// Returns `AppError::InvalidInput` when the epic has fewer than three segments or
+/// an empty segment.
+pub fn parse_epic(epic: &str) -> Result<ParsedEpic, AppError> {
+    lazy_static::lazy_static! {
+        // Option strike and type segment like "6910P" or "21500C"
+        static ref OPTION_SEGMENT: Regex = Regex::new(r"^(\d+)([CP])$").unwrap();
+    }
+
+    let parts: Vec<&str> = epic.trim().split('.').collect();
+    if parts.len() < 3 || parts.iter().any(|part| part.is_empty()) {
+        return Err(AppError::InvalidInput(format!("malformed epic: {epic}")));
+    }
+
+    let mut parsed = ParsedEpic {
+        market_type: parts[0].to_string(),
+        symbol: parts[2].to_string(),
+        strike: None,
+        option_type: None,
+        period: None,
+    };
+
+    if let Some(segment) = parts.get(3) {
+        match OPTION_SEGMENT.captures(segment) {
+            Some(captures) => {
+                parsed.strike = captures[1].parse().ok();
+                parsed.option_type = OptionType::parse(&captures[2]);
+            }
+            None => parsed.period = Some(segment.to_string()),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Timestamp formats seen across the IG REST API, tried in order
+const IG_DATETIME_FORMATS: [&str; 9] = [
+    "%Y/%m/%d %H:%M:%S:%3f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d %H:%M:%S",
+    "%Y/%m/%d %H:%M",
+    "%Y:%m:%d-%H:%M:%S",
+];
+
+/// Parses any of the date-time formats IG uses as UTC
+///
+/// RFC 3339 strings keep their offset; every other format, such as
+/// `2025/10/30 18:13:53:000` or `2025-10-30T17:13:53`, carries no offset and is
+/// interpreted as UTC. Use it on the `...UTC`/`dateUtc` variants of IG fields, as the
+/// plain variants are in the account's local time.
+///
+/// # Arguments
+/// * `raw` - The date-time string as returned by IG
+///
+/// # Returns
+/// The parsed timestamp, or `None` if the value is empty or in an unknown format
+///
+/// # Examples
+/// ```
+/// use ig_client::utils::parsing::parse_ig_datetime;
+///
+/// let parsed = parse_ig_datetime("2025/10/30 18:13:53:000").unwrap();
+/// assert_eq!(parsed.to_rfc3339(), "2025-10-30T18:13:53+00:00");
+/// ```
+#[must_use]
+pub fn parse_ig_datetime(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    if let Ok(date) = DateTime::parse_from_rfc3339(raw) {
+        return Some(date.with_timezone(&Utc));
+    }
+
+    IG_DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(raw, format).ok())
+        .map(|naive| naive.and_utc())
+}
+
 /// Helper function to deserialize null values as empty vectors
 pub fn deserialize_null_as_empty_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
 where
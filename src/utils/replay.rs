@@ -0,0 +1,125 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Replay of recorded price data
+//!
+//! `PriceReplay` reads a JSON-lines file of `PriceData`, such as one recorded with
+//! `spawn_tee`, and feeds it through the same kind of channel `market_subscribe`
+//! returns. Records are either sent as fast as possible or paced by their
+//! `TIMESTAMP` field, optionally sped up.
+
+use crate::error::AppError;
+use crate::presentation::price::PriceData;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+
+/// Replay task, resolving to the number of records sent, and receiver of the records
+type ReplayChannel = (
+    JoinHandle<Result<u64, AppError>>,
+    mpsc::UnboundedReceiver<PriceData>,
+);
+
+/// Source replaying a JSON-lines file of `PriceData`
+#[derive(Debug, Clone)]
+pub struct PriceReplay {
+    path: PathBuf,
+    speed: Option<f64>,
+}
+
+impl PriceReplay {
+    /// Creates a replay sending every record as fast as possible
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - JSON-lines file with one `PriceData` per line
+    #[must_use]
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            speed: None,
+        }
+    }
+
+    /// Paces the records by their timestamps
+    ///
+    /// The gap between two records is divided by `speed`: `1.0` replays in real
+    /// time, `10.0` ten times faster. A non-positive speed sends as fast as possible.
+    /// Records without a timestamp are sent without delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `speed` - Replay speed multiplier
+    #[must_use]
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = (speed > 0.0 && speed.is_finite()).then_some(speed);
+        self
+    }
+
+    /// Starts the replay
+    ///
+    /// Empty lines are skipped. The replay stops at the first line that is not a
+    /// `PriceData` document, or when the receiver is dropped.
+    ///
+    /// # Returns
+    ///
+    /// The replay task, which resolves to the number of records sent, and the
+    /// receiver of the records, or an error if the file cannot be opened.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let (_task, mut receiver) = PriceReplay::new("dax.jsonl").with_speed(10.0).spawn()?;
+    /// while let Some(price) = receiver.recv().await {
+    ///     strategy.on_price(&price);
+    /// }
+    /// ```
+    pub fn spawn(self) -> Result<ReplayChannel, AppError> {
+        // Opened here so a missing file fails the call; the lines are read asynchronously
+        let file = File::from_std(std::fs::File::open(&self.path)?);
+        let (tx, rx) = mpsc::unbounded_channel();
+        let speed = self.speed;
+        let task = tokio::spawn(async move {
+            let mut sent = 0;
+            let mut previous_timestamp: Option<f64> = None;
+            let mut lines = BufReader::new(file).lines();
+            let mut index = 0;
+            while let Some(line) = lines.next_line().await? {
+                index += 1;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let price: PriceData = serde_json::from_str(&line)
+                    .map_err(|e| AppError::Deserialization(format!("line {}: {}", index, e)))?;
+
+                let timestamp = price.fields.timestamp.or(price.changed_fields.timestamp);
+                if let Some(speed) = speed
+                    && let (Some(previous), Some(current)) = (previous_timestamp, timestamp)
+                    && current > previous
+                {
+                    // A tiny speed can overflow the gap, which then waits as long as possible
+                    let gap = Duration::try_from_secs_f64((current - previous) / 1000.0 / speed)
+                        .unwrap_or(Duration::MAX);
+                    sleep(gap).await;
+                }
+                if timestamp.is_some() {
+                    previous_timestamp = timestamp;
+                }
+
+                if tx.send(price).is_err() {
+                    break;
+                }
+                sent += 1;
+            }
+            Ok(sent)
+        });
+        Ok((task, rx))
+    }
+}
@@ -0,0 +1,182 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Generic retry with exponential backoff
+//!
+//! `retry_with_backoff` re-runs any fallible async operation while the error is
+//! considered retryable by the `RetryPolicy`. The delay grows by `multiplier` after
+//! every attempt up to `max_delay`; `AppError::RateLimited` errors carrying a
+//! `retry_after` hint wait for that hint instead.
+
+use crate::error::AppError;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::warn;
+
+/// Retry behaviour for `retry_with_backoff`
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry
+    pub initial_delay: Duration,
+    /// Factor applied to the delay after every retry
+    pub multiplier: f64,
+    /// Upper bound for the computed delay
+    pub max_delay: Duration,
+    /// Decides whether an error should be retried
+    pub retryable: fn(&AppError) -> bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay: Duration::from_millis(500),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(30),
+            retryable: is_transient,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy with the default backoff and transient-error predicate
+    ///
+    /// # Arguments
+    ///
+    /// * `max_retries` - Maximum number of retries after the first attempt
+    /// * `initial_delay` - Delay before the first retry
+    #[must_use]
+    pub fn new(max_retries: u32, initial_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_delay,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the factor applied to the delay after every retry
+    #[must_use]
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Sets the upper bound for the computed delay
+    #[must_use]
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets the predicate deciding which errors are retried
+    #[must_use]
+    pub fn with_retryable(mut self, retryable: fn(&AppError) -> bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Gets the backoff delay before a retry
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - Zero-based index of the retry
+    ///
+    /// # Returns
+    ///
+    /// `initial_delay * multiplier^retry`, capped at `max_delay`
+    #[must_use]
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.max(0.0).powi(retry as i32);
+        let delay = self.initial_delay.as_secs_f64() * factor;
+        if !delay.is_finite() || delay >= self.max_delay.as_secs_f64() {
+            self.max_delay
+        } else {
+            Duration::from_secs_f64(delay)
+        }
+    }
+
+    /// Gets the delay before retrying after `error`
+    ///
+    /// A `retry_after` hint from `AppError::RateLimited` takes precedence over the
+    /// computed backoff.
+    #[must_use]
+    pub fn delay_for(&self, retry: u32, error: &AppError) -> Duration {
+        match error {
+            AppError::RateLimited {
+                retry_after: Some(retry_after),
+                ..
+            } => *retry_after,
+            _ => self.backoff(retry),
+        }
+    }
+}
+
+/// Default retry predicate for transient failures
+///
+/// Network errors, timeouts, rate limiting and 5xx responses are retried.
+#[must_use]
+pub fn is_transient(error: &AppError) -> bool {
+    match error {
+        AppError::Network(_)
+        | AppError::Timeout(_)
+        | AppError::RateLimited { .. }
+        | AppError::RateLimitExceeded => true,
+        AppError::Unexpected(status) => status.is_server_error(),
+        _ => false,
+    }
+}
+
+/// Runs `operation` until it succeeds, fails with a non-retryable error, or the
+/// retries are exhausted
+///
+/// # Arguments
+///
+/// * `operation` - Closure producing the future to run on every attempt
+/// * `policy` - Retry limits, backoff and retryable-error predicate
+///
+/// # Returns
+///
+/// The first successful result, or the last error
+///
+/// # Example
+///
+/// ```ignore
+/// let policy = RetryPolicy::new(5, Duration::from_millis(200));
+/// let positions = retry_with_backoff(|| client.get_positions(), policy).await?;
+/// ```
+pub async fn retry_with_backoff<F, Fut, T>(
+    mut operation: F,
+    policy: RetryPolicy,
+) -> Result<T, AppError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, AppError>>,
+{
+    let mut retry = 0;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                if retry >= policy.max_retries || !(policy.retryable)(&e) {
+                    return Err(e);
+                }
+                let delay = policy.delay_for(retry, &e);
+                retry += 1;
+                warn!(
+                    "Request failed (attempt {}/{}): {}. Retrying in {} ms...",
+                    retry,
+                    policy.max_retries,
+                    e,
+                    delay.as_millis()
+                );
+                sleep(delay).await;
+            }
+        }
+    }
+}
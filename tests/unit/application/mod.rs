@@ -2,4 +2,10 @@ mod models;
 mod services;
 mod test_auth;
 mod test_client;
+mod test_dynamic_streamer;
 mod test_listener;
+mod test_market_cache;
+mod test_price_allowance;
+mod test_stream_stats;
+mod test_streamer_config;
+mod test_subscription_events;
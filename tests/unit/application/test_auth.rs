@@ -1,5 +1,9 @@
 use chrono::Utc;
-use ig_client::application::auth::{Session, WebsocketInfo};
+use ig_client::application::auth::{
+    Session, SessionHandle, WebsocketInfo, lightstreamer_server_url,
+};
+use ig_client::application::config::IgEnvironment;
+use ig_client::error::AppError;
 use ig_client::model::auth::OAuthToken;
 
 fn make_session(expires_in_secs: i64, with_oauth: bool) -> Session {
@@ -27,15 +31,24 @@ fn make_session(expires_in_secs: i64, with_oauth: bool) -> Session {
     }
 }
 
+/// OAuth session whose token was issued just now
+fn make_fresh_oauth_session(expires_in_secs: i64) -> Session {
+    let mut session = make_session(expires_in_secs, true);
+    if let Some(token) = session.oauth_token.as_mut() {
+        token.created_at = Utc::now();
+    }
+    session
+}
+
 #[test]
-fn websocket_info_password_formats_and_empty_when_missing() {
+fn websocket_info_password_formats_and_errors_when_missing() {
     let ws = WebsocketInfo {
         server: "https://ls".into(),
         cst: Some("CST123".into()),
         x_security_token: Some("XST456".into()),
         account_id: "ACC123".into(),
     };
-    assert_eq!(ws.get_ws_password(), "CST-CST123|XST-XST456");
+    assert_eq!(ws.get_ws_password().unwrap(), "CST-CST123|XST-XST456");
 
     let ws_missing = WebsocketInfo {
         server: "https://ls".into(),
@@ -43,7 +56,10 @@ fn websocket_info_password_formats_and_empty_when_missing() {
         x_security_token: Some("XST456".into()),
         account_id: "ACC123".into(),
     };
-    assert_eq!(ws_missing.get_ws_password(), "");
+    assert!(matches!(
+        ws_missing.get_ws_password(),
+        Err(AppError::InvalidInput(message)) if message.contains("CST")
+    ));
 }
 
 #[test]
@@ -80,3 +96,130 @@ fn session_expiry_checks_and_alias() {
     let secs = s.seconds_until_expiry();
     assert!(secs <= 120 && secs > 0);
 }
+
+#[tokio::test]
+async fn session_handle_publishes_fresh_credentials() {
+    let handle = SessionHandle::new();
+    assert!(handle.current().is_none());
+
+    let mut receiver = handle.subscribe();
+    let ws = WebsocketInfo {
+        server: "https://ls".into(),
+        cst: Some("NEWCST".into()),
+        x_security_token: Some("NEWXST".into()),
+        account_id: "ACC123".into(),
+    };
+    handle.clone().publish(ws);
+
+    receiver
+        .changed()
+        .await
+        .expect("handle should still be alive");
+    let published = receiver
+        .borrow_and_update()
+        .clone()
+        .expect("credentials published");
+    assert_eq!(
+        published.get_ws_password().unwrap(),
+        "CST-NEWCST|XST-NEWXST"
+    );
+    assert_eq!(
+        handle.current().map(|ws| ws.account_id),
+        Some("ACC123".to_string())
+    );
+}
+
+#[test]
+fn session_exposes_oauth_tokens_only_for_v3() {
+    let oauth = make_fresh_oauth_session(3600)
+        .oauth_session()
+        .expect("oauth session");
+    assert_eq!(oauth.account_id, "ACC123");
+    assert_eq!(oauth.access_token, "AT");
+    assert_eq!(oauth.refresh_token, "RT");
+    assert_eq!(oauth.authorization_header(), "Bearer AT");
+    assert!(!oauth.is_expired(60));
+    assert!(oauth.is_expired(7200));
+
+    assert!(make_session(3600, false).oauth_session().is_none());
+}
+
+#[test]
+fn lightstreamer_server_url_normalises_endpoints() {
+    assert_eq!(
+        lightstreamer_server_url("demo-apd.marketdatasystems.com"),
+        "https://demo-apd.marketdatasystems.com/lightstreamer"
+    );
+    assert_eq!(
+        lightstreamer_server_url("https://apd.marketdatasystems.com/"),
+        "https://apd.marketdatasystems.com/lightstreamer"
+    );
+    assert_eq!(
+        lightstreamer_server_url("https://apd.marketdatasystems.com/lightstreamer"),
+        "https://apd.marketdatasystems.com/lightstreamer"
+    );
+}
+
+#[test]
+fn websocket_info_server_override_keeps_credentials() {
+    let ws = make_session(120, false)
+        .get_websocket_info()
+        .with_server(IgEnvironment::Demo.lightstreamer_url());
+    assert_eq!(
+        ws.server,
+        "https://demo-apd.marketdatasystems.com/lightstreamer"
+    );
+    assert_eq!(ws.cst.as_deref(), Some("CSTTOKEN"));
+    assert_eq!(ws.account_id, "ACC123");
+}
+
+#[test]
+fn websocket_info_password_uses_session_tokens_verbatim() {
+    let ws = make_session(120, false).get_websocket_info();
+    assert_eq!(ws.get_ws_password().unwrap(), "CST-CSTTOKEN|XST-XSTOKEN");
+
+    let ws = WebsocketInfo {
+        server: "https://ls".into(),
+        cst: Some("a1b2-c3d4".into()),
+        x_security_token: Some("e5f6+g7h8=".into()),
+        account_id: "ACC123".into(),
+    };
+    assert_eq!(
+        ws.get_ws_password().unwrap(),
+        "CST-a1b2-c3d4|XST-e5f6+g7h8="
+    );
+}
+
+#[test]
+fn websocket_info_password_rejects_empty_and_malformed_tokens() {
+    let base = WebsocketInfo {
+        server: "https://ls".into(),
+        cst: Some("CST123".into()),
+        x_security_token: Some("XST456".into()),
+        account_id: "ACC123".into(),
+    };
+
+    let missing_xst = WebsocketInfo {
+        x_security_token: None,
+        ..base.clone()
+    };
+    assert!(matches!(
+        missing_xst.get_ws_password(),
+        Err(AppError::InvalidInput(message)) if message.contains("X-SECURITY-TOKEN")
+    ));
+
+    let empty_cst = WebsocketInfo {
+        cst: Some("  ".into()),
+        ..base.clone()
+    };
+    assert!(matches!(
+        empty_cst.get_ws_password(),
+        Err(AppError::InvalidInput(message)) if message.contains("empty")
+    ));
+
+    let piped = WebsocketInfo {
+        cst: Some("CST|123".into()),
+        ..base
+    };
+    assert!(piped.get_ws_password().is_err());
+}
@@ -1,6 +1,68 @@
-use ig_client::application::client::Client;
+use ig_client::application::client::{
+    Client, StreamClose, is_dry_run_reference, is_graceful_close,
+};
+use ig_client::application::config::{Config, RateLimiterConfig};
 use ig_client::application::interfaces::market::MarketService;
+use ig_client::application::interfaces::order::OrderService;
+use ig_client::application::rate_limiter::RequestKind;
 use ig_client::error::AppError;
+use ig_client::model::http::HttpClientConfig;
+use ig_client::model::requests::CreateOrderRequest;
+use ig_client::presentation::order::Direction;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Serves an OAuth login, then answers every price request with the historical
+/// data allowance error, counting the price requests
+fn serve_exhausted_price_allowance(price_requests: Arc<AtomicUsize>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { break };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let (status, body) = if request_line.contains("/session") {
+                (
+                    "200 OK",
+                    r#"{"clientId":"CLIENT","accountId":"ACCOUNT","timezoneOffset":0,"lightstreamerEndpoint":"https://demo-apd.marketdatasystems.com","oauthToken":{"access_token":"access","refresh_token":"refresh","scope":"profile","token_type":"Bearer","expires_in":"30000"}}"#,
+                )
+            } else {
+                price_requests.fetch_add(1, Ordering::SeqCst);
+                (
+                    "403 Forbidden",
+                    r#"{"errorCode":"error.public-api.exceeded-account-historical-data-allowance"}"#,
+                )
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+    url
+}
 
 #[tokio::test]
 async fn get_multiple_market_details_empty_returns_default() {
@@ -12,6 +74,33 @@ async fn get_multiple_market_details_empty_returns_default() {
     assert!(resp.market_details.is_empty());
 }
 
+#[tokio::test]
+async fn get_client_sentiment_batch_more_than_50_returns_error() {
+    let client = Client::new();
+    let market_ids: Vec<String> = (0..51).map(|i| format!("MARKET{}", i)).collect();
+    let err = client
+        .get_client_sentiment_batch(&market_ids)
+        .await
+        .expect_err("should be Err");
+    match err {
+        AppError::InvalidInput(msg) => {
+            assert!(msg.contains("maximum number of market ids is 50"));
+        }
+        other => panic!("Unexpected error: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn get_market_details_bulk_without_epics_sends_no_batch() {
+    let client = Client::new();
+    assert!(client.get_market_details_batches(&[]).await.is_empty());
+    let details = client
+        .get_market_details_bulk(&[])
+        .await
+        .expect("should be Ok for empty");
+    assert!(details.is_empty());
+}
+
 #[tokio::test]
 async fn get_multiple_market_details_more_than_50_returns_error() {
     let client = Client::new();
@@ -35,3 +124,94 @@ fn client_default_new_equivalence() {
     let _c2: Client = Default::default();
     // Construction should not panic; no further assertions needed
 }
+
+#[test]
+fn stream_close_classifies_known_graceful_reasons() {
+    assert!(is_graceful_close("No more requests to fulfill"));
+    assert!(is_graceful_close(
+        "Error: no more requests to fulfill (code 31)"
+    ));
+    assert!(!is_graceful_close("Connection reset by peer"));
+
+    assert_eq!(
+        StreamClose::from_reason("No more requests to fulfill"),
+        StreamClose::Graceful
+    );
+    let unexpected = StreamClose::from_reason("Connection reset by peer");
+    assert!(!unexpected.is_graceful());
+    assert!(matches!(
+        unexpected.into_result(),
+        Err(AppError::WebSocketError(reason)) if reason == "Connection reset by peer"
+    ));
+}
+
+#[tokio::test]
+async fn dry_run_skips_orders_and_confirms_them() {
+    let client = Client::new().with_dry_run(true);
+    assert!(client.is_dry_run());
+
+    let order = CreateOrderRequest::market(
+        "CS.D.EURUSD.CFD.IP".to_string(),
+        Direction::Buy,
+        1.0,
+        None,
+        None,
+    );
+    let response = client.create_order(&order).await.unwrap();
+    assert!(response.deal_reference.starts_with("DRYRUN-"));
+    assert!(is_dry_run_reference(&response.deal_reference));
+
+    let confirmation = client
+        .get_order_confirmation(&response.deal_reference)
+        .await
+        .unwrap();
+    assert!(confirmation.is_accepted());
+    assert_eq!(confirmation.deal_reference, response.deal_reference);
+
+    let deleted = client.delete_working_order("DIAAAA").await.unwrap();
+    assert!(is_dry_run_reference(&deleted));
+    assert_ne!(deleted, response.deal_reference);
+}
+
+#[tokio::test]
+async fn client_rate_limiter_follows_configuration() {
+    let mut config = Config::new();
+    config.rate_limiter = RateLimiterConfig {
+        max_requests: 1,
+        period_seconds: 3600,
+        burst_size: 1,
+    };
+    let client = Client::from_config(config, HttpClientConfig::default()).unwrap();
+
+    let non_trading = client.rate_limiters().for_kind(RequestKind::NonTrading);
+    let non_trading = non_trading.read().await;
+    assert!(non_trading.check());
+    assert!(!non_trading.check());
+}
+
+#[tokio::test]
+async fn exhausted_historical_allowance_fails_without_retrying() {
+    let price_requests = Arc::new(AtomicUsize::new(0));
+    let mut config = Config::new();
+    config.rest_api.base_url = serve_exhausted_price_allowance(Arc::clone(&price_requests));
+    config.api_version = Some(3);
+    let client = Client::from_config(config, HttpClientConfig::default()).unwrap();
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(5),
+        client.get_historical_prices(
+            "CS.D.EURUSD.CFD.IP",
+            "MINUTE",
+            "2025-01-01T00:00:00",
+            "2025-01-01T01:00:00",
+        ),
+    )
+    .await
+    .expect("the allowance error should not be retried");
+
+    assert!(matches!(
+        result,
+        Err(AppError::AllowanceExceeded { resets_in: None })
+    ));
+    assert_eq!(price_requests.load(Ordering::SeqCst), 1);
+}
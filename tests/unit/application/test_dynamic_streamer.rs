@@ -0,0 +1,134 @@
+use ig_client::application::dynamic_streamer::{
+    ConnectionState, DynamicMarketStreamer, ReconnectDecision, ReconnectPolicy, ReconnectTracker,
+};
+use ig_client::error::AppError;
+use std::collections::HashSet;
+use std::time::Duration;
+
+fn policy(max_attempts: u32) -> ReconnectPolicy {
+    ReconnectPolicy {
+        max_attempts,
+        delay: Duration::from_millis(10),
+    }
+}
+
+#[test]
+fn repeated_connect_failures_give_up_at_ceiling() {
+    let mut tracker = ReconnectTracker::new(policy(3));
+    let mut fatal: Vec<String> = Vec::new();
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+        let err = AppError::WebSocketError(format!("connect failed #{attempts}"));
+        match tracker.on_failure() {
+            ReconnectDecision::Retry(delay) => assert_eq!(delay, Duration::from_millis(10)),
+            ReconnectDecision::GiveUp => {
+                fatal.push(err.to_string());
+                break;
+            }
+        }
+    }
+
+    assert_eq!(attempts, 3);
+    assert_eq!(tracker.failures(), 3);
+    assert_eq!(fatal.len(), 1);
+    assert!(fatal[0].contains("connect failed #3"));
+}
+
+#[test]
+fn reset_clears_failures_after_successful_connection() {
+    let mut tracker = ReconnectTracker::new(policy(2));
+    assert_eq!(
+        tracker.on_failure(),
+        ReconnectDecision::Retry(Duration::from_millis(10))
+    );
+    tracker.reset();
+    assert_eq!(tracker.failures(), 0);
+    assert_eq!(
+        tracker.on_failure(),
+        ReconnectDecision::Retry(Duration::from_millis(10))
+    );
+    assert_eq!(tracker.on_failure(), ReconnectDecision::GiveUp);
+}
+
+#[test]
+fn default_policy_has_finite_ceiling() {
+    let policy = ReconnectPolicy::default();
+    assert!(policy.max_attempts > 0);
+    let mut tracker = ReconnectTracker::new(policy);
+    let decisions: Vec<_> = (0..policy.max_attempts)
+        .map(|_| tracker.on_failure())
+        .collect();
+    assert_eq!(decisions.last(), Some(&ReconnectDecision::GiveUp));
+}
+
+#[tokio::test]
+async fn keyed_receiver_can_only_be_taken_once() {
+    let streamer = DynamicMarketStreamer::new(HashSet::new()).await.unwrap();
+
+    assert!(streamer.get_keyed_receiver().await.is_ok());
+    assert!(matches!(
+        streamer.get_keyed_receiver().await,
+        Err(AppError::InvalidInput(_))
+    ));
+    assert!(streamer.latest("IX.D.DAX.DAILY.IP").await.is_none());
+}
+
+#[tokio::test]
+async fn new_streamer_is_stopped_until_started() {
+    let streamer = DynamicMarketStreamer::new(HashSet::new())
+        .await
+        .unwrap()
+        .with_auto_restart(3, Duration::from_millis(10));
+
+    assert_eq!(streamer.connection_state().await, ConnectionState::Stopped);
+    assert_eq!(
+        streamer.clone().connection_state().await,
+        ConnectionState::Stopped
+    );
+}
+
+#[tokio::test]
+async fn add_without_validator_accepts_any_epic() {
+    let streamer = DynamicMarketStreamer::new(HashSet::new()).await.unwrap();
+
+    streamer.add("NOT.AN.EPIC".to_string()).await.unwrap();
+    streamer.add("NOT.AN.EPIC".to_string()).await.unwrap();
+    assert_eq!(streamer.get_epics().await, vec!["NOT.AN.EPIC".to_string()]);
+}
+
+#[tokio::test]
+async fn add_many_and_remove_many_update_the_epic_set_once() {
+    let streamer = DynamicMarketStreamer::new(HashSet::new()).await.unwrap();
+
+    streamer
+        .add_many(vec![
+            "OP.D.OTCSPX3.6880P.IP".to_string(),
+            "OP.D.OTCSPX3.6880C.IP".to_string(),
+            "OP.D.OTCSPX3.6880P.IP".to_string(),
+        ])
+        .await
+        .unwrap();
+    let mut epics = streamer.get_epics().await;
+    epics.sort();
+    assert_eq!(
+        epics,
+        vec![
+            "OP.D.OTCSPX3.6880C.IP".to_string(),
+            "OP.D.OTCSPX3.6880P.IP".to_string()
+        ]
+    );
+
+    streamer
+        .remove_many(vec![
+            "OP.D.OTCSPX3.6880C.IP".to_string(),
+            "IX.D.DAX.DAILY.IP".to_string(),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(
+        streamer.get_epics().await,
+        vec!["OP.D.OTCSPX3.6880P.IP".to_string()]
+    );
+}
@@ -0,0 +1,35 @@
+use crate::common::market_details;
+use ig_client::application::market_cache::MarketDetailsCache;
+use ig_client::presentation::market::MarketDetails;
+use std::time::Duration;
+
+fn details(epic: &str) -> MarketDetails {
+    market_details(epic, serde_json::json!({}))
+}
+
+#[test]
+fn cache_serves_entries_by_epic_until_cleared() {
+    let cache = MarketDetailsCache::new(Duration::from_secs(60));
+    assert!(cache.is_empty());
+
+    cache.insert(&details("IX.D.DAX.IFD.IP"));
+    cache.insert(&details("IX.D.FTSE.IFD.IP"));
+    assert_eq!(cache.len(), 2);
+    let hit = cache.get("IX.D.DAX.IFD.IP").unwrap();
+    assert_eq!(hit.instrument.epic, "IX.D.DAX.IFD.IP");
+    assert!(cache.get("IX.D.SPTRD.IFD.IP").is_none());
+
+    cache.clear();
+    assert!(cache.get("IX.D.DAX.IFD.IP").is_none());
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn cache_expires_entries_after_ttl() {
+    let cache = MarketDetailsCache::new(Duration::from_millis(1));
+    cache.insert(&details("IX.D.DAX.IFD.IP"));
+    std::thread::sleep(Duration::from_millis(5));
+    assert!(cache.get("IX.D.DAX.IFD.IP").is_none());
+    assert_eq!(cache.len(), 0);
+    assert_eq!(cache.ttl(), Duration::from_millis(1));
+}
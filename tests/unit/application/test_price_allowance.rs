@@ -0,0 +1,104 @@
+use ig_client::application::price_allowance::PriceAllowanceMonitor;
+use ig_client::presentation::market::PriceAllowance;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+fn allowance(remaining: i64) -> PriceAllowance {
+    PriceAllowance {
+        remaining_allowance: remaining,
+        total_allowance: 10000,
+        allowance_expiry: 3600,
+    }
+}
+
+#[test]
+fn callback_fires_only_below_threshold_as_allowance_decreases() {
+    let monitor = PriceAllowanceMonitor::new();
+    let fired = Arc::new(AtomicUsize::new(0));
+    let fired_clone = Arc::clone(&fired);
+    monitor.set_threshold(1000, move |_| {
+        fired_clone.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let observations: Vec<bool> = [5000, 2500, 1000, 999, 400, 0]
+        .into_iter()
+        .map(|remaining| monitor.observe(&allowance(remaining)))
+        .collect();
+
+    assert_eq!(observations, vec![false, false, false, true, true, true]);
+    assert_eq!(fired.load(Ordering::SeqCst), 3);
+    assert_eq!(
+        monitor.last_allowance().map(|a| a.remaining_allowance),
+        Some(0)
+    );
+}
+
+#[test]
+fn pending_delay_only_applies_while_low() {
+    let monitor = PriceAllowanceMonitor::new();
+    monitor.set_threshold(100, |_| {});
+    monitor.set_auto_delay(Some(Duration::from_millis(250)));
+
+    monitor.observe(&allowance(500));
+    assert!(!monitor.is_low());
+    assert_eq!(monitor.pending_delay(), None);
+
+    monitor.observe(&allowance(50));
+    assert!(monitor.is_low());
+    assert_eq!(monitor.pending_delay(), Some(Duration::from_millis(250)));
+
+    // Allowance reset by IG
+    monitor.observe(&allowance(10000));
+    assert_eq!(monitor.pending_delay(), None);
+}
+
+#[test]
+fn no_threshold_never_reports_low() {
+    let monitor = PriceAllowanceMonitor::new();
+    monitor.set_auto_delay(Some(Duration::from_secs(1)));
+    assert!(!monitor.observe(&allowance(0)));
+    assert_eq!(monitor.pending_delay(), None);
+}
+
+#[test]
+fn allowance_reports_exhaustion_remaining_and_reset() {
+    let available = allowance(250);
+    assert!(!available.is_exhausted());
+    assert_eq!(available.remaining(), 250);
+    assert_eq!(available.resets_in(), Duration::from_secs(3600));
+
+    let overdrawn = PriceAllowance {
+        remaining_allowance: -5,
+        total_allowance: 10000,
+        allowance_expiry: -1,
+    };
+    assert!(overdrawn.is_exhausted());
+    assert_eq!(overdrawn.remaining(), 0);
+    assert_eq!(overdrawn.resets_in(), Duration::ZERO);
+    assert!(allowance(0).is_exhausted());
+}
+
+#[test]
+fn warning_threshold_does_not_mark_allowance_low() {
+    let monitor = PriceAllowanceMonitor::new();
+    monitor.set_warning_threshold(Some(1000));
+
+    assert!(!monitor.observe(&allowance(10)));
+    assert!(!monitor.is_low());
+}
+
+#[tokio::test(start_paused = true)]
+async fn monitor_reset_time_accounts_for_elapsed_time() {
+    let monitor = PriceAllowanceMonitor::new();
+    assert_eq!(monitor.resets_in(), None);
+
+    monitor.observe(&allowance(10));
+    assert_eq!(monitor.resets_in(), Some(Duration::from_secs(3600)));
+
+    tokio::time::advance(Duration::from_secs(600)).await;
+    assert_eq!(monitor.resets_in(), Some(Duration::from_secs(3000)));
+
+    tokio::time::advance(Duration::from_secs(7200)).await;
+    assert_eq!(monitor.resets_in(), Some(Duration::ZERO));
+}
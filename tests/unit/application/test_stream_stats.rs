@@ -0,0 +1,53 @@
+use ig_client::application::stream_stats::StreamStatsRecorder;
+use std::time::Duration;
+
+#[test]
+fn recorder_counts_received_and_dropped_per_item() {
+    let recorder = StreamStatsRecorder::new();
+    recorder.record_received("MARKET:IX.D.DAX.DAILY.IP");
+    recorder.record_received("MARKET:IX.D.DAX.DAILY.IP");
+    recorder.record_dropped("MARKET:IX.D.DAX.DAILY.IP");
+    recorder.record_received("MARKET:CS.D.EURUSD.CFD.IP");
+
+    let stats = recorder.snapshot();
+    let dax = stats.get("MARKET:IX.D.DAX.DAILY.IP").unwrap();
+    assert_eq!(dax.received, 2);
+    assert_eq!(dax.dropped, 1);
+    assert!(dax.last_update_at.is_some());
+    assert_eq!(stats.total_received(), 3);
+    assert_eq!(stats.total_dropped(), 1);
+    assert!(stats.get("MARKET:UNKNOWN").is_none());
+}
+
+#[test]
+fn stats_display_uses_prometheus_text_format() {
+    let recorder = StreamStatsRecorder::new();
+    recorder.record_received("MARKET:IX.D.DAX.DAILY.IP");
+    recorder.record_dropped("MARKET:IX.D.DAX.DAILY.IP");
+
+    let text = recorder.snapshot().to_string();
+    assert!(text.contains("# TYPE ig_stream_updates_received_total counter"));
+    assert!(text.contains("ig_stream_updates_received_total{item=\"MARKET:IX.D.DAX.DAILY.IP\"} 1"));
+    assert!(text.contains("ig_stream_updates_dropped_total{item=\"MARKET:IX.D.DAX.DAILY.IP\"} 1"));
+    assert!(
+        text.contains("ig_stream_seconds_since_last_update{item=\"MARKET:IX.D.DAX.DAILY.IP\"}")
+    );
+}
+
+#[test]
+fn stats_freshness_follows_latest_update() {
+    let recorder = StreamStatsRecorder::new();
+    assert!(recorder.snapshot().last_update_at().is_none());
+    assert!(!recorder.snapshot().is_fresh(Duration::from_secs(30)));
+
+    recorder.record_dropped("MARKET:IX.D.DAX.DAILY.IP");
+    assert!(!recorder.snapshot().is_fresh(Duration::from_secs(30)));
+
+    recorder.record_received("MARKET:IX.D.DAX.DAILY.IP");
+    let stats = recorder.snapshot();
+    assert!(stats.last_update_at().is_some());
+    assert!(stats.is_fresh(Duration::from_secs(30)));
+
+    std::thread::sleep(Duration::from_millis(5));
+    assert!(!stats.is_fresh(Duration::from_millis(1)));
+}
@@ -0,0 +1,93 @@
+use ig_client::application::streamer_config::{StreamReconnectPolicy, StreamerConfig};
+use std::time::Duration;
+
+#[test]
+fn default_config_uses_standard_item_prefixes() {
+    let config = StreamerConfig::default();
+    assert!(config.forced_transport.is_some());
+    assert_eq!(
+        config.market_item("IX.D.DAX.DAILY.IP"),
+        "MARKET:IX.D.DAX.DAILY.IP"
+    );
+    assert_eq!(
+        config.price_item("ABC123", "IX.D.DAX.DAILY.IP"),
+        "PRICE:ABC123:IX.D.DAX.DAILY.IP"
+    );
+    assert_eq!(config.trade_item("ABC123"), "TRADE:ABC123");
+    assert_eq!(config.staleness_window, Duration::from_secs(30));
+    assert_eq!(
+        config
+            .with_staleness_window(Duration::from_secs(5))
+            .staleness_window,
+        Duration::from_secs(5)
+    );
+    assert!(StreamerConfig::default().lightstreamer_endpoint.is_none());
+    assert_eq!(
+        StreamerConfig::default()
+            .with_lightstreamer_endpoint("demo-apd.marketdatasystems.com")
+            .lightstreamer_endpoint
+            .as_deref(),
+        Some("demo-apd.marketdatasystems.com")
+    );
+    assert!(!StreamerConfig::default().backfill_on_reconnect);
+    assert!(
+        StreamerConfig::default()
+            .with_backfill_on_reconnect(true)
+            .backfill_on_reconnect
+    );
+}
+
+#[test]
+fn idle_timeout_is_disabled_by_default() {
+    assert!(StreamerConfig::default().idle_timeout.is_none());
+    assert_eq!(
+        StreamerConfig::default()
+            .with_idle_timeout(Duration::from_secs(120))
+            .idle_timeout,
+        Some(Duration::from_secs(120))
+    );
+}
+
+#[test]
+fn custom_prefixes_drive_item_names_and_epic_extraction() {
+    let config = StreamerConfig::default()
+        .with_pricing_adapter("PricingV2")
+        .with_item_prefixes("MKT", "PRC", "TRD");
+    assert_eq!(config.pricing_adapter, "PricingV2");
+    assert_eq!(
+        config.market_item("CS.D.EURUSD.CFD.IP"),
+        "MKT:CS.D.EURUSD.CFD.IP"
+    );
+    assert_eq!(
+        config.item_epic("MKT:CS.D.EURUSD.CFD.IP"),
+        Some("CS.D.EURUSD.CFD.IP")
+    );
+    assert_eq!(
+        config.item_epic("PRC:ABC123:CS.D.EURUSD.CFD.IP"),
+        Some("CS.D.EURUSD.CFD.IP")
+    );
+    assert_eq!(
+        config.item_epic("CHART:CS.D.EURUSD.CFD.IP:1MINUTE"),
+        Some("CS.D.EURUSD.CFD.IP")
+    );
+    assert_eq!(config.item_epic("MARKET:CS.D.EURUSD.CFD.IP"), None);
+    assert_eq!(config.item_epic("TRD:ABC123"), None);
+}
+
+#[test]
+fn reconnect_policy_backoff_and_attempt_limit() {
+    let policy = StreamReconnectPolicy::default();
+    assert_eq!(StreamerConfig::default().reconnect, policy);
+    // Same schedule as the former `(interval + 200 * counter).min(5000)` formula
+    assert_eq!(policy.delay(0), Duration::ZERO);
+    assert_eq!(policy.delay(1), Duration::ZERO);
+    assert_eq!(policy.delay(2), Duration::from_millis(200));
+    assert_eq!(policy.delay(3), Duration::from_millis(600));
+    assert_eq!(policy.delay(4), Duration::from_millis(1200));
+    assert_eq!(policy.delay(10), Duration::from_secs(5));
+    assert!(policy.allows_attempt(2));
+    assert!(!policy.allows_attempt(3));
+
+    let forever = StreamReconnectPolicy::unlimited();
+    assert!(forever.allows_attempt(u32::MAX));
+}
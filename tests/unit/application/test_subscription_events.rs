@@ -0,0 +1,70 @@
+use ig_client::application::subscription_events::{
+    SubscriptionEvent, SubscriptionEventHub, SubscriptionStatusListener,
+};
+use lightstreamer_rs::subscription::SubscriptionListener;
+
+#[test]
+fn test_hub_without_receiver_discards_events() {
+    let hub = SubscriptionEventHub::new();
+    hub.publish(SubscriptionEvent::Subscribed {
+        item: "MARKET:IX.D.DAX.DAILY.IP".to_string(),
+    });
+
+    let mut events = hub.subscribe();
+    assert!(events.try_recv().is_err());
+}
+
+#[test]
+fn test_status_listener_reports_each_item() {
+    let hub = SubscriptionEventHub::new();
+    let mut events = hub.subscribe();
+    let mut listener = SubscriptionStatusListener::new(
+        vec![
+            "MARKET:OP.D.OTCDAX1.021100P.IP".to_string(),
+            "MARKET:OP.D.OTCDAX1.021200P.IP".to_string(),
+        ],
+        hub.clone(),
+    );
+
+    listener.on_subscription_error(21, Some("Bad item name"));
+    listener.on_unsubscription();
+
+    let first = events.try_recv().unwrap();
+    assert_eq!(
+        first,
+        SubscriptionEvent::Rejected {
+            item: "MARKET:OP.D.OTCDAX1.021100P.IP".to_string(),
+            reason: "21: Bad item name".to_string(),
+        }
+    );
+    let second = events.try_recv().unwrap();
+    assert_eq!(second.item(), "MARKET:OP.D.OTCDAX1.021200P.IP");
+    assert!(matches!(
+        events.try_recv().unwrap(),
+        SubscriptionEvent::Unsubscribed { .. }
+    ));
+    assert!(matches!(
+        events.try_recv().unwrap(),
+        SubscriptionEvent::Unsubscribed { .. }
+    ));
+    assert!(events.try_recv().is_err());
+}
+
+#[test]
+fn test_new_receiver_replaces_previous_one() {
+    let hub = SubscriptionEventHub::new();
+    let mut old = hub.subscribe();
+    let mut new = hub.subscribe();
+    let mut listener =
+        SubscriptionStatusListener::new(vec!["TRADE:ABC123".to_string()], hub.clone());
+
+    listener.on_subscription();
+
+    assert!(old.try_recv().is_err());
+    assert_eq!(
+        new.try_recv().unwrap(),
+        SubscriptionEvent::Subscribed {
+            item: "TRADE:ABC123".to_string()
+        }
+    );
+}
@@ -0,0 +1,78 @@
+//! Fixtures shared by the unit tests
+
+use ig_client::presentation::market::{MarketData, MarketDetails};
+use serde_json::{Value, json};
+
+/// Recursively replaces the fields of `base` given in `overrides`
+fn merge(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                merge(base.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
+
+/// Builds a `MarketData` from its IG JSON representation
+///
+/// Every field is set to a neutral default: a tradeable index without prices.
+/// `overrides` replaces any of them, e.g. `json!({"bid": 10.0})`.
+pub fn market_data(epic: &str, overrides: Value) -> MarketData {
+    let mut market = json!({
+        "epic": epic,
+        "instrumentName": epic,
+        "instrumentType": "INDICES",
+        "expiry": "-",
+        "highLimitPrice": null,
+        "lowLimitPrice": null,
+        "marketStatus": "TRADEABLE",
+        "netChange": null,
+        "percentageChange": null,
+        "updateTime": null,
+        "updateTimeUTC": null,
+        "bid": null,
+        "offer": null
+    });
+    merge(&mut market, overrides);
+    serde_json::from_value(market).unwrap()
+}
+
+/// Builds a `MarketDetails` from its IG JSON representation
+///
+/// Defaults to a tradeable index quoted 18000/18001 with dealing rules in points.
+/// `overrides` replaces nested fields, e.g.
+/// `json!({"dealingRules": {"minDealSize": {"value": 0.5}}})`.
+pub fn market_details(epic: &str, overrides: Value) -> MarketDetails {
+    let step = |value: f64| json!({"unit": "POINTS", "value": value});
+    let mut details = json!({
+        "instrument": {
+            "epic": epic,
+            "name": "Germany 40",
+            "expiry": "-",
+            "contractSize": "1",
+            "valueOfOnePip": "1",
+            "instrumentType": "INDICES"
+        },
+        "snapshot": {
+            "marketStatus": "TRADEABLE",
+            "bid": 18000.0,
+            "offer": 18001.0,
+            "scalingFactor": 1
+        },
+        "dealingRules": {
+            "minStepDistance": step(1.0),
+            "minDealSize": step(1.0),
+            "minControlledRiskStopDistance": step(5.0),
+            "minNormalStopOrLimitDistance": step(1.0),
+            "maxStopOrLimitDistance": step(1000.0),
+            "controlledRiskSpacing": step(1.0),
+            "marketOrderPreference": "AVAILABLE_DEFAULT_OFF",
+            "trailingStopsPreference": "AVAILABLE_DEFAULT_OFF",
+            "maxDealSize": 100.0
+        }
+    });
+    merge(&mut details, overrides);
+    serde_json::from_value(details).unwrap()
+}
@@ -1,4 +1,5 @@
 mod application;
+mod common;
 mod error_tests;
 mod model;
 mod presentation;
@@ -5,6 +5,8 @@
 ******************************************************************************/
 mod test_auth;
 mod test_config;
+mod test_http;
+mod test_http_observer;
 mod test_requests;
 mod test_responses;
 mod test_streaming;
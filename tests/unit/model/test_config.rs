@@ -1,5 +1,5 @@
 use ig_client::application::config::{
-    Config, Credentials, RateLimiterConfig, RestApiConfig, WebSocketConfig,
+    Config, Credentials, IgEnvironment, RateLimiterConfig, RestApiConfig, WebSocketConfig,
 };
 use ig_client::storage::config::DatabaseConfig;
 
@@ -280,3 +280,66 @@ fn test_credentials_without_tokens() {
     assert_eq!(creds.client_token, None);
     assert_eq!(creds.account_token, None);
 }
+
+fn lookup(vars: &[(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: Vec<(&'static str, &'static str)> = vars.to_vec();
+    move |name| {
+        vars.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.to_string())
+    }
+}
+
+#[test]
+fn test_config_from_vars_lists_every_missing_variable() {
+    let err = Config::from_vars(lookup(&[("IG_USERNAME", "user"), ("IG_ENV", "paper")]))
+        .err()
+        .unwrap();
+    let message = err.to_string();
+    assert!(message.contains("IG_API_KEY is not set"));
+    assert!(message.contains("IG_PASSWORD is not set"));
+    assert!(message.contains("IG_ACCOUNT_ID is not set"));
+    assert!(message.contains("IG_ENV must be demo or live"));
+    assert!(!message.contains("IG_USERNAME"));
+}
+
+#[test]
+fn test_config_from_vars_resolves_rest_host_from_environment() {
+    let credentials = [
+        ("IG_API_KEY", "key"),
+        ("IG_USERNAME", "user"),
+        ("IG_PASSWORD", "pass"),
+        ("IG_ACCOUNT_ID", "ACC123"),
+    ];
+    let demo = Config::from_vars(lookup(&credentials)).unwrap();
+    assert_eq!(
+        demo.rest_api.base_url,
+        "https://demo-api.ig.com/gateway/deal"
+    );
+    assert_eq!(demo.credentials.account_id, "ACC123");
+
+    let mut live_vars = credentials.to_vec();
+    live_vars.push(("IG_ENV", "LIVE"));
+    let live = Config::from_vars(lookup(&live_vars)).unwrap();
+    assert_eq!(live.rest_api.base_url, "https://api.ig.com/gateway/deal");
+    assert_eq!(live.credentials.api_key, "key");
+    assert_eq!(live.environment(), IgEnvironment::Live);
+    assert_eq!(demo.environment(), IgEnvironment::Demo);
+}
+
+#[test]
+fn test_ig_environment_parsing_and_hosts() {
+    assert_eq!(IgEnvironment::parse(" Demo "), Some(IgEnvironment::Demo));
+    assert_eq!(IgEnvironment::parse("LIVE"), Some(IgEnvironment::Live));
+    assert_eq!(IgEnvironment::parse("paper"), None);
+
+    for env in [IgEnvironment::Demo, IgEnvironment::Live] {
+        assert_eq!(IgEnvironment::from_base_url(env.rest_base_url()), env);
+    }
+    // Unknown hosts are treated as live so that order warnings are not skipped
+    assert_eq!(
+        IgEnvironment::from_base_url("https://proxy.internal/gateway/deal"),
+        IgEnvironment::Live
+    );
+    assert_eq!(IgEnvironment::Live.to_string(), "live");
+}
@@ -0,0 +1,91 @@
+use chrono::{TimeZone, Utc};
+use ig_client::error::AppError;
+use ig_client::model::http::{
+    HttpClientConfig, parse_http_date, parse_retry_after, unauthorized_error,
+};
+use std::time::Duration;
+
+#[test]
+fn unauthorized_error_maps_invalid_oauth_tokens() {
+    for body in [
+        r#"{"errorCode":"error.security.oauth-token-invalid"}"#,
+        r#"{"errorCode":"error.security.token-invalid"}"#,
+    ] {
+        assert!(matches!(
+            unauthorized_error(body),
+            AppError::OAuthTokenExpired
+        ));
+    }
+}
+
+#[test]
+fn unauthorized_error_maps_invalid_client_tokens() {
+    let body = r#"{"errorCode":"error.security.client-token-invalid"}"#;
+    assert!(matches!(unauthorized_error(body), AppError::SessionExpired));
+}
+
+#[test]
+fn unauthorized_error_defaults_to_unauthorized() {
+    let body = r#"{"errorCode":"error.security.invalid-details"}"#;
+    assert!(matches!(unauthorized_error(body), AppError::Unauthorized));
+    assert!(matches!(unauthorized_error(""), AppError::Unauthorized));
+}
+
+#[test]
+fn parse_retry_after_reads_seconds() {
+    assert_eq!(parse_retry_after("30"), Some(Duration::from_secs(30)));
+    assert_eq!(parse_retry_after(" 5 "), Some(Duration::from_secs(5)));
+    assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+}
+
+#[test]
+fn http_client_config_defaults_to_thirty_second_requests() {
+    let config = HttpClientConfig::default();
+    assert_eq!(config.request_timeout, Duration::from_secs(30));
+    assert!(config.build_client().is_ok());
+}
+
+#[test]
+fn http_client_config_applies_proxy_and_rejects_bad_certificates() {
+    let config = HttpClientConfig::default().with_https_proxy("http://127.0.0.1:3128");
+    assert!(config.build_client().is_ok());
+
+    let err = HttpClientConfig::default()
+        .with_https_proxy("not a url")
+        .build_client()
+        .unwrap_err();
+    assert!(matches!(err, AppError::Config(_)));
+
+    let err = HttpClientConfig::default()
+        .with_root_cert("/nonexistent/corporate-ca.pem")
+        .build_client()
+        .unwrap_err();
+    assert!(matches!(err, AppError::Config(ref msg) if msg.contains("corporate-ca.pem")));
+}
+
+#[tokio::test]
+async fn request_timeout_surfaces_as_timeout_error() {
+    // Accepts the connection but never answers
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let client = HttpClientConfig {
+        request_timeout: Duration::from_millis(100),
+        ..HttpClientConfig::default()
+    }
+    .build_client()
+    .unwrap();
+
+    let err = AppError::from(client.get(url).send().await.unwrap_err());
+    assert!(matches!(err, AppError::Timeout(_)));
+    drop(listener);
+}
+
+#[test]
+fn parse_http_date_reads_imf_fixdate() {
+    assert_eq!(
+        parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+        Some(Utc.with_ymd_and_hms(1994, 11, 6, 8, 49, 37).unwrap())
+    );
+    assert_eq!(parse_http_date("not a date"), None);
+}
@@ -0,0 +1,51 @@
+use ig_client::model::http_observer::{
+    HttpObserver, HttpRequestInfo, HttpResponseInfo, NoopHttpObserver, REDACTED, redact_headers,
+};
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+
+#[test]
+fn redact_headers_hides_credentials_only() {
+    let headers = redact_headers(&[
+        ("X-IG-API-KEY", "key"),
+        ("authorization", "Bearer token"),
+        ("CST", "cst"),
+        ("X-SECURITY-TOKEN", "xst"),
+        ("Version", "2"),
+        ("IG-ACCOUNT-ID", "ABC123"),
+    ]);
+    for (name, value) in &headers[..4] {
+        assert_eq!(value, REDACTED, "{name} was not redacted");
+    }
+    assert_eq!(headers[4], ("Version".to_string(), "2".to_string()));
+    assert_eq!(headers[5].1, "ABC123");
+}
+
+#[test]
+fn response_info_maps_errors_to_status() {
+    let request = HttpRequestInfo::new(Method::POST, "/positions/otc", 2, &[("CST", "secret")]);
+    assert_eq!(request.path, "positions/otc");
+    assert_eq!(request.headers[0].1, REDACTED);
+
+    let failed = HttpResponseInfo::new(
+        &request,
+        &Err(ig_client::error::AppError::Unexpected(
+            StatusCode::BAD_REQUEST,
+        )),
+        Duration::from_millis(12),
+    );
+    assert_eq!(failed.status, Some(StatusCode::BAD_REQUEST));
+    assert!(!failed.is_success());
+    assert!(failed.error.is_some());
+
+    let timed_out = HttpResponseInfo::new(
+        &request,
+        &Err(ig_client::error::AppError::Timeout("30s".to_string())),
+        Duration::from_secs(30),
+    );
+    assert_eq!(timed_out.status, None);
+
+    // The default observer accepts calls without side effects
+    NoopHttpObserver.on_request(&request);
+    NoopHttpObserver.on_response(&failed);
+}
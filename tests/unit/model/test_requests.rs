@@ -1,7 +1,13 @@
+use crate::common::market_data;
 use ig_client::constants::{DEFAULT_ORDER_BUY_LEVEL, DEFAULT_ORDER_SELL_LEVEL};
+use ig_client::error::AppError;
 use ig_client::model::requests::{
-    ClosePositionRequest, CreateOrderRequest, CreateWorkingOrderRequest, RecentPricesRequest,
+    ActivityFilter, ClosePositionRequest, CreateOrderRequest, CreateWorkingOrderRequest,
+    MarketDetailFilter, MarketSearchFilter, RecentPricesRequest, UpdatePositionRequest,
+    UpdateWorkingOrderRequest,
 };
+use ig_client::presentation::account::{ActivityType, Position, PositionDetails, PositionMarket};
+use ig_client::presentation::instrument::InstrumentType;
 use ig_client::presentation::order::{Direction, OrderType, TimeInForce};
 
 fn json_value<T: serde::Serialize>(v: &T) -> serde_json::Value {
@@ -27,6 +33,36 @@ fn recent_prices_request_builders() {
     assert_eq!(req.page_number, Some(2));
 }
 
+#[test]
+fn recent_prices_request_builder_validates_combinations() {
+    let req = RecentPricesRequest::builder("CS.D.EURUSD.TODAY.IP")
+        .resolution("MINUTE_5")
+        .from("2025-10-19T10:00:00")
+        .to("2025-10-19T12:00:00")
+        .page_size(0)
+        .build()
+        .unwrap();
+    assert_eq!(req.resolution, Some("MINUTE_5"));
+    assert_eq!(req.max_points, None);
+    assert_eq!(req.page_size, Some(0));
+
+    let invalid = [
+        RecentPricesRequest::builder("CS.D.EURUSD.TODAY.IP")
+            .from("2025-10-19T10:00:00")
+            .max_points(10),
+        RecentPricesRequest::builder("CS.D.EURUSD.TODAY.IP")
+            .from("2025-10-19T12:00:00")
+            .to("2025-10-19T10:00:00"),
+        RecentPricesRequest::builder("CS.D.EURUSD.TODAY.IP").from("2025-10-19 10:00:00"),
+        RecentPricesRequest::builder("CS.D.EURUSD.TODAY.IP").max_points(0),
+        RecentPricesRequest::builder("CS.D.EURUSD.TODAY.IP").page_number(0),
+        RecentPricesRequest::builder(""),
+    ];
+    for builder in invalid {
+        assert!(matches!(builder.build(), Err(AppError::InvalidInput(_))));
+    }
+}
+
 #[test]
 fn create_order_market_defaults_and_rounding() {
     let order = CreateOrderRequest::market(
@@ -74,7 +110,7 @@ fn create_order_limit_and_chainers() {
     .with_reference("XREF".to_string())
     .with_stop_distance(25.0)
     .with_limit_distance(50.0)
-    .with_guaranteed_stop(true);
+    .with_guaranteed_stop(25.0);
 
     assert_eq!(order.order_type, OrderType::Limit);
     assert_eq!(order.time_in_force, TimeInForce::GoodTillCancelled);
@@ -212,3 +248,202 @@ fn create_working_order_builders() {
     assert_eq!(ws.order_type, OrderType::Stop);
     assert_eq!(ws.time_in_force, TimeInForce::GoodTillCancelled);
 }
+
+#[test]
+fn market_detail_filter_emits_query_parameter() {
+    assert_eq!(MarketDetailFilter::default(), MarketDetailFilter::All);
+    assert_eq!(
+        MarketDetailFilter::All.market_details_path("IX.D.DAX.DAILY.IP"),
+        "markets/IX.D.DAX.DAILY.IP?filter=ALL"
+    );
+    assert_eq!(
+        MarketDetailFilter::SnapshotOnly.market_details_path("IX.D.DAX.DAILY.IP"),
+        "markets/IX.D.DAX.DAILY.IP?filter=SNAPSHOT_ONLY"
+    );
+    assert_eq!(
+        json_value(&MarketDetailFilter::SnapshotOnly),
+        serde_json::json!("SNAPSHOT_ONLY")
+    );
+}
+
+fn open_position(deal_id: &str, direction: Direction, size: f64) -> Position {
+    Position {
+        position: PositionDetails {
+            contract_size: 1.0,
+            created_date: "2025-10-19T10:00:00".into(),
+            created_date_utc: "2025-10-19T08:00:00Z".into(),
+            deal_id: deal_id.into(),
+            deal_reference: "R1".into(),
+            direction,
+            limit_level: None,
+            level: 100.0,
+            size,
+            stop_level: None,
+            trailing_step: None,
+            trailing_stop_distance: None,
+            currency: "EUR".into(),
+            controlled_risk: false,
+            limited_risk_premium: None,
+        },
+        market: PositionMarket {
+            instrument_name: "Germany 40".into(),
+            expiry: "-".into(),
+            epic: "IX.D.DAX.IFD.IP".into(),
+            instrument_type: "INDEX".into(),
+            lot_size: 1.0,
+            high: None,
+            low: None,
+            percentage_change: 0.0,
+            net_change: 0.0,
+            bid: Some(95.0),
+            offer: Some(96.0),
+            update_time: "10:00:00".into(),
+            update_time_utc: "08:00:00".into(),
+            delay_time: 0,
+            streaming_prices_available: true,
+            market_status: "TRADEABLE".into(),
+            scaling_factor: 1,
+        },
+        pnl: None,
+    }
+}
+
+#[test]
+fn close_request_from_position_uses_opposite_direction_and_full_size() {
+    let close_buy = ClosePositionRequest::from(&open_position("D1", Direction::Buy, 2.5));
+    assert_eq!(close_buy.deal_id.as_deref(), Some("D1"));
+    assert_eq!(close_buy.direction, Direction::Sell);
+    assert_eq!(close_buy.size, 2.5);
+    assert_eq!(close_buy.order_type, OrderType::Market);
+
+    let close_sell = ClosePositionRequest::from(&open_position("D2", Direction::Sell, 1.0));
+    assert_eq!(close_sell.deal_id.as_deref(), Some("D2"));
+    assert_eq!(close_sell.direction, Direction::Buy);
+    assert_eq!(close_sell.size, 1.0);
+}
+
+#[test]
+fn update_position_limit_only_serializes_limit_presence() {
+    let set = json_value(&UpdatePositionRequest::limit_only(Some(7500.5)));
+    assert_eq!(set, serde_json::json!({ "limitLevel": 7500.5 }));
+
+    let cleared = json_value(&UpdatePositionRequest::limit_only(None));
+    assert_eq!(cleared, serde_json::json!({}));
+    assert!(cleared.get("limitLevel").is_none());
+    assert!(cleared.get("stopLevel").is_none());
+}
+
+#[test]
+fn update_working_order_serializes_set_fields_and_validates() {
+    let update = UpdateWorkingOrderRequest::new()
+        .with_level(7450.0)
+        .with_stop_distance(20.0)
+        .expires_at("2025/12/31 23:59:59".to_string());
+    assert!(update.validate().is_ok());
+    assert_eq!(
+        json_value(&update),
+        serde_json::json!({
+            "level": 7450.0,
+            "stopDistance": 20.0,
+            "timeInForce": "GOOD_TILL_DATE",
+            "goodTillDate": "2025/12/31 23:59:59"
+        })
+    );
+
+    let empty = UpdateWorkingOrderRequest::new();
+    assert!(matches!(empty.validate(), Err(AppError::InvalidInput(_))));
+    assert_eq!(json_value(&empty), serde_json::json!({}));
+}
+
+#[test]
+fn activity_filter_builds_and_encodes_fiql() {
+    let filter = ActivityFilter::new()
+        .epic("IX.D.DAX.*")
+        .deal_id("DIAAAABBB")
+        .activity_type(ActivityType::Position);
+    assert_eq!(
+        filter.to_fiql(),
+        "epic==IX.D.DAX.*;dealId==DIAAAABBB;type==POSITION"
+    );
+    assert_eq!(
+        filter.to_query_value(),
+        "epic%3D%3DIX.D.DAX.%2A%3BdealId%3D%3DDIAAAABBB%3Btype%3D%3DPOSITION"
+    );
+    assert!(ActivityFilter::new().is_empty());
+}
+
+#[test]
+fn create_order_stop_builders_and_validate() {
+    let market = || {
+        CreateOrderRequest::market(
+            "IX.D.DAX.IFD.IP".to_string(),
+            Direction::Buy,
+            1.0,
+            None,
+            None,
+        )
+    };
+
+    let guaranteed = market()
+        .with_guaranteed_stop(40.0)
+        .with_limit_distance(80.0);
+    assert!(guaranteed.guaranteed_stop);
+    assert_eq!(guaranteed.stop_distance, Some(40.0));
+    assert!(guaranteed.validate().is_ok());
+
+    let trailing = market().with_trailing_stop(30.0, 5.0);
+    assert_eq!(trailing.trailing_stop, Some(true));
+    assert_eq!(trailing.stop_distance, Some(30.0));
+    assert_eq!(trailing.trailing_stop_increment, Some(5.0));
+    assert!(trailing.validate().is_ok());
+
+    let both = market()
+        .with_trailing_stop(30.0, 5.0)
+        .with_guaranteed_stop(30.0);
+    assert!(matches!(both.validate(), Err(AppError::InvalidInput(_))));
+
+    let no_increment = market().with_stop_distance(30.0);
+    let no_increment = CreateOrderRequest {
+        trailing_stop: Some(true),
+        ..no_increment
+    };
+    assert!(matches!(
+        no_increment.validate(),
+        Err(AppError::InvalidInput(_))
+    ));
+
+    let double_limit = market().with_take_profit(16000.0).with_limit_distance(50.0);
+    assert!(matches!(
+        double_limit.validate(),
+        Err(AppError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn market_search_filter_matches_all_criteria() {
+    let market = |epic: &str, instrument_type: &str, expiry: &str, status: &str| {
+        market_data(
+            epic,
+            serde_json::json!({
+                "instrumentName": "DAX",
+                "instrumentType": instrument_type,
+                "expiry": expiry,
+                "marketStatus": status
+            }),
+        )
+    };
+    let option = market("OP.D.OTCDAX1.24000C.IP", "OPT_INDICES", "DFB", "TRADEABLE");
+    let closed = market("OP.D.OTCDAX1.23000P.IP", "OPT_INDICES", "DFB", "CLOSED");
+    let index = market("IX.D.DAX.DAILY.IP", "INDICES", "DFB", "TRADEABLE");
+
+    assert!(MarketSearchFilter::new().matches(&closed));
+
+    let filter = MarketSearchFilter::new()
+        .instrument_type(InstrumentType::OptIndices)
+        .expiry("dfb")
+        .epic_prefix("OP.D.OTCDAX1.")
+        .tradeable_only(true);
+    assert!(filter.matches(&option));
+    assert!(!filter.matches(&closed));
+    assert!(!filter.matches(&index));
+}
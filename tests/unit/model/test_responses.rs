@@ -79,6 +79,7 @@ fn multiple_market_details_response_helpers_and_display() {
         limited_risk_premium: None,
         news_code: None,
         chart_code: None,
+        streaming_prices_available: None,
     };
     let snapshot = MarketSnapshot {
         market_status: "TRADEABLE".into(),
@@ -222,6 +223,84 @@ fn historical_prices_response_helpers_and_display() {
     assert!(s.contains("Remaining allowance: 99"));
 }
 
+#[test]
+fn historical_prices_to_ohlc_parses_times_and_skips_bad_points() {
+    let point = |bid: f64, ask: f64| PricePoint {
+        bid: Some(bid),
+        ask: Some(ask),
+        last_traded: None,
+    };
+    let price = |time: &str| HistoricalPrice {
+        snapshot_time: time.into(),
+        open_price: point(1.0, 2.0),
+        high_price: point(3.0, 4.0),
+        low_price: point(0.5, 1.5),
+        close_price: point(2.0, 3.0),
+        last_traded_volume: Some(42),
+    };
+    let resp = HistoricalPricesResponse {
+        prices: vec![
+            price("2025/10/19 10:00:00"),
+            price("not a time"),
+            price("2025-10-19T10:01:00"),
+        ],
+        instrument_type: InstrumentType::Currencies,
+        allowance: None,
+    };
+
+    let bid = resp.to_ohlc(PriceSide::Bid);
+    assert_eq!(bid.len(), 2);
+    assert_eq!(bid[0].timestamp.to_rfc3339(), "2025-10-19T10:00:00+00:00");
+    assert_eq!(
+        (bid[0].open, bid[0].high, bid[0].low, bid[0].close),
+        (1.0, 3.0, 0.5, 2.0)
+    );
+    assert_eq!(bid[0].volume, Some(42.0));
+
+    let ask = resp.to_ohlc(PriceSide::Ask);
+    assert_eq!(ask[1].close, 3.0);
+
+    let mid = resp.to_ohlc(PriceSide::Mid);
+    assert_eq!(
+        (mid[0].open, mid[0].high, mid[0].low, mid[0].close),
+        (1.5, 3.5, 1.0, 2.5)
+    );
+}
+
+#[test]
+fn historical_prices_write_csv_outputs_header_and_rows() {
+    let point = |bid: f64, ask: f64| PricePoint {
+        bid: Some(bid),
+        ask: Some(ask),
+        last_traded: None,
+    };
+    let price = |time: &str, volume: Option<i64>| HistoricalPrice {
+        snapshot_time: time.into(),
+        open_price: point(1.0, 2.0),
+        high_price: point(3.0, 4.0),
+        low_price: point(0.5, 1.5),
+        close_price: point(2.0, 3.0),
+        last_traded_volume: volume,
+    };
+    let resp = HistoricalPricesResponse {
+        prices: vec![
+            price("2025/10/19 10:00:00", Some(7)),
+            price("2025/10/19 10:01:00", None),
+        ],
+        instrument_type: InstrumentType::Currencies,
+        allowance: None,
+    };
+
+    let mut out = Vec::new();
+    resp.write_csv(&mut out, PriceSide::Mid).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "timestamp,open,high,low,close,volume");
+    assert_eq!(lines[1], "2025-10-19T10:00:00+00:00,1.5,3.5,1,2.5,7");
+    assert_eq!(lines[2], "2025-10-19T10:01:00+00:00,1.5,3.5,1,2.5,");
+    assert_eq!(lines.len(), 3);
+}
+
 #[test]
 fn market_search_response_helpers_and_display() {
     let m1 = MarketData {
@@ -281,6 +360,25 @@ fn market_navigation_response_deserializes_null_as_empty() {
     assert!(resp.markets.is_empty());
 }
 
+#[test]
+fn market_hierarchy_stats_reports_depth_limit() {
+    let exhausted = MarketHierarchyStats {
+        levels_processed: 3,
+        nodes_visited: 12,
+        markets_found: 40,
+        node_errors: 1,
+        unvisited_nodes: 0,
+    };
+    assert!(!exhausted.hit_depth_limit());
+
+    let truncated = MarketHierarchyStats {
+        unvisited_nodes: 5,
+        ..exhausted
+    };
+    assert!(truncated.hit_depth_limit());
+    assert!(!MarketHierarchyStats::default().hit_depth_limit());
+}
+
 #[test]
 fn positions_response_compact_by_epic_merges_positions() {
     // Two positions for the same epic with opposite directions
@@ -356,12 +454,158 @@ fn positions_response_compact_by_epic_merges_positions() {
     // Opposite directions => abs differences
     assert_eq!(m.position.contract_size, 4.0);
     assert_eq!(m.position.size, 2.0);
-    // Level averaged
-    assert!((m.position.level - 55.0).abs() < 1e-9);
+    // Netted => direction and level follow the larger (buy) side
+    assert_eq!(m.position.direction, Direction::Buy);
+    assert!((m.position.level - 50.0).abs() < 1e-9);
     // PnL added
     assert_eq!(m.pnl, Some(6.0));
 }
 
+#[test]
+fn positions_response_totals_group_by_currency() {
+    let position = |currency: &str, size: f64, level: f64, contract_size: f64, pnl: f64| Position {
+        position: PositionDetails {
+            contract_size,
+            created_date: "2025-10-19T10:00:00".into(),
+            created_date_utc: "2025-10-19T08:00:00Z".into(),
+            deal_id: "D1".into(),
+            deal_reference: "R1".into(),
+            direction: Direction::Buy,
+            limit_level: None,
+            level,
+            size,
+            stop_level: None,
+            trailing_step: None,
+            trailing_stop_distance: None,
+            currency: currency.into(),
+            controlled_risk: false,
+            limited_risk_premium: None,
+        },
+        market: PositionMarket {
+            instrument_name: "Germany 40".into(),
+            expiry: "-".into(),
+            epic: "IX.D.DAX.IFD.IP".into(),
+            instrument_type: "INDEX".into(),
+            lot_size: 1.0,
+            high: None,
+            low: None,
+            percentage_change: 0.0,
+            net_change: 0.0,
+            bid: None,
+            offer: None,
+            update_time: "10:00:00".into(),
+            update_time_utc: "08:00:00".into(),
+            delay_time: 0,
+            streaming_prices_available: true,
+            market_status: "OPEN".into(),
+            scaling_factor: 1,
+        },
+        pnl: Some(pnl),
+    };
+
+    let response = PositionsResponse {
+        positions: vec![
+            position("EUR", 2.0, 100.0, 1.0, 10.0),
+            position("USD", 1.0, 50.0, 10.0, -4.0),
+            position("EUR", 1.0, 10.0, 1.0, 2.5),
+        ],
+    };
+
+    assert!((response.total_pnl() - 8.5).abs() < 1e-9);
+    let by_currency = response.total_pnl_by_currency();
+    assert_eq!(by_currency.len(), 2);
+    assert!((by_currency["EUR"] - 12.5).abs() < 1e-9);
+    assert!((by_currency["USD"] + 4.0).abs() < 1e-9);
+    // 200 + 500 + 10
+    assert!((response.total_exposure() - 710.0).abs() < 1e-9);
+    assert_eq!(PositionsResponse::default().total_pnl(), 0.0);
+}
+
+#[test]
+fn positions_response_option_summary_nets_calls_and_puts_per_underlying() {
+    let position = |epic: &str, name: &str, direction: Direction, size: f64| Position {
+        position: PositionDetails {
+            contract_size: 1.0,
+            created_date: "2025-10-19T10:00:00".into(),
+            created_date_utc: "2025-10-19T08:00:00Z".into(),
+            deal_id: format!("D-{epic}-{size}"),
+            deal_reference: "R1".into(),
+            direction,
+            limit_level: None,
+            level: 10.0,
+            size,
+            stop_level: None,
+            trailing_step: None,
+            trailing_stop_distance: None,
+            currency: "USD".into(),
+            controlled_risk: false,
+            limited_risk_premium: None,
+        },
+        market: PositionMarket {
+            instrument_name: name.into(),
+            expiry: "DEC-25".into(),
+            epic: epic.into(),
+            instrument_type: "OPT_INDEX".into(),
+            lot_size: 1.0,
+            high: None,
+            low: None,
+            percentage_change: 0.0,
+            net_change: 0.0,
+            bid: None,
+            offer: None,
+            update_time: "10:00:00".into(),
+            update_time_utc: "08:00:00".into(),
+            delay_time: 0,
+            streaming_prices_available: true,
+            market_status: "TRADEABLE".into(),
+            scaling_factor: 1,
+        },
+        pnl: None,
+    };
+
+    let response = PositionsResponse {
+        positions: vec![
+            position("OP.D.SPX.6800C.IP", "US 500 6800 CALL", Direction::Buy, 3.0),
+            position(
+                "OP.D.SPX.6800C.IP",
+                "US 500 6800 CALL",
+                Direction::Sell,
+                1.0,
+            ),
+            position(
+                "OP.D.SPX.6900C.IP",
+                "US 500 6900 CALL",
+                Direction::Sell,
+                1.0,
+            ),
+            position("OP.D.SPX.6700P.IP", "US 500 6700 PUT", Direction::Sell, 2.0),
+            position(
+                "OP.D.DAX.24000P.IP",
+                "Germany 40 24000 PUT",
+                Direction::Buy,
+                1.5,
+            ),
+            position("IX.D.DAX.IFD.IP", "Germany 40", Direction::Buy, 5.0),
+        ],
+    };
+
+    let summary = response.option_summary();
+    assert_eq!(summary.len(), 2);
+
+    let spx = summary.get("US 500").unwrap();
+    assert!((spx.net_calls - 1.0).abs() < 1e-9);
+    assert!((spx.net_puts + 2.0).abs() < 1e-9);
+    assert_eq!((spx.call_epics, spx.put_epics), (2, 1));
+
+    let dax = summary.get("Germany 40").unwrap();
+    assert_eq!(dax.net_calls, 0.0);
+    assert!((dax.net_puts - 1.5).abs() < 1e-9);
+
+    let underlyings: Vec<&String> = summary.iter().map(|(name, _)| name).collect();
+    assert_eq!(underlyings, vec!["Germany 40", "US 500"]);
+    assert!(PositionsResponse::default().option_summary().is_empty());
+}
+
 #[test]
 fn order_confirmation_response_deserialize_status_and_fields() {
     // Status can be null -> should become default (Open)
@@ -412,6 +656,44 @@ fn order_confirmation_response_deserialize_status_and_fields() {
     let r2: OrderConfirmationResponse = serde_json::from_str(json_ok).unwrap();
     assert_eq!(r2.status, Status::Accepted);
     assert_eq!(r2.direction, Some(Direction::Sell));
+    assert!(r2.is_accepted());
+    assert!(r2.is_terminal());
+}
+
+#[test]
+fn order_confirmation_terminal_status() {
+    let json = r#"{
+        "date": "2025-10-19T10:00:00",
+        "status": null,
+        "reason": "INSUFFICIENT_FUNDS",
+        "dealId": null,
+        "dealReference": "R2",
+        "dealStatus": "REJECTED",
+        "epic": "CS.D.EURUSD.TODAY.IP",
+        "expiry": null,
+        "guaranteedStop": null,
+        "level": null,
+        "limitDistance": null,
+        "limitLevel": null,
+        "size": null,
+        "stopDistance": null,
+        "stopLevel": null,
+        "trailingStop": null,
+        "direction": null
+    }"#;
+    let mut confirmation: OrderConfirmationResponse = serde_json::from_str(json).unwrap();
+    assert!(confirmation.is_rejected());
+    assert!(confirmation.is_terminal());
+    assert_eq!(confirmation.reason.as_deref(), Some("INSUFFICIENT_FUNDS"));
+
+    let outcome = OrderOutcome::from(confirmation.clone());
+    assert_eq!(outcome.status, Status::Rejected);
+    assert!(!outcome.is_accepted());
+    assert_eq!(outcome.deal_reference, "R2");
+    assert_eq!(outcome.reason.as_deref(), Some("INSUFFICIENT_FUNDS"));
+
+    confirmation.deal_status = None;
+    assert!(!confirmation.is_terminal());
 }
 
 #[test]
@@ -440,3 +722,82 @@ fn simple_deal_reference_responses_serde_field_names() {
     let j = json_value(&x);
     assert_eq!(j.get("dealReference").unwrap(), "X1");
 }
+
+#[test]
+fn client_sentiment_responses_deserialize() {
+    let single: ClientSentimentResponse = serde_json::from_str(
+        r#"{"marketId":"EURUSD","longPositionPercentage":62.5,"shortPositionPercentage":37.5}"#,
+    )
+    .unwrap();
+    assert_eq!(single.market_id, "EURUSD");
+    assert_eq!(single.long_position_percentage, 62.5);
+    assert_eq!(single.short_position_percentage, 37.5);
+
+    let list: ClientSentimentListResponse = serde_json::from_str(
+        r#"{"clientSentiments":[
+            {"marketId":"EURUSD","longPositionPercentage":62.5,"shortPositionPercentage":37.5},
+            {"marketId":"GBPUSD","longPositionPercentage":40.0,"shortPositionPercentage":60.0}
+        ]}"#,
+    )
+    .unwrap();
+    assert_eq!(list.client_sentiments.len(), 2);
+    assert_eq!(list.client_sentiments[1].market_id, "GBPUSD");
+    assert_eq!(
+        json_value(&list.client_sentiments[0])["longPositionPercentage"],
+        62.5
+    );
+}
+
+#[test]
+fn watchlist_responses_deserialize() {
+    let list: WatchlistsResponse = serde_json::from_str(
+        r#"{"watchlists":[{"id":"12345","name":"Indices","editable":true,"deleteable":true,"defaultSystemWatchlist":false}]}"#,
+    )
+    .unwrap();
+    assert_eq!(list.watchlists.len(), 1);
+    assert_eq!(list.watchlists[0].id, "12345");
+    assert!(!list.watchlists[0].default_system_watchlist);
+
+    let created: CreateWatchlistResponse =
+        serde_json::from_str(r#"{"watchlistId":"67890","status":"SUCCESS"}"#).unwrap();
+    assert_eq!(created.watchlist_id.as_deref(), Some("67890"));
+
+    let not_created: CreateWatchlistResponse =
+        serde_json::from_str(r#"{"watchlistId":null,"status":"SUCCESS_NOT_CREATED"}"#).unwrap();
+    assert!(not_created.watchlist_id.is_none());
+    assert_eq!(not_created.status, "SUCCESS_NOT_CREATED");
+
+    let empty: WatchlistResponse = serde_json::from_str(r#"{"markets":[]}"#).unwrap();
+    assert!(empty.markets.is_empty());
+}
+
+#[test]
+fn account_activity_next_page_path_strips_slash_and_ends_on_last_page() {
+    let page: AccountActivityResponse = serde_json::from_value(serde_json::json!({
+        "activities": [],
+        "metadata": {
+            "paging": {
+                "size": 50,
+                "next": "/history/activity?version=3&from=2024-01-01T00:00:00&to=2024-01-02T00:00:00&pageSize=50&page=2"
+            }
+        }
+    }))
+    .unwrap();
+    assert_eq!(
+        page.next_page_path().as_deref(),
+        Some(
+            "history/activity?version=3&from=2024-01-01T00:00:00&to=2024-01-02T00:00:00&pageSize=50&page=2"
+        )
+    );
+
+    let last: AccountActivityResponse = serde_json::from_value(serde_json::json!({
+        "activities": [],
+        "metadata": { "paging": { "size": 50, "next": null } }
+    }))
+    .unwrap();
+    assert!(last.next_page_path().is_none());
+
+    let no_metadata: AccountActivityResponse =
+        serde_json::from_value(serde_json::json!({ "activities": [] })).unwrap();
+    assert!(no_metadata.next_page_path().is_none());
+}
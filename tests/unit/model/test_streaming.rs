@@ -6,8 +6,10 @@
 
 //! Tests for streaming model enums, specifically focusing on Display and Debug implementations.
 
+use ig_client::error::AppError;
 use ig_client::model::streaming::{
     StreamingAccountDataField, StreamingChartField, StreamingMarketField, StreamingPriceField,
+    parse_fields,
 };
 use std::collections::HashSet;
 
@@ -661,3 +663,127 @@ fn test_streaming_chart_field_in_hashset() {
     assert!(set.contains(&StreamingChartField::Ofr));
     assert!(!set.contains(&StreamingChartField::Ltp));
 }
+
+#[test]
+fn test_streaming_field_presets() {
+    let top = StreamingMarketField::top_of_book();
+    assert_eq!(top.len(), 4);
+    for field in [
+        StreamingMarketField::Bid,
+        StreamingMarketField::Offer,
+        StreamingMarketField::UpdateTime,
+        StreamingMarketField::MarketState,
+    ] {
+        assert!(top.contains(&field), "{field} missing from top_of_book");
+    }
+
+    let ladder = StreamingPriceField::full_ladder();
+    assert_eq!(ladder.len(), 22);
+    assert!(ladder.contains(&StreamingPriceField::BidPrice5));
+    assert!(ladder.contains(&StreamingPriceField::AskSize5));
+    assert!(ladder.contains(&StreamingPriceField::DlgFlag));
+    assert!(!ladder.contains(&StreamingPriceField::C1BidSize1));
+
+    let bbo = StreamingPriceField::best_bid_offer();
+    assert!(bbo.is_subset(&ladder));
+    assert_eq!(
+        bbo,
+        HashSet::from([
+            StreamingPriceField::BidPrice1,
+            StreamingPriceField::AskPrice1,
+            StreamingPriceField::BidSize1,
+            StreamingPriceField::AskSize1,
+            StreamingPriceField::Timestamp,
+            StreamingPriceField::DlgFlag,
+        ])
+    );
+
+    let risk = StreamingAccountDataField::risk_set();
+    assert_eq!(risk.len(), 6);
+    for field in [
+        StreamingAccountDataField::Pnl,
+        StreamingAccountDataField::Funds,
+        StreamingAccountDataField::Margin,
+        StreamingAccountDataField::Equity,
+        StreamingAccountDataField::EquityUsed,
+        StreamingAccountDataField::AvailableToDeal,
+    ] {
+        assert!(risk.contains(&field), "{field} missing from risk_set");
+    }
+}
+
+#[test]
+fn test_streaming_fields_parse_case_insensitively() {
+    for name in ["bid", "BID", "Bid", " bid "] {
+        assert_eq!(
+            name.parse::<StreamingMarketField>().unwrap(),
+            StreamingMarketField::Bid
+        );
+    }
+    assert_eq!(
+        "mid_open".parse::<StreamingPriceField>().unwrap(),
+        StreamingPriceField::MidOpen
+    );
+    assert_eq!(
+        "dlg_flag".parse::<StreamingPriceField>().unwrap(),
+        StreamingPriceField::DlgFlag
+    );
+    assert_eq!(
+        "c1bidsize1".parse::<StreamingPriceField>().unwrap(),
+        StreamingPriceField::C1BidSize1
+    );
+    assert_eq!(
+        "available_to_deal"
+            .parse::<StreamingAccountDataField>()
+            .unwrap(),
+        StreamingAccountDataField::AvailableToDeal
+    );
+    assert!("bidd".parse::<StreamingMarketField>().is_err());
+}
+
+#[test]
+fn test_streaming_fields_serde_round_trip() {
+    let market: StreamingMarketField = serde_json::from_str("\"change_pct\"").unwrap();
+    assert_eq!(market, StreamingMarketField::ChangePct);
+
+    for field in StreamingMarketField::ALL {
+        let json = serde_json::to_string(field).unwrap();
+        assert_eq!(
+            &serde_json::from_str::<StreamingMarketField>(&json).unwrap(),
+            field
+        );
+    }
+    for field in StreamingPriceField::ALL {
+        let json = serde_json::to_string(field).unwrap();
+        assert_eq!(
+            &serde_json::from_str::<StreamingPriceField>(&json).unwrap(),
+            field
+        );
+    }
+    for field in StreamingAccountDataField::ALL {
+        let json = serde_json::to_string(field).unwrap();
+        assert_eq!(
+            &serde_json::from_str::<StreamingAccountDataField>(&json).unwrap(),
+            field
+        );
+    }
+    assert!(serde_json::from_str::<StreamingMarketField>("\"nope\"").is_err());
+}
+
+#[test]
+fn test_parse_fields_reports_unknown_names() {
+    let names = vec!["bid".to_string(), "Offer".to_string(), "BID".to_string()];
+    let fields: HashSet<StreamingMarketField> = parse_fields(&names).unwrap();
+    assert_eq!(
+        fields,
+        HashSet::from([StreamingMarketField::Bid, StreamingMarketField::Offer])
+    );
+
+    let names = vec!["bid".to_string(), "foo".to_string(), "bar".to_string()];
+    match parse_fields::<StreamingMarketField>(&names) {
+        Err(AppError::InvalidInput(message)) => {
+            assert!(message.contains("foo") && message.contains("bar"));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
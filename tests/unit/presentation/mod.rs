@@ -2,6 +2,9 @@ mod serialization_tests;
 mod test_account;
 mod test_chart;
 mod test_market;
+mod test_option_chain;
+mod test_order;
+mod test_portfolio;
 mod test_price;
 mod test_serialization;
 mod test_trade;
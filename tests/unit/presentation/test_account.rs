@@ -1,4 +1,6 @@
-use ig_client::presentation::account::{AccountData, AccountFields};
+use ig_client::presentation::account::{
+    AccountData, AccountFields, ActionType, Activity, ActivityType,
+};
 use lightstreamer_rs::subscription::ItemUpdate;
 use std::collections::HashMap;
 
@@ -48,8 +50,113 @@ fn test_account_data_from_item_update_with_fields() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_account_fields_getters_and_map() {
+    let mut fields = HashMap::new();
+    fields.insert("PNL".to_string(), Some("-12.5".to_string()));
+    fields.insert("EQUITY".to_string(), Some("10250.5".to_string()));
+    fields.insert("AVAILABLE_TO_DEAL".to_string(), Some("8000".to_string()));
+    fields.insert("MARGIN".to_string(), Some("".to_string()));
+
+    let item_update = ItemUpdate {
+        item_name: Some("ACCOUNT:TEST".to_string()),
+        item_pos: 1,
+        is_snapshot: true,
+        fields,
+        changed_fields: HashMap::new(),
+    };
+
+    let account = AccountData::from_item_update(&item_update).unwrap();
+    assert_eq!(account.fields.pnl(), Some(-12.5));
+    assert_eq!(account.fields.equity(), Some(10250.5));
+    assert_eq!(account.fields.available_to_deal(), Some(8000.0));
+    assert_eq!(account.fields.margin(), None);
+
+    let map = account.fields.to_map();
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get("EQUITY"), Some(&10250.5));
+    assert!(!map.contains_key("MARGIN"));
+}
+
+#[test]
+fn test_account_data_changed_yields_only_changed_fields() {
+    let mut fields = HashMap::new();
+    fields.insert("PNL".to_string(), Some("5.0".to_string()));
+    fields.insert("FUNDS".to_string(), Some("1000.0".to_string()));
+
+    let mut changed_fields = HashMap::new();
+    changed_fields.insert("PNL".to_string(), "5.0".to_string());
+
+    let item_update = ItemUpdate {
+        item_name: Some("ACCOUNT:TEST".to_string()),
+        item_pos: 1,
+        is_snapshot: false,
+        fields,
+        changed_fields,
+    };
+
+    let account = AccountData::from_item_update(&item_update).unwrap();
+    let changed: Vec<_> = account.changed().collect();
+    assert_eq!(changed, vec![("PNL", 5.0)]);
+}
+
 #[test]
 fn test_account_data_clone() {
     let account = AccountData::default();
     let _cloned = account.clone();
 }
+
+#[test]
+fn test_activities_with_unknown_types_still_deserialize() {
+    let json = r#"[
+        {
+            "date": "2025-10-30T17:13:53",
+            "dealId": "DIAAAA1",
+            "type": "POSITION",
+            "details": {
+                "actions": [
+                    {"actionType": "POSITION_OPENED", "affectedDealId": "DIAAAA1"},
+                    {"actionType": "POSITION_TELEPORTED", "affectedDealId": "DIAAAA1"}
+                ]
+            }
+        },
+        {
+            "date": "2025-10-30T17:14:00",
+            "type": "CORPORATE_ACTION"
+        }
+    ]"#;
+
+    let activities: Vec<Activity> = serde_json::from_str(json).unwrap();
+    assert_eq!(activities.len(), 2);
+    assert!(matches!(
+        activities[0].activity_type,
+        ActivityType::Position
+    ));
+    assert!(matches!(activities[1].activity_type, ActivityType::Unknown));
+
+    let actions = &activities[0].details.as_ref().unwrap().actions;
+    assert!(matches!(actions[0].action_type, ActionType::PositionOpened));
+    assert!(matches!(
+        &actions[1].action_type,
+        ActionType::Unknown(value) if value == "POSITION_TELEPORTED"
+    ));
+}
+
+#[test]
+fn test_activity_occurred_at_utc_prefers_date_utc() {
+    let activity: Activity = serde_json::from_str(
+        r#"{"date": "2025-10-30T18:13:53", "dateUTC": "2025-10-30T17:13:53", "type": "POSITION"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        activity.occurred_at_utc().unwrap().to_rfc3339(),
+        "2025-10-30T17:13:53+00:00"
+    );
+
+    let activity: Activity =
+        serde_json::from_str(r#"{"date": "2025-10-30T18:13:53", "type": "POSITION"}"#).unwrap();
+    assert_eq!(
+        activity.occurred_at_utc().unwrap().to_rfc3339(),
+        "2025-10-30T18:13:53+00:00"
+    );
+}
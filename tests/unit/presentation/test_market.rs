@@ -1,5 +1,11 @@
-use ig_client::presentation::instrument::InstrumentType;
-use ig_client::presentation::market::MarketData;
+use crate::common::{market_data, market_details};
+use chrono::{TimeZone, Utc};
+use ig_client::presentation::account::PositionMarket;
+use ig_client::presentation::instrument::{InstrumentType, OptionType};
+use ig_client::presentation::market::{
+    DealingRuleViolation, ExpiryDetails, FilteredMarketDetails, Instrument, MarketData,
+    MarketDetails, MarketSnapshot, MarketSnapshotView, MarketState, Resolution, apply_scaling,
+};
 
 #[test]
 fn test_market_data_display() {
@@ -72,3 +78,341 @@ fn test_market_data_serialization() {
     assert_eq!(market.epic, deserialized.epic);
     assert_eq!(market.bid, deserialized.bid);
 }
+
+#[test]
+fn test_market_data_unknown_instrument_type_falls_back() {
+    let market = market_data(
+        "NEW.D.PRODUCT.IP",
+        serde_json::json!({"instrumentType": "QUANTUM_FUTURES", "bid": 10.0, "offer": 11.0}),
+    );
+    assert_eq!(
+        market.instrument_type,
+        InstrumentType::Unknown("QUANTUM_FUTURES".to_string())
+    );
+
+    let known: InstrumentType = serde_json::from_str("\"OPT_INDICES\"").unwrap();
+    assert_eq!(known, InstrumentType::OptIndices);
+}
+
+fn render_row<M: MarketSnapshotView>(market: &M) -> String {
+    format!(
+        "{}|{}|{:?}|{:?}|{:?}",
+        market.epic(),
+        market.instrument_name(),
+        market.bid(),
+        market.offer(),
+        market.status()
+    )
+}
+
+#[test]
+fn test_market_state_from_status_str() {
+    assert_eq!(
+        MarketState::from_status_str("TRADEABLE"),
+        MarketState::Tradeable
+    );
+    assert_eq!(
+        MarketState::from_status_str("edits_only"),
+        MarketState::EditsOnly
+    );
+    assert_eq!(
+        MarketState::from_status_str("maintenance"),
+        MarketState::Unknown("MAINTENANCE".to_string())
+    );
+}
+
+#[test]
+fn test_market_snapshot_view_is_generic_over_market_types() {
+    let market = MarketData {
+        bid: Some(100.5),
+        epic: "OP.D.SPX.5000C.IP".to_string(),
+        expiry: "DEC-24".to_string(),
+        high_limit_price: None,
+        instrument_name: "US 500 5000 CALL".to_string(),
+        instrument_type: InstrumentType::Shares,
+        low_limit_price: None,
+        market_status: "TRADEABLE".to_string(),
+        net_change: None,
+        offer: Some(101.5),
+        percentage_change: None,
+        update_time: None,
+        update_time_utc: None,
+    };
+    let position_market = PositionMarket {
+        instrument_name: "US 500 5000 CALL".into(),
+        expiry: "DEC-24".into(),
+        epic: "OP.D.SPX.5000C.IP".into(),
+        instrument_type: "OPT_INDEX".into(),
+        lot_size: 1.0,
+        high: None,
+        low: None,
+        percentage_change: 0.0,
+        net_change: 0.0,
+        bid: Some(100.5),
+        offer: Some(101.5),
+        update_time: "10:00:00".into(),
+        update_time_utc: "08:00:00".into(),
+        delay_time: 0,
+        streaming_prices_available: true,
+        market_status: "TRADEABLE".into(),
+        scaling_factor: 1,
+    };
+
+    assert_eq!(render_row(&market), render_row(&position_market));
+    assert_eq!(MarketSnapshotView::mid(&market), Some(101.0));
+    assert!(MarketSnapshotView::is_call(&position_market));
+    assert!(!MarketSnapshotView::is_put(&market));
+    assert_eq!(
+        MarketSnapshotView::option_type(&market),
+        Some(OptionType::Call)
+    );
+}
+
+fn instrument_with_expiry(expiry_details: Option<ExpiryDetails>) -> Instrument {
+    Instrument {
+        epic: "OP.D.OTCDAX1.21100C.IP".into(),
+        name: "Daily Germany 40 21100 CALL".into(),
+        expiry: "20-DEC-24".into(),
+        contract_size: "1".into(),
+        lot_size: Some(1.0),
+        high_limit_price: None,
+        low_limit_price: None,
+        margin_factor: None,
+        margin_factor_unit: None,
+        currencies: None,
+        value_of_one_pip: "1".into(),
+        instrument_type: Some(InstrumentType::Indices),
+        expiry_details,
+        slippage_factor: None,
+        limited_risk_premium: None,
+        news_code: None,
+        chart_code: None,
+        streaming_prices_available: None,
+    }
+}
+
+#[test]
+fn test_last_dealing_date_utc_parses_ig_format() {
+    let details: ExpiryDetails = serde_json::from_str(
+        r#"{"lastDealingDate":"2024-12-20T16:00","settlementInfo":"Settles on the close"}"#,
+    )
+    .unwrap();
+    let instrument = instrument_with_expiry(Some(details));
+
+    assert_eq!(
+        instrument.last_dealing_date_utc(),
+        Some(Utc.with_ymd_and_hms(2024, 12, 20, 16, 0, 0).unwrap())
+    );
+}
+
+#[test]
+fn test_last_dealing_date_utc_accepts_seconds_and_slashes() {
+    let details = ExpiryDetails {
+        last_dealing_date: "2024/12/20 16:00:30".into(),
+        settlement_info: None,
+    };
+    assert_eq!(
+        details.last_dealing_date_utc(),
+        Some(Utc.with_ymd_and_hms(2024, 12, 20, 16, 0, 30).unwrap())
+    );
+}
+
+#[test]
+fn test_last_dealing_date_utc_missing_or_invalid() {
+    assert_eq!(instrument_with_expiry(None).last_dealing_date_utc(), None);
+
+    let empty = ExpiryDetails {
+        last_dealing_date: String::new(),
+        settlement_info: None,
+    };
+    assert_eq!(
+        instrument_with_expiry(Some(empty)).last_dealing_date_utc(),
+        None
+    );
+
+    let invalid = ExpiryDetails {
+        last_dealing_date: "DFB".into(),
+        settlement_info: None,
+    };
+    assert_eq!(invalid.last_dealing_date_utc(), None);
+}
+
+fn snapshot_with_scaling(scaling_factor: Option<i64>) -> MarketSnapshot {
+    let mut value = serde_json::json!({
+        "marketStatus": "TRADEABLE",
+        "bid": 1250.0,
+        "offer": 1262.0,
+        "high": 1300.0,
+        "low": 1200.0,
+    });
+    if let Some(factor) = scaling_factor {
+        value["scalingFactor"] = serde_json::json!(factor);
+    }
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_apply_scaling() {
+    assert_eq!(apply_scaling(1250.0, 100), 12.5);
+    assert_eq!(apply_scaling(1250.0, 1), 1250.0);
+    assert_eq!(apply_scaling(1250.0, 0), 1250.0);
+}
+
+#[test]
+fn test_scaled_snapshot_divides_prices_by_scaling_factor() {
+    let scaled = snapshot_with_scaling(Some(100)).scaled();
+    assert_eq!(scaled.scaling_factor, 100);
+    assert_eq!(scaled.bid, Some(12.5));
+    assert_eq!(scaled.offer, Some(12.62));
+    assert_eq!(scaled.high, Some(13.0));
+    assert_eq!(scaled.low, Some(12.0));
+}
+
+#[test]
+fn test_scaled_snapshot_without_scaling_factor_is_unchanged() {
+    let scaled = snapshot_with_scaling(None).scaled();
+    assert_eq!(scaled.scaling_factor, 1);
+    assert_eq!(scaled.bid, Some(1250.0));
+    assert_eq!(scaled.low, Some(1200.0));
+}
+
+#[test]
+fn test_market_state_tradeability() {
+    assert!(MarketState::Tradeable.is_tradeable());
+    assert!(!MarketState::EditsOnly.is_tradeable());
+    assert!(MarketState::EditsOnly.is_closing_only());
+    assert!(!MarketState::Closed.is_tradeable());
+    assert!(MarketState::from_status_str("tradeable").is_tradeable());
+}
+
+fn market_details_with_rules() -> MarketDetails {
+    let points = |value: f64| serde_json::json!({"unit": "POINTS", "value": value});
+    market_details(
+        "IX.D.DAX.DAILY.IP",
+        serde_json::json!({
+            "instrument": {"streamingPricesAvailable": true},
+            "snapshot": {"bid": 19990.0, "offer": 20010.0},
+            "dealingRules": {
+                "minDealSize": points(0.5),
+                "minControlledRiskStopDistance": points(40.0),
+                "minNormalStopOrLimitDistance": points(8.0),
+                "maxStopOrLimitDistance": {"unit": "PERCENTAGE", "value": 10.0}
+            }
+        }),
+    )
+}
+
+#[test]
+fn test_market_details_dealing_rules() {
+    let details = market_details_with_rules();
+    assert_eq!(details.instrument.streaming_prices_available, Some(true));
+    assert_eq!(details.min_deal_size(), Some(0.5));
+    assert_eq!(details.max_deal_size(), Some(100.0));
+    assert_eq!(details.min_stop_distance(), Some(8.0));
+    assert_eq!(details.min_controlled_risk_stop_distance(), Some(40.0));
+    assert_eq!(details.max_stop_distance(), Some(2000.0));
+}
+
+#[test]
+fn test_validate_order_accepts_valid_order() {
+    let details = market_details_with_rules();
+    assert!(details.validate_order(0.5, Some(8.0), false).is_ok());
+    assert!(details.validate_order(2.0, None, true).is_ok());
+    assert!(details.validate_order(1.0, Some(40.0), true).is_ok());
+}
+
+#[test]
+fn test_validate_order_reports_every_violation() {
+    let details = market_details_with_rules();
+    assert_eq!(
+        details.validate_order(0.1, Some(5.0), false),
+        Err(vec![
+            DealingRuleViolation::BelowMinDealSize {
+                size: 0.1,
+                min: 0.5
+            },
+            DealingRuleViolation::StopTooClose {
+                distance: 5.0,
+                min: 8.0
+            },
+        ])
+    );
+    assert_eq!(
+        details.validate_order(0.0, Some(2500.0), false),
+        Err(vec![
+            DealingRuleViolation::InvalidSize { size: 0.0 },
+            DealingRuleViolation::StopTooFar {
+                distance: 2500.0,
+                max: 2000.0
+            },
+        ])
+    );
+}
+
+#[test]
+fn test_validate_order_rejects_close_guaranteed_stop() {
+    let details = market_details_with_rules();
+    // Far enough for a normal stop, too close for a guaranteed one
+    assert!(details.validate_order(1.0, Some(20.0), false).is_ok());
+    assert_eq!(
+        details.validate_order(1.0, Some(20.0), true),
+        Err(vec![DealingRuleViolation::GuaranteedStopTooClose {
+            distance: 20.0,
+            min: 40.0
+        }])
+    );
+}
+
+#[test]
+fn test_resolution_tokens_round_trip() {
+    assert_eq!(Resolution::Minute5.as_str(), "MINUTE_5");
+    assert_eq!(Resolution::Hour4.to_string(), "HOUR_4");
+    assert_eq!(
+        serde_json::to_string(&Resolution::Second).unwrap(),
+        "\"SECOND\""
+    );
+    for resolution in Resolution::ALL {
+        assert_eq!(
+            resolution.as_str().parse::<Resolution>().unwrap(),
+            resolution
+        );
+    }
+    assert_eq!(
+        "minute_30".parse::<Resolution>().unwrap(),
+        Resolution::Minute30
+    );
+    assert!("1MIN".parse::<Resolution>().is_err());
+}
+
+#[test]
+fn test_filtered_market_details_accepts_snapshot_only_body() {
+    let body = r#"{
+        "snapshot": {
+            "marketStatus": "TRADEABLE",
+            "netChange": 12.5,
+            "percentageChange": 0.08,
+            "updateTime": "15:42:10",
+            "delayTime": 0,
+            "bid": 15432.1,
+            "offer": 15433.3,
+            "high": 15480.0,
+            "low": 15390.2,
+            "binaryOdds": null,
+            "decimalPlacesFactor": 1,
+            "scalingFactor": 1,
+            "controlledRiskExtraSpread": 1.0
+        }
+    }"#;
+    let details: FilteredMarketDetails = serde_json::from_str(body).unwrap();
+    assert!(details.instrument.is_none());
+    assert!(details.dealing_rules.is_none());
+    assert_eq!(details.snapshot.market_status, "TRADEABLE");
+    assert_eq!(details.snapshot.bid, Some(15432.1));
+    assert!(details.into_market_details().is_none());
+
+    let nulls =
+        r#"{"instrument": null, "dealingRules": null, "snapshot": {"marketStatus": "CLOSED"}}"#;
+    let details: FilteredMarketDetails = serde_json::from_str(nulls).unwrap();
+    assert!(details.instrument.is_none());
+    assert_eq!(details.snapshot.market_status, "CLOSED");
+}
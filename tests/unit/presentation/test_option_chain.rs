@@ -0,0 +1,55 @@
+use crate::common::market_data;
+use ig_client::presentation::market::MarketData;
+use ig_client::presentation::option_chain::OptionChain;
+
+fn option(epic: &str, name: &str, expiry: &str) -> MarketData {
+    market_data(
+        epic,
+        serde_json::json!({
+            "instrumentName": name,
+            "instrumentType": "OPT_INDICES",
+            "expiry": expiry
+        }),
+    )
+}
+
+#[test]
+fn test_option_chain_groups_by_expiry_and_strike() {
+    let markets = vec![
+        option("OP.D.DAX.24500C.IP", "Germany 40 24500 CALL", "DEC-25"),
+        option("OP.D.DAX.24000P.IP", "Germany 40 24000 PUT", "DEC-25"),
+        option("OP.D.DAX.24000C.IP", "Germany 40 24000 CALL", "DEC-25"),
+        option("OP.D.DAX.24000C.IP.JAN", "Germany 40 24000 CALL", "JAN-26"),
+        option("IX.D.DAX.DAILY.IP", "Germany 40", "DFB"),
+    ];
+
+    let chain = OptionChain::from_markets("node-1", &markets);
+    assert_eq!(chain.underlying, "Germany 40");
+    assert_eq!(chain.expiries.len(), 2);
+
+    let december = chain.expiry("DEC-25").unwrap();
+    assert_eq!(
+        december.strikes,
+        vec![
+            (
+                24000.0,
+                Some("OP.D.DAX.24000C.IP".to_string()),
+                Some("OP.D.DAX.24000P.IP".to_string())
+            ),
+            (24500.0, Some("OP.D.DAX.24500C.IP".to_string()), None),
+        ]
+    );
+
+    let january = chain.expiry("JAN-26").unwrap();
+    assert_eq!(january.strike(24000.0).unwrap().2, None);
+}
+
+#[test]
+fn test_option_chain_without_options_keeps_fallback_name() {
+    let chain = OptionChain::from_markets(
+        "node-1",
+        &[option("IX.D.DAX.DAILY.IP", "Germany 40", "DFB")],
+    );
+    assert_eq!(chain.underlying, "node-1");
+    assert!(chain.is_empty());
+}
@@ -0,0 +1,11 @@
+use ig_client::presentation::order::{Direction, signed_size};
+
+#[test]
+fn test_direction_opposite_and_sign() {
+    assert_eq!(Direction::Buy.opposite(), Direction::Sell);
+    assert_eq!(Direction::Sell.opposite(), Direction::Buy);
+    assert_eq!(Direction::Buy.sign(), 1.0);
+    assert_eq!(Direction::Sell.sign(), -1.0);
+    assert_eq!(signed_size(&Direction::Buy, 2.5), 2.5);
+    assert_eq!(signed_size(&Direction::Sell, 2.5), -2.5);
+}
@@ -0,0 +1,120 @@
+use ig_client::application::streamer_config::StreamerConfig;
+use ig_client::model::responses::PositionsResponse;
+use ig_client::presentation::account::{Position, PositionDetails, PositionMarket};
+use ig_client::presentation::order::Direction;
+use ig_client::presentation::portfolio::LivePortfolio;
+use ig_client::presentation::price::{PriceData, PriceFields};
+
+fn position(epic: &str, direction: Direction, level: f64, size: f64) -> Position {
+    Position {
+        position: PositionDetails {
+            contract_size: 1.0,
+            created_date: "2025/10/30 18:13:53:000".to_string(),
+            created_date_utc: "2025-10-30T17:13:53".to_string(),
+            deal_id: format!("D-{epic}"),
+            deal_reference: format!("R-{epic}"),
+            direction,
+            limit_level: None,
+            level,
+            size,
+            stop_level: None,
+            trailing_step: None,
+            trailing_stop_distance: None,
+            currency: "EUR".to_string(),
+            controlled_risk: false,
+            limited_risk_premium: None,
+        },
+        market: PositionMarket {
+            instrument_name: epic.to_string(),
+            expiry: "-".to_string(),
+            epic: epic.to_string(),
+            instrument_type: "INDICES".to_string(),
+            lot_size: 1.0,
+            high: None,
+            low: None,
+            percentage_change: 0.0,
+            net_change: 0.0,
+            bid: None,
+            offer: None,
+            update_time: "10:00:00".to_string(),
+            update_time_utc: "08:00:00".to_string(),
+            delay_time: 0,
+            streaming_prices_available: true,
+            market_status: "TRADEABLE".to_string(),
+            scaling_factor: 1,
+        },
+        pnl: None,
+    }
+}
+
+#[test]
+fn apply_price_revalues_matching_positions() {
+    let mut portfolio = LivePortfolio::from(PositionsResponse {
+        positions: vec![
+            position("IX.D.DAX.DAILY.IP", Direction::Buy, 100.0, 2.0),
+            position("IX.D.FTSE.DAILY.IP", Direction::Sell, 50.0, 1.0),
+        ],
+    });
+
+    assert_eq!(
+        portfolio.apply_price("IX.D.DAX.DAILY.IP", Some(110.0), Some(111.0)),
+        1
+    );
+    // Buy: (110 - 100) * 2; the FTSE position has no price yet
+    assert!((portfolio.total_pnl() - 20.0).abs() < 1e-9);
+
+    // A missing side keeps the previous price
+    assert_eq!(
+        portfolio.apply_price("IX.D.DAX.DAILY.IP", None, Some(112.0)),
+        1
+    );
+    assert_eq!(portfolio.positions()[0].market.bid, Some(110.0));
+    assert_eq!(portfolio.apply_price("UNKNOWN", Some(1.0), Some(1.0)), 0);
+}
+
+#[test]
+fn apply_update_strips_market_prefix() {
+    let mut portfolio = LivePortfolio::new(vec![position(
+        "IX.D.FTSE.DAILY.IP",
+        Direction::Sell,
+        50.0,
+        1.0,
+    )]);
+
+    let update = PriceData {
+        item_name: "MARKET:IX.D.FTSE.DAILY.IP".to_string(),
+        fields: PriceFields {
+            bid: Some(47.0),
+            offer: Some(48.0),
+            ..PriceFields::default()
+        },
+        ..PriceData::default()
+    };
+
+    assert_eq!(portfolio.apply_update(&update), 1);
+    // Sell: 50 - 48
+    assert!((portfolio.total_pnl() - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn apply_update_uses_configured_market_prefix() {
+    let mut portfolio = LivePortfolio::new(vec![position(
+        "IX.D.FTSE.DAILY.IP",
+        Direction::Sell,
+        50.0,
+        1.0,
+    )])
+    .with_streamer_config(StreamerConfig::default().with_item_prefixes("MKT", "PRICE", "TRADE"));
+
+    let update = PriceData {
+        item_name: "MKT:IX.D.FTSE.DAILY.IP".to_string(),
+        fields: PriceFields {
+            bid: Some(47.0),
+            offer: Some(48.0),
+            ..PriceFields::default()
+        },
+        ..PriceData::default()
+    };
+
+    assert_eq!(portfolio.apply_update(&update), 1);
+}
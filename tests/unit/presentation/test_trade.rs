@@ -1,4 +1,8 @@
-use ig_client::presentation::trade::{TradeData, TradeFields};
+use ig_client::presentation::account::Activity;
+use ig_client::presentation::order::Direction;
+use ig_client::presentation::trade::{
+    DealStatus, OpuStatus, TradeConfirmation, TradeData, TradeFields, WouStatus,
+};
 use lightstreamer_rs::subscription::ItemUpdate;
 use std::collections::HashMap;
 
@@ -57,6 +61,80 @@ fn test_trade_data_from_item_update_with_confirms() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_trade_data_propagates_snapshot_flag_to_fields() {
+    let mut fields = HashMap::new();
+    fields.insert("CONFIRMS".to_string(), Some("DEAL123".to_string()));
+    let mut item_update = ItemUpdate {
+        item_name: Some("TRADE:ACCOUNT".to_string()),
+        item_pos: 1,
+        is_snapshot: true,
+        fields,
+        changed_fields: HashMap::new(),
+    };
+
+    let snapshot = TradeData::from(&item_update);
+    assert!(snapshot.is_snapshot);
+    assert!(snapshot.fields.is_snapshot);
+    assert!(snapshot.changed_fields.is_snapshot);
+
+    item_update.is_snapshot = false;
+    let live = TradeData::from(&item_update);
+    assert!(!live.fields.is_snapshot);
+    assert!(
+        !serde_json::to_string(&live.fields)
+            .unwrap()
+            .contains("IS_SNAPSHOT")
+    );
+}
+
+#[test]
+fn test_trade_fields_confirmation_parses_confirms_json() {
+    let fields = TradeFields {
+        confirms: Some(
+            r#"{"dealId":"DIAAAAB","dealReference":"REF1","status":"OPEN","dealStatus":"ACCEPTED","reason":"SUCCESS","epic":"IX.D.DAX.DAILY.IP","direction":"BUY","size":1.5,"level":"18000.5","profit":null,"expiry":"-","date":"2025-10-30T17:13:53.123"}"#
+                .to_string(),
+        ),
+        ..TradeFields::default()
+    };
+
+    let confirmation = fields.confirmation().expect("confirmation");
+    assert_eq!(confirmation.deal_id.as_deref(), Some("DIAAAAB"));
+    assert_eq!(confirmation.deal_reference.as_deref(), Some("REF1"));
+    assert_eq!(confirmation.direction, Some(Direction::Buy));
+    assert_eq!(confirmation.size, Some(1.5));
+    assert_eq!(confirmation.level, Some(18000.5));
+    assert_eq!(confirmation.profit, None);
+    assert!(confirmation.is_accepted());
+    assert!(!confirmation.is_rejected());
+
+    let raw = TradeFields {
+        confirms: Some("DEAL123".to_string()),
+        ..TradeFields::default()
+    };
+    assert!(raw.confirmation().is_none());
+    assert!(TradeFields::default().confirmation().is_none());
+}
+
+#[test]
+fn test_trade_confirmation_from_activity_round_trips_through_confirms() {
+    let activity: Activity = serde_json::from_str(
+        r#"{"date":"2025-10-30T17:13:53","dealId":"DIAAAAB","epic":"IX.D.DAX.DAILY.IP","period":"-","dealReference":"REF1","type":"POSITION","status":"ACCEPTED","description":"Position opened: DIAAAAB","details":{"dealReference":"REF1","actions":[],"size":2.0,"direction":"SELL","level":18000.5}}"#,
+    )
+    .unwrap();
+
+    let confirmation = TradeConfirmation::from(&activity);
+    assert_eq!(confirmation.deal_id.as_deref(), Some("DIAAAAB"));
+    assert_eq!(confirmation.direction, Some(Direction::Sell));
+    assert_eq!(confirmation.size, Some(2.0));
+    assert_eq!(confirmation.level, Some(18000.5));
+    assert_eq!(confirmation.reason, None);
+    assert!(confirmation.is_accepted());
+
+    let fields = TradeFields::from(&confirmation);
+    assert_eq!(fields.confirmation(), Some(confirmation));
+}
+
 #[test]
 fn test_trade_data_clone() {
     let trade = TradeData {
@@ -72,3 +150,53 @@ fn test_trade_data_clone() {
     assert_eq!(trade.item_pos, cloned.item_pos);
     assert_eq!(trade.is_snapshot, cloned.is_snapshot);
 }
+
+#[test]
+fn test_trade_data_parses_typed_opu_and_wou_status() {
+    let mut fields = HashMap::new();
+    fields.insert(
+        "OPU".to_string(),
+        Some(r#"{"dealId":"DIAAAAB","status":"DELETED","dealStatus":"ACCEPTED"}"#.to_string()),
+    );
+    fields.insert(
+        "WOU".to_string(),
+        Some(r#"{"dealId":"DIAAAAC","status":"PHASED_OUT","dealStatus":"ACCEPTED"}"#.to_string()),
+    );
+    let item_update = ItemUpdate {
+        item_name: Some("TRADE:ACCOUNT".to_string()),
+        item_pos: 1,
+        is_snapshot: false,
+        fields,
+        changed_fields: HashMap::new(),
+    };
+
+    let trade = TradeData::from_item_update(&item_update).unwrap();
+    let opu = trade.fields.opu.expect("opu");
+    let wou = trade.fields.wou.expect("wou");
+    assert_eq!(opu.status, Some(OpuStatus::Deleted));
+    assert_eq!(opu.deal_status, Some(DealStatus::Accepted));
+    assert_eq!(
+        wou.status,
+        Some(WouStatus::Unknown("PHASED_OUT".to_string()))
+    );
+
+    let status: OpuStatus = serde_json::from_str("\"FULLY_CLOSED\"").unwrap();
+    assert_eq!(status, OpuStatus::FullyClosed);
+    assert_eq!(serde_json::to_string(&WouStatus::Open).unwrap(), "\"OPEN\"");
+}
+
+#[test]
+fn test_trade_confirmation_keeps_unknown_deal_status() {
+    let fields = TradeFields {
+        confirms: Some(r#"{"dealId":"DIAAAAB","dealStatus":"PENDING"}"#.to_string()),
+        ..TradeFields::default()
+    };
+
+    let confirmation = fields.confirmation().expect("confirmation");
+    assert_eq!(
+        confirmation.deal_status,
+        Some(DealStatus::Unknown("PENDING".to_string()))
+    );
+    assert!(!confirmation.is_accepted());
+    assert!(!confirmation.is_rejected());
+}
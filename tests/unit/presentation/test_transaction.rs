@@ -264,3 +264,36 @@ fn test_transaction_list_as_ref() {
     assert_eq!(slice.len(), 1);
     assert_eq!(slice[0].reference, "REF1");
 }
+
+#[test]
+fn test_account_transaction_numeric_values() {
+    let mut account_tx = AccountTransaction {
+        date: "2024-01-15".to_string(),
+        date_utc: "2024-01-15T10:30:00".to_string(),
+        open_date_utc: "2024-01-15T09:00:00".to_string(),
+        instrument_name: "GOLD".to_string(),
+        period: "JAN-24".to_string(),
+        profit_and_loss: "E1,234.56".to_string(),
+        transaction_type: "DEAL".to_string(),
+        reference: "REF123".to_string(),
+        open_level: "1,800.5".to_string(),
+        close_level: "1850".to_string(),
+        size: "-2.5".to_string(),
+        currency: "EUR".to_string(),
+        cash_transaction: false,
+    };
+
+    assert_eq!(account_tx.profit_and_loss_value(), Some(1234.56));
+    assert_eq!(account_tx.open_level_value(), Some(1800.5));
+    assert_eq!(account_tx.close_level_value(), Some(1850.0));
+    assert_eq!(account_tx.size_value(), Some(-2.5));
+
+    account_tx.profit_and_loss = "$-12.30".to_string();
+    assert_eq!(account_tx.profit_and_loss_value(), Some(-12.30));
+
+    account_tx.profit_and_loss = "£2,000".to_string();
+    assert_eq!(account_tx.profit_and_loss_value(), Some(2000.0));
+
+    account_tx.profit_and_loss = "-".to_string();
+    assert_eq!(account_tx.profit_and_loss_value(), None);
+}
@@ -1,4 +1,5 @@
-use ig_client::error::AppError;
+use ig_client::error::{AppError, OrderRejectCode, RateLimitScope};
+use ig_client::model::http::order_rejection;
 use reqwest::StatusCode;
 
 #[test]
@@ -49,6 +50,18 @@ fn test_app_error_display_invalid_input() {
     assert_eq!(error.to_string(), "invalid input: Size must be positive");
 }
 
+#[test]
+fn test_app_error_display_watchlist_not_created() {
+    let error = AppError::WatchlistNotCreated("Indices".to_string());
+    assert_eq!(error.to_string(), "watchlist not created: Indices");
+}
+
+#[test]
+fn test_app_error_display_config() {
+    let error = AppError::Config("missing IG_API_KEY".to_string());
+    assert_eq!(error.to_string(), "configuration error: missing IG_API_KEY");
+}
+
 // Note: reqwest::Error cannot be easily constructed in tests
 // This conversion is tested through integration tests
 
@@ -80,3 +93,134 @@ fn test_app_error_oauth_token_expired() {
     let error = AppError::OAuthTokenExpired;
     assert_eq!(error.to_string(), "oauth token expired");
 }
+
+#[test]
+fn test_app_error_session_expired() {
+    let error = AppError::SessionExpired;
+    assert_eq!(error.to_string(), "session expired");
+}
+
+#[test]
+fn test_rate_limit_scope_from_ig_error_codes() {
+    let cases = [
+        (
+            "error.public-api.exceeded-account-trading-allowance",
+            RateLimitScope::AccountTrading,
+        ),
+        (
+            "error.public-api.exceeded-account-allowance",
+            RateLimitScope::AccountNonTrading,
+        ),
+        (
+            "error.public-api.exceeded-api-key-allowance",
+            RateLimitScope::App,
+        ),
+        (
+            "error.public-api.exceeded-account-historical-data-allowance",
+            RateLimitScope::HistoricalData,
+        ),
+    ];
+    for (code, scope) in cases {
+        assert_eq!(RateLimitScope::from_error_code(code), Some(scope), "{code}");
+        let body = format!(r#"{{"errorCode":"{code}"}}"#);
+        assert_eq!(
+            RateLimitScope::from_error_code(&body),
+            Some(scope),
+            "{body}"
+        );
+    }
+    assert_eq!(
+        RateLimitScope::from_error_code("error.security.invalid-details"),
+        None
+    );
+}
+
+#[test]
+fn test_app_error_display_rate_limited() {
+    let error = AppError::RateLimited {
+        scope: RateLimitScope::AccountTrading,
+        retry_after: Some(std::time::Duration::from_secs(30)),
+    };
+    assert_eq!(
+        error.to_string(),
+        "rate limited (account trading allowance), retry after 30s"
+    );
+
+    let error = AppError::RateLimited {
+        scope: RateLimitScope::App,
+        retry_after: None,
+    };
+    assert_eq!(error.to_string(), "rate limited (app allowance)");
+}
+
+#[test]
+fn test_app_error_display_allowance_exceeded() {
+    let error = AppError::AllowanceExceeded {
+        resets_in: Some(std::time::Duration::from_secs(3600)),
+    };
+    assert_eq!(
+        error.to_string(),
+        "historical price allowance exceeded, resets in 3600s"
+    );
+
+    let error = AppError::AllowanceExceeded { resets_in: None };
+    assert_eq!(error.to_string(), "historical price allowance exceeded");
+}
+
+#[test]
+fn test_order_reject_code_from_code() {
+    assert_eq!(
+        OrderRejectCode::from_code("MARKET_CLOSED_WITH_EDITS"),
+        OrderRejectCode::MarketClosed
+    );
+    assert_eq!(
+        OrderRejectCode::from_code("INSUFFICIENT_FUNDS"),
+        OrderRejectCode::InsufficientFunds
+    );
+    assert_eq!(
+        OrderRejectCode::from_code("error.trading.attached-order-level-error"),
+        OrderRejectCode::AttachedOrderLevelError
+    );
+    assert_eq!(
+        OrderRejectCode::from_code("REJECT_SPREADBET_ORDER_ON_CFD_ACCOUNT"),
+        OrderRejectCode::Other("REJECT_SPREADBET_ORDER_ON_CFD_ACCOUNT".to_string())
+    );
+}
+
+#[test]
+fn test_app_error_display_order_rejected() {
+    let error = AppError::OrderRejected {
+        code: OrderRejectCode::InsufficientFunds,
+        message: "error.trading.insufficient-funds".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "order rejected (INSUFFICIENT_FUNDS): error.trading.insufficient-funds"
+    );
+}
+
+#[test]
+fn test_order_rejection_only_for_dealing_endpoints() {
+    let body = r#"{"errorCode":"error.trading.market-closed"}"#;
+    match order_rejection("https://demo-api.ig.com/gateway/deal/positions/otc", body) {
+        Some(AppError::OrderRejected { code, message }) => {
+            assert_eq!(code, OrderRejectCode::MarketClosed);
+            assert_eq!(message, "error.trading.market-closed");
+        }
+        other => panic!("unexpected: {other:?}"),
+    }
+    assert!(order_rejection("https://demo-api.ig.com/gateway/deal/markets", body).is_none());
+    assert!(
+        order_rejection(
+            "https://demo-api.ig.com/gateway/deal/workingorders/otc",
+            "not json"
+        )
+        .is_none()
+    );
+}
+
+#[test]
+fn test_app_error_display_not_supported() {
+    let error = AppError::NotSupported("try a live account".to_string());
+    assert_eq!(error.to_string(), "not supported: try a live account");
+}
@@ -1,7 +1,9 @@
 mod parsing_tests;
 mod test_config;
+mod test_export;
 mod test_finance;
 mod test_id;
 mod test_model_utils;
+mod test_replay;
 mod test_retry;
 mod tools_tests;
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use ig_client::utils::parsing::{ParsedOptionInfo, normalize_text, parse_instrument_name};
+    use ig_client::presentation::instrument::OptionType;
+    use ig_client::utils::parsing::{
+        ParsedOptionInfo, normalize_text, parse_epic, parse_ig_datetime, parse_instrument_name,
+    };
 
     #[test]
     fn test_normalize_text() {
@@ -111,4 +114,62 @@ mod tests {
         assert_eq!(info.strike, Some("18500".to_string()));
         assert_eq!(info.option_type, Some("CALL".to_string()));
     }
+
+    #[test]
+    fn test_parse_epic_option() {
+        let epic = parse_epic("OP.D.OTCSPX3.6910P.IP").unwrap();
+        assert_eq!(epic.market_type, "OP");
+        assert_eq!(epic.symbol, "OTCSPX3");
+        assert_eq!(epic.strike, Some(6910.0));
+        assert_eq!(epic.option_type, Some(OptionType::Put));
+        assert_eq!(epic.period, None);
+        assert!(epic.is_option());
+    }
+
+    #[test]
+    fn test_parse_epic_non_option() {
+        let epic = parse_epic("CS.D.EURUSD.TODAY.IP").unwrap();
+        assert_eq!(epic.market_type, "CS");
+        assert_eq!(epic.symbol, "EURUSD");
+        assert_eq!(epic.period.as_deref(), Some("TODAY"));
+        assert!(!epic.is_option());
+    }
+
+    #[test]
+    fn test_parse_epic_with_instrument_name() {
+        let epic = parse_epic("DO.D.OTCDDAX.41.IP")
+            .unwrap()
+            .with_instrument_name("Daily Germany 40 27500 CALL");
+        assert_eq!(epic.strike, Some(27500.0));
+        assert_eq!(epic.option_type, Some(OptionType::Call));
+    }
+
+    #[test]
+    fn test_parse_epic_malformed() {
+        assert!(parse_epic("DAX").is_err());
+        assert!(parse_epic("IX..DAX.IP").is_err());
+    }
+
+    #[test]
+    fn test_parse_ig_datetime_known_formats() {
+        let expected = "2025-10-30T17:13:53+00:00";
+        for raw in [
+            "2025/10/30 17:13:53:000",
+            "2025-10-30T17:13:53",
+            "2025-10-30T17:13:53.000",
+            "2025/10/30 17:13:53",
+            "2025-10-30T17:13:53Z",
+            "2025-10-30T18:13:53+01:00",
+        ] {
+            let parsed = parse_ig_datetime(raw).unwrap_or_else(|| panic!("failed: {raw}"));
+            assert_eq!(parsed.to_rfc3339(), expected, "{raw}");
+        }
+        assert!(parse_ig_datetime("2025-10-30T17:13").is_some());
+    }
+
+    #[test]
+    fn test_parse_ig_datetime_invalid() {
+        assert!(parse_ig_datetime("").is_none());
+        assert!(parse_ig_datetime("yesterday").is_none());
+    }
 }
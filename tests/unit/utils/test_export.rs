@@ -0,0 +1,81 @@
+use ig_client::constants::JSONL_FLUSH_INTERVAL_MS;
+use ig_client::presentation::price::{PriceData, PriceFields};
+use ig_client::utils::export::{JsonLinesWriter, spawn_tee};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ig_client_{}_{}.jsonl", name, std::process::id()))
+}
+
+fn price(item: &str, bid: f64) -> PriceData {
+    PriceData {
+        item_name: item.to_string(),
+        fields: PriceFields {
+            bid: Some(bid),
+            ..PriceFields::default()
+        },
+        ..PriceData::default()
+    }
+}
+
+#[test]
+fn json_lines_writer_writes_one_document_per_line() {
+    let path = temp_path("writer");
+    let _ = std::fs::remove_file(&path);
+    {
+        let mut writer = JsonLinesWriter::create(&path).unwrap();
+        writer.write(&price("MARKET:A", 1.0)).unwrap();
+        writer.write(&price("MARKET:B", 2.0)).unwrap();
+        assert_eq!(writer.written(), 2);
+    }
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines.len(), 2);
+    let second: PriceData = serde_json::from_str(lines[1]).unwrap();
+    assert_eq!(second.item_name, "MARKET:B");
+    assert_eq!(second.fields.bid, Some(2.0));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn spawn_tee_forwards_and_records_every_item() {
+    let path = temp_path("tee");
+    let _ = std::fs::remove_file(&path);
+    let (tx, rx) = mpsc::unbounded_channel();
+    let (task, mut downstream) = spawn_tee(rx, &path).await.unwrap();
+
+    tx.send(price("MARKET:A", 1.0)).unwrap();
+    tx.send(price("MARKET:A", 1.5)).unwrap();
+    drop(tx);
+
+    assert_eq!(downstream.recv().await.unwrap().fields.bid, Some(1.0));
+    assert_eq!(downstream.recv().await.unwrap().fields.bid, Some(1.5));
+    assert!(downstream.recv().await.is_none());
+    task.await.unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(content.lines().count(), 2);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test(start_paused = true)]
+async fn spawn_tee_flushes_periodically_while_streaming() {
+    let path = temp_path("tee_interval");
+    let _ = std::fs::remove_file(&path);
+    let (tx, rx) = mpsc::unbounded_channel();
+    let (task, mut downstream) = spawn_tee(rx, &path).await.unwrap();
+
+    tx.send(price("MARKET:A", 1.0)).unwrap();
+    assert!(downstream.recv().await.is_some());
+    tokio::time::sleep(Duration::from_millis(JSONL_FLUSH_INTERVAL_MS * 2)).await;
+    // The source is still open, yet the item already reached the file
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(content.lines().count(), 1);
+
+    drop(tx);
+    task.await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+}
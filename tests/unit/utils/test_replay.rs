@@ -0,0 +1,77 @@
+use ig_client::presentation::price::{PriceData, PriceFields};
+use ig_client::utils::export::JsonLinesWriter;
+use ig_client::utils::replay::PriceReplay;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+fn recording(name: &str, timestamps: &[f64]) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("ig_client_{}_{}.jsonl", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut writer = JsonLinesWriter::create(&path).unwrap();
+    for (index, timestamp) in timestamps.iter().enumerate() {
+        writer
+            .write(&PriceData {
+                item_name: "MARKET:IX.D.DAX.DAILY.IP".to_string(),
+                fields: PriceFields {
+                    bid: Some(index as f64),
+                    timestamp: Some(*timestamp),
+                    ..PriceFields::default()
+                },
+                ..PriceData::default()
+            })
+            .unwrap();
+    }
+    path
+}
+
+#[tokio::test]
+async fn price_replay_sends_records_in_order() {
+    let path = recording("replay_burst", &[1_000.0, 61_000.0, 121_000.0]);
+    let (task, mut receiver) = PriceReplay::new(&path).spawn().unwrap();
+
+    let start = Instant::now();
+    for expected in 0..3 {
+        let price = receiver.recv().await.unwrap();
+        assert_eq!(price.fields.bid, Some(expected as f64));
+    }
+    assert!(receiver.recv().await.is_none());
+    assert_eq!(task.await.unwrap().unwrap(), 3);
+    // Without a speed, the minute-long gaps are not waited for
+    assert!(start.elapsed() < Duration::from_secs(5));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn price_replay_paces_by_timestamp_and_speed() {
+    // 200 ms of recorded time replayed at 2x
+    let path = recording("replay_paced", &[1_000.0, 1_200.0]);
+    let (task, mut receiver) = PriceReplay::new(&path).with_speed(2.0).spawn().unwrap();
+
+    receiver.recv().await.unwrap();
+    let start = Instant::now();
+    receiver.recv().await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(90));
+    assert_eq!(task.await.unwrap().unwrap(), 2);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test(start_paused = true)]
+async fn price_replay_survives_an_overflowing_gap() {
+    let path = recording("replay_overflow", &[1_000.0, 2_000.0]);
+    let (task, mut receiver) = PriceReplay::new(&path)
+        .with_speed(f64::MIN_POSITIVE)
+        .spawn()
+        .unwrap();
+
+    assert_eq!(receiver.recv().await.unwrap().fields.bid, Some(0.0));
+    assert_eq!(receiver.recv().await.unwrap().fields.bid, Some(1.0));
+    assert_eq!(task.await.unwrap().unwrap(), 2);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn price_replay_fails_on_missing_file() {
+    let path = std::env::temp_dir().join("ig_client_replay_missing.jsonl");
+    assert!(PriceReplay::new(path).spawn().is_err());
+}
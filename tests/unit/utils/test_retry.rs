@@ -1,4 +1,9 @@
+use ig_client::error::{AppError, RateLimitScope};
 use ig_client::model::retry::RetryConfig;
+use ig_client::utils::retry::{RetryPolicy, is_transient, retry_with_backoff};
+use reqwest::StatusCode;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
 
 #[test]
 fn test_retry_config_new() {
@@ -71,3 +76,71 @@ fn test_retry_config_delay_secs_getter() {
     };
     assert_eq!(config2.delay_secs(), 10);
 }
+
+#[test]
+fn test_retry_policy_backoff_is_capped() {
+    let policy = RetryPolicy::new(5, Duration::from_millis(100))
+        .with_multiplier(2.0)
+        .with_max_delay(Duration::from_millis(300));
+    assert_eq!(policy.backoff(0), Duration::from_millis(100));
+    assert_eq!(policy.backoff(1), Duration::from_millis(200));
+    assert_eq!(policy.backoff(2), Duration::from_millis(300));
+    assert_eq!(policy.backoff(10), Duration::from_millis(300));
+}
+
+#[test]
+fn test_retry_policy_honors_retry_after() {
+    let policy = RetryPolicy::new(3, Duration::from_millis(100));
+    let err = AppError::RateLimited {
+        scope: RateLimitScope::AccountNonTrading,
+        retry_after: Some(Duration::from_secs(7)),
+    };
+    assert_eq!(policy.delay_for(0, &err), Duration::from_secs(7));
+}
+
+#[test]
+fn test_is_transient() {
+    assert!(is_transient(&AppError::Timeout("slow".to_string())));
+    assert!(is_transient(&AppError::Unexpected(StatusCode::BAD_GATEWAY)));
+    assert!(!is_transient(&AppError::Unexpected(
+        StatusCode::BAD_REQUEST
+    )));
+    assert!(!is_transient(&AppError::NotFound));
+}
+
+#[tokio::test]
+async fn test_retry_with_backoff_retries_transient_errors() {
+    let counter = AtomicU32::new(0);
+    let attempts = &counter;
+    let policy = RetryPolicy::new(3, Duration::from_millis(1));
+    let result = retry_with_backoff(
+        || async move {
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(AppError::Unexpected(StatusCode::SERVICE_UNAVAILABLE))
+            } else {
+                Ok(42)
+            }
+        },
+        policy,
+    )
+    .await;
+    assert_eq!(result.unwrap(), 42);
+    assert_eq!(counter.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_retry_with_backoff_stops_on_permanent_errors() {
+    let counter = AtomicU32::new(0);
+    let attempts = &counter;
+    let policy = RetryPolicy::new(3, Duration::from_millis(1));
+    let result: Result<(), AppError> = retry_with_backoff(
+        || async move {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(AppError::NotFound)
+        },
+        policy,
+    )
+    .await;
+    assert!(matches!(result, Err(AppError::NotFound)));
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+}